@@ -0,0 +1,6 @@
+-- Per-repo options for large repositories: sparse-checkout cones in workspace
+-- worktrees, the partial clone filter used when cloning, and the history depth
+-- scanned by the file ranker
+ALTER TABLE repos ADD COLUMN sparse_checkout_paths TEXT;
+ALTER TABLE repos ADD COLUMN partial_clone_filter TEXT;
+ALTER TABLE repos ADD COLUMN file_ranker_history_depth INTEGER;
@@ -0,0 +1,3 @@
+-- Initialize submodules and fetch Git LFS objects when workspace worktrees are created
+ALTER TABLE repos ADD COLUMN init_submodules INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE repos ADD COLUMN fetch_lfs INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,44 @@
+-- Searchable text of normalized conversation entries (assistant messages, tool
+-- commands, file paths, errors), mirrored into an FTS5 index
+CREATE TABLE conversation_entries (
+    id                   INTEGER PRIMARY KEY,
+    execution_process_id BLOB NOT NULL,
+    entry_index          INTEGER NOT NULL,
+    entry_type           TEXT NOT NULL,
+    content              TEXT NOT NULL,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE,
+    UNIQUE (execution_process_id, entry_index)
+);
+
+CREATE INDEX idx_conversation_entries_entry_type ON conversation_entries(entry_type);
+
+CREATE VIRTUAL TABLE conversation_entries_fts USING fts5(
+    content,
+    content = 'conversation_entries',
+    content_rowid = 'id'
+);
+
+CREATE TRIGGER conversation_entries_ai AFTER INSERT ON conversation_entries BEGIN
+    INSERT INTO conversation_entries_fts (rowid, content) VALUES (new.id, new.content);
+END;
+
+CREATE TRIGGER conversation_entries_ad AFTER DELETE ON conversation_entries BEGIN
+    INSERT INTO conversation_entries_fts (conversation_entries_fts, rowid, content)
+    VALUES ('delete', old.id, old.content);
+END;
+
+CREATE TRIGGER conversation_entries_au AFTER UPDATE OF content ON conversation_entries BEGIN
+    INSERT INTO conversation_entries_fts (conversation_entries_fts, rowid, content)
+    VALUES ('delete', old.id, old.content);
+    INSERT INTO conversation_entries_fts (rowid, content) VALUES (new.id, new.content);
+END;
+
+-- Execution processes whose conversation has been fully indexed; anything
+-- missing here is picked up by the startup backfill
+CREATE TABLE conversation_index_state (
+    execution_process_id BLOB PRIMARY KEY,
+    indexed_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
@@ -0,0 +1,21 @@
+-- Logs of finished processes, compacted from execution_process_logs rows into
+-- zstd-compressed JSONL chunks
+CREATE TABLE execution_process_log_chunks (
+    id                   INTEGER PRIMARY KEY,
+    execution_id         BLOB NOT NULL,
+    chunk_index          INTEGER NOT NULL,
+    data                 BLOB NOT NULL,      -- zstd-compressed JSONL (one LogMsg per line)
+    raw_byte_size        INTEGER NOT NULL,
+    compressed_byte_size INTEGER NOT NULL,
+    -- FALSE once raw stdout/stderr has been replaced by normalized entries
+    contains_raw         BOOLEAN NOT NULL DEFAULT TRUE,
+    -- zstd-compressed, newline-separated RFC 3339 inserted_at of every line, so
+    -- compacted logs keep their timing; NULL for chunks of normalized entries
+    line_timestamps      BLOB,
+    inserted_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_id) REFERENCES execution_processes(id) ON DELETE CASCADE,
+    UNIQUE (execution_id, chunk_index)
+);
+
+CREATE INDEX idx_execution_process_log_chunks_contains_raw
+    ON execution_process_log_chunks (contains_raw, execution_id);
@@ -0,0 +1,26 @@
+-- Inline review comments anchored to a line on one side of a workspace diff
+CREATE TABLE review_comments (
+    id                        BLOB PRIMARY KEY,
+    workspace_id              BLOB NOT NULL,
+    repo_id                   BLOB NOT NULL,
+    file_path                 TEXT NOT NULL,
+    side                      TEXT NOT NULL CHECK (side IN ('old', 'new')),
+    line                      INTEGER NOT NULL,
+    body                      TEXT NOT NULL,
+    -- Git blob of the commented file side, used to re-anchor after rebases
+    anchor_blob               TEXT,
+    line_content              TEXT,
+    outdated                  BOOLEAN NOT NULL DEFAULT FALSE,
+    status                    TEXT NOT NULL DEFAULT 'open'
+                                CHECK (status IN ('open', 'sent', 'resolved')),
+    sent_execution_process_id BLOB,
+    created_at                TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at                TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+    FOREIGN KEY (repo_id) REFERENCES repos(id) ON DELETE CASCADE,
+    FOREIGN KEY (sent_execution_process_id) REFERENCES execution_processes(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_review_comments_workspace_id ON review_comments(workspace_id);
+CREATE INDEX idx_review_comments_sent_execution_process_id
+ON review_comments(sent_execution_process_id);
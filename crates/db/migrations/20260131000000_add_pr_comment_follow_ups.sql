@@ -0,0 +1,30 @@
+-- PR comments seen on a workspace's pull request and the follow-up turns that
+-- addressed them
+CREATE TABLE pr_comment_follow_ups (
+    id                   BLOB PRIMARY KEY,
+    workspace_id         BLOB NOT NULL,
+    merge_id             BLOB NOT NULL,
+    -- Provider-unique key of the comment
+    comment_key          TEXT NOT NULL,
+    comment_id           TEXT NOT NULL,
+    thread_id            INTEGER,
+    is_review            BOOLEAN NOT NULL DEFAULT FALSE,
+    status               TEXT NOT NULL DEFAULT 'queued'
+                           CHECK (status IN ('queued', 'sent', 'addressed', 'failed')),
+    session_id           BLOB,
+    execution_process_id BLOB,
+    -- Commit pushed after the agent addressed the comment
+    addressed_commit     TEXT,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+    FOREIGN KEY (merge_id) REFERENCES merges(id) ON DELETE CASCADE,
+    FOREIGN KEY (session_id) REFERENCES sessions(id) ON DELETE SET NULL,
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE SET NULL,
+    UNIQUE (merge_id, comment_key)
+);
+
+CREATE INDEX idx_pr_comment_follow_ups_session_status
+ON pr_comment_follow_ups(session_id, status);
+CREATE INDEX idx_pr_comment_follow_ups_execution_process_id
+ON pr_comment_follow_ups(execution_process_id);
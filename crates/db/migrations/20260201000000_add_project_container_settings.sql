@@ -0,0 +1,4 @@
+-- Run the project's workspaces inside an OCI container built from this image,
+-- optionally without network access
+ALTER TABLE projects ADD COLUMN container_image TEXT;
+ALTER TABLE projects ADD COLUMN container_network_isolated INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,29 @@
+-- SSH hosts that agents and scripts can run on instead of this machine
+CREATE TABLE worker_hosts (
+    id                        BLOB PRIMARY KEY,
+    name                      TEXT NOT NULL,
+    host                      TEXT NOT NULL,
+    port                      INTEGER,
+    username                  TEXT,
+    identity_file             TEXT,
+    -- Absolute directory on the host holding repository mirrors and worktrees
+    base_dir                  TEXT NOT NULL,
+    max_concurrent_executions INTEGER,
+    created_at                TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at                TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+
+-- Host a workspace's worktrees were created on; workspaces stay on it
+CREATE TABLE workspace_worker_hosts (
+    workspace_id   BLOB PRIMARY KEY,
+    worker_host_id BLOB NOT NULL,
+    created_at     TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+    FOREIGN KEY (worker_host_id) REFERENCES worker_hosts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_workspace_worker_hosts_worker_host_id
+ON workspace_worker_hosts(worker_host_id);
+
+ALTER TABLE projects ADD COLUMN worker_host_id BLOB REFERENCES worker_hosts(id) ON DELETE SET NULL;
+ALTER TABLE projects ADD COLUMN worker_host_auto INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,34 @@
+-- Accounts for people sharing one server; identities from a trusted proxy
+-- header are created here on first sight
+CREATE TABLE users (
+    id               BLOB PRIMARY KEY,
+    username         TEXT NOT NULL UNIQUE,
+    display_name     TEXT,
+    -- NULL for accounts that can only sign in through the proxy header
+    password_hash    TEXT,
+    role             TEXT NOT NULL DEFAULT 'member'
+                     CHECK (role IN ('admin', 'member')),
+    -- JSON overlay on the global config (editor, language, notifications)
+    config_overrides TEXT,
+    created_at       TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at       TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+
+-- Who did what; `actor` keeps the name once the user is deleted
+CREATE TABLE activities (
+    id           BLOB PRIMARY KEY,
+    user_id      BLOB REFERENCES users(id) ON DELETE SET NULL,
+    actor        TEXT NOT NULL,
+    project_id   BLOB NOT NULL,
+    task_id      BLOB,
+    workspace_id BLOB,
+    action       TEXT NOT NULL,
+    details      TEXT,
+    created_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_activities_project_id_created_at ON activities(project_id, created_at);
+CREATE INDEX idx_activities_task_id_created_at ON activities(task_id, created_at);
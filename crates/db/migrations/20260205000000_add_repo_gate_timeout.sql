@@ -0,0 +1,2 @@
+-- Gate scripts running longer than this are killed and fail the gate (NULL uses the default)
+ALTER TABLE repos ADD COLUMN gate_timeout_secs INTEGER;
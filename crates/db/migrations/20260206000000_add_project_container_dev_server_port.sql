@@ -0,0 +1,3 @@
+-- Port the dev server listens on inside a workspace container, published on the
+-- host's loopback interface
+ALTER TABLE projects ADD COLUMN container_dev_server_port INTEGER;
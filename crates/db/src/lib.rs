@@ -1,5 +1,16 @@
-use std::{str::FromStr, sync::Arc};
+use std::{
+    ffi::{CStr, CString, c_int},
+    path::Path,
+    ptr,
+    str::FromStr,
+    sync::Arc,
+};
 
+use libsqlite3_sys::{
+    SQLITE_OK, SQLITE_OPEN_CREATE, SQLITE_OPEN_READONLY, SQLITE_OPEN_READWRITE, sqlite3,
+    sqlite3_backup_finish, sqlite3_backup_init, sqlite3_backup_step, sqlite3_close, sqlite3_errmsg,
+    sqlite3_open_v2,
+};
 use sqlx::{
     Error, Pool, Sqlite, SqlitePool,
     migrate::MigrateError,
@@ -8,6 +19,8 @@ use sqlx::{
 use utils::assets::asset_dir;
 
 pub mod models;
+#[cfg(test)]
+mod test_utils;
 
 async fn run_migrations(pool: &Pool<Sqlite>) -> Result<(), Error> {
     use std::collections::HashSet;
@@ -67,12 +80,123 @@ async fn run_migrations(pool: &Pool<Sqlite>) -> Result<(), Error> {
     }
 }
 
+/// Version of the newest migration this build knows about
+pub fn latest_migration_version() -> i64 {
+    sqlx::migrate!("./migrations")
+        .iter()
+        .map(|migration| migration.version)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Connection to a database file outside the pool, closed on drop
+struct FileConnection(*mut sqlite3);
+
+impl FileConnection {
+    fn open(path: &Path, flags: c_int) -> Result<Self, Error> {
+        let path = CString::new(path.to_string_lossy().as_bytes())
+            .map_err(|e| Error::Configuration(e.into()))?;
+        let mut db = ptr::null_mut();
+        // SAFETY: `path` is NUL-terminated; `db` receives a handle even on
+        // failure, which the drop releases
+        let rc = unsafe { sqlite3_open_v2(path.as_ptr(), &mut db, flags, ptr::null()) };
+        let connection = Self(db);
+        if rc != SQLITE_OK {
+            return Err(last_error(connection.0, "open"));
+        }
+        Ok(connection)
+    }
+}
+
+impl Drop for FileConnection {
+    fn drop(&mut self) {
+        // SAFETY: the handle came from sqlite3_open_v2 and is closed exactly once
+        unsafe {
+            sqlite3_close(self.0);
+        }
+    }
+}
+
+fn last_error(db: *mut sqlite3, action: &str) -> Error {
+    // SAFETY: `db` is an open handle and the message is copied right away
+    let message = unsafe { CStr::from_ptr(sqlite3_errmsg(db)) };
+    Error::Protocol(format!(
+        "SQLite {action} failed: {}",
+        message.to_string_lossy()
+    ))
+}
+
+/// Copy the main database of `source` over `dest` with SQLite's online backup
+/// API. All pages are copied in one step, so the copy is a consistent snapshot
+/// even while other connections write to the source.
+fn copy_database(dest: *mut sqlite3, source: *mut sqlite3) -> Result<(), Error> {
+    // SAFETY: both handles are open for the duration of the call and the backup
+    // object is finished before returning
+    unsafe {
+        let backup = sqlite3_backup_init(dest, c"main".as_ptr(), source, c"main".as_ptr());
+        if !backup.is_null() {
+            sqlite3_backup_step(backup, -1);
+            if sqlite3_backup_finish(backup) == SQLITE_OK {
+                return Ok(());
+            }
+        }
+    }
+    Err(last_error(dest, "backup"))
+}
+
 #[derive(Clone)]
 pub struct DBService {
     pub pool: Pool<Sqlite>,
 }
 
 impl DBService {
+    /// Newest migration applied to this database
+    pub async fn applied_migration_version(&self) -> Result<i64, Error> {
+        sqlx::query_scalar(
+            "SELECT COALESCE(MAX(version), 0) FROM _sqlx_migrations WHERE success = 1",
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Write a consistent snapshot of the live database to a new file at `dest`
+    pub async fn backup_to(&self, dest: &Path) -> Result<(), Error> {
+        let dest = dest.to_path_buf();
+        self.with_raw_handle(move |live| {
+            let dest = FileConnection::open(&dest, SQLITE_OPEN_READWRITE | SQLITE_OPEN_CREATE)?;
+            copy_database(dest.0, live)
+        })
+        .await
+    }
+
+    /// Replace the live database with the snapshot at `source`, then bring it up
+    /// to this build's schema. Pooled connections see the restored data at once.
+    pub async fn restore_from(&self, source: &Path) -> Result<(), Error> {
+        let source = source.to_path_buf();
+        self.with_raw_handle(move |live| {
+            let source = FileConnection::open(&source, SQLITE_OPEN_READONLY)?;
+            copy_database(live, source.0)
+        })
+        .await?;
+        run_migrations(&self.pool).await
+    }
+
+    /// Run `f` with the raw handle of a pooled connection on the blocking pool,
+    /// since copying a whole database blocks until it is done
+    async fn with_raw_handle<F>(&self, f: F) -> Result<(), Error>
+    where
+        F: FnOnce(*mut sqlite3) -> Result<(), Error> + Send + 'static,
+    {
+        let mut conn = self.pool.acquire().await?;
+        let runtime = tokio::runtime::Handle::current();
+        tokio::task::spawn_blocking(move || {
+            let mut handle = runtime.block_on(conn.lock_handle())?;
+            f(handle.as_raw_handle().as_ptr())
+        })
+        .await
+        .map_err(|e| Error::Io(std::io::Error::other(e)))?
+    }
+
     pub async fn new() -> Result<DBService, Error> {
         let database_url = format!(
             "sqlite://{}",
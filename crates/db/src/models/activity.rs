@@ -0,0 +1,276 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, Type)]
+#[sqlx(type_name = "activity_action", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityAction {
+    TaskCreated,
+    FollowUpSent,
+    ApprovalResponded,
+    Merged,
+    PrCreated,
+}
+
+/// Something a person did, for the task and project activity feeds
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct Activity {
+    pub id: Uuid,
+    /// None for the local user, and once the user is deleted
+    pub user_id: Option<Uuid>,
+    pub actor: String,
+    pub project_id: Uuid,
+    pub task_id: Option<Uuid>,
+    pub workspace_id: Option<Uuid>,
+    pub action: ActivityAction,
+    /// Short action-specific summary, such as the PR URL or approval outcome
+    pub details: Option<String>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+/// Who performed an action
+#[derive(Debug, Clone)]
+pub struct Actor {
+    pub user_id: Option<Uuid>,
+    pub name: String,
+}
+
+impl Activity {
+    pub async fn record_for_task(
+        pool: &SqlitePool,
+        actor: &Actor,
+        task_id: Uuid,
+        action: ActivityAction,
+        details: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            r#"INSERT INTO activities (id, user_id, actor, project_id, task_id, action, details)
+               SELECT $1, $2, $3, project_id, id, $4, $5 FROM tasks WHERE id = $6"#,
+            id,
+            actor.user_id,
+            actor.name,
+            action,
+            details,
+            task_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn record_for_workspace(
+        pool: &SqlitePool,
+        actor: &Actor,
+        workspace_id: Uuid,
+        action: ActivityAction,
+        details: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            r#"INSERT INTO activities
+                   (id, user_id, actor, project_id, task_id, workspace_id, action, details)
+               SELECT $1, $2, $3, t.project_id, t.id, w.id, $4, $5
+               FROM workspaces w
+               JOIN tasks t ON t.id = w.task_id
+               WHERE w.id = $6"#,
+            id,
+            actor.user_id,
+            actor.name,
+            action,
+            details,
+            workspace_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Newest first
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Activity,
+            r#"SELECT id as "id!: Uuid", user_id as "user_id: Uuid", actor, project_id as "project_id!: Uuid", task_id as "task_id: Uuid", workspace_id as "workspace_id: Uuid", action as "action!: ActivityAction", details, created_at as "created_at!: DateTime<Utc>"
+               FROM activities
+               WHERE task_id = $1
+               ORDER BY created_at DESC
+               LIMIT $2"#,
+            task_id,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Newest first
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Activity,
+            r#"SELECT id as "id!: Uuid", user_id as "user_id: Uuid", actor, project_id as "project_id!: Uuid", task_id as "task_id: Uuid", workspace_id as "workspace_id: Uuid", action as "action!: ActivityAction", details, created_at as "created_at!: DateTime<Utc>"
+               FROM activities
+               WHERE project_id = $1
+               ORDER BY created_at DESC
+               LIMIT $2"#,
+            project_id,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::user::{CreateUser, User, UserRole},
+        test_utils::{insert_session, test_pool},
+    };
+
+    /// Insert a second task in the project of `task_id`
+    async fn insert_sibling_task(pool: &SqlitePool, task_id: Uuid) -> Uuid {
+        let id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO tasks (id, project_id, title) SELECT ?, project_id, 'other' FROM tasks WHERE id = ?",
+        )
+        .bind(id)
+        .bind(task_id)
+        .execute(pool)
+        .await
+        .unwrap();
+        id
+    }
+
+    /// Spread the recorded activities one minute apart in insertion order
+    async fn space_out(pool: &SqlitePool) {
+        sqlx::query(
+            "UPDATE activities SET created_at = datetime('2026-01-01', '+' || rowid || ' minutes')",
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn feeds_list_recorded_activity_newest_first() {
+        let pool = test_pool().await;
+        let (task_id, session_id) = insert_session(&pool).await;
+        let other_task_id = insert_sibling_task(&pool, task_id).await;
+        let (project_id, workspace_id): (Uuid, Uuid) = sqlx::query_as(
+            r#"SELECT t.project_id, s.workspace_id
+               FROM sessions s JOIN workspaces w ON w.id = s.workspace_id JOIN tasks t ON t.id = w.task_id
+               WHERE s.id = ?"#,
+        )
+        .bind(session_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let local = Actor {
+            user_id: None,
+            name: "local".to_string(),
+        };
+
+        Activity::record_for_task(&pool, &local, task_id, ActivityAction::TaskCreated, None)
+            .await
+            .unwrap();
+        Activity::record_for_workspace(
+            &pool,
+            &local,
+            workspace_id,
+            ActivityAction::PrCreated,
+            Some("https://example.com/pr/1"),
+        )
+        .await
+        .unwrap();
+        Activity::record_for_task(
+            &pool,
+            &local,
+            other_task_id,
+            ActivityAction::TaskCreated,
+            None,
+        )
+        .await
+        .unwrap();
+        // Unknown tasks record nothing
+        Activity::record_for_task(
+            &pool,
+            &local,
+            Uuid::new_v4(),
+            ActivityAction::TaskCreated,
+            None,
+        )
+        .await
+        .unwrap();
+        space_out(&pool).await;
+
+        let task_feed = Activity::find_by_task_id(&pool, task_id, 10).await.unwrap();
+        assert_eq!(
+            task_feed.iter().map(|a| a.action).collect::<Vec<_>>(),
+            [ActivityAction::PrCreated, ActivityAction::TaskCreated]
+        );
+        assert_eq!(task_feed[0].project_id, project_id);
+        assert_eq!(task_feed[0].workspace_id, Some(workspace_id));
+        assert_eq!(
+            task_feed[0].details.as_deref(),
+            Some("https://example.com/pr/1")
+        );
+        assert_eq!(task_feed[1].workspace_id, None);
+
+        let project_feed = Activity::find_by_project_id(&pool, project_id, 10)
+            .await
+            .unwrap();
+        assert_eq!(
+            project_feed.iter().map(|a| a.task_id).collect::<Vec<_>>(),
+            [Some(other_task_id), Some(task_id), Some(task_id)]
+        );
+        let limited = Activity::find_by_project_id(&pool, project_id, 1)
+            .await
+            .unwrap();
+        assert_eq!(limited.len(), 1);
+        assert_eq!(limited[0].task_id, Some(other_task_id));
+    }
+
+    #[tokio::test]
+    async fn activity_keeps_the_actor_name_after_the_user_is_deleted() {
+        let pool = test_pool().await;
+        let (task_id, _) = insert_session(&pool).await;
+        let user = User::create(
+            &pool,
+            Uuid::new_v4(),
+            &CreateUser {
+                username: "ada".to_string(),
+                display_name: Some("Ada".to_string()),
+                role: UserRole::Member,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+        let actor = Actor {
+            user_id: Some(user.id),
+            name: user.name().to_string(),
+        };
+        Activity::record_for_task(&pool, &actor, task_id, ActivityAction::FollowUpSent, None)
+            .await
+            .unwrap();
+
+        User::delete(&pool, user.id).await.unwrap();
+
+        let feed = Activity::find_by_task_id(&pool, task_id, 10).await.unwrap();
+        assert_eq!(feed.len(), 1);
+        assert_eq!(feed[0].user_id, None);
+        assert_eq!(feed[0].actor, "Ada");
+    }
+}
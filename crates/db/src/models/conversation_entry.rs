@@ -0,0 +1,195 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Searchable text extracted from one normalized conversation entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConversationEntryText {
+    pub entry_index: i64,
+    /// Normalized entry type, e.g. `assistant_message`, `tool_use`, `error_message`
+    pub entry_type: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, TS)]
+pub struct ConversationSearchParams {
+    pub query: String,
+    pub project_id: Option<Uuid>,
+    /// Executor name as stored on the session, e.g. `CLAUDE_CODE`
+    pub executor: Option<String>,
+    /// Only runs started at or after this time
+    #[ts(type = "Date | null")]
+    pub from: Option<DateTime<Utc>>,
+    /// Only runs started before this time
+    #[ts(type = "Date | null")]
+    pub to: Option<DateTime<Utc>>,
+    /// Comma-separated entry types to match
+    pub entry_types: Option<String>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct ConversationSearchHit {
+    pub execution_process_id: Uuid,
+    pub session_id: Uuid,
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub task_title: String,
+    pub executor: Option<String>,
+    /// Index into the normalized entries of the execution process
+    pub entry_index: i64,
+    pub entry_type: String,
+    /// Matching text with hits wrapped in `<mark>` tags
+    pub snippet: String,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+const DEFAULT_SEARCH_LIMIT: i64 = 50;
+const MAX_SEARCH_LIMIT: i64 = 200;
+
+pub struct ConversationEntry;
+
+impl ConversationEntry {
+    /// Insert or replace the searchable text of conversation entries for a process
+    pub async fn upsert_many(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        entries: &[ConversationEntryText],
+    ) -> Result<(), sqlx::Error> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let mut tx = pool.begin().await?;
+        for entry in entries {
+            sqlx::query!(
+                r#"INSERT INTO conversation_entries (execution_process_id, entry_index, entry_type, content)
+                   VALUES ($1, $2, $3, $4)
+                   ON CONFLICT(execution_process_id, entry_index) DO UPDATE SET
+                       entry_type = excluded.entry_type,
+                       content = excluded.content,
+                       updated_at = datetime('now', 'subsec')
+                   WHERE content != excluded.content OR entry_type != excluded.entry_type"#,
+                execution_process_id,
+                entry.entry_index,
+                entry.entry_type,
+                entry.content
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+
+    /// Record that the conversation of a process has been fully indexed
+    pub async fn mark_indexed(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO conversation_index_state (execution_process_id)
+               VALUES ($1)
+               ON CONFLICT(execution_process_id) DO UPDATE SET indexed_at = datetime('now', 'subsec')"#,
+            execution_process_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Finished coding agent processes whose conversation has not been indexed yet
+    pub async fn find_unindexed_process_ids(
+        pool: &SqlitePool,
+        limit: i64,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT ep.id AS "id!: Uuid"
+               FROM execution_processes ep
+               LEFT JOIN conversation_index_state cis ON cis.execution_process_id = ep.id
+               WHERE cis.execution_process_id IS NULL
+                 AND ep.run_reason = 'codingagent'
+                 AND ep.status != 'running'
+               ORDER BY ep.created_at DESC
+               LIMIT $1"#,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Full-text search across indexed conversation entries, newest runs first
+    pub async fn search(
+        pool: &SqlitePool,
+        params: &ConversationSearchParams,
+    ) -> Result<Vec<ConversationSearchHit>, sqlx::Error> {
+        let Some(match_expr) = fts_match_expression(&params.query) else {
+            return Ok(Vec::new());
+        };
+        let limit = params
+            .limit
+            .unwrap_or(DEFAULT_SEARCH_LIMIT)
+            .clamp(1, MAX_SEARCH_LIMIT);
+
+        let entry_types = params
+            .entry_types
+            .as_deref()
+            .map(|types| types.replace(' ', ""))
+            .filter(|types| !types.is_empty());
+        let executor = params.executor.as_deref();
+
+        sqlx::query_as!(
+            ConversationSearchHit,
+            r#"SELECT ep.id AS "execution_process_id!: Uuid",
+                      s.id AS "session_id!: Uuid",
+                      w.id AS "workspace_id!: Uuid",
+                      t.id AS "task_id!: Uuid",
+                      t.project_id AS "project_id!: Uuid",
+                      t.title AS "task_title!",
+                      s.executor,
+                      ce.entry_index AS "entry_index!",
+                      ce.entry_type AS "entry_type!",
+                      snippet(conversation_entries_fts, 0, '<mark>', '</mark>', '…', 24) AS "snippet!: String",
+                      ep.created_at AS "created_at!: DateTime<Utc>"
+               FROM conversation_entries_fts
+               JOIN conversation_entries ce ON ce.id = conversation_entries_fts.rowid
+               JOIN execution_processes ep ON ep.id = ce.execution_process_id
+               JOIN sessions s ON s.id = ep.session_id
+               JOIN workspaces w ON w.id = s.workspace_id
+               JOIN tasks t ON t.id = w.task_id
+               WHERE conversation_entries_fts MATCH $1
+                 AND ($2 IS NULL OR t.project_id = $2)
+                 AND ($3 IS NULL OR s.executor = $3)
+                 AND ($4 IS NULL OR ep.created_at >= datetime($4))
+                 AND ($5 IS NULL OR ep.created_at < datetime($5))
+                 AND ($6 IS NULL OR instr(',' || $6 || ',', ',' || ce.entry_type || ',') > 0)
+               ORDER BY ep.created_at DESC, ce.entry_index ASC
+               LIMIT $7"#,
+            match_expr,
+            params.project_id,
+            executor,
+            params.from,
+            params.to,
+            entry_types,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
+
+/// Turn free text into an FTS5 expression: every whitespace-separated term is
+/// quoted so paths and commands like `src/main.rs` match as phrases.
+fn fts_match_expression(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect();
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
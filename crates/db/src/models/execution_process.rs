@@ -57,6 +57,7 @@ pub enum ExecutionProcessRunReason {
     CleanupScript,
     CodingAgent,
     DevServer,
+    GateScript,
 }
 
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
@@ -282,6 +283,21 @@ impl ExecutionProcess {
         .await
     }
 
+    /// Running execution processes grouped by the session's executor and the run reason
+    pub async fn count_running_by_executor(
+        pool: &SqlitePool,
+    ) -> Result<Vec<(String, String, i64)>, sqlx::Error> {
+        sqlx::query_as::<_, (String, String, i64)>(
+            r#"SELECT COALESCE(s.executor, 'none'), ep.run_reason, COUNT(*)
+               FROM execution_processes ep
+               JOIN sessions s ON ep.session_id = s.id
+               WHERE ep.status = 'running'
+               GROUP BY 1, 2"#,
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     /// Find running dev servers for a specific project
     pub async fn find_running_dev_servers_by_project(
         pool: &SqlitePool,
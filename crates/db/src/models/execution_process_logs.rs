@@ -5,6 +5,12 @@ use ts_rs::TS;
 use utils::log_msg::LogMsg;
 use uuid::Uuid;
 
+/// Uncompressed JSONL bytes collected into one chunk before it is compressed
+const CHUNK_RAW_BYTES: usize = 1024 * 1024;
+const ZSTD_LEVEL: i32 = 3;
+/// Compression ratio assumed when estimating savings before any chunk exists
+const ESTIMATED_COMPRESSION_RATIO: f64 = 0.15;
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
 pub struct ExecutionProcessLogs {
     pub execution_id: Uuid,
@@ -13,13 +19,98 @@ pub struct ExecutionProcessLogs {
     pub inserted_at: DateTime<Utc>,
 }
 
+struct LogChunk {
+    data: Vec<u8>,
+    raw_byte_size: i64,
+    inserted_at: DateTime<Utc>,
+    line_timestamps: Option<Vec<u8>>,
+}
+
+impl LogChunk {
+    /// Expand a chunk back into one record per line when the original line
+    /// timestamps were kept, otherwise into a single record
+    fn into_records(self, execution_id: Uuid) -> Result<Vec<ExecutionProcessLogs>, sqlx::Error> {
+        let logs = decompress_chunk(&self.data)?;
+        if let Some(line_timestamps) = &self.line_timestamps {
+            let timestamps = decompress_chunk(line_timestamps)?
+                .lines()
+                .map(DateTime::parse_from_rfc3339)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| sqlx::Error::Decode(e.into()))?;
+            let lines: Vec<&str> = logs.split_inclusive('\n').collect();
+            if lines.len() == timestamps.len() {
+                return Ok(lines
+                    .into_iter()
+                    .zip(timestamps)
+                    .map(|(line, inserted_at)| ExecutionProcessLogs {
+                        execution_id,
+                        logs: line.to_string(),
+                        byte_size: line.len() as i64,
+                        inserted_at: inserted_at.with_timezone(&Utc),
+                    })
+                    .collect());
+            }
+        }
+        Ok(vec![ExecutionProcessLogs {
+            execution_id,
+            logs,
+            byte_size: self.raw_byte_size,
+            inserted_at: self.inserted_at,
+        }])
+    }
+}
+
+/// Rows collected into one chunk before compression
+struct PendingChunk {
+    logs: String,
+    line_timestamps: Option<String>,
+}
+
+/// Storage used by execution process logs and how much of it can be reclaimed
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct LogStorageStats {
+    /// Bytes held in uncompressed log rows
+    pub uncompressed_bytes: i64,
+    /// Uncompressed bytes belonging to finished processes, awaiting compaction
+    pub pending_compaction_bytes: i64,
+    /// Bytes held in compressed chunks
+    pub compressed_bytes: i64,
+    /// Original size of the logs held in compressed chunks
+    pub compressed_original_bytes: i64,
+    /// Estimated bytes freed by compacting finished processes
+    pub reclaimable_by_compaction_bytes: i64,
+    /// Compressed bytes of raw output past the retention period; normalized
+    /// entries are kept, so slightly less than this is actually freed
+    pub reclaimable_by_retention_bytes: i64,
+    pub raw_log_retention_days: Option<u32>,
+}
+
 impl ExecutionProcessLogs {
-    /// Find logs by execution process ID
+    /// Find logs by execution process ID, decompressing compacted chunks first
     pub async fn find_by_execution_id(
         pool: &SqlitePool,
         execution_id: Uuid,
     ) -> Result<Vec<Self>, sqlx::Error> {
-        sqlx::query_as!(
+        let chunks = sqlx::query_as!(
+            LogChunk,
+            r#"SELECT data,
+                      raw_byte_size,
+                      inserted_at AS "inserted_at!: DateTime<Utc>",
+                      line_timestamps
+               FROM execution_process_log_chunks
+               WHERE execution_id = $1
+               ORDER BY chunk_index ASC"#,
+            execution_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut records = Vec::new();
+        for chunk in chunks {
+            records.extend(chunk.into_records(execution_id)?);
+        }
+
+        let rows = sqlx::query_as!(
             ExecutionProcessLogs,
             r#"SELECT 
                 execution_id as "execution_id!: Uuid",
@@ -32,7 +123,10 @@ impl ExecutionProcessLogs {
             execution_id
         )
         .fetch_all(pool)
-        .await
+        .await?;
+        records.extend(rows);
+
+        Ok(records)
     }
 
     /// Parse JSONL logs back into Vec<LogMsg>
@@ -47,6 +141,21 @@ impl ExecutionProcessLogs {
         Ok(messages)
     }
 
+    /// Parse JSONL logs into messages paired with the time they were recorded
+    pub fn parse_timed_logs(
+        records: &[Self],
+    ) -> Result<Vec<(DateTime<Utc>, LogMsg)>, serde_json::Error> {
+        let mut messages = Vec::new();
+        for record in records {
+            for line in record.logs.lines() {
+                if !line.trim().is_empty() {
+                    messages.push((record.inserted_at, serde_json::from_str(line)?));
+                }
+            }
+        }
+        Ok(messages)
+    }
+
     /// Append a JSONL line to the logs for an execution process
     pub async fn append_log_line(
         pool: &SqlitePool,
@@ -66,4 +175,354 @@ impl ExecutionProcessLogs {
 
         Ok(())
     }
+
+    /// Move the uncompressed log rows of a process into zstd-compressed chunks.
+    /// Returns the number of rows compacted.
+    pub async fn compact(pool: &SqlitePool, execution_id: Uuid) -> Result<usize, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+
+        let rows = sqlx::query!(
+            r#"SELECT rowid AS "rowid!: i64",
+                      logs,
+                      inserted_at AS "inserted_at!: DateTime<Utc>"
+               FROM execution_process_logs
+               WHERE execution_id = $1
+               ORDER BY inserted_at ASC, rowid ASC"#,
+            execution_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        let Some(max_rowid) = rows.iter().map(|row| row.rowid).max() else {
+            return Ok(0);
+        };
+
+        let next_index = sqlx::query_scalar!(
+            r#"SELECT COALESCE(MAX(chunk_index) + 1, 0) AS "next_index!: i64"
+               FROM execution_process_log_chunks
+               WHERE execution_id = $1"#,
+            execution_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let chunks = group_into_chunks(
+            rows.iter()
+                .map(|row| (row.logs.as_str(), Some(row.inserted_at))),
+        );
+        insert_chunks(&mut tx, execution_id, next_index, &chunks, true).await?;
+
+        // Only delete what was read; rows appended meanwhile are compacted next time
+        sqlx::query!(
+            "DELETE FROM execution_process_logs WHERE execution_id = $1 AND rowid <= $2",
+            execution_id,
+            max_rowid
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(rows.len())
+    }
+
+    /// Replace all stored logs of a process with the given messages, dropping
+    /// the raw output they were derived from
+    pub async fn replace_with_normalized(
+        pool: &SqlitePool,
+        execution_id: Uuid,
+        messages: &[LogMsg],
+    ) -> Result<(), sqlx::Error> {
+        let lines = messages
+            .iter()
+            .map(|msg| serde_json::to_string(msg).map(|line| format!("{line}\n")))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| sqlx::Error::Encode(e.into()))?;
+        let chunks = group_into_chunks(lines.iter().map(|line| (line.as_str(), None)));
+
+        let mut tx = pool.begin().await?;
+        sqlx::query!(
+            "DELETE FROM execution_process_log_chunks WHERE execution_id = $1",
+            execution_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "DELETE FROM execution_process_logs WHERE execution_id = $1",
+            execution_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        insert_chunks(&mut tx, execution_id, 0, &chunks, false).await?;
+        tx.commit().await
+    }
+
+    /// Finished processes that still have uncompressed log rows
+    pub async fn find_uncompacted_execution_ids(
+        pool: &SqlitePool,
+        limit: i64,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT DISTINCT epl.execution_id AS "execution_id!: Uuid"
+               FROM execution_process_logs epl
+               JOIN execution_processes ep ON ep.id = epl.execution_id
+               WHERE ep.status != 'running'
+               LIMIT $1"#,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Coding agent processes that finished more than `retention_days` ago and
+    /// still keep their raw output
+    pub async fn find_expired_raw_execution_ids(
+        pool: &SqlitePool,
+        retention_days: u32,
+        limit: i64,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        let cutoff = retention_cutoff(retention_days);
+        sqlx::query_scalar!(
+            r#"SELECT DISTINCT c.execution_id AS "execution_id!: Uuid"
+               FROM execution_process_log_chunks c
+               JOIN execution_processes ep ON ep.id = c.execution_id
+               WHERE c.contains_raw = 1
+                 AND ep.run_reason = 'codingagent'
+                 AND ep.status != 'running'
+                 AND COALESCE(ep.completed_at, ep.created_at) < datetime('now', $1)
+               LIMIT $2"#,
+            cutoff,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn storage_stats(
+        pool: &SqlitePool,
+        raw_log_retention_days: Option<u32>,
+    ) -> Result<LogStorageStats, sqlx::Error> {
+        let uncompressed = sqlx::query!(
+            r#"SELECT COALESCE(SUM(epl.byte_size), 0) AS "uncompressed_bytes!: i64",
+                      COALESCE(SUM(CASE WHEN ep.status != 'running' THEN epl.byte_size ELSE 0 END), 0)
+                          AS "pending_compaction_bytes!: i64"
+               FROM execution_process_logs epl
+               LEFT JOIN execution_processes ep ON ep.id = epl.execution_id"#
+        )
+        .fetch_one(pool)
+        .await?;
+        let (uncompressed_bytes, pending_compaction_bytes) = (
+            uncompressed.uncompressed_bytes,
+            uncompressed.pending_compaction_bytes,
+        );
+
+        let compressed = sqlx::query!(
+            r#"SELECT COALESCE(SUM(compressed_byte_size), 0) AS "compressed_bytes!: i64",
+                      COALESCE(SUM(raw_byte_size), 0) AS "original_bytes!: i64"
+               FROM execution_process_log_chunks"#
+        )
+        .fetch_one(pool)
+        .await?;
+        let (compressed_bytes, compressed_original_bytes) =
+            (compressed.compressed_bytes, compressed.original_bytes);
+
+        let reclaimable_by_retention_bytes = match raw_log_retention_days {
+            Some(days) => {
+                let cutoff = retention_cutoff(days);
+                sqlx::query_scalar!(
+                    r#"SELECT COALESCE(SUM(c.compressed_byte_size), 0) AS "bytes!: i64"
+                       FROM execution_process_log_chunks c
+                       JOIN execution_processes ep ON ep.id = c.execution_id
+                       WHERE c.contains_raw = 1
+                         AND ep.run_reason = 'codingagent'
+                         AND ep.status != 'running'
+                         AND COALESCE(ep.completed_at, ep.created_at) < datetime('now', $1)"#,
+                    cutoff
+                )
+                .fetch_one(pool)
+                .await?
+            }
+            None => 0,
+        };
+
+        let ratio = if compressed_original_bytes > 0 {
+            compressed_bytes as f64 / compressed_original_bytes as f64
+        } else {
+            ESTIMATED_COMPRESSION_RATIO
+        };
+        let reclaimable_by_compaction_bytes =
+            (pending_compaction_bytes as f64 * (1.0 - ratio)).max(0.0) as i64;
+
+        Ok(LogStorageStats {
+            uncompressed_bytes,
+            pending_compaction_bytes,
+            compressed_bytes,
+            compressed_original_bytes,
+            reclaimable_by_compaction_bytes,
+            reclaimable_by_retention_bytes,
+            raw_log_retention_days,
+        })
+    }
+}
+
+fn retention_cutoff(retention_days: u32) -> String {
+    format!("-{retention_days} days")
+}
+
+/// Concatenate JSONL rows into chunks of roughly `CHUNK_RAW_BYTES`, keeping the
+/// timestamp of every row when all rows have one
+fn group_into_chunks<'a>(
+    rows: impl Iterator<Item = (&'a str, Option<DateTime<Utc>>)>,
+) -> Vec<PendingChunk> {
+    let mut chunks = Vec::new();
+    let mut current: Option<PendingChunk> = None;
+    for (row, inserted_at) in rows {
+        if current
+            .as_ref()
+            .is_some_and(|chunk| chunk.logs.len() + row.len() > CHUNK_RAW_BYTES)
+        {
+            chunks.extend(current.take());
+        }
+        let chunk = current.get_or_insert_with(|| PendingChunk {
+            logs: String::new(),
+            line_timestamps: inserted_at.map(|_| String::new()),
+        });
+        chunk.logs.push_str(row);
+        chunk.line_timestamps = match (chunk.line_timestamps.take(), inserted_at) {
+            (Some(mut timestamps), Some(inserted_at)) => {
+                timestamps.push_str(&inserted_at.to_rfc3339());
+                timestamps.push('\n');
+                Some(timestamps)
+            }
+            _ => None,
+        };
+    }
+    chunks.extend(current);
+    chunks
+}
+
+async fn insert_chunks(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    execution_id: Uuid,
+    first_index: i64,
+    chunks: &[PendingChunk],
+    contains_raw: bool,
+) -> Result<(), sqlx::Error> {
+    for (offset, chunk) in chunks.iter().enumerate() {
+        let data = compress_chunk(&chunk.logs)?;
+        let line_timestamps = chunk
+            .line_timestamps
+            .as_deref()
+            .map(compress_chunk)
+            .transpose()?;
+        let chunk_index = first_index + offset as i64;
+        let raw_byte_size = chunk.logs.len() as i64;
+        let compressed_byte_size = data.len() as i64;
+        sqlx::query!(
+            r#"INSERT INTO execution_process_log_chunks
+                   (execution_id, chunk_index, data, raw_byte_size, compressed_byte_size,
+                    contains_raw, line_timestamps)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)"#,
+            execution_id,
+            chunk_index,
+            data,
+            raw_byte_size,
+            compressed_byte_size,
+            contains_raw,
+            line_timestamps
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+    Ok(())
+}
+
+fn compress_chunk(text: &str) -> Result<Vec<u8>, sqlx::Error> {
+    zstd::encode_all(text.as_bytes(), ZSTD_LEVEL).map_err(|e| sqlx::Error::Encode(e.into()))
+}
+
+fn decompress_chunk(data: &[u8]) -> Result<String, sqlx::Error> {
+    let bytes = zstd::decode_all(data).map_err(|e| sqlx::Error::Decode(e.into()))?;
+    String::from_utf8(bytes).map_err(|e| sqlx::Error::Decode(e.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{insert_finished_process, insert_session, test_pool};
+
+    #[tokio::test]
+    async fn compacted_logs_read_back_and_expire_after_retention() {
+        let pool = test_pool().await;
+        let (_, session_id) = insert_session(&pool).await;
+        let recent = insert_finished_process(&pool, session_id, 1).await;
+        let expired = insert_finished_process(&pool, session_id, 40).await;
+
+        for id in [recent, expired] {
+            for line in ["first", "second", "third"] {
+                let msg = serde_json::to_string(&LogMsg::Stdout(line.to_string())).unwrap();
+                ExecutionProcessLogs::append_log_line(&pool, id, &format!("{msg}\n"))
+                    .await
+                    .unwrap();
+            }
+        }
+        let before = ExecutionProcessLogs::find_by_execution_id(&pool, expired)
+            .await
+            .unwrap();
+
+        let uncompacted = ExecutionProcessLogs::find_uncompacted_execution_ids(&pool, 10)
+            .await
+            .unwrap();
+        assert_eq!(uncompacted.len(), 2);
+        for id in uncompacted {
+            assert_eq!(ExecutionProcessLogs::compact(&pool, id).await.unwrap(), 3);
+        }
+
+        // Every line comes back with the time it was originally recorded
+        let after = ExecutionProcessLogs::find_by_execution_id(&pool, expired)
+            .await
+            .unwrap();
+        assert_eq!(after.len(), before.len());
+        for (a, b) in after.iter().zip(&before) {
+            assert_eq!(a.logs, b.logs);
+            assert_eq!(a.inserted_at, b.inserted_at);
+        }
+
+        let stats = ExecutionProcessLogs::storage_stats(&pool, Some(30))
+            .await
+            .unwrap();
+        assert_eq!(stats.uncompressed_bytes, 0);
+        assert!(stats.compressed_bytes > 0);
+        assert!(stats.reclaimable_by_retention_bytes > 0);
+
+        let expired_ids = ExecutionProcessLogs::find_expired_raw_execution_ids(&pool, 30, 10)
+            .await
+            .unwrap();
+        assert_eq!(expired_ids, vec![expired]);
+
+        let normalized = vec![LogMsg::Stdout("normalized".to_string())];
+        ExecutionProcessLogs::replace_with_normalized(&pool, expired, &normalized)
+            .await
+            .unwrap();
+        let records = ExecutionProcessLogs::find_by_execution_id(&pool, expired)
+            .await
+            .unwrap();
+        let messages = ExecutionProcessLogs::parse_logs(&records).unwrap();
+        assert!(matches!(messages.as_slice(), [LogMsg::Stdout(line)] if line == "normalized"));
+        assert!(
+            ExecutionProcessLogs::find_expired_raw_execution_ids(&pool, 30, 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // The recent process keeps its raw output
+        let recent_records = ExecutionProcessLogs::find_by_execution_id(&pool, recent)
+            .await
+            .unwrap();
+        assert_eq!(
+            ExecutionProcessLogs::parse_logs(&recent_records)
+                .unwrap()
+                .len(),
+            3
+        );
+    }
 }
@@ -1,16 +1,22 @@
+pub mod activity;
 pub mod coding_agent_turn;
+pub mod conversation_entry;
 pub mod execution_process;
 pub mod execution_process_logs;
 pub mod execution_process_repo_state;
 pub mod image;
 pub mod merge;
+pub mod pr_comment_follow_up;
 pub mod project;
 pub mod project_repo;
 pub mod repo;
+pub mod review_comment;
 pub mod scratch;
 pub mod session;
 pub mod tag;
 pub mod task;
 pub mod task_session;
+pub mod user;
+pub mod worker_host;
 pub mod workspace;
 pub mod workspace_repo;
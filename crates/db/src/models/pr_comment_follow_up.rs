@@ -0,0 +1,159 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "pr_comment_follow_up_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PrCommentFollowUpStatus {
+    /// Waiting in the session's follow-up queue
+    Queued,
+    /// Carried by a running or finished coding agent turn
+    Sent,
+    Addressed,
+    Failed,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct PrCommentFollowUp {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub merge_id: Uuid,
+    pub comment_key: String,
+    pub comment_id: String,
+    pub thread_id: Option<i64>,
+    pub is_review: bool,
+    pub status: PrCommentFollowUpStatus,
+    pub session_id: Option<Uuid>,
+    pub execution_process_id: Option<Uuid>,
+    pub addressed_commit: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A PR comment as recorded for follow-up tracking
+#[derive(Debug, Clone)]
+pub struct PrCommentRef {
+    pub comment_key: String,
+    pub comment_id: String,
+    pub thread_id: Option<i64>,
+    pub is_review: bool,
+}
+
+const SELECT_COLUMNS: &str = r#"SELECT id,
+                      workspace_id,
+                      merge_id,
+                      comment_key,
+                      comment_id,
+                      thread_id,
+                      is_review,
+                      status,
+                      session_id,
+                      execution_process_id,
+                      addressed_commit,
+                      created_at,
+                      updated_at
+               FROM pr_comment_follow_ups"#;
+
+impl PrCommentFollowUp {
+    pub async fn find_by_merge_id(
+        pool: &SqlitePool,
+        merge_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, PrCommentFollowUp>(&format!(
+            "{SELECT_COLUMNS} WHERE merge_id = ? ORDER BY created_at"
+        ))
+        .bind(merge_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Comments carried by the given coding agent turn
+    pub async fn find_sent_by_execution_process_id(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, PrCommentFollowUp>(&format!(
+            "{SELECT_COLUMNS} WHERE execution_process_id = ? AND status = 'sent'"
+        ))
+        .bind(execution_process_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Record comments with `status`. Comments already sent or addressed are kept
+    /// as they are, so re-importing never sends a comment twice.
+    pub async fn record_many(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        merge_id: Uuid,
+        comments: &[PrCommentRef],
+        status: PrCommentFollowUpStatus,
+        session_id: Option<Uuid>,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        for comment in comments {
+            sqlx::query(
+                r#"INSERT INTO pr_comment_follow_ups
+                       (id, workspace_id, merge_id, comment_key, comment_id, thread_id, is_review, status, session_id)
+                   VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                   ON CONFLICT(merge_id, comment_key) DO UPDATE SET
+                       status = excluded.status,
+                       session_id = excluded.session_id,
+                       execution_process_id = NULL,
+                       updated_at = datetime('now', 'subsec')
+                   WHERE pr_comment_follow_ups.status IN ('queued', 'failed')"#,
+            )
+            .bind(Uuid::new_v4())
+            .bind(workspace_id)
+            .bind(merge_id)
+            .bind(&comment.comment_key)
+            .bind(&comment.comment_id)
+            .bind(comment.thread_id)
+            .bind(comment.is_review)
+            .bind(status)
+            .bind(session_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+
+    /// Attach the comments queued on a session to the turn that picked up the queue
+    pub async fn mark_queued_sent(
+        pool: &SqlitePool,
+        session_id: Uuid,
+        execution_process_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"UPDATE pr_comment_follow_ups
+               SET status = 'sent', execution_process_id = ?, updated_at = datetime('now', 'subsec')
+               WHERE session_id = ? AND status = 'queued'"#,
+        )
+        .bind(execution_process_id)
+        .bind(session_id)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn update_status(
+        pool: &SqlitePool,
+        id: Uuid,
+        status: PrCommentFollowUpStatus,
+        addressed_commit: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"UPDATE pr_comment_follow_ups
+               SET status = ?, addressed_commit = ?, updated_at = datetime('now', 'subsec')
+               WHERE id = ?"#,
+        )
+        .bind(status)
+        .bind(addressed_commit)
+        .bind(id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_with::rust::double_option;
 use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
 use thiserror::Error;
 use ts_rs::TS;
@@ -15,6 +16,10 @@ pub enum ProjectError {
     ProjectNotFound,
     #[error("Failed to create project: {0}")]
     CreateFailed(String),
+    #[error("Invalid container image: {0:?}")]
+    InvalidContainerImage(String),
+    #[error("Invalid dev server port: {0}")]
+    InvalidDevServerPort(i64),
 }
 
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
@@ -23,6 +28,17 @@ pub struct Project {
     pub name: String,
     pub default_agent_working_dir: Option<String>,
     pub remote_project_id: Option<Uuid>,
+    /// Image for workspace containers; workspaces run on the host when unset
+    pub container_image: Option<String>,
+    /// Start workspace containers without network access
+    pub container_network_isolated: bool,
+    /// Port the dev server listens on in the container, published on the
+    /// host's loopback interface
+    pub container_dev_server_port: Option<i64>,
+    /// SSH worker host that runs the project's agents and scripts
+    pub worker_host_id: Option<Uuid>,
+    /// Pick the least loaded worker host for each new workspace
+    pub worker_host_auto: bool,
     #[ts(type = "Date")]
     pub created_at: DateTime<Utc>,
     #[ts(type = "Date")]
@@ -38,6 +54,52 @@ pub struct CreateProject {
 #[derive(Debug, Deserialize, TS)]
 pub struct UpdateProject {
     pub name: Option<String>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "string | null")]
+    pub container_image: Option<Option<String>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "boolean | null")]
+    pub container_network_isolated: Option<Option<bool>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "number | null")]
+    pub container_dev_server_port: Option<Option<i64>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "string | null")]
+    pub worker_host_id: Option<Option<Uuid>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "boolean | null")]
+    pub worker_host_auto: Option<Option<bool>>,
+}
+
+/// Whether `image` can be handed to the container runtime as an image
+/// reference; anything starting with `-` would be read as an option
+pub fn is_valid_container_image(image: &str) -> bool {
+    !image.is_empty() && !image.starts_with('-') && !image.contains(char::is_whitespace)
 }
 
 #[derive(Debug, Serialize, TS)]
@@ -48,6 +110,11 @@ pub struct SearchResult {
     /// Ranking score based on git history (higher = more recently/frequently edited)
     #[serde(default)]
     pub score: i64,
+    /// 1-based line of a content or symbol match
+    pub line: Option<u32>,
+    /// Matching line for content matches, first line of the definition for symbols
+    pub preview: Option<String>,
+    pub symbol: Option<SearchSymbol>,
 }
 
 #[derive(Debug, Clone, Serialize, TS)]
@@ -55,6 +122,28 @@ pub enum SearchMatchType {
     FileName,
     DirectoryName,
     FullPath,
+    Content,
+    Symbol,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct SearchSymbol {
+    pub name: String,
+    pub kind: SymbolKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum SymbolKind {
+    Function,
+    Method,
+    Class,
+    Struct,
+    Enum,
+    Interface,
+    Trait,
+    TypeAlias,
+    Module,
 }
 
 impl Project {
@@ -71,6 +160,11 @@ impl Project {
                       name,
                       default_agent_working_dir,
                       remote_project_id as "remote_project_id: Uuid",
+                      container_image,
+                      container_network_isolated as "container_network_isolated!: bool",
+                      container_dev_server_port,
+                      worker_host_id as "worker_host_id: Uuid",
+                      worker_host_auto as "worker_host_auto!: bool",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM projects
@@ -88,6 +182,11 @@ impl Project {
             SELECT p.id as "id!: Uuid", p.name,
                    p.default_agent_working_dir,
                    p.remote_project_id as "remote_project_id: Uuid",
+                   p.container_image,
+                   p.container_network_isolated as "container_network_isolated!: bool",
+                   p.container_dev_server_port,
+                   p.worker_host_id as "worker_host_id: Uuid",
+                   p.worker_host_auto as "worker_host_auto!: bool",
                    p.created_at as "created_at!: DateTime<Utc>", p.updated_at as "updated_at!: DateTime<Utc>"
             FROM projects p
             WHERE p.id IN (
@@ -111,6 +210,11 @@ impl Project {
                       name,
                       default_agent_working_dir,
                       remote_project_id as "remote_project_id: Uuid",
+                      container_image,
+                      container_network_isolated as "container_network_isolated!: bool",
+                      container_dev_server_port,
+                      worker_host_id as "worker_host_id: Uuid",
+                      worker_host_auto as "worker_host_auto!: bool",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM projects
@@ -128,6 +232,11 @@ impl Project {
                       name,
                       default_agent_working_dir,
                       remote_project_id as "remote_project_id: Uuid",
+                      container_image,
+                      container_network_isolated as "container_network_isolated!: bool",
+                      container_dev_server_port,
+                      worker_host_id as "worker_host_id: Uuid",
+                      worker_host_auto as "worker_host_auto!: bool",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM projects
@@ -148,6 +257,11 @@ impl Project {
                       name,
                       default_agent_working_dir,
                       remote_project_id as "remote_project_id: Uuid",
+                      container_image,
+                      container_network_isolated as "container_network_isolated!: bool",
+                      container_dev_server_port,
+                      worker_host_id as "worker_host_id: Uuid",
+                      worker_host_auto as "worker_host_auto!: bool",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM projects
@@ -165,11 +279,16 @@ impl Project {
                       name,
                       default_agent_working_dir,
                       remote_project_id,
+                      container_image,
+                      container_network_isolated,
+                      container_dev_server_port,
+                      worker_host_id,
+                      worker_host_auto,
                       created_at,
                       updated_at
                FROM projects
                WHERE name = ?
-               LIMIT 1"#
+               LIMIT 1"#,
         )
         .bind(name)
         .fetch_optional(pool)
@@ -193,6 +312,11 @@ impl Project {
                           name,
                           default_agent_working_dir,
                           remote_project_id as "remote_project_id: Uuid",
+                          container_image,
+                          container_network_isolated as "container_network_isolated!: bool",
+                          container_dev_server_port,
+                          worker_host_id as "worker_host_id: Uuid",
+                          worker_host_auto as "worker_host_auto!: bool",
                           created_at as "created_at!: DateTime<Utc>",
                           updated_at as "updated_at!: DateTime<Utc>""#,
             project_id,
@@ -206,29 +330,79 @@ impl Project {
         pool: &SqlitePool,
         id: Uuid,
         payload: &UpdateProject,
-    ) -> Result<Self, sqlx::Error> {
+    ) -> Result<Self, ProjectError> {
         let existing = Self::find_by_id(pool, id)
             .await?
             .ok_or(sqlx::Error::RowNotFound)?;
 
         let name = payload.name.clone().unwrap_or(existing.name);
+        let container_image = match &payload.container_image {
+            None => existing.container_image,
+            Some(v) => v
+                .as_deref()
+                .map(str::trim)
+                .filter(|image| !image.is_empty())
+                .map(str::to_string),
+        };
+        if let Some(image) = &container_image
+            && !is_valid_container_image(image)
+        {
+            return Err(ProjectError::InvalidContainerImage(image.clone()));
+        }
+        let container_network_isolated = match &payload.container_network_isolated {
+            None => existing.container_network_isolated,
+            Some(v) => v.unwrap_or(false),
+        };
+        let container_dev_server_port = match &payload.container_dev_server_port {
+            None => existing.container_dev_server_port,
+            Some(v) => *v,
+        };
+        if let Some(port) = container_dev_server_port
+            && !(1..=65535).contains(&port)
+        {
+            return Err(ProjectError::InvalidDevServerPort(port));
+        }
+        let worker_host_id = match &payload.worker_host_id {
+            None => existing.worker_host_id,
+            Some(v) => *v,
+        };
+        let worker_host_auto = match &payload.worker_host_auto {
+            None => existing.worker_host_auto,
+            Some(v) => v.unwrap_or(false),
+        };
 
         sqlx::query_as!(
             Project,
             r#"UPDATE projects
-               SET name = $2
+               SET name = $2,
+                   container_image = $3,
+                   container_network_isolated = $4,
+                   worker_host_id = $5,
+                   worker_host_auto = $6,
+                   container_dev_server_port = $7
                WHERE id = $1
                RETURNING id as "id!: Uuid",
                          name,
                          default_agent_working_dir,
                          remote_project_id as "remote_project_id: Uuid",
+                         container_image,
+                         container_network_isolated as "container_network_isolated!: bool",
+                         container_dev_server_port,
+                         worker_host_id as "worker_host_id: Uuid",
+                         worker_host_auto as "worker_host_auto!: bool",
                          created_at as "created_at!: DateTime<Utc>",
                          updated_at as "updated_at!: DateTime<Utc>""#,
             id,
             name,
+            container_image,
+            container_network_isolated,
+            worker_host_id,
+            worker_host_auto,
+            container_dev_server_port,
         )
         .fetch_one(pool)
         .await
+        .map_err(ProjectError::from)
     }
 
     pub async fn set_remote_project_id(
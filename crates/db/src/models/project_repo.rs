@@ -83,6 +83,14 @@ impl ProjectRepo {
                       r.parallel_setup_script as "parallel_setup_script!: bool",
                       r.dev_server_script,
                       r.default_target_branch,
+                      r.sparse_checkout_paths,
+                      r.partial_clone_filter,
+                      r.file_ranker_history_depth,
+                      r.init_submodules as "init_submodules!: bool",
+                      r.fetch_lfs as "fetch_lfs!: bool",
+                      r.pre_merge_script,
+                      r.pre_push_script,
+                      r.gate_timeout_secs,
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>"
                FROM repos r
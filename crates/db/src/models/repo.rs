@@ -28,6 +28,22 @@ pub struct Repo {
     pub parallel_setup_script: bool,
     pub dev_server_script: Option<String>,
     pub default_target_branch: Option<String>,
+    /// Comma-separated directories for a cone-mode sparse checkout in workspace worktrees
+    pub sparse_checkout_paths: Option<String>,
+    /// Partial clone filter used when the repository was cloned, e.g. `blob:none`
+    pub partial_clone_filter: Option<String>,
+    /// Number of recent commits scanned when ranking files for search
+    pub file_ranker_history_depth: Option<i64>,
+    /// Run `git submodule update --init --recursive` when a worktree is created
+    pub init_submodules: bool,
+    /// Run `git lfs pull` when a worktree is created
+    pub fetch_lfs: bool,
+    /// Gate script that must pass in the worktree before merging
+    pub pre_merge_script: Option<String>,
+    /// Gate script that must pass in the worktree before pushing
+    pub pre_push_script: Option<String>,
+    /// Seconds a gate script may run before it is killed; None uses the default
+    pub gate_timeout_secs: Option<i64>,
     #[ts(type = "Date")]
     pub created_at: DateTime<Utc>,
     #[ts(type = "Date")]
@@ -92,6 +108,70 @@ pub struct UpdateRepo {
     )]
     #[ts(optional, type = "string | null")]
     pub default_target_branch: Option<Option<String>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "string | null")]
+    pub sparse_checkout_paths: Option<Option<String>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "string | null")]
+    pub partial_clone_filter: Option<Option<String>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "number | null")]
+    pub file_ranker_history_depth: Option<Option<i64>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "boolean | null")]
+    pub init_submodules: Option<Option<bool>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "boolean | null")]
+    pub fetch_lfs: Option<Option<bool>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "string | null")]
+    pub pre_merge_script: Option<Option<String>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "string | null")]
+    pub pre_push_script: Option<Option<String>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "number | null")]
+    pub gate_timeout_secs: Option<Option<i64>>,
 }
 
 impl Repo {
@@ -110,6 +190,14 @@ impl Repo {
                       parallel_setup_script as "parallel_setup_script!: bool",
                       dev_server_script,
                       default_target_branch,
+                      sparse_checkout_paths,
+                      partial_clone_filter,
+                      file_ranker_history_depth,
+                      init_submodules as "init_submodules!: bool",
+                      fetch_lfs as "fetch_lfs!: bool",
+                      pre_merge_script,
+                      pre_push_script,
+                      gate_timeout_secs,
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM repos
@@ -149,6 +237,14 @@ impl Repo {
                       parallel_setup_script as "parallel_setup_script!: bool",
                       dev_server_script,
                       default_target_branch,
+                      sparse_checkout_paths,
+                      partial_clone_filter,
+                      file_ranker_history_depth,
+                      init_submodules as "init_submodules!: bool",
+                      fetch_lfs as "fetch_lfs!: bool",
+                      pre_merge_script,
+                      pre_push_script,
+                      gate_timeout_secs,
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM repos
@@ -205,6 +301,14 @@ impl Repo {
                          parallel_setup_script as "parallel_setup_script!: bool",
                          dev_server_script,
                          default_target_branch,
+                         sparse_checkout_paths,
+                         partial_clone_filter,
+                         file_ranker_history_depth,
+                         init_submodules as "init_submodules!: bool",
+                         fetch_lfs as "fetch_lfs!: bool",
+                         pre_merge_script,
+                         pre_push_script,
+                         gate_timeout_secs,
                          created_at as "created_at!: DateTime<Utc>",
                          updated_at as "updated_at!: DateTime<Utc>""#,
             id,
@@ -240,6 +344,14 @@ impl Repo {
                       parallel_setup_script as "parallel_setup_script!: bool",
                       dev_server_script,
                       default_target_branch,
+                      sparse_checkout_paths,
+                      partial_clone_filter,
+                      file_ranker_history_depth,
+                      init_submodules as "init_submodules!: bool",
+                      fetch_lfs as "fetch_lfs!: bool",
+                      pre_merge_script,
+                      pre_push_script,
+                      gate_timeout_secs,
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM repos
@@ -277,6 +389,22 @@ impl Repo {
         Ok(())
     }
 
+    /// Record the partial clone filter the repository was cloned with
+    pub async fn set_partial_clone_filter(
+        pool: &SqlitePool,
+        id: Uuid,
+        filter: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE repos SET partial_clone_filter = ?, updated_at = datetime('now', 'subsec') WHERE id = ?",
+        )
+        .bind(filter)
+        .bind(id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
     pub async fn update(
         pool: &SqlitePool,
         id: Uuid,
@@ -317,6 +445,38 @@ impl Repo {
             None => existing.default_target_branch,
             Some(v) => v.clone(),
         };
+        let sparse_checkout_paths = match &payload.sparse_checkout_paths {
+            None => existing.sparse_checkout_paths,
+            Some(v) => v.clone(),
+        };
+        let partial_clone_filter = match &payload.partial_clone_filter {
+            None => existing.partial_clone_filter,
+            Some(v) => v.clone(),
+        };
+        let file_ranker_history_depth = match &payload.file_ranker_history_depth {
+            None => existing.file_ranker_history_depth,
+            Some(v) => *v,
+        };
+        let init_submodules = match &payload.init_submodules {
+            None => existing.init_submodules,
+            Some(v) => v.unwrap_or(false),
+        };
+        let fetch_lfs = match &payload.fetch_lfs {
+            None => existing.fetch_lfs,
+            Some(v) => v.unwrap_or(false),
+        };
+        let pre_merge_script = match &payload.pre_merge_script {
+            None => existing.pre_merge_script,
+            Some(v) => v.clone(),
+        };
+        let pre_push_script = match &payload.pre_push_script {
+            None => existing.pre_push_script,
+            Some(v) => v.clone(),
+        };
+        let gate_timeout_secs = match &payload.gate_timeout_secs {
+            None => existing.gate_timeout_secs,
+            Some(v) => *v,
+        };
 
         sqlx::query_as!(
             Repo,
@@ -328,8 +488,16 @@ impl Repo {
                    parallel_setup_script = $5,
                    dev_server_script = $6,
                    default_target_branch = $7,
+                   sparse_checkout_paths = $8,
+                   partial_clone_filter = $9,
+                   file_ranker_history_depth = $10,
+                   init_submodules = $11,
+                   fetch_lfs = $12,
+                   pre_merge_script = $13,
+                   pre_push_script = $14,
+                   gate_timeout_secs = $15,
                    updated_at = datetime('now', 'subsec')
-               WHERE id = $8
+               WHERE id = $16
                RETURNING id as "id!: Uuid",
                          path,
                          name,
@@ -340,6 +508,14 @@ impl Repo {
                          parallel_setup_script as "parallel_setup_script!: bool",
                          dev_server_script,
                          default_target_branch,
+                         sparse_checkout_paths,
+                         partial_clone_filter,
+                         file_ranker_history_depth,
+                         init_submodules as "init_submodules!: bool",
+                         fetch_lfs as "fetch_lfs!: bool",
+                         pre_merge_script,
+                         pre_push_script,
+                         gate_timeout_secs,
                          created_at as "created_at!: DateTime<Utc>",
                          updated_at as "updated_at!: DateTime<Utc>""#,
             display_name,
@@ -349,6 +525,14 @@ impl Repo {
             parallel_setup_script,
             dev_server_script,
             default_target_branch,
+            sparse_checkout_paths,
+            partial_clone_filter,
+            file_ranker_history_depth,
+            init_submodules,
+            fetch_lfs,
+            pre_merge_script,
+            pre_push_script,
+            gate_timeout_secs,
             id
         )
         .fetch_one(pool)
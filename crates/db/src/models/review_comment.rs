@@ -0,0 +1,266 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum ReviewCommentError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("Review comment not found")]
+    NotFound,
+}
+
+/// Side of the diff a comment is attached to: `old` is the base version, `new`
+/// the workspace version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, Type)]
+#[sqlx(type_name = "review_side", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewSide {
+    Old,
+    New,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, Type)]
+#[sqlx(type_name = "review_comment_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewCommentStatus {
+    Open,
+    /// Included in a review prompt, waiting for the agent's turn to finish
+    Sent,
+    Resolved,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct ReviewComment {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub repo_id: Uuid,
+    pub file_path: String,
+    pub side: ReviewSide,
+    /// 1-based line on `side`
+    pub line: u32,
+    pub body: String,
+    #[serde(skip)]
+    #[ts(skip)]
+    pub anchor_blob: Option<String>,
+    /// Text of the commented line when it was last anchored
+    pub line_content: Option<String>,
+    /// The commented line no longer exists; `line` is its last known position
+    pub outdated: bool,
+    pub status: ReviewCommentStatus,
+    pub sent_execution_process_id: Option<Uuid>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateReviewComment {
+    pub repo_id: Uuid,
+    pub file_path: String,
+    pub side: ReviewSide,
+    pub line: u32,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateReviewComment {
+    pub body: Option<String>,
+    pub status: Option<ReviewCommentStatus>,
+}
+
+/// Where a comment points after (re-)anchoring against the current diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewAnchor {
+    pub line: u32,
+    pub anchor_blob: Option<String>,
+    pub line_content: Option<String>,
+    pub outdated: bool,
+}
+
+const SELECT_COLUMNS: &str = r#"SELECT id,
+                      workspace_id,
+                      repo_id,
+                      file_path,
+                      side,
+                      line,
+                      body,
+                      anchor_blob,
+                      line_content,
+                      outdated,
+                      status,
+                      sent_execution_process_id,
+                      created_at,
+                      updated_at
+               FROM review_comments"#;
+
+impl ReviewComment {
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as::<_, ReviewComment>(&format!("{SELECT_COLUMNS} WHERE id = ?"))
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// All comments of a workspace in file and line order
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, ReviewComment>(&format!(
+            "{SELECT_COLUMNS} WHERE workspace_id = ? ORDER BY file_path, side, line, created_at"
+        ))
+        .bind(workspace_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Comments sent to the agent with the given execution process
+    pub async fn find_by_sent_execution_process_id(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, ReviewComment>(&format!(
+            "{SELECT_COLUMNS} WHERE sent_execution_process_id = ? AND status = 'sent'"
+        ))
+        .bind(execution_process_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        id: Uuid,
+        workspace_id: Uuid,
+        data: &CreateReviewComment,
+        anchor: &ReviewAnchor,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query(
+            r#"INSERT INTO review_comments
+                   (id, workspace_id, repo_id, file_path, side, line, body, anchor_blob, line_content, outdated)
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+        )
+        .bind(id)
+        .bind(workspace_id)
+        .bind(data.repo_id)
+        .bind(&data.file_path)
+        .bind(data.side)
+        .bind(anchor.line)
+        .bind(&data.body)
+        .bind(&anchor.anchor_blob)
+        .bind(&anchor.line_content)
+        .bind(anchor.outdated)
+        .execute(pool)
+        .await?;
+
+        Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateReviewComment,
+    ) -> Result<Self, ReviewCommentError> {
+        let existing = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(ReviewCommentError::NotFound)?;
+        let body = data.body.as_ref().unwrap_or(&existing.body);
+        let status = data.status.unwrap_or(existing.status);
+        // Reopening detaches the comment from the turn it was sent with
+        let sent_execution_process_id = match status {
+            ReviewCommentStatus::Open => None,
+            _ => existing.sent_execution_process_id,
+        };
+
+        sqlx::query(
+            r#"UPDATE review_comments
+               SET body = ?, status = ?, sent_execution_process_id = ?,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = ?"#,
+        )
+        .bind(body)
+        .bind(status)
+        .bind(sent_execution_process_id)
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        Self::find_by_id(pool, id)
+            .await?
+            .ok_or(ReviewCommentError::NotFound)
+    }
+
+    pub async fn update_anchor(
+        pool: &SqlitePool,
+        id: Uuid,
+        anchor: &ReviewAnchor,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"UPDATE review_comments
+               SET line = ?, anchor_blob = ?, line_content = ?, outdated = ?,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = ?"#,
+        )
+        .bind(anchor.line)
+        .bind(&anchor.anchor_blob)
+        .bind(&anchor.line_content)
+        .bind(anchor.outdated)
+        .bind(id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Mark comments as sent with the execution process that carries the review
+    pub async fn mark_sent(
+        pool: &SqlitePool,
+        ids: &[Uuid],
+        execution_process_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        for id in ids {
+            sqlx::query(
+                r#"UPDATE review_comments
+                   SET status = 'sent', sent_execution_process_id = ?,
+                       updated_at = datetime('now', 'subsec')
+                   WHERE id = ?"#,
+            )
+            .bind(execution_process_id)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+
+    pub async fn update_status(
+        pool: &SqlitePool,
+        id: Uuid,
+        status: ReviewCommentStatus,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"UPDATE review_comments
+               SET status = ?, updated_at = datetime('now', 'subsec')
+               WHERE id = ?"#,
+        )
+        .bind(status)
+        .bind(id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM review_comments WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -50,7 +50,8 @@ impl Session {
 
     /// Find all sessions for a workspace, ordered by most recently used.
     /// "Most recently used" is defined as the most recent non-dev server execution process.
-    /// Sessions with no executions fall back to created_at for ordering.
+    /// Sessions with no executions fall back to created_at for ordering, and sessions that
+    /// only ran gate scripts come last.
     pub async fn find_by_workspace_id(
         pool: &SqlitePool,
         workspace_id: Uuid,
@@ -64,13 +65,16 @@ impl Session {
                       s.updated_at AS "updated_at!: DateTime<Utc>"
                FROM sessions s
                LEFT JOIN (
-                   SELECT ep.session_id, MAX(ep.created_at) as last_used
+                   SELECT ep.session_id,
+                          MAX(ep.created_at) as last_used,
+                          MIN(ep.run_reason = 'gatescript') as gate_only
                    FROM execution_processes ep
                    WHERE ep.run_reason != 'devserver' AND ep.dropped = FALSE
                    GROUP BY ep.session_id
                ) latest_ep ON s.id = latest_ep.session_id
                WHERE s.workspace_id = $1
-               ORDER BY COALESCE(latest_ep.last_used, s.created_at) DESC"#,
+               ORDER BY COALESCE(latest_ep.gate_only, 0) ASC,
+                        COALESCE(latest_ep.last_used, s.created_at) DESC"#,
             workspace_id
         )
         .fetch_all(pool)
@@ -79,7 +83,8 @@ impl Session {
 
     /// Find the most recently used session for a workspace.
     /// "Most recently used" is defined as the most recent non-dev server execution process.
-    /// Sessions with no executions fall back to created_at for ordering.
+    /// Sessions with no executions fall back to created_at for ordering, and sessions that
+    /// only ran gate scripts are only returned when there is no other session.
     pub async fn find_latest_by_workspace_id(
         pool: &SqlitePool,
         workspace_id: Uuid,
@@ -93,13 +98,16 @@ impl Session {
                       s.updated_at AS "updated_at!: DateTime<Utc>"
                FROM sessions s
                LEFT JOIN (
-                   SELECT ep.session_id, MAX(ep.created_at) as last_used
+                   SELECT ep.session_id,
+                          MAX(ep.created_at) as last_used,
+                          MIN(ep.run_reason = 'gatescript') as gate_only
                    FROM execution_processes ep
                    WHERE ep.run_reason != 'devserver' AND ep.dropped = FALSE
                    GROUP BY ep.session_id
                ) latest_ep ON s.id = latest_ep.session_id
                WHERE s.workspace_id = $1
-               ORDER BY COALESCE(latest_ep.last_used, s.created_at) DESC
+               ORDER BY COALESCE(latest_ep.gate_only, 0) ASC,
+                        COALESCE(latest_ep.last_used, s.created_at) DESC
                LIMIT 1"#,
             workspace_id
         )
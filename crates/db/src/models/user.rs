@@ -0,0 +1,255 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum UserError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("User not found")]
+    NotFound,
+    #[error("Username is already taken")]
+    UsernameTaken,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, Type)]
+#[sqlx(type_name = "user_role", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum UserRole {
+    Admin,
+    Member,
+}
+
+/// Person using a shared server, signed in with a password or identified by
+/// the reverse proxy in front of it
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct User {
+    pub id: Uuid,
+    pub username: String,
+    pub display_name: Option<String>,
+    #[serde(skip)]
+    #[ts(skip)]
+    pub password_hash: Option<String>,
+    pub role: UserRole,
+    /// JSON overlay on the global config, parsed by the config service
+    #[serde(skip)]
+    #[ts(skip)]
+    pub config_overrides: Option<String>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateUser {
+    pub username: String,
+    pub display_name: Option<String>,
+    pub role: UserRole,
+}
+
+impl User {
+    /// Name shown on activity records
+    pub fn name(&self) -> &str {
+        self.display_name.as_deref().unwrap_or(&self.username)
+    }
+
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            User,
+            r#"SELECT id as "id!: Uuid", username, display_name, password_hash, role as "role!: UserRole", config_overrides, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM users
+               ORDER BY username"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            User,
+            r#"SELECT id as "id!: Uuid", username, display_name, password_hash, role as "role!: UserRole", config_overrides, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM users
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_username(
+        pool: &SqlitePool,
+        username: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            User,
+            r#"SELECT id as "id!: Uuid", username, display_name, password_hash, role as "role!: UserRole", config_overrides, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM users
+               WHERE username = $1"#,
+            username
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &CreateUser,
+        password_hash: Option<&str>,
+    ) -> Result<Self, UserError> {
+        sqlx::query_as!(
+            User,
+            r#"INSERT INTO users (id, username, display_name, password_hash, role)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING id as "id!: Uuid", username, display_name, password_hash, role as "role!: UserRole", config_overrides, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.username,
+            data.display_name,
+            password_hash,
+            data.role
+        )
+        .fetch_one(pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => UserError::UsernameTaken,
+            e => UserError::Database(e),
+        })
+    }
+
+    /// User for a name asserted by the trusted proxy header, created as a
+    /// member the first time it is seen
+    pub async fn find_or_create_by_username(
+        pool: &SqlitePool,
+        username: &str,
+    ) -> Result<Self, UserError> {
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            r#"INSERT INTO users (id, username, role) VALUES ($1, $2, 'member')
+               ON CONFLICT(username) DO NOTHING"#,
+            id,
+            username
+        )
+        .execute(pool)
+        .await?;
+
+        Self::find_by_username(pool, username)
+            .await?
+            .ok_or(UserError::NotFound)
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        display_name: Option<&str>,
+        role: UserRole,
+    ) -> Result<Self, UserError> {
+        let result = sqlx::query!(
+            r#"UPDATE users
+               SET display_name = $2, role = $3, updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            display_name,
+            role
+        )
+        .execute(pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(UserError::NotFound);
+        }
+
+        Self::find_by_id(pool, id).await?.ok_or(UserError::NotFound)
+    }
+
+    pub async fn set_password_hash(
+        pool: &SqlitePool,
+        id: Uuid,
+        password_hash: Option<&str>,
+    ) -> Result<(), UserError> {
+        let result = sqlx::query!(
+            "UPDATE users SET password_hash = $2, updated_at = datetime('now', 'subsec') WHERE id = $1",
+            id,
+            password_hash
+        )
+        .execute(pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(UserError::NotFound);
+        }
+        Ok(())
+    }
+
+    pub async fn set_config_overrides(
+        pool: &SqlitePool,
+        id: Uuid,
+        config_overrides: Option<&str>,
+    ) -> Result<(), UserError> {
+        let result = sqlx::query!(
+            "UPDATE users SET config_overrides = $2, updated_at = datetime('now', 'subsec') WHERE id = $1",
+            id,
+            config_overrides
+        )
+        .execute(pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(UserError::NotFound);
+        }
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM users WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_pool;
+
+    #[tokio::test]
+    async fn proxy_users_are_created_once_as_members() {
+        let pool = test_pool().await;
+
+        let first = User::find_or_create_by_username(&pool, "ada")
+            .await
+            .unwrap();
+        assert_eq!(first.role, UserRole::Member);
+        assert!(first.password_hash.is_none());
+
+        User::update(&pool, first.id, Some("Ada"), UserRole::Admin)
+            .await
+            .unwrap();
+        let again = User::find_or_create_by_username(&pool, "ada")
+            .await
+            .unwrap();
+        assert_eq!(again.id, first.id);
+        assert_eq!(again.role, UserRole::Admin);
+        assert_eq!(again.name(), "Ada");
+        assert_eq!(User::find_all(&pool).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn usernames_are_unique() {
+        let pool = test_pool().await;
+        let data = CreateUser {
+            username: "ada".to_string(),
+            display_name: None,
+            role: UserRole::Admin,
+        };
+        let created = User::create(&pool, Uuid::new_v4(), &data, Some("hash"))
+            .await
+            .unwrap();
+        assert_eq!(created.password_hash.as_deref(), Some("hash"));
+        assert!(matches!(
+            User::create(&pool, Uuid::new_v4(), &data, None).await,
+            Err(UserError::UsernameTaken)
+        ));
+    }
+}
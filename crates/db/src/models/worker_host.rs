@@ -0,0 +1,190 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum WorkerHostError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("Worker host not found")]
+    NotFound,
+}
+
+/// SSH host that runs agents and scripts for workspaces of projects assigned to it
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct WorkerHost {
+    pub id: Uuid,
+    pub name: String,
+    /// Hostname or `~/.ssh/config` alias
+    pub host: String,
+    pub port: Option<u16>,
+    pub username: Option<String>,
+    pub identity_file: Option<String>,
+    /// Absolute directory on the host holding repository mirrors and worktrees
+    pub base_dir: String,
+    /// Skipped by load-based selection once this many executions run on it
+    pub max_concurrent_executions: Option<u32>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateWorkerHost {
+    pub name: String,
+    pub host: String,
+    pub port: Option<u16>,
+    pub username: Option<String>,
+    pub identity_file: Option<String>,
+    pub base_dir: String,
+    pub max_concurrent_executions: Option<u32>,
+}
+
+const SELECT_COLUMNS: &str = r#"SELECT id,
+                      name,
+                      host,
+                      port,
+                      username,
+                      identity_file,
+                      base_dir,
+                      max_concurrent_executions,
+                      created_at,
+                      updated_at
+               FROM worker_hosts"#;
+
+impl WorkerHost {
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, WorkerHost>(&format!("{SELECT_COLUMNS} ORDER BY name"))
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as::<_, WorkerHost>(&format!("{SELECT_COLUMNS} WHERE id = ?"))
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Host the workspace's worktrees live on, if it runs on one
+    pub async fn find_for_workspace(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as::<_, WorkerHost>(&format!(
+            "{SELECT_COLUMNS} WHERE id = (SELECT worker_host_id FROM workspace_worker_hosts WHERE workspace_id = ?)"
+        ))
+        .bind(workspace_id)
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &CreateWorkerHost,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query(
+            r#"INSERT INTO worker_hosts
+                   (id, name, host, port, username, identity_file, base_dir, max_concurrent_executions)
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?)"#,
+        )
+        .bind(id)
+        .bind(&data.name)
+        .bind(&data.host)
+        .bind(data.port)
+        .bind(&data.username)
+        .bind(&data.identity_file)
+        .bind(&data.base_dir)
+        .bind(data.max_concurrent_executions)
+        .execute(pool)
+        .await?;
+
+        Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &CreateWorkerHost,
+    ) -> Result<Self, WorkerHostError> {
+        let result = sqlx::query(
+            r#"UPDATE worker_hosts
+               SET name = ?, host = ?, port = ?, username = ?, identity_file = ?, base_dir = ?,
+                   max_concurrent_executions = ?, updated_at = datetime('now', 'subsec')
+               WHERE id = ?"#,
+        )
+        .bind(&data.name)
+        .bind(&data.host)
+        .bind(data.port)
+        .bind(&data.username)
+        .bind(&data.identity_file)
+        .bind(&data.base_dir)
+        .bind(data.max_concurrent_executions)
+        .bind(id)
+        .execute(pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(WorkerHostError::NotFound);
+        }
+
+        Self::find_by_id(pool, id)
+            .await?
+            .ok_or(WorkerHostError::NotFound)
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM worker_hosts WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Running execution processes per host, for load-based selection
+    pub async fn running_execution_counts(
+        pool: &SqlitePool,
+    ) -> Result<Vec<(Uuid, i64)>, sqlx::Error> {
+        sqlx::query_as::<_, (Uuid, i64)>(
+            r#"SELECT wwh.worker_host_id, COUNT(ep.id)
+               FROM workspace_worker_hosts wwh
+               JOIN sessions s ON s.workspace_id = wwh.workspace_id
+               JOIN execution_processes ep ON ep.session_id = s.id
+               WHERE ep.status = 'running'
+               GROUP BY wwh.worker_host_id"#,
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn assign_workspace(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        worker_host_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"INSERT INTO workspace_worker_hosts (workspace_id, worker_host_id)
+               VALUES (?, ?)
+               ON CONFLICT(workspace_id) DO NOTHING"#,
+        )
+        .bind(workspace_id)
+        .bind(worker_host_id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// SSH destination, `user@host` or just the host
+    pub fn destination(&self) -> String {
+        match &self.username {
+            Some(user) => format!("{user}@{}", self.host),
+            None => self.host.clone(),
+        }
+    }
+}
@@ -121,6 +121,14 @@ impl WorkspaceRepo {
                       r.parallel_setup_script as "parallel_setup_script!: bool",
                       r.dev_server_script,
                       r.default_target_branch,
+                      r.sparse_checkout_paths,
+                      r.partial_clone_filter,
+                      r.file_ranker_history_depth,
+                      r.init_submodules as "init_submodules!: bool",
+                      r.fetch_lfs as "fetch_lfs!: bool",
+                      r.pre_merge_script,
+                      r.pre_push_script,
+                      r.gate_timeout_secs,
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>"
                FROM repos r
@@ -148,6 +156,14 @@ impl WorkspaceRepo {
                       r.parallel_setup_script as "parallel_setup_script!: bool",
                       r.dev_server_script,
                       r.default_target_branch,
+                      r.sparse_checkout_paths,
+                      r.partial_clone_filter,
+                      r.file_ranker_history_depth,
+                      r.init_submodules as "init_submodules!: bool",
+                      r.fetch_lfs as "fetch_lfs!: bool",
+                      r.pre_merge_script,
+                      r.pre_push_script,
+                      r.gate_timeout_secs,
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>",
                       wr.target_branch
@@ -174,6 +190,14 @@ impl WorkspaceRepo {
                     parallel_setup_script: row.parallel_setup_script,
                     dev_server_script: row.dev_server_script,
                     default_target_branch: row.default_target_branch,
+                    sparse_checkout_paths: row.sparse_checkout_paths,
+                    partial_clone_filter: row.partial_clone_filter,
+                    file_ranker_history_depth: row.file_ranker_history_depth,
+                    init_submodules: row.init_submodules,
+                    fetch_lfs: row.fetch_lfs,
+                    pre_merge_script: row.pre_merge_script,
+                    pre_push_script: row.pre_push_script,
+                    gate_timeout_secs: row.gate_timeout_secs,
                     created_at: row.created_at,
                     updated_at: row.updated_at,
                 },
@@ -261,6 +285,14 @@ impl WorkspaceRepo {
                       r.parallel_setup_script as "parallel_setup_script!: bool",
                       r.dev_server_script,
                       r.default_target_branch,
+                      r.sparse_checkout_paths,
+                      r.partial_clone_filter,
+                      r.file_ranker_history_depth,
+                      r.init_submodules as "init_submodules!: bool",
+                      r.fetch_lfs as "fetch_lfs!: bool",
+                      r.pre_merge_script,
+                      r.pre_push_script,
+                      r.gate_timeout_secs,
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>"
                FROM repos r
@@ -0,0 +1,67 @@
+//! Fixtures for tests that need a migrated database
+
+use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+use uuid::Uuid;
+
+/// Fresh in-memory database with all migrations applied. A single connection
+/// keeps every query on the same in-memory database.
+pub async fn test_pool() -> SqlitePool {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("open in-memory database");
+    sqlx::migrate!("./migrations")
+        .run(&pool)
+        .await
+        .expect("run migrations");
+    pool
+}
+
+/// Insert a project, task, workspace and session; returns (task_id, session_id)
+pub async fn insert_session(pool: &SqlitePool) -> (Uuid, Uuid) {
+    let project_id = Uuid::new_v4();
+    let task_id = Uuid::new_v4();
+    let workspace_id = Uuid::new_v4();
+    let session_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO projects (id, name) VALUES (?, 'project')")
+        .bind(project_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO tasks (id, project_id, title) VALUES (?, ?, 'task')")
+        .bind(task_id)
+        .bind(project_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO workspaces (id, task_id) VALUES (?, ?)")
+        .bind(workspace_id)
+        .bind(task_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO sessions (id, workspace_id, executor) VALUES (?, ?, 'CLAUDE_CODE')")
+        .bind(session_id)
+        .bind(workspace_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    (task_id, session_id)
+}
+
+/// Insert a coding agent process that finished `days_ago` days ago
+pub async fn insert_finished_process(pool: &SqlitePool, session_id: Uuid, days_ago: u32) -> Uuid {
+    let id = Uuid::new_v4();
+    sqlx::query(
+        r#"INSERT INTO execution_processes (id, session_id, run_reason, status, exit_code, completed_at)
+           VALUES (?, ?, 'codingagent', 'completed', 0, datetime('now', ?))"#,
+    )
+    .bind(id)
+    .bind(session_id)
+    .bind(format!("-{days_ago} days"))
+    .execute(pool)
+    .await
+    .unwrap();
+    id
+}
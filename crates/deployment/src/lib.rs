@@ -35,7 +35,7 @@ use services::services::{
 };
 use sqlx::Error as SqlxError;
 use thiserror::Error;
-use tokio::sync::RwLock;
+use tokio::sync::{RwLock, mpsc};
 use utils::sentry as sentry_utils;
 
 #[derive(Debug, Clone, Copy, Error)]
@@ -129,7 +129,33 @@ pub trait Deployment: Clone + Send + Sync + 'static {
                 user_id: self.user_id().to_string(),
                 analytics_service: analytics_service.clone(),
             });
-        PrMonitorService::spawn(db, analytics).await
+        // Comments queued on an idle session are started here, since only an
+        // exiting execution picks up the queue otherwise
+        let (wake_tx, mut wake_rx) = mpsc::unbounded_channel();
+        let deployment = self.clone();
+        tokio::spawn(async move {
+            while let Some(session_id) = wake_rx.recv().await {
+                if let Err(e) = deployment
+                    .container()
+                    .start_queued_message(session_id)
+                    .await
+                {
+                    tracing::error!(
+                        "Failed to start queued PR comment follow-up for session {}: {}",
+                        session_id,
+                        e
+                    );
+                }
+            }
+        });
+        PrMonitorService::spawn(
+            db,
+            analytics,
+            self.config().clone(),
+            self.queued_message_service().clone(),
+            wake_tx,
+        )
+        .await
     }
 
     async fn track_if_analytics_allowed(&self, event_name: &str, properties: Value) {
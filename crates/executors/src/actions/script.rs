@@ -1,7 +1,6 @@
 use std::{path::Path, sync::Arc};
 
 use async_trait::async_trait;
-use command_group::AsyncCommandGroup;
 use serde::{Deserialize, Serialize};
 use tokio::process::Command;
 use ts_rs::TS;
@@ -25,6 +24,8 @@ pub enum ScriptContext {
     CleanupScript,
     DevServer,
     ToolInstallScript,
+    /// Pre-merge or pre-push check that must pass before the git operation runs
+    GateScript,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
@@ -52,7 +53,12 @@ impl Executable for ScriptRequest {
             None => current_dir.to_path_buf(),
         };
 
-        let (shell_cmd, shell_arg) = get_shell_command();
+        // The host's shell may not exist in the container image or on the worker
+        let (shell_cmd, shell_arg) = if env.runs_remotely() {
+            ("sh".to_string(), "-c")
+        } else {
+            get_shell_command()
+        };
         let mut command = Command::new(shell_cmd);
         command
             .kill_on_drop(true)
@@ -66,7 +72,9 @@ impl Executable for ScriptRequest {
         // Apply environment variables
         env.apply_to_command(&mut command);
 
-        let child = command.group_spawn()?;
+        let mut child = env.group_spawn(&mut command).await?;
+        // Remote commands always get a stdin pipe; close it so scripts see EOF
+        drop(child.inner().stdin.take());
 
         Ok(child.into())
     }
@@ -1,6 +1,12 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    process::Stdio,
+};
 
-use tokio::process::Command;
+use command_group::{AsyncCommandGroup, AsyncGroupChild};
+use tokio::{io::AsyncWriteExt, process::Command};
+use workspace_utils::shell::quote;
 
 use crate::command::CmdOverrides;
 
@@ -28,12 +34,156 @@ impl RepoContext {
     }
 }
 
+/// OCI container that executor processes run in instead of the host, through
+/// `docker exec` or `podman exec`
+#[derive(Debug, Clone)]
+pub struct ContainerTarget {
+    /// Path to the `docker` or `podman` executable
+    pub runtime: PathBuf,
+    pub container: String,
+    /// File inside the container the process writes its pid to, so it can be
+    /// killed there; killing the host-side exec client leaves it running
+    pub pid_file: String,
+}
+
+impl ContainerTarget {
+    /// Rewrite a host command into an exec in the container. The program is
+    /// looked up on the container's PATH, and only variables set explicitly on
+    /// the command are passed through, by name so values stay out of argv.
+    pub fn wrap(&self, command: &Command) -> Command {
+        let host = command.as_std();
+        let mut wrapped = Command::new(&self.runtime);
+        wrapped
+            .kill_on_drop(true)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .args(["exec", "-i"]);
+        if let Some(dir) = host.get_current_dir() {
+            wrapped.arg("-w").arg(dir);
+        }
+        for (key, value) in host.get_envs() {
+            if let Some(value) = value {
+                wrapped.arg("-e").arg(key).env(key, value);
+            }
+        }
+        let program = Path::new(host.get_program());
+        wrapped
+            .arg(&self.container)
+            .args(["sh", "-c", "echo $$ > \"$0\"; exec \"$@\""])
+            .arg(&self.pid_file)
+            .arg(program.file_name().unwrap_or(program.as_os_str()))
+            .args(host.get_args());
+        wrapped
+    }
+}
+
+/// SSH worker host that executor processes run on instead of this machine. The
+/// workspace's worktrees are mirrored under `remote_root` on the host.
+#[derive(Debug, Clone)]
+pub struct SshTarget {
+    /// Options and destination passed to `ssh` before the remote command
+    pub ssh_args: Vec<String>,
+    pub local_root: PathBuf,
+    pub remote_root: String,
+    /// File on the host the process writes its pid to, so it can be killed
+    /// there; closing the SSH connection does not reliably stop it
+    pub pid_file: String,
+}
+
+impl SshTarget {
+    /// Map a path in the local workspace to its counterpart on the host
+    pub fn remote_path(&self, path: &Path) -> String {
+        match path.strip_prefix(&self.local_root) {
+            Ok(rest) if rest.as_os_str().is_empty() => self.remote_root.clone(),
+            Ok(rest) => format!(
+                "{}/{}",
+                self.remote_root.trim_end_matches('/'),
+                rest.to_string_lossy()
+            ),
+            Err(_) => path.to_string_lossy().into_owned(),
+        }
+    }
+
+    /// Rewrite a host command into a remote command over SSH. The program is
+    /// looked up on the host's PATH. Variables set explicitly on the command are
+    /// read from stdin before it starts (see `env_prelude`), so their values stay
+    /// out of argv on both machines.
+    pub fn wrap(&self, command: &Command) -> Command {
+        let host = command.as_std();
+        let mut script = Vec::new();
+        if let Some(dir) = host.get_current_dir() {
+            script.push(format!("cd {} &&", quote(&self.remote_path(dir))));
+        }
+        // `read` stops at each newline, leaving the rest of stdin to the program
+        script.push(
+            "vk_nl='\n' && while IFS= read -r vk_line && [ -n \"$vk_line\" ]; \
+             do eval \"$vk_line\"; done &&"
+                .to_string(),
+        );
+        let program = Path::new(host.get_program());
+        script.push("exec sh -c 'echo $$ > \"$0\"; exec \"$@\"'".to_string());
+        script.push(quote(&self.pid_file));
+        script.push(quote(
+            &program
+                .file_name()
+                .unwrap_or(program.as_os_str())
+                .to_string_lossy(),
+        ));
+        script.extend(host.get_args().map(|arg| quote(&arg.to_string_lossy())));
+
+        let mut wrapped = Command::new("ssh");
+        wrapped
+            .kill_on_drop(true)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .args(["-T", "-o", "BatchMode=yes"])
+            .args(&self.ssh_args)
+            .arg(script.join(" "));
+        wrapped
+    }
+
+    /// Lines to write to the wrapped command's stdin: one `export` per variable
+    /// set on `command`, then an empty line. Newlines in values are spelled
+    /// `$vk_nl` so each export stays on one line.
+    pub fn env_prelude(command: &Command) -> String {
+        let mut prelude = String::new();
+        for (key, value) in command.as_std().get_envs() {
+            let Some(value) = value else {
+                continue;
+            };
+            let key = key.to_string_lossy();
+            if !is_env_name(&key) {
+                tracing::warn!("Not passing variable {key:?} to the worker host");
+                continue;
+            }
+            let value = quote(&value.to_string_lossy()).replace('\n', "'\"$vk_nl\"'");
+            prelude.push_str(&format!("export {key}={value}\n"));
+        }
+        prelude.push('\n');
+        prelude
+    }
+}
+
+fn is_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
 /// Environment variables to inject into executor processes
 #[derive(Debug, Clone)]
 pub struct ExecutionEnv {
     pub vars: HashMap<String, String>,
     pub repo_context: RepoContext,
     pub commit_reminder: bool,
+    /// Set when the workspace runs inside a container
+    pub container: Option<ContainerTarget>,
+    /// Set when the workspace runs on an SSH worker host
+    pub ssh: Option<SshTarget>,
 }
 
 impl ExecutionEnv {
@@ -42,6 +192,8 @@ impl ExecutionEnv {
             vars: HashMap::new(),
             repo_context,
             commit_reminder,
+            container: None,
+            ssh: None,
         }
     }
 
@@ -78,6 +230,30 @@ impl ExecutionEnv {
         }
     }
 
+    /// Whether processes run somewhere other than this machine's host environment
+    pub fn runs_remotely(&self) -> bool {
+        self.container.is_some() || self.ssh.is_some()
+    }
+
+    /// Spawn a fully configured command, inside the workspace's container or on
+    /// its worker host when there is one
+    pub async fn group_spawn(&self, command: &mut Command) -> std::io::Result<AsyncGroupChild> {
+        match (&self.container, &self.ssh) {
+            (Some(target), _) => target.wrap(command).group_spawn(),
+            (None, Some(target)) => {
+                let mut child = target.wrap(command).group_spawn()?;
+                if let Some(stdin) = child.inner().stdin.as_mut() {
+                    stdin
+                        .write_all(SshTarget::env_prelude(command).as_bytes())
+                        .await?;
+                    stdin.flush().await?;
+                }
+                Ok(child)
+            }
+            (None, None) => command.group_spawn(),
+        }
+    }
+
     pub fn contains_key(&self, key: &str) -> bool {
         self.vars.contains_key(key)
     }
@@ -107,4 +283,103 @@ mod tests {
         assert_eq!(merged.vars.get("FOO").unwrap(), "profile"); // overrides
         assert_eq!(merged.vars.get("BAR").unwrap(), "profile");
     }
+
+    #[test]
+    fn container_target_wraps_command_in_exec() {
+        let target = ContainerTarget {
+            runtime: PathBuf::from("/usr/bin/docker"),
+            container: "vk-1234".to_string(),
+            pid_file: "/tmp/vk-exec-1.pid".to_string(),
+        };
+        let mut command = Command::new("/home/me/.local/bin/npx");
+        command
+            .args(["-y", "agent"])
+            .current_dir("/work/repo")
+            .env("API_KEY", "secret");
+
+        let wrapped = target.wrap(&command);
+        let wrapped = wrapped.as_std();
+        let args: Vec<_> = wrapped
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(wrapped.get_program(), "/usr/bin/docker");
+        assert_eq!(
+            args,
+            vec![
+                "exec",
+                "-i",
+                "-w",
+                "/work/repo",
+                "-e",
+                "API_KEY",
+                "vk-1234",
+                "sh",
+                "-c",
+                "echo $$ > \"$0\"; exec \"$@\"",
+                "/tmp/vk-exec-1.pid",
+                "npx",
+                "-y",
+                "agent",
+            ]
+        );
+        assert!(
+            wrapped
+                .get_envs()
+                .any(|(key, value)| key == "API_KEY" && value == Some("secret".as_ref()))
+        );
+    }
+
+    #[test]
+    fn ssh_target_runs_command_in_mirrored_worktree() {
+        let target = SshTarget {
+            ssh_args: vec!["-p".into(), "2222".into(), "dev@build".into()],
+            local_root: PathBuf::from("/home/me/worktrees/abcd-task"),
+            remote_root: "/srv/vk/worktrees/abcd-task".to_string(),
+            pid_file: "/tmp/vk-exec-1.pid".to_string(),
+        };
+        let mut command = Command::new("/home/me/.local/bin/npx");
+        command
+            .args(["-y", "it's"])
+            .current_dir("/home/me/worktrees/abcd-task/app")
+            .env("API_KEY", "secret");
+
+        let wrapped = target.wrap(&command);
+        let wrapped = wrapped.as_std();
+        let args: Vec<_> = wrapped
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(wrapped.get_program(), "ssh");
+        assert_eq!(
+            args[..6],
+            ["-T", "-o", "BatchMode=yes", "-p", "2222", "dev@build"]
+        );
+        assert!(args[6].starts_with("cd '/srv/vk/worktrees/abcd-task/app' && vk_nl="));
+        assert!(args[6].ends_with(
+            "exec sh -c 'echo $$ > \"$0\"; exec \"$@\"' '/tmp/vk-exec-1.pid' 'npx' '-y' 'it'\\''s'"
+        ));
+        assert!(!args.iter().any(|arg| arg.contains("secret")));
+    }
+
+    #[test]
+    fn ssh_env_prelude_exports_one_variable_per_line() {
+        let mut command = Command::new("npx");
+        command
+            .env("API_KEY", "it's secret")
+            .env("NOTES", "line one\nline two")
+            .env("BAD NAME", "ignored");
+
+        let prelude = SshTarget::env_prelude(&command);
+        let mut lines: Vec<_> = prelude.lines().collect();
+        assert_eq!(lines.pop(), Some(""));
+        lines.sort();
+        assert_eq!(
+            lines,
+            vec![
+                "export API_KEY='it'\\''s secret'",
+                "export NOTES='line one'\"$vk_nl\"'line two'",
+            ]
+        );
+    }
 }
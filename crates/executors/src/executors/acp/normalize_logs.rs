@@ -8,7 +8,9 @@ use agent_client_protocol::{self as acp, SessionNotification};
 use futures::StreamExt;
 use regex::Regex;
 use serde::Deserialize;
-use workspace_utils::{approvals::ApprovalStatus, msg_store::MsgStore};
+use workspace_utils::{
+    approvals::ApprovalStatus, msg_store::MsgStore, telemetry::spawn_in_current_span,
+};
 
 pub use super::AcpAgentHarness;
 use super::AcpEvent;
@@ -30,7 +32,7 @@ pub fn normalize_logs(msg_store: Arc<MsgStore>, worktree_path: &Path) {
     // stdout normalization (main loop)
     let worktree_path = worktree_path.to_path_buf();
     // Type aliases to simplify complex state types and appease clippy
-    tokio::spawn(async move {
+    spawn_in_current_span(async move {
         type ToolStates = std::collections::HashMap<String, PartialToolCallData>;
 
         let mut stored_session_id = false;
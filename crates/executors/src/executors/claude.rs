@@ -12,7 +12,6 @@ use std::{
 };
 
 use async_trait::async_trait;
-use command_group::AsyncCommandGroup;
 use futures::StreamExt;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -20,7 +19,7 @@ use tokio::process::Command;
 use ts_rs::TS;
 use workspace_utils::{
     approvals::ApprovalStatus, diff::create_unified_diff, log_msg::LogMsg, msg_store::MsgStore,
-    path::make_path_relative,
+    path::make_path_relative, telemetry::spawn_in_current_span,
 };
 
 use self::{
@@ -312,7 +311,7 @@ impl ClaudeCode {
             tracing::info!("ANTHROPIC_API_KEY removed from environment");
         }
 
-        let mut child = command.group_spawn()?;
+        let mut child = env.group_spawn(&mut command).await?;
         let child_stdout = child.inner().stdout.take().ok_or_else(|| {
             ExecutorError::Io(std::io::Error::other("Claude Code missing stdout"))
         })?;
@@ -423,7 +422,7 @@ impl ClaudeLogProcessor {
         strategy: HistoryStrategy,
     ) {
         let current_dir_clone = current_dir.to_owned();
-        tokio::spawn(async move {
+        spawn_in_current_span(async move {
             let mut stream = msg_store.history_plus_stream();
             let mut buffer = String::new();
             let worktree_path = current_dir_clone.to_string_lossy().to_string();
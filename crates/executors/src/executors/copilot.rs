@@ -6,7 +6,6 @@ use std::{
 };
 
 use async_trait::async_trait;
-use command_group::AsyncCommandGroup;
 use futures::StreamExt;
 use regex::Regex;
 use schemars::JsonSchema;
@@ -19,7 +18,9 @@ use tokio::{
 };
 use ts_rs::TS;
 use uuid::Uuid;
-use workspace_utils::{msg_store::MsgStore, path::get_vibe_kanban_temp_dir};
+use workspace_utils::{
+    msg_store::MsgStore, path::get_vibe_kanban_temp_dir, telemetry::spawn_in_current_span,
+};
 
 use crate::{
     command::{CmdOverrides, CommandBuildError, CommandBuilder, apply_overrides},
@@ -127,7 +128,7 @@ impl StandardCodingAgentExecutor for Copilot {
             .with_profile(&self.cmd)
             .apply_to_command(&mut command);
 
-        let mut child = command.group_spawn()?;
+        let mut child = env.group_spawn(&mut command).await?;
 
         // Write prompt to stdin
         if let Some(mut stdin) = child.inner().stdin.take() {
@@ -172,7 +173,7 @@ impl StandardCodingAgentExecutor for Copilot {
             .with_profile(&self.cmd)
             .apply_to_command(&mut command);
 
-        let mut child = command.group_spawn()?;
+        let mut child = env.group_spawn(&mut command).await?;
 
         // Write comprehensive prompt to stdin
         if let Some(mut stdin) = child.inner().stdin.take() {
@@ -194,7 +195,7 @@ impl StandardCodingAgentExecutor for Copilot {
         normalize_stderr_logs(msg_store.clone(), entry_index_counter.clone());
 
         // Normalize Agent logs
-        tokio::spawn(async move {
+        spawn_in_current_span(async move {
             let mut stdout_lines = msg_store.stdout_lines_stream();
 
             let mut processor = Self::create_simple_stdout_normalizer(entry_index_counter);
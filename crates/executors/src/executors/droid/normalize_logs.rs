@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use workspace_utils::{
     diff::normalize_unified_diff, msg_store::MsgStore, path::make_path_relative,
+    telemetry::spawn_in_current_span,
 };
 
 use crate::logs::{
@@ -29,7 +30,7 @@ pub fn normalize_logs(
     normalize_stderr_logs(msg_store.clone(), entry_index_provider.clone());
 
     let worktree_path = worktree_path.to_path_buf();
-    tokio::spawn(async move {
+    spawn_in_current_span(async move {
         let mut state = ToolCallStates::new(entry_index_provider.clone());
         let mut session_id_extracted = false;
         let mut sent_completion = false;
@@ -669,7 +670,7 @@ pub fn normalize_logs(
 }
 
 fn normalize_stderr_logs(msg_store: Arc<MsgStore>, entry_index_provider: EntryIndexProvider) {
-    tokio::spawn(async move {
+    spawn_in_current_span(async move {
         let mut stderr = msg_store.stderr_chunked_stream();
 
         let mut processor = PlainTextLogProcessor::builder()
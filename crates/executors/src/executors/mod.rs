@@ -85,6 +85,8 @@ pub enum ExecutorError {
     SetupHelperNotSupported,
     #[error("Auth required: {0}")]
     AuthRequired(String),
+    #[error("{0} cannot run inside a workspace container or on a worker host")]
+    ContainerNotSupported(String),
 }
 
 #[enum_dispatch]
@@ -1,7 +1,7 @@
 use std::{path::Path, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
-use command_group::{AsyncCommandGroup, AsyncGroupChild};
+use command_group::AsyncGroupChild;
 use derivative::Derivative;
 use futures::StreamExt;
 use schemars::JsonSchema;
@@ -87,6 +87,10 @@ impl Opencode {
         current_dir: &Path,
         env: &ExecutionEnv,
     ) -> Result<(AsyncGroupChild, ServerPassword), ExecutorError> {
+        // The server listens on the container's or worker's loopback, out of reach
+        if env.runs_remotely() {
+            return Err(ExecutorError::ContainerNotSupported("OpenCode".to_string()));
+        }
         let command_parts = self.build_command_builder()?.build_initial()?;
         let (program_path, args) = command_parts.into_resolved().await?;
 
@@ -109,7 +113,7 @@ impl Opencode {
             .with_profile(&self.cmd)
             .apply_to_command(&mut command);
 
-        let child = command.group_spawn()?;
+        let child = env.group_spawn(&mut command).await?;
 
         Ok((child, server_password))
     }
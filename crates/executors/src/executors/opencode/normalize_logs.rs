@@ -3,7 +3,10 @@ use std::{collections::HashMap, path::Path, sync::Arc};
 use futures::StreamExt;
 use serde::Deserialize;
 use serde_json::Value;
-use workspace_utils::{approvals::ApprovalStatus, msg_store::MsgStore, path::make_path_relative};
+use workspace_utils::{
+    approvals::ApprovalStatus, msg_store::MsgStore, path::make_path_relative,
+    telemetry::spawn_in_current_span,
+};
 
 use super::types::{
     MessageInfo, MessageRole, OpencodeExecutorEvent, Part, PermissionAskedEvent, SdkEvent, SdkTodo,
@@ -37,7 +40,7 @@ pub fn normalize_logs(msg_store: Arc<MsgStore>, worktree_path: &Path) {
     normalize_stderr_logs(msg_store.clone(), entry_index.clone());
 
     let worktree_path = worktree_path.to_path_buf();
-    tokio::spawn(async move {
+    spawn_in_current_span(async move {
         let mut stored_session_id = false;
         let mut state = LogState::new(entry_index.clone(), msg_store.clone());
 
@@ -11,7 +11,7 @@
 use std::{sync::Arc, time::Duration};
 
 use futures::StreamExt;
-use workspace_utils::msg_store::MsgStore;
+use workspace_utils::{msg_store::MsgStore, telemetry::spawn_in_current_span};
 
 use super::{
     NormalizedEntry, NormalizedEntryError, NormalizedEntryType,
@@ -37,7 +37,7 @@ use crate::logs::utils::EntryIndexProvider;
 /// * `msg_store` - the message store providing a stream of stderr chunks and accepting patches.
 /// * `entry_index_provider` - provider of incremental entry indices for patch ordering.
 pub fn normalize_stderr_logs(msg_store: Arc<MsgStore>, entry_index_provider: EntryIndexProvider) {
-    tokio::spawn(async move {
+    spawn_in_current_span(async move {
         let mut stderr = msg_store.stderr_chunked_stream();
 
         // Create a processor with time-based emission for stderr
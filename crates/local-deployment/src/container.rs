@@ -14,10 +14,14 @@ use db::{
     models::{
         coding_agent_turn::CodingAgentTurn,
         execution_process::{
-            ExecutionContext, ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus,
+            ExecutionContext, ExecutionProcess, ExecutionProcessError, ExecutionProcessRunReason,
+            ExecutionProcessStatus,
         },
         execution_process_repo_state::ExecutionProcessRepoState,
+        merge::{Merge, PrMerge},
+        pr_comment_follow_up::{PrCommentFollowUp, PrCommentFollowUpStatus},
         repo::Repo,
+        review_comment::{ReviewComment, ReviewCommentStatus, ReviewSide, UpdateReviewComment},
         scratch::{DraftFollowUpData, Scratch, ScratchType},
         session::{Session, SessionError},
         task::{Task, TaskStatus},
@@ -33,7 +37,7 @@ use executors::{
         coding_agent_initial::CodingAgentInitialRequest,
     },
     approvals::{ExecutorApprovalService, NoopExecutorApprovalService},
-    env::{ExecutionEnv, RepoContext},
+    env::{ContainerTarget, ExecutionEnv, RepoContext},
     executors::{BaseCodingAgent, ExecutorExitResult, ExecutorExitSignal, InterruptSender},
     logs::{NormalizedEntryType, utils::patch::extract_normalized_entry_from_patch},
 };
@@ -43,30 +47,46 @@ use services::services::{
     analytics::AnalyticsContext,
     approvals::{Approvals, executor_approvals::ExecutorApprovalBridge},
     config::Config,
-    container::{ContainerError, ContainerRef, ContainerService},
-    diff_stream::{self, DiffStreamHandle},
-    git::{GitCli, GitService},
+    container::{ContainerError, ContainerRef, ContainerService, GateKind},
+    diff_stream::{self, DiffStreamHandle, DiffStreamTarget},
+    git::{Commit, GitCli, GitService},
+    git_host::{self, GitHostProvider, PrCommentThread},
     image::ImageService,
     notification::NotificationService,
+    pr_comment_follow_ups,
     queued_message::QueuedMessageService,
+    review_comments,
     workspace_manager::{RepoWorkspaceInput, WorkspaceManager},
 };
 use tokio::{sync::RwLock, task::JoinHandle};
 use tokio_util::io::ReaderStream;
 use utils::{
     log_msg::LogMsg,
+    metrics::metrics,
     msg_store::MsgStore,
+    telemetry::spawn_in_current_span,
     text::{git_branch_id, short_uuid, truncate_to_char_boundary},
 };
 use uuid::Uuid;
 
-use crate::{command, copy};
+use crate::{
+    command, copy, oci,
+    worker_host::{self, WorkerWorkspace},
+};
+
+/// How often a workspace is checked for idleness before PR comment fixes are pushed
+const PR_FOLLOW_UP_POLL_INTERVAL: Duration = Duration::from_secs(2);
 
 #[derive(Clone)]
 pub struct LocalContainerService {
     db: DBService,
     child_store: Arc<RwLock<HashMap<Uuid, Arc<RwLock<AsyncGroupChild>>>>>,
     interrupt_senders: Arc<RwLock<HashMap<Uuid, InterruptSender>>>,
+    /// Executions running inside a workspace container; the workspaces of
+    /// projects with a container image run in one, see `oci`
+    container_execs: Arc<RwLock<HashMap<Uuid, ContainerTarget>>>,
+    /// Executions running on an SSH worker host, synced back when they exit
+    worker_execs: Arc<RwLock<HashMap<Uuid, WorkerWorkspace>>>,
     msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
     config: Arc<RwLock<Config>>,
     git: GitService,
@@ -91,12 +111,16 @@ impl LocalContainerService {
     ) -> Self {
         let child_store = Arc::new(RwLock::new(HashMap::new()));
         let interrupt_senders = Arc::new(RwLock::new(HashMap::new()));
+        let container_execs = Arc::new(RwLock::new(HashMap::new()));
+        let worker_execs = Arc::new(RwLock::new(HashMap::new()));
         let notification_service = NotificationService::new(config.clone());
 
         let container = LocalContainerService {
             db,
             child_store,
             interrupt_senders,
+            container_execs,
+            worker_execs,
             msg_stores,
             config,
             git,
@@ -137,10 +161,27 @@ impl LocalContainerService {
         map.remove(id)
     }
 
+    /// Stop the execution's process inside its workspace container or on its
+    /// worker host, if it runs in one
+    async fn kill_remote_exec(&self, id: &Uuid) {
+        let target = self.container_execs.write().await.remove(id);
+        if let Some(target) = target {
+            oci::kill_exec(&target).await;
+        }
+        // Kept until the exit monitor has synced the worktrees back
+        let worker = self.worker_execs.read().await.get(id).cloned();
+        if let Some(worker) = worker {
+            worker_host::kill_exec(&worker.host, &worker.exec_target(*id)).await;
+        }
+    }
+
     pub async fn cleanup_workspace(db: &DBService, workspace: &Workspace) {
+        // A container may outlive a workspace that never got a directory
+        oci::remove_workspace_container(workspace.id).await;
         let Some(container_ref) = &workspace.container_ref else {
             return;
         };
+        worker_host::remove_workspace(&db.pool, workspace.id).await;
         let workspace_dir = PathBuf::from(container_ref);
 
         let repositories = WorkspaceRepo::find_repos_for_workspace(&db.pool, workspace.id)
@@ -228,6 +269,251 @@ impl LocalContainerService {
         }
     }
 
+    /// Settle the review comments sent with this turn: resolved when the turn changed
+    /// their line, reopened otherwise so they go out with the next review.
+    async fn settle_review_comments(&self, exec_id: Uuid) {
+        let pool = &self.db.pool;
+        let comments = match ReviewComment::find_by_sent_execution_process_id(pool, exec_id).await {
+            Ok(comments) if !comments.is_empty() => comments,
+            Ok(_) => return,
+            Err(e) => {
+                tracing::warn!("Failed to load review comments for {}: {}", exec_id, e);
+                return;
+            }
+        };
+        let Ok(ctx) = ExecutionProcess::load_context(pool, exec_id).await else {
+            return;
+        };
+        let repo_states = ExecutionProcessRepoState::find_by_execution_process_id(pool, exec_id)
+            .await
+            .unwrap_or_default();
+        let workspace_root = self.workspace_to_current_dir(&ctx.workspace);
+
+        for comment in comments {
+            let Some(repo) = ctx.repos.iter().find(|repo| repo.id == comment.repo_id) else {
+                continue;
+            };
+            let worktree_path = workspace_root.join(&repo.name);
+            let before = match comment.side {
+                // The snapshot taken when the review was sent
+                ReviewSide::New => comment
+                    .anchor_blob
+                    .as_deref()
+                    .and_then(|blob| self.git().read_blob(&worktree_path, blob).ok().flatten()),
+                ReviewSide::Old => repo_states
+                    .iter()
+                    .find(|state| state.repo_id == repo.id)
+                    .and_then(|state| state.before_head_commit.as_deref())
+                    .and_then(|sha| {
+                        self.git()
+                            .read_file_at_commit(&worktree_path, sha, &comment.file_path)
+                            .ok()
+                            .flatten()
+                    }),
+            };
+            let after = std::fs::read_to_string(worktree_path.join(&comment.file_path)).ok();
+            let changed = match &before {
+                Some(before) => review_comments::line_changed(&comment, before, after.as_deref()),
+                None => after.is_some(),
+            };
+
+            let status = if changed {
+                ReviewCommentStatus::Resolved
+            } else {
+                ReviewCommentStatus::Open
+            };
+            let update = UpdateReviewComment {
+                body: None,
+                status: Some(status),
+            };
+            if let Err(e) = ReviewComment::update(pool, comment.id, &update).await {
+                tracing::warn!("Failed to settle review comment {}: {}", comment.id, e);
+            }
+        }
+    }
+
+    /// Push the fixes for PR comments sent with this turn once the workspace is idle,
+    /// then mark the comments addressed and optionally reply with the pushed commit.
+    async fn settle_pr_comment_follow_ups(&self, exec_id: Uuid) {
+        let pool = &self.db.pool;
+        let follow_ups =
+            match PrCommentFollowUp::find_sent_by_execution_process_id(pool, exec_id).await {
+                Ok(follow_ups) if !follow_ups.is_empty() => follow_ups,
+                Ok(_) => return,
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to load PR comment follow-ups for {}: {}",
+                        exec_id,
+                        e
+                    );
+                    return;
+                }
+            };
+        let Ok(ctx) = ExecutionProcess::load_context(pool, exec_id).await else {
+            return;
+        };
+
+        let status = if ctx.execution_process.status == ExecutionProcessStatus::Completed {
+            // Wait for the cleanup script and any chained actions before pushing
+            loop {
+                match ExecutionProcess::has_running_non_dev_server_processes_for_workspace(
+                    pool,
+                    ctx.workspace.id,
+                )
+                .await
+                {
+                    Ok(true) => tokio::time::sleep(PR_FOLLOW_UP_POLL_INTERVAL).await,
+                    Ok(false) => break,
+                    Err(e) => {
+                        tracing::warn!("Failed to check running processes: {}", e);
+                        return;
+                    }
+                }
+            }
+            self.push_pr_comment_fixes(&ctx, &follow_ups).await
+        } else {
+            Err(anyhow!(
+                "Agent turn ended with status {:?}",
+                ctx.execution_process.status
+            ))
+        };
+
+        let merges = Merge::find_by_workspace_id(pool, ctx.workspace.id)
+            .await
+            .unwrap_or_default();
+        let replies_enabled = self.config.read().await.pr_comment_replies_enabled;
+        for follow_up in follow_ups {
+            let pushed = status.as_ref().ok().and_then(|pushed| {
+                pushed
+                    .iter()
+                    .find(|(merge_id, _)| *merge_id == follow_up.merge_id)
+            });
+            let Some((_, commit)) = pushed else {
+                if let Err(e) = &status {
+                    tracing::warn!(
+                        "PR comment {} was not addressed: {}",
+                        follow_up.comment_id,
+                        e
+                    );
+                }
+                let _ = PrCommentFollowUp::update_status(
+                    pool,
+                    follow_up.id,
+                    PrCommentFollowUpStatus::Failed,
+                    None,
+                )
+                .await;
+                continue;
+            };
+
+            if replies_enabled
+                && let Some(Merge::Pr(pr_merge)) = merges
+                    .iter()
+                    .find(|merge| matches!(merge, Merge::Pr(pr) if pr.id == follow_up.merge_id))
+                && let Err(e) = self
+                    .reply_to_pr_comment(&ctx, pr_merge, &follow_up, commit)
+                    .await
+            {
+                tracing::warn!(
+                    "Failed to reply to PR comment {}: {}",
+                    follow_up.comment_id,
+                    e
+                );
+            }
+            if let Err(e) = PrCommentFollowUp::update_status(
+                pool,
+                follow_up.id,
+                PrCommentFollowUpStatus::Addressed,
+                Some(commit),
+            )
+            .await
+            {
+                tracing::warn!("Failed to settle PR comment follow-up: {}", e);
+            }
+        }
+    }
+
+    /// Run the pre-push gate and push every repository with addressed comments.
+    /// Returns the pushed HEAD commit per PR.
+    async fn push_pr_comment_fixes(
+        &self,
+        ctx: &ExecutionContext,
+        follow_ups: &[PrCommentFollowUp],
+    ) -> Result<Vec<(Uuid, String)>, anyhow::Error> {
+        let merges = Merge::find_by_workspace_id(&self.db.pool, ctx.workspace.id).await?;
+        let workspace_root = self.workspace_to_current_dir(&ctx.workspace);
+        let mut pushed = Vec::new();
+
+        for merge in merges {
+            let Merge::Pr(pr_merge) = merge else {
+                continue;
+            };
+            if !follow_ups.iter().any(|f| f.merge_id == pr_merge.id) {
+                continue;
+            }
+            let repo = ctx
+                .repos
+                .iter()
+                .find(|repo| repo.id == pr_merge.repo_id)
+                .ok_or_else(|| {
+                    anyhow!("Repository of PR #{} not found", pr_merge.pr_info.number)
+                })?;
+            if let Some(failure) = self
+                .run_gate(&ctx.workspace, repo, GateKind::PrePush)
+                .await?
+            {
+                return Err(anyhow!(
+                    "{} gate failed in {}",
+                    failure.gate.label(),
+                    failure.repo_name
+                ));
+            }
+
+            let worktree_path = workspace_root.join(&repo.name);
+            self.git
+                .push_to_remote(&worktree_path, &ctx.workspace.branch, false)?;
+            let head = self.git.get_head_info(&worktree_path)?;
+            pushed.push((pr_merge.id, head.oid));
+        }
+
+        Ok(pushed)
+    }
+
+    async fn reply_to_pr_comment(
+        &self,
+        ctx: &ExecutionContext,
+        pr_merge: &PrMerge,
+        follow_up: &PrCommentFollowUp,
+        commit: &str,
+    ) -> Result<(), anyhow::Error> {
+        let repo = ctx
+            .repos
+            .iter()
+            .find(|repo| repo.id == pr_merge.repo_id)
+            .ok_or_else(|| anyhow!("Repository of PR #{} not found", pr_merge.pr_info.number))?;
+        let remote_url = self.git.get_remote_url(
+            &repo.path,
+            &self
+                .git
+                .resolve_remote_name_for_branch(&repo.path, &pr_merge.target_branch_name)?,
+        )?;
+        let thread = PrCommentThread {
+            comment_id: follow_up.comment_id.clone(),
+            thread_id: follow_up.thread_id,
+            is_review: follow_up.is_review,
+        };
+        git_host::GitHostService::from_url(&remote_url)?
+            .reply_to_pr_comment(
+                &repo.path,
+                &remote_url,
+                pr_merge.pr_info.number,
+                &thread,
+                &pr_comment_follow_ups::reply_body(commit),
+            )
+            .await?;
+        Ok(())
+    }
+
     /// Get the commit message based on the execution run reason.
     async fn get_commit_message(&self, ctx: &ExecutionContext) -> String {
         match ctx.execution_process.run_reason {
@@ -380,7 +666,9 @@ impl LocalContainerService {
 
         let mut process_exit_rx = self.spawn_os_exit_watcher(exec_id);
 
-        tokio::spawn(async move {
+        // Runs in the execution's trace so the commit and next action show up in it
+        let _span = tracing::info_span!("exit_monitor", execution_id = %exec_id).entered();
+        spawn_in_current_span(async move {
             let mut exit_signal_future = exit_signal
                 .map(|rx| rx.boxed()) // wait for result
                 .unwrap_or_else(|| std::future::pending().boxed()); // no signal, stall forever
@@ -394,6 +682,7 @@ impl LocalContainerService {
                 // signals when processing has finished to gracefully kill the process.
                 exit_result = &mut exit_signal_future => {
                     // Executor signaled completion: kill group and use the provided result
+                    container.kill_remote_exec(&exec_id).await;
                     if let Some(child_lock) = child_store.read().await.get(&exec_id).cloned() {
                         let mut child = child_lock.write().await ;
                         if let Err(err) = command::kill_process_group(&mut child).await {
@@ -434,7 +723,14 @@ impl LocalContainerService {
                 tracing::error!("Failed to update execution process completion: {}", e);
             }
 
+            let worker = container.worker_execs.write().await.remove(&exec_id);
+            if let Some(worker) = worker {
+                worker.sync_back().await;
+            }
+
             if let Ok(ctx) = ExecutionProcess::load_context(&db.pool, exec_id).await {
+                record_execution_metrics(&ctx);
+
                 // Update executor session summary if available
                 if let Err(e) = container.update_executor_session_summary(&exec_id).await {
                     tracing::warn!("Failed to update executor session summary: {}", e);
@@ -570,6 +866,11 @@ impl LocalContainerService {
             // Now that commit/next-action/finalization steps for this process are complete,
             // capture the HEAD OID as the definitive "after" state (best-effort).
             container.update_after_head_commits(exec_id).await;
+            container.settle_review_comments(exec_id).await;
+            let settle_container = container.clone();
+            tokio::spawn(async move {
+                settle_container.settle_pr_comment_follow_ups(exec_id).await;
+            });
 
             // Cleanup msg store
             if let Some(msg_arc) = msg_stores.write().await.remove(&exec_id) {
@@ -587,6 +888,7 @@ impl LocalContainerService {
 
             // Cleanup child handle
             child_store.write().await.remove(&exec_id);
+            container.container_execs.write().await.remove(&exec_id);
         })
     }
 
@@ -667,6 +969,154 @@ impl LocalContainerService {
             .map_err(|e| ContainerError::Other(anyhow!("{e}")))
     }
 
+    async fn create_snapshot_diff_stream(
+        &self,
+        args: diff_stream::SnapshotDiffArgs,
+    ) -> Result<DiffStreamHandle, ContainerError> {
+        diff_stream::create_snapshot(args)
+            .await
+            .map_err(|e| ContainerError::Other(anyhow!("{e}")))
+    }
+
+    /// Live diff of each repo from its merge base with the target branch
+    async fn workspace_diff_streams(
+        &self,
+        workspace: &Workspace,
+        repositories: Vec<Repo>,
+        stats_only: bool,
+    ) -> Result<Vec<DiffStreamHandle>, ContainerError> {
+        let workspace_repos =
+            WorkspaceRepo::find_by_workspace_id(&self.db.pool, workspace.id).await?;
+        let target_branches: HashMap<_, _> = workspace_repos
+            .iter()
+            .map(|wr| (wr.repo_id, wr.target_branch.clone()))
+            .collect();
+
+        let mut streams = Vec::new();
+
+        let container_ref = self.ensure_container_exists(workspace).await?;
+        let workspace_root = PathBuf::from(container_ref);
+
+        for repo in repositories {
+            let worktree_path = workspace_root.join(&repo.name);
+            let branch = &workspace.branch;
+
+            let Some(target_branch) = target_branches.get(&repo.id) else {
+                tracing::warn!(
+                    "Skipping diff stream for repo {}: no target branch configured",
+                    repo.name
+                );
+                continue;
+            };
+
+            let base_commit = match self
+                .git()
+                .get_base_commit(&repo.path, branch, target_branch)
+            {
+                Ok(c) => c,
+                Err(e) => {
+                    tracing::warn!(
+                        "Skipping diff stream for repo {}: failed to get base commit: {}",
+                        repo.name,
+                        e
+                    );
+                    continue;
+                }
+            };
+
+            let stream = self
+                .create_live_diff_stream(diff_stream::DiffStreamArgs {
+                    git_service: self.git().clone(),
+                    db: self.db().clone(),
+                    workspace_id: workspace.id,
+                    repo_id: repo.id,
+                    repo_path: repo.path.clone(),
+                    worktree_path: worktree_path.clone(),
+                    branch: branch.to_string(),
+                    target_branch: target_branch.clone(),
+                    base_commit: base_commit.clone(),
+                    stats_only,
+                    path_prefix: Some(repo.name.clone()),
+                    fixed_base: false,
+                })
+                .await?;
+
+            streams.push(stream);
+        }
+
+        Ok(streams)
+    }
+
+    /// Diff of what one execution changed in each repo. Finished executions are
+    /// a fixed commit range; running ones compare the worktree against the
+    /// commit they started from.
+    async fn execution_diff_streams(
+        &self,
+        workspace: &Workspace,
+        repositories: &[Repo],
+        execution_id: Uuid,
+        stats_only: bool,
+    ) -> Result<Vec<DiffStreamHandle>, ContainerError> {
+        let process = ExecutionProcess::find_by_id(&self.db.pool, execution_id)
+            .await?
+            .ok_or(ExecutionProcessError::ExecutionProcessNotFound)?;
+        let running = process.status == ExecutionProcessStatus::Running;
+        let target_branches: HashMap<_, _> =
+            WorkspaceRepo::find_by_workspace_id(&self.db.pool, workspace.id)
+                .await?
+                .into_iter()
+                .map(|wr| (wr.repo_id, wr.target_branch))
+                .collect();
+        let workspace_root = PathBuf::from(self.ensure_container_exists(workspace).await?);
+
+        let mut streams = Vec::new();
+        for state in
+            ExecutionProcessRepoState::find_by_execution_process_id(&self.db.pool, execution_id)
+                .await?
+        {
+            let Some(repo) = repositories.iter().find(|repo| repo.id == state.repo_id) else {
+                continue;
+            };
+            let Some(before) = state.before_head_commit else {
+                continue;
+            };
+
+            let stream = match state.after_head_commit {
+                Some(after) if !running => {
+                    self.create_snapshot_diff_stream(diff_stream::SnapshotDiffArgs {
+                        git_service: self.git().clone(),
+                        repo_id: repo.id,
+                        repo_path: repo.path.clone(),
+                        from_commit: Some(before),
+                        to_commit: after,
+                        stats_only,
+                        path_prefix: Some(repo.name.clone()),
+                    })
+                    .await?
+                }
+                _ => {
+                    self.create_live_diff_stream(diff_stream::DiffStreamArgs {
+                        git_service: self.git().clone(),
+                        db: self.db().clone(),
+                        workspace_id: workspace.id,
+                        repo_id: repo.id,
+                        repo_path: repo.path.clone(),
+                        worktree_path: workspace_root.join(&repo.name),
+                        branch: workspace.branch.clone(),
+                        target_branch: target_branches.get(&repo.id).cloned().unwrap_or_default(),
+                        base_commit: Commit::from_sha(&before)?,
+                        stats_only,
+                        path_prefix: Some(repo.name.clone()),
+                        fixed_base: true,
+                    })
+                    .await?
+                }
+            };
+            streams.push(stream);
+        }
+        Ok(streams)
+    }
+
     /// Extract the last assistant message from the MsgStore history
     fn extract_last_assistant_message(&self, exec_id: &Uuid) -> Option<String> {
         // Get the MsgStore for this execution
@@ -880,16 +1330,48 @@ impl LocalContainerService {
 
         let action = ExecutorAction::new(action_type, cleanup_action.map(Box::new));
 
-        self.start_execution(
-            &ctx.workspace,
-            &ctx.session,
-            &action,
-            &ExecutionProcessRunReason::CodingAgent,
-        )
-        .await
+        let execution_process = self
+            .start_execution(
+                &ctx.workspace,
+                &ctx.session,
+                &action,
+                &ExecutionProcessRunReason::CodingAgent,
+            )
+            .await?;
+
+        if queued_data
+            .message
+            .contains(pr_comment_follow_ups::PROMPT_HEADER)
+        {
+            PrCommentFollowUp::mark_queued_sent(
+                &self.db.pool,
+                ctx.session.id,
+                execution_process.id,
+            )
+            .await?;
+        }
+
+        Ok(execution_process)
     }
 }
 
+fn record_execution_metrics(ctx: &ExecutionContext) {
+    let process = &ctx.execution_process;
+    let run_reason = serde_json::to_value(&process.run_reason).unwrap_or_default();
+    let status = serde_json::to_value(&process.status).unwrap_or_default();
+    let labels = [
+        ctx.session.executor.as_deref().unwrap_or("none"),
+        run_reason.as_str().unwrap_or_default(),
+        status.as_str().unwrap_or_default(),
+    ];
+    let finished_at = process.completed_at.unwrap_or_else(chrono::Utc::now);
+    let duration = (finished_at - process.started_at)
+        .to_std()
+        .unwrap_or_default();
+    metrics().executions_finished.inc(&labels);
+    metrics().execution_duration.observe(&labels, duration);
+}
+
 fn failure_exit_status() -> std::process::ExitStatus {
     #[cfg(unix)]
     {
@@ -929,6 +1411,7 @@ impl ContainerService for LocalContainerService {
         PathBuf::from(workspace.container_ref.clone().unwrap_or_default())
     }
 
+    #[tracing::instrument(name = "create_worktrees", skip_all, fields(workspace_id = %workspace.id))]
     async fn create(&self, workspace: &Workspace) -> Result<ContainerRef, ContainerError> {
         let task = workspace
             .parent_task(&self.db.pool)
@@ -1066,6 +1549,11 @@ impl ContainerService for LocalContainerService {
         Ok(true)
     }
 
+    #[tracing::instrument(
+        name = "spawn_execution",
+        skip_all,
+        fields(execution_id = %execution_process.id)
+    )]
     async fn start_execution_inner(
         &self,
         workspace: &Workspace,
@@ -1123,6 +1611,27 @@ impl ContainerService for LocalContainerService {
         env.insert("VK_WORKSPACE_ID", workspace.id.to_string());
         env.insert("VK_WORKSPACE_BRANCH", &workspace.branch);
 
+        let workspace_container =
+            oci::ensure_workspace_container(&self.db.pool, workspace, &current_dir)
+                .await
+                .map_err(|e| ContainerError::Other(anyhow!(e)))?;
+
+        let mut worker_workspace = None;
+        if let Some(workspace_container) = workspace_container {
+            env.container = Some(workspace_container.exec_target(execution_process.id));
+        } else if !matches!(
+            execution_process.run_reason,
+            ExecutionProcessRunReason::DevServer
+        ) && let Some(worker) =
+            worker_host::prepare_workspace(&self.db.pool, workspace, &current_dir)
+                .await
+                .map_err(|e| ContainerError::Other(anyhow!(e)))?
+        {
+            // Dev servers stay local so their ports are reachable
+            env.ssh = Some(worker.exec_target(execution_process.id));
+            worker_workspace = Some(worker);
+        }
+
         // Create the child and stream, add to execution tracker with timeout
         let mut spawned = tokio::time::timeout(
             Duration::from_secs(30),
@@ -1135,6 +1644,19 @@ impl ContainerService for LocalContainerService {
             ))
         })??;
 
+        if let Some(target) = env.container {
+            self.container_execs
+                .write()
+                .await
+                .insert(execution_process.id, target);
+        }
+        if let Some(worker) = worker_workspace {
+            self.worker_execs
+                .write()
+                .await
+                .insert(execution_process.id, worker);
+        }
+
         self.track_child_msgs_in_store(execution_process.id, &mut spawned.child)
             .await;
 
@@ -1204,6 +1726,7 @@ impl ContainerService for LocalContainerService {
         }
 
         // Kill the child process and remove from the store
+        self.kill_remote_exec(&execution_process.id).await;
         {
             let mut child_guard = child.write().await;
             if let Err(e) = command::kill_process_group(&mut child_guard).await {
@@ -1226,7 +1749,7 @@ impl ContainerService for LocalContainerService {
         if let Ok(ctx) = ExecutionProcess::load_context(&self.db.pool, execution_process.id).await
             && !matches!(
                 ctx.execution_process.run_reason,
-                ExecutionProcessRunReason::DevServer
+                ExecutionProcessRunReason::DevServer | ExecutionProcessRunReason::GateScript
             )
             && let Err(e) =
                 Task::update_status(&self.db.pool, ctx.task.id, TaskStatus::InReview).await
@@ -1248,78 +1771,61 @@ impl ContainerService for LocalContainerService {
     async fn stream_diff(
         &self,
         workspace: &Workspace,
+        target: &DiffStreamTarget,
         stats_only: bool,
     ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, ContainerError>
     {
-        let workspace_repos =
-            WorkspaceRepo::find_by_workspace_id(&self.db.pool, workspace.id).await?;
-        let target_branches: HashMap<_, _> = workspace_repos
-            .iter()
-            .map(|wr| (wr.repo_id, wr.target_branch.clone()))
-            .collect();
-
         let repositories =
             WorkspaceRepo::find_repos_for_workspace(&self.db.pool, workspace.id).await?;
 
-        let mut streams = Vec::new();
-
-        let container_ref = self.ensure_container_exists(workspace).await?;
-        let workspace_root = PathBuf::from(container_ref);
-
-        for repo in repositories {
-            let worktree_path = workspace_root.join(&repo.name);
-            let branch = &workspace.branch;
-
-            let Some(target_branch) = target_branches.get(&repo.id) else {
-                tracing::warn!(
-                    "Skipping diff stream for repo {}: no target branch configured",
-                    repo.name
-                );
-                continue;
-            };
-
-            let base_commit = match self
-                .git()
-                .get_base_commit(&repo.path, branch, target_branch)
-            {
-                Ok(c) => c,
-                Err(e) => {
-                    tracing::warn!(
-                        "Skipping diff stream for repo {}: failed to get base commit: {}",
-                        repo.name,
-                        e
-                    );
-                    continue;
-                }
-            };
-
-            let stream = self
-                .create_live_diff_stream(diff_stream::DiffStreamArgs {
-                    git_service: self.git().clone(),
-                    db: self.db().clone(),
-                    workspace_id: workspace.id,
-                    repo_id: repo.id,
-                    repo_path: repo.path.clone(),
-                    worktree_path: worktree_path.clone(),
-                    branch: branch.to_string(),
-                    target_branch: target_branch.clone(),
-                    base_commit: base_commit.clone(),
-                    stats_only,
-                    path_prefix: Some(repo.name.clone()),
-                })
-                .await?;
-
-            streams.push(Box::pin(stream));
-        }
+        let streams = match target {
+            DiffStreamTarget::Workspace => {
+                self.workspace_diff_streams(workspace, repositories, stats_only)
+                    .await?
+            }
+            DiffStreamTarget::Execution(execution_id) => {
+                self.execution_diff_streams(workspace, &repositories, *execution_id, stats_only)
+                    .await?
+            }
+            DiffStreamTarget::Commit { repo_id, sha } => {
+                let repo = repositories
+                    .iter()
+                    .find(|repo| repo.id == *repo_id)
+                    .ok_or_else(|| {
+                        ContainerError::Other(anyhow!(
+                            "Repository {repo_id} is not part of this workspace"
+                        ))
+                    })?;
+                vec![
+                    self.create_snapshot_diff_stream(diff_stream::SnapshotDiffArgs {
+                        git_service: self.git().clone(),
+                        repo_id: repo.id,
+                        repo_path: repo.path.clone(),
+                        from_commit: None,
+                        to_commit: sha.clone(),
+                        stats_only,
+                        path_prefix: Some(repo.name.clone()),
+                    })
+                    .await?,
+                ]
+            }
+        };
 
         if streams.is_empty() {
             return Ok(Box::pin(futures::stream::empty()));
         }
 
         // Merge all streams into one
-        Ok(Box::pin(futures::stream::select_all(streams)))
+        Ok(Box::pin(futures::stream::select_all(
+            streams.into_iter().map(Box::pin),
+        )))
     }
 
+    #[tracing::instrument(
+        name = "commit_changes",
+        skip_all,
+        fields(workspace_id = %ctx.workspace.id, execution_id = %ctx.execution_process.id)
+    )]
     async fn try_commit_changes(&self, ctx: &ExecutionContext) -> Result<bool, ContainerError> {
         if !matches!(
             ctx.execution_process.run_reason,
@@ -1346,6 +1852,47 @@ impl ContainerService for LocalContainerService {
         Ok(self.commit_repos(repos_with_changes, &message))
     }
 
+    async fn start_queued_message(
+        &self,
+        session_id: Uuid,
+    ) -> Result<Option<ExecutionProcess>, ContainerError> {
+        let pool = &self.db.pool;
+        let Some(session) = Session::find_by_id(pool, session_id).await? else {
+            return Ok(None);
+        };
+        if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(
+            pool,
+            session.workspace_id,
+        )
+        .await?
+        {
+            return Ok(None);
+        }
+        let Some(latest) = ExecutionProcess::find_latest_by_session_and_run_reason(
+            pool,
+            session_id,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?
+        else {
+            return Ok(None);
+        };
+        let Some(queued_msg) = self.queued_message_service.take_queued(session_id) else {
+            return Ok(None);
+        };
+
+        if let Err(e) = Scratch::delete(pool, session_id, &ScratchType::DraftFollowUp).await {
+            tracing::warn!(
+                "Failed to delete scratch after consuming queued message: {}",
+                e
+            );
+        }
+        let ctx = ExecutionProcess::load_context(pool, latest.id).await?;
+        self.start_queued_follow_up(&ctx, &queued_msg.data)
+            .await
+            .map(Some)
+    }
+
     /// Copy files from the original project directory to the worktree.
     /// Skips files that already exist at target with same size.
     async fn copy_project_files(
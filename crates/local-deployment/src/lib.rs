@@ -34,7 +34,9 @@ use crate::{container::LocalContainerService, pty::PtyService};
 mod command;
 pub mod container;
 mod copy;
+pub mod oci;
 pub mod pty;
+pub mod worker_host;
 
 #[derive(Clone)]
 pub struct LocalDeployment {
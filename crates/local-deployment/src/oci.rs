@@ -0,0 +1,553 @@
+//! Workspace containers for projects with a container image.
+//!
+//! Each workspace gets one long-lived container started from the project's image,
+//! with the workspace directory and the `.git` directory of every repository
+//! bind-mounted at their host paths, so git worktrees resolve inside it. The
+//! `.git` directory is mounted read-only, except for the objects, refs and the
+//! workspace's own worktree metadata, so commits work but hooks and config that
+//! the host's git would run stay out of reach. Containers get their own bridge
+//! network, with the project's dev server port published on the host's loopback
+//! interface. Agents, scripts, the dev server and terminals then run through
+//! `exec`; `LocalContainerService` routes executions there. The runtime's CLI is
+//! used rather than its API, which lets `DOCKER_HOST` or `CONTAINER_HOST` pick
+//! the socket. Set `VK_CONTAINER_RUNTIME` to choose between Docker and Podman;
+//! otherwise the first one on the PATH is used.
+
+use std::{
+    path::{Path, PathBuf},
+    process::Output,
+};
+
+use db::models::{
+    project::{Project, is_valid_container_image},
+    workspace::Workspace,
+    workspace_repo::WorkspaceRepo,
+};
+use executors::env::ContainerTarget;
+use portable_pty::CommandBuilder;
+use sqlx::SqlitePool;
+use thiserror::Error;
+use tokio::{process::Command, sync::OnceCell};
+use utils::shell::resolve_executable_path;
+use uuid::Uuid;
+
+const RUNTIME_ENV: &str = "VK_CONTAINER_RUNTIME";
+const NAME_PREFIX: &str = "vibe-kanban-";
+const WORKSPACE_LABEL: &str = "vibe-kanban.workspace";
+/// Image and network settings the container was created with, so it is
+/// recreated when the project's settings change
+const SPEC_LABEL: &str = "vibe-kanban.spec";
+
+#[derive(Debug, Error)]
+pub enum OciError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("Neither docker nor podman was found; set {RUNTIME_ENV} to the runtime's path")]
+    RuntimeNotFound,
+    #[error("`{command}` failed: {stderr}")]
+    CommandFailed { command: String, stderr: String },
+    #[error("Invalid container image: {0:?}")]
+    InvalidImage(String),
+}
+
+/// A running workspace container
+#[derive(Debug, Clone)]
+pub struct WorkspaceContainer {
+    pub runtime: PathBuf,
+    pub name: String,
+}
+
+impl WorkspaceContainer {
+    /// Target for one execution process, with its own pid file
+    pub fn exec_target(&self, execution_id: Uuid) -> ContainerTarget {
+        ContainerTarget {
+            runtime: self.runtime.clone(),
+            container: self.name.clone(),
+            pid_file: format!("/tmp/vk-exec-{}.pid", execution_id.simple()),
+        }
+    }
+
+    /// Interactive shell in the container for a terminal session
+    pub fn shell_command(&self, working_dir: &Path) -> CommandBuilder {
+        let mut cmd = CommandBuilder::new(&self.runtime);
+        cmd.args(["exec", "-it", "-w"]);
+        cmd.arg(working_dir);
+        cmd.args(["-e", "TERM=xterm-256color", "-e", "COLORTERM=truecolor"]);
+        cmd.args(["-e", "PS1=$ ", "-e", "VIBE_KANBAN_TERMINAL=1"]);
+        cmd.arg(&self.name);
+        cmd.arg("sh");
+        cmd
+    }
+}
+
+pub fn container_name(workspace_id: Uuid) -> String {
+    format!("{NAME_PREFIX}{}", workspace_id.simple())
+}
+
+async fn runtime() -> Result<PathBuf, OciError> {
+    static RUNTIME: OnceCell<Option<PathBuf>> = OnceCell::const_new();
+    RUNTIME
+        .get_or_init(|| async {
+            match std::env::var(RUNTIME_ENV) {
+                Ok(runtime) => resolve_executable_path(&runtime).await,
+                Err(_) => match resolve_executable_path("docker").await {
+                    Some(docker) => Some(docker),
+                    None => resolve_executable_path("podman").await,
+                },
+            }
+        })
+        .await
+        .clone()
+        .ok_or(OciError::RuntimeNotFound)
+}
+
+fn is_podman(runtime: &Path) -> bool {
+    runtime
+        .file_name()
+        .is_some_and(|name| name.to_string_lossy().contains("podman"))
+}
+
+async fn run(runtime: &Path, args: &[String]) -> Result<Output, OciError> {
+    Ok(Command::new(runtime)
+        .args(args)
+        .kill_on_drop(true)
+        .output()
+        .await?)
+}
+
+async fn run_checked(runtime: &Path, args: Vec<String>) -> Result<String, OciError> {
+    let output = run(runtime, &args).await?;
+    if !output.status.success() {
+        return Err(OciError::CommandFailed {
+            command: format!("{} {}", runtime.display(), args.join(" ")),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn network_mode(project: &Project) -> &'static str {
+    if project.container_network_isolated {
+        "none"
+    } else {
+        "bridge"
+    }
+}
+
+/// Dev server port to publish; an isolated container has nothing to publish
+fn published_port(project: &Project) -> Option<i64> {
+    project
+        .container_dev_server_port
+        .filter(|_| !project.container_network_isolated)
+}
+
+fn container_spec(project: &Project, image: &str) -> String {
+    let port = published_port(project).map(|port| port.to_string());
+    format!(
+        "{image};{};{}",
+        network_mode(project),
+        port.unwrap_or_default()
+    )
+}
+
+/// A host path bind-mounted at the same path in the container
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BindMount {
+    path: PathBuf,
+    read_only: bool,
+}
+
+impl BindMount {
+    fn read_write(path: PathBuf) -> Self {
+        Self {
+            path,
+            read_only: false,
+        }
+    }
+
+    fn read_only(path: PathBuf) -> Self {
+        Self {
+            path,
+            read_only: true,
+        }
+    }
+
+    /// `--mount` value. Unlike `-v`, it has no trouble with `:` in paths, and
+    /// fields with a `,` are quoted the way its CSV parser expects.
+    fn arg(&self) -> String {
+        let path = self.path.to_string_lossy();
+        let mut fields = vec![
+            "type=bind".to_string(),
+            csv_field(&format!("source={path}")),
+            csv_field(&format!("target={path}")),
+        ];
+        if self.read_only {
+            fields.push("readonly".to_string());
+        }
+        fields.join(",")
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// The admin directory of a worktree, from the `gitdir:` line of its `.git` file
+fn worktree_git_dir(worktree: &Path) -> Option<PathBuf> {
+    let contents = std::fs::read_to_string(worktree.join(".git")).ok()?;
+    let git_dir = contents.lines().next()?.strip_prefix("gitdir:")?.trim();
+    let git_dir = Path::new(git_dir);
+    Some(if git_dir.is_absolute() {
+        git_dir.to_path_buf()
+    } else {
+        worktree.join(git_dir)
+    })
+}
+
+/// Mounts for a repository's shared `.git` directory. It is read-only apart
+/// from what committing on the worktree's branch writes to. The worktree's
+/// `.git` file is writable from the container, so its admin directory is only
+/// mounted when it really is one.
+fn git_dir_mounts(git_dir: &Path, worktree_git_dir: Option<&Path>) -> Vec<BindMount> {
+    let mut mounts = vec![BindMount::read_only(git_dir.to_path_buf())];
+    let worktrees = git_dir.join("worktrees").canonicalize().ok();
+    let worktree_git_dir = worktree_git_dir
+        .and_then(|dir| dir.canonicalize().ok())
+        .filter(|dir| worktrees.is_some() && dir.parent() == worktrees.as_deref());
+    let writable = ["objects", "refs", "logs"]
+        .into_iter()
+        .map(|dir| git_dir.join(dir))
+        .chain(worktree_git_dir);
+    for path in writable {
+        if path.is_dir() {
+            mounts.push(BindMount::read_write(path));
+        }
+    }
+    mounts
+}
+
+/// `run` arguments for a workspace container
+fn run_args(
+    runtime: &Path,
+    name: &str,
+    workspace_id: Uuid,
+    project: &Project,
+    image: &str,
+    mounts: &[BindMount],
+    workspace_dir: &Path,
+) -> Result<Vec<String>, OciError> {
+    // Anything starting with `-` would be parsed as another option
+    if !is_valid_container_image(image) {
+        return Err(OciError::InvalidImage(image.to_string()));
+    }
+    let mut args: Vec<String> = vec!["run".into(), "-d".into(), "--init".into()];
+    args.extend(["--name".into(), name.to_string()]);
+    args.extend([
+        "--label".into(),
+        format!("{WORKSPACE_LABEL}={workspace_id}"),
+        "--label".into(),
+        format!("{SPEC_LABEL}={}", container_spec(project, image)),
+    ]);
+    args.push(format!("--network={}", network_mode(project)));
+    if let Some(port) = published_port(project) {
+        args.extend(["-p".into(), format!("127.0.0.1:{port}:{port}")]);
+    }
+    // Files written to the bind mounts stay owned by the host user
+    if is_podman(runtime) {
+        args.push("--userns=keep-id".into());
+    } else {
+        #[cfg(unix)]
+        {
+            use nix::unistd::{getgid, getuid};
+            args.extend(["--user".into(), format!("{}:{}", getuid(), getgid())]);
+            args.extend(["-e".into(), "HOME=/tmp".into()]);
+        }
+    }
+    // Later mounts nest over earlier ones, so writable subdirectories come last
+    for mount in mounts {
+        args.extend(["--mount".into(), mount.arg()]);
+    }
+    args.extend(["-w".into(), workspace_dir.to_string_lossy().into_owned()]);
+    args.push(image.to_string());
+    args.extend(["tail".into(), "-f".into(), "/dev/null".into()]);
+    Ok(args)
+}
+
+/// Start the workspace's container if its project has an image, reusing one
+/// that is already there. Returns None for projects that run on the host.
+pub async fn ensure_workspace_container(
+    pool: &SqlitePool,
+    workspace: &Workspace,
+    workspace_dir: &Path,
+) -> Result<Option<WorkspaceContainer>, OciError> {
+    let Some(task) = workspace.parent_task(pool).await? else {
+        return Ok(None);
+    };
+    let Some(project) = task.parent_project(pool).await? else {
+        return Ok(None);
+    };
+    let Some(image) = project
+        .container_image
+        .as_deref()
+        .map(str::trim)
+        .filter(|image| !image.is_empty())
+    else {
+        return Ok(None);
+    };
+
+    let runtime = runtime().await?;
+    let name = container_name(workspace.id);
+    let spec = container_spec(&project, image);
+    let inspect = run(
+        &runtime,
+        &[
+            "container".into(),
+            "inspect".into(),
+            "--format".into(),
+            format!("{{{{.State.Running}}}} {{{{index .Config.Labels \"{SPEC_LABEL}\"}}}}"),
+            name.clone(),
+        ],
+    )
+    .await?;
+    if inspect.status.success() {
+        let state = String::from_utf8_lossy(&inspect.stdout);
+        match state.trim().split_once(' ') {
+            Some(("true", existing)) if existing == spec => {
+                return Ok(Some(WorkspaceContainer { runtime, name }));
+            }
+            Some((_, existing)) if existing == spec => {
+                run_checked(&runtime, vec!["start".into(), name.clone()]).await?;
+                return Ok(Some(WorkspaceContainer { runtime, name }));
+            }
+            _ => {
+                tracing::info!("Recreating container {} after a settings change", name);
+                run_checked(&runtime, vec!["rm".into(), "-f".into(), name.clone()]).await?;
+            }
+        }
+    }
+
+    let mut mounts = vec![BindMount::read_write(workspace_dir.to_path_buf())];
+    for repo in WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await? {
+        let git_dir = repo.path.join(".git");
+        if git_dir.is_dir() {
+            let worktree_git_dir = worktree_git_dir(&workspace_dir.join(&repo.name));
+            mounts.extend(git_dir_mounts(&git_dir, worktree_git_dir.as_deref()));
+        }
+    }
+    let args = run_args(
+        &runtime,
+        &name,
+        workspace.id,
+        &project,
+        image,
+        &mounts,
+        workspace_dir,
+    )?;
+    tracing::info!("Starting container {} from image {}", name, image);
+    run_checked(&runtime, args).await?;
+
+    Ok(Some(WorkspaceContainer { runtime, name }))
+}
+
+/// The workspace's container if it is running
+pub async fn running_container(workspace_id: Uuid) -> Option<WorkspaceContainer> {
+    let runtime = runtime().await.ok()?;
+    let name = container_name(workspace_id);
+    let running = run_checked(
+        &runtime,
+        vec![
+            "container".into(),
+            "inspect".into(),
+            "--format".into(),
+            "{{.State.Running}}".into(),
+            name.clone(),
+        ],
+    )
+    .await
+    .ok()?;
+    (running == "true").then_some(WorkspaceContainer { runtime, name })
+}
+
+/// Stop an execution's process inside the container. Killing the host-side
+/// exec client does not reach it.
+pub async fn kill_exec(target: &ContainerTarget) {
+    let script = r#"pid=$(cat "$0" 2>/dev/null) || exit 0
+kill -TERM "$pid" 2>/dev/null && sleep 1 && kill -KILL "$pid" 2>/dev/null
+rm -f "$0""#;
+    let args = vec![
+        "exec".into(),
+        target.container.clone(),
+        "sh".into(),
+        "-c".into(),
+        script.into(),
+        target.pid_file.clone(),
+    ];
+    if let Err(e) = run_checked(&target.runtime, args).await {
+        tracing::warn!("Failed to stop process in {}: {}", target.container, e);
+    }
+}
+
+/// Remove the workspace's container, if any, along with everything running in it
+pub async fn remove_workspace_container(workspace_id: Uuid) {
+    let Ok(runtime) = runtime().await else {
+        return;
+    };
+    let name = container_name(workspace_id);
+    let output = run(&runtime, &["rm".into(), "-f".into(), name.clone()]).await;
+    if let Ok(output) = output
+        && output.status.success()
+    {
+        tracing::info!("Removed container {}", name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn project(network_isolated: bool, dev_server_port: Option<i64>) -> Project {
+        Project {
+            id: Uuid::new_v4(),
+            name: "app".to_string(),
+            default_agent_working_dir: None,
+            remote_project_id: None,
+            container_image: Some("node:22".to_string()),
+            container_network_isolated: network_isolated,
+            container_dev_server_port: dev_server_port,
+            worker_host_id: None,
+            worker_host_auto: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn args_for(
+        project: &Project,
+        image: &str,
+        mounts: &[BindMount],
+    ) -> Result<Vec<String>, OciError> {
+        let workspace_id = Uuid::new_v4();
+        run_args(
+            Path::new("/usr/bin/podman"),
+            &container_name(workspace_id),
+            workspace_id,
+            project,
+            image,
+            mounts,
+            Path::new("/ws/app"),
+        )
+    }
+
+    #[test]
+    fn isolated_container_has_no_network() {
+        let mounts = [
+            BindMount::read_write(PathBuf::from("/ws/app")),
+            BindMount::read_only(PathBuf::from("/src/app/.git")),
+            BindMount::read_write(PathBuf::from("/src/app/.git/objects")),
+        ];
+        let args = args_for(&project(true, Some(3000)), "node:22", &mounts).unwrap();
+        assert!(args.contains(&"--network=none".to_string()));
+        assert!(!args.contains(&"-p".to_string()));
+        assert!(args.contains(&"--userns=keep-id".to_string()));
+        assert!(args.contains(&"type=bind,source=/ws/app,target=/ws/app".to_string()));
+        assert!(
+            args.contains(
+                &"type=bind,source=/src/app/.git,target=/src/app/.git,readonly".to_string()
+            )
+        );
+        assert!(args.contains(
+            &"type=bind,source=/src/app/.git/objects,target=/src/app/.git/objects".to_string()
+        ));
+        assert!(args.contains(&format!("{SPEC_LABEL}=node:22;none;")));
+        assert_eq!(
+            args[args.len() - 4..],
+            ["node:22", "tail", "-f", "/dev/null"]
+        );
+    }
+
+    #[test]
+    fn container_gets_a_bridge_network_and_publishes_the_dev_server_port() {
+        let args = args_for(&project(false, Some(5173)), "node:22", &[]).unwrap();
+        assert!(args.contains(&"--network=bridge".to_string()));
+        assert!(!args.iter().any(|arg| arg == "--network=host"));
+        let publish = args.iter().position(|arg| arg == "-p").unwrap();
+        assert_eq!(args[publish + 1], "127.0.0.1:5173:5173");
+        assert!(args.contains(&format!("{SPEC_LABEL}=node:22;bridge;5173")));
+
+        let args = args_for(&project(false, None), "node:22", &[]).unwrap();
+        assert!(!args.contains(&"-p".to_string()));
+    }
+
+    #[test]
+    fn mount_paths_may_contain_colons_and_commas() {
+        let mount = BindMount::read_only(PathBuf::from("/ws/a:b,c"));
+        assert_eq!(
+            mount.arg(),
+            r#"type=bind,"source=/ws/a:b,c","target=/ws/a:b,c",readonly"#
+        );
+        let mount = BindMount::read_write(PathBuf::from("/ws/a:b"));
+        assert_eq!(mount.arg(), "type=bind,source=/ws/a:b,target=/ws/a:b");
+    }
+
+    #[test]
+    fn image_that_looks_like_an_option_is_rejected() {
+        for image in ["--privileged", "-v/:/host", "node 22", ""] {
+            assert!(matches!(
+                args_for(&project(false, None), image, &[]),
+                Err(OciError::InvalidImage(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn shared_git_dir_is_read_only_except_objects_refs_and_own_worktree() {
+        let td = TempDir::new().unwrap();
+        let root = td.path().canonicalize().unwrap();
+        let git_dir = root.join("repo/.git");
+        for dir in [
+            "objects",
+            "refs",
+            "logs",
+            "hooks",
+            "worktrees/ws-1",
+            "worktrees/ws-2",
+        ] {
+            std::fs::create_dir_all(git_dir.join(dir)).unwrap();
+        }
+        let worktree = root.join("ws/repo");
+        std::fs::create_dir_all(&worktree).unwrap();
+        std::fs::write(
+            worktree.join(".git"),
+            format!("gitdir: {}\n", git_dir.join("worktrees/ws-1").display()),
+        )
+        .unwrap();
+
+        let own = worktree_git_dir(&worktree).unwrap();
+        assert_eq!(own, git_dir.join("worktrees/ws-1"));
+        let mounts = git_dir_mounts(&git_dir, Some(&own));
+        assert_eq!(
+            mounts,
+            vec![
+                BindMount::read_only(git_dir.clone()),
+                BindMount::read_write(git_dir.join("objects")),
+                BindMount::read_write(git_dir.join("refs")),
+                BindMount::read_write(git_dir.join("logs")),
+                BindMount::read_write(git_dir.join("worktrees/ws-1")),
+            ]
+        );
+
+        // A `.git` file pointing anywhere else gets no writable mount
+        for elsewhere in [root.clone(), git_dir.join("worktrees/../hooks")] {
+            assert_eq!(git_dir_mounts(&git_dir, Some(&elsewhere)).len(), 4);
+        }
+    }
+}
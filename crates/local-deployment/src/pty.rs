@@ -1,17 +1,23 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     io::{Read, Write},
     path::PathBuf,
-    sync::{Arc, Mutex},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
     thread,
+    time::SystemTime,
 };
 
-use portable_pty::{CommandBuilder, NativePtySystem, PtySize, PtySystem};
+use portable_pty::{ChildKiller, CommandBuilder, NativePtySystem, PtySize, PtySystem};
 use thiserror::Error;
-use tokio::sync::mpsc;
+use tokio::sync::broadcast;
 use utils::shell::get_interactive_shell;
 use uuid::Uuid;
 
+use crate::oci::WorkspaceContainer;
+
 #[derive(Debug, Error)]
 pub enum PtyError {
     #[error("Failed to create PTY: {0}")]
@@ -24,15 +30,116 @@ pub enum PtyError {
     ResizeFailed(String),
     #[error("Session already closed")]
     SessionClosed,
+    #[error("Workspace already has {0} terminal sessions")]
+    TooManySessions(usize),
+}
+
+/// Bytes of output kept per session and replayed when a viewer attaches
+const SCROLLBACK_BYTES: usize = 256 * 1024;
+/// Output chunks buffered per viewer before it starts dropping output
+const OUTPUT_CHANNEL_CAPACITY: usize = 1024;
+const MAX_SESSIONS_PER_WORKSPACE: usize = 8;
+
+/// Ring buffer holding the most recent terminal output
+struct Scrollback {
+    buf: VecDeque<u8>,
+    limit: usize,
+}
+
+impl Scrollback {
+    fn new(limit: usize) -> Self {
+        Self {
+            buf: VecDeque::new(),
+            limit,
+        }
+    }
+
+    fn push(&mut self, data: &[u8]) {
+        let data = &data[data.len().saturating_sub(self.limit)..];
+        let overflow = (self.buf.len() + data.len()).saturating_sub(self.limit);
+        self.buf.drain(..overflow);
+        self.buf.extend(data);
+    }
+
+    fn snapshot(&self) -> Vec<u8> {
+        self.buf.iter().copied().collect()
+    }
+}
+
+/// Output shared between the PTY reader thread and attached viewers. Kept
+/// behind one lock so a viewer never misses or duplicates output between the
+/// scrollback snapshot and its live subscription.
+struct SessionOutput {
+    scrollback: Scrollback,
+    /// Dropped when the shell exits, which ends every viewer's stream
+    tx: Option<broadcast::Sender<Vec<u8>>>,
 }
 
 struct PtySession {
+    workspace_id: Uuid,
+    name: String,
+    persistent: bool,
+    creator: String,
+    created_at: SystemTime,
     writer: Box<dyn Write + Send>,
     master: Box<dyn portable_pty::MasterPty + Send>,
+    killer: Box<dyn ChildKiller + Send + Sync>,
+    output: Arc<Mutex<SessionOutput>>,
+    viewers: Arc<AtomicUsize>,
     _output_handle: thread::JoinHandle<()>,
     closed: bool,
 }
 
+impl PtySession {
+    fn info(&self, id: Uuid) -> PtySessionInfo {
+        PtySessionInfo {
+            id,
+            workspace_id: self.workspace_id,
+            name: self.name.clone(),
+            persistent: self.persistent,
+            creator: self.creator.clone(),
+            created_at: self.created_at,
+            viewers: self.viewers.load(Ordering::Relaxed),
+            exited: self
+                .output
+                .lock()
+                .map(|output| output.tx.is_none())
+                .unwrap_or(true),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PtySessionInfo {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub name: String,
+    /// Persistent sessions outlive their WebSocket and must be killed explicitly
+    pub persistent: bool,
+    /// Opaque identity of whoever created the session; only it may send input
+    pub creator: String,
+    pub created_at: SystemTime,
+    pub viewers: usize,
+    pub exited: bool,
+}
+
+/// Counts a viewer for as long as its attachment is alive
+struct ViewerGuard(Arc<AtomicUsize>);
+
+impl Drop for ViewerGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// A viewer's handle on a session: past output followed by live output
+pub struct PtyAttachment {
+    pub scrollback: Vec<u8>,
+    /// `None` when the shell has already exited
+    pub output: Option<broadcast::Receiver<Vec<u8>>>,
+    _viewer: ViewerGuard,
+}
+
 #[derive(Clone)]
 pub struct PtyService {
     sessions: Arc<Mutex<HashMap<Uuid, PtySession>>>,
@@ -45,14 +152,30 @@ impl PtyService {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn create_session(
         &self,
+        workspace_id: Uuid,
+        name: Option<String>,
+        persistent: bool,
+        creator: String,
         working_dir: PathBuf,
+        container: Option<WorkspaceContainer>,
         cols: u16,
         rows: u16,
-    ) -> Result<(Uuid, mpsc::UnboundedReceiver<Vec<u8>>), PtyError> {
+    ) -> Result<PtySessionInfo, PtyError> {
+        let existing = self.list_sessions(workspace_id).len();
+        if existing >= MAX_SESSIONS_PER_WORKSPACE {
+            return Err(PtyError::TooManySessions(existing));
+        }
+
         let session_id = Uuid::new_v4();
-        let (output_tx, output_rx) = mpsc::unbounded_channel();
+        let (output_tx, _) = broadcast::channel(OUTPUT_CHANNEL_CAPACITY);
+        let output = Arc::new(Mutex::new(SessionOutput {
+            scrollback: Scrollback::new(SCROLLBACK_BYTES),
+            tx: Some(output_tx),
+        }));
+        let reader_output = output.clone();
         let shell = get_interactive_shell().await;
 
         let result = tokio::task::spawn_blocking(move || {
@@ -67,44 +190,52 @@ impl PtyService {
                 })
                 .map_err(|e| PtyError::CreateFailed(e.to_string()))?;
 
-            let mut cmd = CommandBuilder::new(&shell);
-            cmd.cwd(&working_dir);
-
-            // Configure shell-specific options
             let shell_name = shell.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            let in_container = container.is_some();
+            let cmd = match container {
+                // The shell inside the container gets its environment from `exec`
+                Some(container) => container.shell_command(&working_dir),
+                None => {
+                    let mut cmd = CommandBuilder::new(&shell);
+                    cmd.cwd(&working_dir);
+
+                    // Configure shell-specific options
+                    if shell_name == "powershell.exe" || shell_name == "pwsh.exe" {
+                        // PowerShell: use -NoLogo for cleaner startup
+                        cmd.arg("-NoLogo");
+                    } else if shell_name == "cmd.exe" {
+                        // cmd.exe: no special args needed
+                    } else {
+                        // Unix shells
+                        cmd.env("VIBE_KANBAN_TERMINAL", "1");
+
+                        if shell_name == "bash" {
+                            cmd.env("PROMPT_COMMAND", r#"PS1='$ '; unset PROMPT_COMMAND"#);
+                        } else if shell_name == "zsh" {
+                            // PROMPT is set after spawning
+                        } else {
+                            cmd.env("PS1", "$ ");
+                        }
+                    }
 
-            if shell_name == "powershell.exe" || shell_name == "pwsh.exe" {
-                // PowerShell: use -NoLogo for cleaner startup
-                cmd.arg("-NoLogo");
-            } else if shell_name == "cmd.exe" {
-                // cmd.exe: no special args needed
-            } else {
-                // Unix shells
-                cmd.env("VIBE_KANBAN_TERMINAL", "1");
-
-                if shell_name == "bash" {
-                    cmd.env("PROMPT_COMMAND", r#"PS1='$ '; unset PROMPT_COMMAND"#);
-                } else if shell_name == "zsh" {
-                    // PROMPT is set after spawning
-                } else {
-                    cmd.env("PS1", "$ ");
+                    cmd.env("TERM", "xterm-256color");
+                    cmd.env("COLORTERM", "truecolor");
+                    cmd
                 }
-            }
-
-            cmd.env("TERM", "xterm-256color");
-            cmd.env("COLORTERM", "truecolor");
+            };
 
             let child = pty_pair
                 .slave
                 .spawn_command(cmd)
                 .map_err(|e| PtyError::CreateFailed(e.to_string()))?;
+            let killer = child.clone_killer();
 
             let mut writer = pty_pair
                 .master
                 .take_writer()
                 .map_err(|e| PtyError::CreateFailed(e.to_string()))?;
 
-            if shell_name == "zsh" {
+            if shell_name == "zsh" && !in_container {
                 let _ = writer.write_all(b" PROMPT='$ '; RPROMPT=''\n");
                 let _ = writer.flush();
                 let _ = writer.write_all(b"\x0c");
@@ -122,36 +253,104 @@ impl PtyService {
                     match reader.read(&mut buf) {
                         Ok(0) => break,
                         Ok(n) => {
-                            if output_tx.send(buf[..n].to_vec()).is_err() {
+                            let Ok(mut output) = reader_output.lock() else {
                                 break;
+                            };
+                            output.scrollback.push(&buf[..n]);
+                            if let Some(tx) = &output.tx {
+                                // No receivers just means nobody is watching right now
+                                let _ = tx.send(buf[..n].to_vec());
                             }
                         }
                         Err(_) => break,
                     }
                 }
+                if let Ok(mut output) = reader_output.lock() {
+                    output.tx = None;
+                }
                 drop(child);
             });
 
-            Ok::<_, PtyError>((pty_pair.master, writer, output_handle))
+            Ok::<_, PtyError>((pty_pair.master, writer, killer, output_handle))
         })
         .await
         .map_err(|e| PtyError::CreateFailed(e.to_string()))??;
 
-        let (master, writer, output_handle) = result;
+        let (master, writer, killer, output_handle) = result;
 
+        let mut sessions = self
+            .sessions
+            .lock()
+            .map_err(|e| PtyError::CreateFailed(e.to_string()))?;
+        let name = name
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| {
+                let count = sessions
+                    .values()
+                    .filter(|s| s.workspace_id == workspace_id)
+                    .count();
+                format!("Terminal {}", count + 1)
+            });
         let session = PtySession {
+            workspace_id,
+            name,
+            persistent,
+            creator,
+            created_at: SystemTime::now(),
             writer,
             master,
+            killer,
+            output,
+            viewers: Arc::new(AtomicUsize::new(0)),
             _output_handle: output_handle,
             closed: false,
         };
+        let info = session.info(session_id);
+        sessions.insert(session_id, session);
 
+        Ok(info)
+    }
+
+    /// Attach a viewer, returning the scrollback and a stream of new output
+    pub fn attach(&self, session_id: Uuid) -> Result<PtyAttachment, PtyError> {
+        let sessions = self.sessions.lock().map_err(|_| PtyError::SessionClosed)?;
+        let session = sessions
+            .get(&session_id)
+            .ok_or(PtyError::SessionNotFound(session_id))?;
+        if session.closed {
+            return Err(PtyError::SessionClosed);
+        }
+
+        let output = session.output.lock().map_err(|_| PtyError::SessionClosed)?;
+        session.viewers.fetch_add(1, Ordering::Relaxed);
+        Ok(PtyAttachment {
+            scrollback: output.scrollback.snapshot(),
+            output: output.tx.as_ref().map(|tx| tx.subscribe()),
+            _viewer: ViewerGuard(session.viewers.clone()),
+        })
+    }
+
+    pub fn get_session(&self, session_id: Uuid) -> Option<PtySessionInfo> {
         self.sessions
             .lock()
-            .map_err(|e| PtyError::CreateFailed(e.to_string()))?
-            .insert(session_id, session);
+            .ok()?
+            .get(&session_id)
+            .map(|session| session.info(session_id))
+    }
 
-        Ok((session_id, output_rx))
+    /// Sessions of a workspace, oldest first
+    pub fn list_sessions(&self, workspace_id: Uuid) -> Vec<PtySessionInfo> {
+        let Ok(sessions) = self.sessions.lock() else {
+            return Vec::new();
+        };
+        let mut infos: Vec<_> = sessions
+            .iter()
+            .filter(|(_, session)| session.workspace_id == workspace_id)
+            .map(|(id, session)| session.info(*id))
+            .collect();
+        infos.sort_by_key(|info| info.created_at);
+        infos
     }
 
     pub async fn write(&self, session_id: Uuid, data: &[u8]) -> Result<(), PtyError> {
@@ -214,10 +413,19 @@ impl PtyService {
             .remove(&session_id)
         {
             session.closed = true;
+            // The shell may ignore the hangup from dropping the master
+            let _ = session.killer.kill();
         }
         Ok(())
     }
 
+    /// Kill every terminal of a workspace, e.g. before deleting it
+    pub async fn close_workspace_sessions(&self, workspace_id: Uuid) {
+        for info in self.list_sessions(workspace_id) {
+            let _ = self.close_session(info.id).await;
+        }
+    }
+
     pub fn session_exists(&self, session_id: &Uuid) -> bool {
         self.sessions
             .lock()
@@ -231,3 +439,19 @@ impl Default for PtyService {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scrollback_keeps_most_recent_bytes() {
+        let mut scrollback = Scrollback::new(8);
+        scrollback.push(b"hello");
+        scrollback.push(b" world");
+        assert_eq!(scrollback.snapshot(), b"lo world");
+
+        scrollback.push(b"0123456789");
+        assert_eq!(scrollback.snapshot(), b"23456789");
+    }
+}
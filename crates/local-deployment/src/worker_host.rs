@@ -0,0 +1,459 @@
+//! SSH worker hosts that run a workspace's agents and scripts.
+//!
+//! The local worktrees stay the source of truth. Before each execution, the
+//! state of every local worktree, uncommitted changes included, is pushed to a
+//! bare mirror of the repository on the host and checked out in a worktree
+//! there. The process then runs over SSH, its output streamed back like a local
+//! process's. Afterwards the remote worktree's commits and changes are fetched
+//! back, so the usual commit step and diffs work unchanged. Dev servers and
+//! terminals keep running locally against the synced worktrees. The system
+//! `ssh` client is used, so keys, agents and `~/.ssh/config` apply as usual.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use db::models::{
+    repo::Repo, worker_host::WorkerHost, workspace::Workspace, workspace_repo::WorkspaceRepo,
+};
+use executors::env::SshTarget;
+use sqlx::SqlitePool;
+use thiserror::Error;
+use tokio::process::Command;
+use utils::shell::quote;
+use uuid::Uuid;
+
+const SYNC_REF_PREFIX: &str = "refs/vibe-kanban/sync/";
+const SYNC_IDENTITY: [&str; 4] = [
+    "-c",
+    "user.name=Vibe Kanban",
+    "-c",
+    "user.email=noreply@vibekanban.com",
+];
+
+#[derive(Debug, Error)]
+pub enum WorkerSyncError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("`{command}` failed: {stderr}")]
+    CommandFailed { command: String, stderr: String },
+}
+
+/// HEAD and full working tree of a local worktree when it was pushed
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LocalState {
+    head: String,
+    tree: String,
+}
+
+/// A workspace's worktrees on its worker host, prepared for one execution
+#[derive(Debug, Clone)]
+pub struct WorkerWorkspace {
+    pub host: WorkerHost,
+    workspace_id: Uuid,
+    branch: String,
+    local_root: PathBuf,
+    remote_root: String,
+    repos: Vec<Repo>,
+    pushed: HashMap<Uuid, LocalState>,
+}
+
+impl WorkerWorkspace {
+    /// Target for one execution process, with its own pid file
+    pub fn exec_target(&self, execution_id: Uuid) -> SshTarget {
+        SshTarget {
+            ssh_args: ssh_args(&self.host),
+            local_root: self.local_root.clone(),
+            remote_root: self.remote_root.clone(),
+            pid_file: format!("/tmp/vk-exec-{}.pid", execution_id.simple()),
+        }
+    }
+
+    fn sync_ref(&self) -> String {
+        format!("{SYNC_REF_PREFIX}{}", self.workspace_id)
+    }
+
+    fn remote_worktree(&self, repo: &Repo) -> String {
+        format!("{}/{}", self.remote_root, repo.name)
+    }
+
+    /// Make the remote worktree of `repo` match the local one
+    async fn push_repo(&mut self, repo: &Repo) -> Result<(), WorkerSyncError> {
+        let local = self.local_root.join(&repo.name);
+        let mirror = mirror_path(&self.host, repo.id);
+        let worktree = self.remote_worktree(repo);
+        run_ssh(
+            &self.host,
+            &format!(
+                "mkdir -p {root} && {{ [ -d {mirror} ] || git init -q --bare {mirror}; }}",
+                root = quote(&self.remote_root),
+                mirror = quote(&mirror),
+            ),
+        )
+        .await?;
+
+        let state = local_state(&local).await?;
+        let head_tree = output(git(&local).args(["rev-parse", "HEAD^{tree}"])).await?;
+        let dirty = state.tree != head_tree;
+        let commit = if dirty {
+            output(
+                git(&local)
+                    .args(SYNC_IDENTITY)
+                    .args(["commit-tree", &state.tree, "-p", "HEAD", "-m"])
+                    .arg("Uncommitted changes"),
+            )
+            .await?
+        } else {
+            state.head.clone()
+        };
+        output(
+            git(&local)
+                .env("GIT_SSH_COMMAND", git_ssh_command(&self.host))
+                .args(["push", "-q", "--force"])
+                .arg(git_url(&self.host, &mirror))
+                .arg(format!("{commit}:{}", self.sync_ref())),
+        )
+        .await?;
+
+        // Leftovers of an interrupted run are dropped; they were never synced
+        let mut script = format!(
+            "if [ -e {worktree}/.git ]; then cd {worktree} && git reset -q --hard {sync_ref} && git clean -fdq; \
+             else git -C {mirror} worktree prune && git -C {mirror} worktree add -q -B {branch} {worktree} {sync_ref} && cd {worktree}; fi",
+            worktree = quote(&worktree),
+            mirror = quote(&mirror),
+            branch = quote(&self.branch),
+            sync_ref = quote(&self.sync_ref()),
+        );
+        if dirty {
+            script.push_str(" && git reset -q --soft HEAD~1");
+        }
+        run_ssh(&self.host, &script).await?;
+
+        self.pushed.insert(repo.id, state);
+        Ok(())
+    }
+
+    /// Bring the remote worktrees' commits and changes back into the local
+    /// worktrees. Repositories changed locally while the execution ran are
+    /// left alone.
+    pub async fn sync_back(&self) {
+        for repo in &self.repos {
+            if let Err(e) = self.sync_repo_back(repo).await {
+                tracing::error!(
+                    "Failed to sync {} back from worker host {}: {}",
+                    repo.name,
+                    self.host.name,
+                    e
+                );
+            }
+        }
+    }
+
+    async fn sync_repo_back(&self, repo: &Repo) -> Result<(), WorkerSyncError> {
+        let local = self.local_root.join(&repo.name);
+        let Some(pushed) = self.pushed.get(&repo.id) else {
+            return Ok(());
+        };
+        if local_state(&local).await? != *pushed {
+            tracing::warn!(
+                "{} changed locally while running on worker host {}; keeping the local changes",
+                local.display(),
+                self.host.name
+            );
+            return Ok(());
+        }
+
+        let identity = SYNC_IDENTITY.map(quote).join(" ");
+        let snapshot = run_ssh(
+            &self.host,
+            &format!(
+                "cd {} && git add -A && if git diff --cached --quiet; then echo clean; \
+                 else git {identity} commit -q --no-verify -m 'Uncommitted changes' && echo dirty; fi",
+                quote(&self.remote_worktree(repo)),
+            ),
+        )
+        .await?;
+
+        output(
+            git(&local)
+                .env("GIT_SSH_COMMAND", git_ssh_command(&self.host))
+                .args(["fetch", "-q"])
+                .arg(git_url(&self.host, &mirror_path(&self.host, repo.id)))
+                .arg(format!("refs/heads/{}", self.branch)),
+        )
+        .await?;
+        output(git(&local).args(["reset", "-q", "--hard", "FETCH_HEAD"])).await?;
+        output(git(&local).args(["clean", "-fdq"])).await?;
+        if snapshot == "dirty" {
+            output(git(&local).args(["reset", "-q", "--soft", "HEAD~1"])).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Options and destination for `ssh`
+fn ssh_args(host: &WorkerHost) -> Vec<String> {
+    let mut args = Vec::new();
+    if let Some(port) = host.port {
+        args.extend(["-p".to_string(), port.to_string()]);
+    }
+    if let Some(identity_file) = &host.identity_file {
+        args.extend(["-i".to_string(), identity_file.clone()]);
+    }
+    args.push(host.destination());
+    args
+}
+
+fn git_ssh_command(host: &WorkerHost) -> String {
+    match &host.identity_file {
+        Some(identity_file) => format!("ssh -o BatchMode=yes -i {}", quote(identity_file)),
+        None => "ssh -o BatchMode=yes".to_string(),
+    }
+}
+
+fn git_url(host: &WorkerHost, path: &str) -> String {
+    match host.port {
+        Some(port) => format!("ssh://{}:{port}{path}", host.destination()),
+        None => format!("ssh://{}{path}", host.destination()),
+    }
+}
+
+fn base_dir(host: &WorkerHost) -> &str {
+    host.base_dir.trim_end_matches('/')
+}
+
+fn mirror_path(host: &WorkerHost, repo_id: Uuid) -> String {
+    format!("{}/repos/{repo_id}.git", base_dir(host))
+}
+
+/// Directory on the host holding the workspace's worktrees
+pub fn remote_root(host: &WorkerHost, workspace_id: Uuid) -> String {
+    format!("{}/worktrees/{workspace_id}", base_dir(host))
+}
+
+fn git(dir: &Path) -> Command {
+    let mut command = Command::new("git");
+    command
+        .arg("-C")
+        .arg(dir)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .kill_on_drop(true);
+    command
+}
+
+async fn output(command: &mut Command) -> Result<String, WorkerSyncError> {
+    let output = command.output().await?;
+    if !output.status.success() {
+        let std = command.as_std();
+        let mut description = std.get_program().to_string_lossy().into_owned();
+        for arg in std.get_args() {
+            description.push(' ');
+            description.push_str(&arg.to_string_lossy());
+        }
+        return Err(WorkerSyncError::CommandFailed {
+            command: description,
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+async fn run_ssh(host: &WorkerHost, script: &str) -> Result<String, WorkerSyncError> {
+    output(
+        Command::new("ssh")
+            .args(["-T", "-o", "BatchMode=yes"])
+            .args(ssh_args(host))
+            .arg(script)
+            .kill_on_drop(true),
+    )
+    .await
+}
+
+/// HEAD and a tree of the working directory including untracked files, written
+/// through a copy of the index so the worktree itself is untouched
+async fn local_state(dir: &Path) -> Result<LocalState, WorkerSyncError> {
+    let head = output(git(dir).args(["rev-parse", "HEAD"])).await?;
+    let index = PathBuf::from(output(git(dir).args(["rev-parse", "--git-path", "index"])).await?);
+    let index = if index.is_absolute() {
+        index
+    } else {
+        dir.join(index)
+    };
+    let scratch = std::env::temp_dir().join(format!("vk-sync-index-{}", Uuid::new_v4().simple()));
+    if index.exists() {
+        tokio::fs::copy(&index, &scratch).await?;
+    }
+    let tree = async {
+        output(git(dir).env("GIT_INDEX_FILE", &scratch).args(["add", "-A"])).await?;
+        output(git(dir).env("GIT_INDEX_FILE", &scratch).arg("write-tree")).await
+    }
+    .await;
+    let _ = tokio::fs::remove_file(&scratch).await;
+    Ok(LocalState { head, tree: tree? })
+}
+
+/// Host with the fewest running executions that still has capacity
+fn least_loaded(hosts: Vec<WorkerHost>, running: &HashMap<Uuid, i64>) -> Option<WorkerHost> {
+    hosts
+        .into_iter()
+        .map(|host| (running.get(&host.id).copied().unwrap_or(0), host))
+        .filter(|(running, host)| {
+            host.max_concurrent_executions
+                .is_none_or(|max| *running < i64::from(max))
+        })
+        .min_by_key(|(running, _)| *running)
+        .map(|(_, host)| host)
+}
+
+/// The workspace's worker host: the one it already runs on, else the project's
+/// host or, with automatic selection, the least loaded one. Workspaces stay on
+/// their host, since their worktrees live there. None runs on this machine.
+pub async fn select_host(
+    pool: &SqlitePool,
+    workspace: &Workspace,
+) -> Result<Option<WorkerHost>, WorkerSyncError> {
+    if let Some(host) = WorkerHost::find_for_workspace(pool, workspace.id).await? {
+        return Ok(Some(host));
+    }
+    let Some(task) = workspace.parent_task(pool).await? else {
+        return Ok(None);
+    };
+    let Some(project) = task.parent_project(pool).await? else {
+        return Ok(None);
+    };
+
+    let host = match project.worker_host_id {
+        Some(id) => WorkerHost::find_by_id(pool, id).await?,
+        None if project.worker_host_auto => {
+            let running = WorkerHost::running_execution_counts(pool)
+                .await?
+                .into_iter()
+                .collect();
+            least_loaded(WorkerHost::find_all(pool).await?, &running)
+        }
+        None => None,
+    };
+    if let Some(host) = &host {
+        WorkerHost::assign_workspace(pool, workspace.id, host.id).await?;
+        tracing::info!(
+            "Running workspace {} on worker host {}",
+            workspace.id,
+            host.name
+        );
+    }
+    Ok(host)
+}
+
+/// Git version on the host, checking that it is reachable without a prompt
+pub async fn check_host(host: &WorkerHost) -> Result<String, WorkerSyncError> {
+    run_ssh(host, "git --version").await
+}
+
+/// Sync the workspace to its worker host ahead of an execution. Returns None
+/// for workspaces that run on this machine.
+pub async fn prepare_workspace(
+    pool: &SqlitePool,
+    workspace: &Workspace,
+    local_root: &Path,
+) -> Result<Option<WorkerWorkspace>, WorkerSyncError> {
+    let Some(host) = select_host(pool, workspace).await? else {
+        return Ok(None);
+    };
+    let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
+    let mut worker = WorkerWorkspace {
+        remote_root: remote_root(&host, workspace.id),
+        host,
+        workspace_id: workspace.id,
+        branch: workspace.branch.clone(),
+        local_root: local_root.to_path_buf(),
+        repos: repos.clone(),
+        pushed: HashMap::new(),
+    };
+    for repo in &repos {
+        worker.push_repo(repo).await?;
+    }
+    Ok(Some(worker))
+}
+
+/// Stop an execution's process on the host. Closing the SSH connection does
+/// not reliably reach it.
+pub async fn kill_exec(host: &WorkerHost, target: &SshTarget) {
+    let script = format!(
+        r#"pid=$(cat {pid_file} 2>/dev/null) || exit 0
+kill -TERM "$pid" 2>/dev/null && sleep 1 && kill -KILL "$pid" 2>/dev/null
+rm -f {pid_file}"#,
+        pid_file = quote(&target.pid_file)
+    );
+    if let Err(e) = run_ssh(host, &script).await {
+        tracing::warn!("Failed to stop process on {}: {}", host.name, e);
+    }
+}
+
+/// Remove the workspace's worktrees from its worker host, if it has one
+pub async fn remove_workspace(pool: &SqlitePool, workspace_id: Uuid) {
+    let Ok(Some(host)) = WorkerHost::find_for_workspace(pool, workspace_id).await else {
+        return;
+    };
+    let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace_id)
+        .await
+        .unwrap_or_default();
+    let root = remote_root(&host, workspace_id);
+    let mut script = String::new();
+    for repo in &repos {
+        script.push_str(&format!(
+            "git -C {} worktree remove --force {} 2>/dev/null; ",
+            quote(&mirror_path(&host, repo.id)),
+            quote(&format!("{root}/{}", repo.name)),
+        ));
+    }
+    script.push_str(&format!("rm -rf {}", quote(&root)));
+    match run_ssh(&host, &script).await {
+        Ok(_) => tracing::info!("Removed worktrees of {} from {}", workspace_id, host.name),
+        Err(e) => tracing::warn!("Failed to remove worktrees from {}: {}", host.name, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn host(name: &str, max_concurrent_executions: Option<u32>) -> WorkerHost {
+        WorkerHost {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            host: format!("{name}.internal"),
+            port: Some(2222),
+            username: Some("ci".to_string()),
+            identity_file: None,
+            base_dir: "/srv/vibe-kanban/".to_string(),
+            max_concurrent_executions,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn picks_least_loaded_host_with_capacity() {
+        let busy = host("busy", None);
+        let full = host("full", Some(1));
+        let idle = host("idle", Some(4));
+        let running = HashMap::from([(busy.id, 3), (full.id, 1), (idle.id, 2)]);
+
+        let picked = least_loaded(vec![busy, full.clone(), idle.clone()], &running);
+        assert_eq!(picked.map(|host| host.id), Some(idle.id));
+        assert!(least_loaded(vec![full.clone()], &running).is_none());
+
+        assert_eq!(
+            git_url(&full, &mirror_path(&full, full.id)),
+            format!(
+                "ssh://ci@full.internal:2222/srv/vibe-kanban/repos/{}.git",
+                full.id
+            )
+        );
+    }
+}
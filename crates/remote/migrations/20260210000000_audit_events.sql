@@ -0,0 +1,46 @@
+CREATE TYPE audit_action AS ENUM (
+    'member_joined',
+    'member_removed',
+    'member_role_changed',
+    'invitation_created',
+    'invitation_revoked',
+    'github_app_installed',
+    'github_app_uninstalled',
+    'project_deleted',
+    'issue_deleted'
+);
+
+CREATE TABLE audit_events (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    -- NULL for changes made by GitHub webhooks, and once the user is deleted
+    actor_user_id   UUID REFERENCES users(id) ON DELETE SET NULL,
+    action          audit_action NOT NULL,
+    -- The member, invitation, installation, project or issue acted on.
+    -- Not a foreign key, since the subject is often deleted.
+    subject_id      UUID,
+    details         JSONB NOT NULL DEFAULT '{}',
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_audit_events_org_created
+    ON audit_events (organization_id, created_at DESC, id DESC);
+
+-- Audit events are append-only. Changes made by foreign key actions (deleting
+-- the organization or the actor) run one trigger level deeper and are allowed.
+CREATE FUNCTION audit_events_append_only() RETURNS trigger AS $$
+BEGIN
+    IF pg_trigger_depth() <= 1 THEN
+        RAISE EXCEPTION 'audit_events is append-only';
+    END IF;
+    IF TG_OP = 'DELETE' THEN
+        RETURN OLD;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_audit_events_append_only
+    BEFORE UPDATE OR DELETE ON audit_events
+    FOR EACH ROW
+    EXECUTE FUNCTION audit_events_append_only();
@@ -7,8 +7,8 @@ use tracing::instrument;
 use crate::{
     AppState,
     auth::{
-        GitHubOAuthProvider, GoogleOAuthProvider, JwtService, OAuthHandoffService,
-        OAuthTokenValidator, ProviderRegistry,
+        GitHubOAuthProvider, GitLabOAuthProvider, GoogleOAuthProvider, JwtService,
+        OAuthHandoffService, OAuthTokenValidator, OidcProvider, ProviderRegistry,
     },
     config::RemoteServerConfig,
     db,
@@ -60,6 +60,22 @@ impl Server {
             )?);
         }
 
+        if let Some(gitlab) = auth_config.gitlab() {
+            registry.register(GitLabOAuthProvider::new(
+                gitlab.base_url(),
+                gitlab.oauth().client_id().to_string(),
+                gitlab.oauth().client_secret().clone(),
+            )?);
+        }
+
+        if let Some(oidc) = auth_config.oidc() {
+            registry.register(
+                OidcProvider::discover(oidc)
+                    .await
+                    .context("failed to discover OpenID Connect provider")?,
+            );
+        }
+
         if registry.is_empty() {
             bail!("no OAuth providers configured");
         }
@@ -71,6 +87,7 @@ impl Server {
             registry.clone(),
             jwt.clone(),
             auth_config.public_base_url().to_string(),
+            auth_config.auto_join_domains().to_vec(),
         ));
 
         let oauth_token_validator =
@@ -1,6 +1,7 @@
 use std::{fmt::Write, sync::Arc};
 
 use anyhow::Error as AnyhowError;
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
 use chrono::{DateTime, Duration, Utc};
 use rand::{Rng, distr::Alphanumeric};
 use reqwest::StatusCode;
@@ -17,6 +18,7 @@ use super::{
     provider::{AuthorizationGrant, AuthorizationProvider, ProviderUser},
 };
 use crate::{
+    config::EmailDomainRule,
     configure_user_scope,
     db::{
         auth::{AuthSessionError, AuthSessionRepository, MAX_SESSION_INACTIVITY_DURATION},
@@ -101,6 +103,7 @@ pub struct OAuthHandoffService {
     providers: Arc<ProviderRegistry>,
     jwt: Arc<JwtService>,
     public_origin: String,
+    auto_join_domains: Vec<EmailDomainRule>,
 }
 
 impl OAuthHandoffService {
@@ -109,6 +112,7 @@ impl OAuthHandoffService {
         providers: Arc<ProviderRegistry>,
         jwt: Arc<JwtService>,
         public_origin: String,
+        auto_join_domains: Vec<EmailDomainRule>,
     ) -> Self {
         let trimmed_origin = public_origin.trim_end_matches('/').to_string();
         Self {
@@ -116,6 +120,7 @@ impl OAuthHandoffService {
             providers,
             jwt,
             public_origin: trimmed_origin,
+            auto_join_domains,
         }
     }
 
@@ -204,8 +209,14 @@ impl OAuthHandoffService {
             provider.name()
         );
 
+        let code_verifier = self.jwt.derive_pkce_verifier(&record.state)?;
+
         provider
-            .authorize_url(&record.state, &redirect_uri)
+            .authorize_url(
+                &record.state,
+                &redirect_uri,
+                &pkce_challenge(&code_verifier),
+            )
             .map(|url| url.into())
             .map_err(HandoffError::Provider)
     }
@@ -261,8 +272,10 @@ impl OAuthHandoffService {
             provider.name()
         );
 
+        let code_verifier = self.jwt.derive_pkce_verifier(&record.state)?;
+
         let grant = provider
-            .exchange_code(code, &redirect_uri)
+            .exchange_code(code, &redirect_uri, &code_verifier)
             .await
             .map_err(HandoffError::Provider)?;
 
@@ -458,6 +471,13 @@ impl OAuthHandoffService {
             .ensure_personal_org_and_admin_membership(user.id, username.as_deref())
             .await?;
 
+        if profile.email_verified
+            && let Some(verified_email) = profile.email.as_deref()
+        {
+            self.auto_join_organizations(user.id, verified_email)
+                .await?;
+        }
+
         account_repo
             .upsert(OAuthAccountInsert {
                 user_id: user.id,
@@ -472,6 +492,38 @@ impl OAuthHandoffService {
 
         Ok(user)
     }
+
+    /// Adds the user to every organization configured for their email domain
+    async fn auto_join_organizations(
+        &self,
+        user_id: Uuid,
+        email: &str,
+    ) -> Result<(), HandoffError> {
+        let Some((_, domain)) = email.rsplit_once('@') else {
+            return Ok(());
+        };
+        let domain = domain.to_lowercase();
+        let org_repo = OrganizationRepository::new(&self.pool);
+
+        for rule in self
+            .auto_join_domains
+            .iter()
+            .filter(|rule| rule.domain == domain)
+        {
+            if org_repo
+                .join_by_slug(&rule.organization_slug, user_id)
+                .await?
+            {
+                tracing::info!(
+                    %user_id,
+                    organization = %rule.organization_slug,
+                    "user joined organization by email domain"
+                );
+            }
+        }
+
+        Ok(())
+    }
 }
 
 type IdentityUser = crate::db::users::User;
@@ -516,6 +568,11 @@ fn hash_sha256_hex(input: &str) -> String {
     output
 }
 
+/// S256 challenge for a PKCE code verifier
+fn pkce_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
+
 fn generate_state() -> String {
     rand::rng()
         .sample_iter(&Alphanumeric)
@@ -539,6 +596,7 @@ fn ensure_email(provider: &str, profile: &ProviderUser) -> String {
     match provider {
         "github" => format!("{}@users.noreply.github.com", profile.id),
         "google" => format!("{}@users.noreply.google.com", profile.id),
+        "gitlab" => format!("{}@users.noreply.gitlab.com", profile.id),
         _ => format!("{}@oauth.local", profile.id),
     }
 }
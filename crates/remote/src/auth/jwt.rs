@@ -9,6 +9,7 @@ use base64::{
     engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
 };
 use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use hmac::{Hmac, Mac};
 use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, encode};
 use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize, Serialize};
@@ -238,6 +239,17 @@ impl JwtService {
         self.encrypt_data(json.as_bytes())
     }
 
+    /// PKCE code verifier for the provider leg of an OAuth handoff. It is
+    /// derived from the handoff state so it never has to be stored.
+    pub fn derive_pkce_verifier(&self, state: &str) -> Result<String, JwtError> {
+        let key_bytes = self.derive_key()?;
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&key_bytes)
+            .map_err(|_| JwtError::InvalidSecret)?;
+        mac.update(b"pkce:");
+        mac.update(state.as_bytes());
+        Ok(URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes()))
+    }
+
     fn encrypt_data(&self, data: &[u8]) -> Result<String, JwtError> {
         let key_bytes = self.derive_key()?;
         let key = Key::<Aes256Gcm>::from(key_bytes);
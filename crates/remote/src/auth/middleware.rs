@@ -1,7 +1,7 @@
 use axum::{
     body::Body,
     extract::State,
-    http::{Request, StatusCode},
+    http::{Method, Request, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
@@ -15,6 +15,9 @@ use crate::{
     db::{
         auth::{AuthSessionError, AuthSessionRepository, MAX_SESSION_INACTIVITY_DURATION},
         identity_errors::IdentityError,
+        personal_access_tokens::{
+            PersonalAccessToken, PersonalAccessTokenRepository, TOKEN_PREFIX, TokenScope,
+        },
         users::{User, UserRepository},
     },
 };
@@ -22,9 +25,53 @@ use crate::{
 #[derive(Clone)]
 pub struct RequestContext {
     pub user: User,
-    pub session_id: Uuid,
+    /// None when the request was made with a personal access token
+    pub session_id: Option<Uuid>,
     #[allow(dead_code)]
-    pub access_token_expires_at: DateTime<Utc>,
+    pub access_token_expires_at: Option<DateTime<Utc>>,
+}
+
+/// A request authenticated with a personal access token. Its `RequestContext`
+/// is only handed to the route once `require_token_scope` has checked the
+/// token against the route's access, so routes without one refuse tokens.
+#[derive(Clone)]
+struct TokenRequest {
+    context: RequestContext,
+    token: PersonalAccessToken,
+}
+
+/// What a personal access token needs to use a group of routes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenAccess {
+    /// Only signed-in sessions may use the routes
+    SessionOnly,
+    /// Reads need `read` and everything else needs `write`
+    Scoped { read: TokenScope, write: TokenScope },
+}
+
+impl TokenAccess {
+    pub const ISSUES: Self = Self::Scoped {
+        read: TokenScope::IssuesRead,
+        write: TokenScope::IssuesWrite,
+    };
+    pub const ORG_ADMIN: Self = Self::Scoped {
+        read: TokenScope::OrgAdmin,
+        write: TokenScope::OrgAdmin,
+    };
+
+    /// Scope a token needs for a request, or None when tokens are refused
+    fn required_scope(self, method: &Method) -> Option<TokenScope> {
+        match self {
+            Self::SessionOnly => None,
+            Self::Scoped { read, write } => {
+                Some(if matches!(*method, Method::GET | Method::HEAD) {
+                    read
+                } else {
+                    write
+                })
+            }
+        }
+    }
 }
 
 pub async fn require_session(
@@ -37,12 +84,84 @@ pub async fn require_session(
         None => return StatusCode::UNAUTHORIZED.into_response(),
     };
 
+    let context = if bearer.starts_with(TOKEN_PREFIX) {
+        personal_access_token_context(&state, &bearer)
+            .await
+            .map(|(context, token)| (context, Some(token)))
+    } else {
+        session_context(&state, &bearer)
+            .await
+            .map(|context| (context, None))
+    };
+    let (context, token) = match context {
+        Ok(context) => context,
+        Err(response) => return response,
+    };
+
+    configure_user_scope(
+        context.user.id,
+        context.user.username.as_deref(),
+        Some(context.user.email.as_str()),
+    );
+
+    match token {
+        Some(token) => {
+            req.extensions_mut().insert(TokenRequest { context, token });
+        }
+        None => {
+            req.extensions_mut().insert(context);
+        }
+    }
+
+    next.run(req).await
+}
+
+/// Route layer admitting personal access tokens that hold the scope the routes
+/// declare; requests with a session pass straight through
+pub async fn require_token_scope(
+    State(access): State<TokenAccess>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Response {
+    let Some(TokenRequest { context, token }) = req.extensions_mut().remove::<TokenRequest>()
+    else {
+        return next.run(req).await;
+    };
+    if let Err(response) = check_token_scope(access, req.method(), &token) {
+        return response;
+    }
+    req.extensions_mut().insert(context);
+    next.run(req).await
+}
+
+fn check_token_scope(
+    access: TokenAccess,
+    method: &Method,
+    token: &PersonalAccessToken,
+) -> Result<(), Response> {
+    let Some(required) = access.required_scope(method) else {
+        return Err(StatusCode::FORBIDDEN.into_response());
+    };
+    if !token.has_scope(required) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            format!(
+                "personal access token lacks the `{}` scope",
+                required.as_str()
+            ),
+        )
+            .into_response());
+    }
+    Ok(())
+}
+
+async fn session_context(state: &AppState, bearer: &str) -> Result<RequestContext, Response> {
     let jwt = state.jwt();
-    let identity = match jwt.decode_access_token(&bearer) {
+    let identity = match jwt.decode_access_token(bearer) {
         Ok(details) => details,
         Err(error) => {
             warn!(?error, "failed to decode access token");
-            return StatusCode::UNAUTHORIZED.into_response();
+            return Err(StatusCode::UNAUTHORIZED.into_response());
         }
     };
 
@@ -52,21 +171,21 @@ pub async fn require_session(
         Ok(session) => session,
         Err(AuthSessionError::NotFound) => {
             warn!("session `{}` not found", identity.session_id);
-            return StatusCode::UNAUTHORIZED.into_response();
+            return Err(StatusCode::UNAUTHORIZED.into_response());
         }
         Err(AuthSessionError::Database(error)) => {
             warn!(?error, "failed to load session");
-            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            return Err(StatusCode::INTERNAL_SERVER_ERROR.into_response());
         }
         Err(_) => {
             warn!("failed to load session for unknown reason");
-            return StatusCode::UNAUTHORIZED.into_response();
+            return Err(StatusCode::UNAUTHORIZED.into_response());
         }
     };
 
     if session.revoked_at.is_some() {
         warn!("session `{}` rejected (revoked)", identity.session_id);
-        return StatusCode::UNAUTHORIZED.into_response();
+        return Err(StatusCode::UNAUTHORIZED.into_response());
     }
 
     if session.inactivity_duration(Utc::now()) > MAX_SESSION_INACTIVITY_DURATION {
@@ -77,38 +196,131 @@ pub async fn require_session(
         if let Err(error) = session_repo.revoke(session.id).await {
             warn!(?error, "failed to revoke inactive session");
         }
-        return StatusCode::UNAUTHORIZED.into_response();
+        return Err(StatusCode::UNAUTHORIZED.into_response());
     }
 
-    let user_repo = UserRepository::new(pool);
-    let user = match user_repo.fetch_user(identity.user_id).await {
-        Ok(user) => user,
+    let user = load_user(state, identity.user_id).await?;
+
+    match session_repo.touch(session.id).await {
+        Ok(_) => {}
+        Err(error) => warn!(?error, "failed to update session last-used timestamp"),
+    }
+
+    Ok(RequestContext {
+        user,
+        session_id: Some(session.id),
+        access_token_expires_at: Some(identity.expires_at),
+    })
+}
+
+async fn personal_access_token_context(
+    state: &AppState,
+    bearer: &str,
+) -> Result<(RequestContext, PersonalAccessToken), Response> {
+    let token_repo = PersonalAccessTokenRepository::new(state.pool());
+    let token = match token_repo.find_active(bearer).await {
+        Ok(Some(token)) => token,
+        Ok(None) => {
+            warn!("personal access token rejected (unknown, revoked or expired)");
+            return Err(StatusCode::UNAUTHORIZED.into_response());
+        }
+        Err(error) => {
+            warn!(?error, "failed to load personal access token");
+            return Err(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+        }
+    };
+
+    let user = load_user(state, token.user_id).await?;
+
+    if let Err(error) = token_repo.touch(token.id).await {
+        warn!(
+            ?error,
+            "failed to update personal access token last-used timestamp"
+        );
+    }
+
+    let context = RequestContext {
+        user,
+        session_id: None,
+        access_token_expires_at: token.expires_at,
+    };
+    Ok((context, token))
+}
+
+async fn load_user(state: &AppState, user_id: Uuid) -> Result<User, Response> {
+    let user_repo = UserRepository::new(state.pool());
+    match user_repo.fetch_user(user_id).await {
+        Ok(user) => Ok(user),
         Err(IdentityError::NotFound) => {
-            warn!("user `{}` missing", identity.user_id);
-            return StatusCode::UNAUTHORIZED.into_response();
+            warn!("user `{}` missing", user_id);
+            Err(StatusCode::UNAUTHORIZED.into_response())
         }
         Err(IdentityError::Database(error)) => {
             warn!(?error, "failed to load user");
-            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            Err(StatusCode::INTERNAL_SERVER_ERROR.into_response())
         }
         Err(_) => {
             warn!("unexpected error loading user");
-            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            Err(StatusCode::INTERNAL_SERVER_ERROR.into_response())
         }
-    };
+    }
+}
 
-    configure_user_scope(user.id, user.username.as_deref(), Some(user.email.as_str()));
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    req.extensions_mut().insert(RequestContext {
-        user,
-        session_id: session.id,
-        access_token_expires_at: identity.expires_at,
-    });
+    fn token(scopes: &[TokenScope]) -> PersonalAccessToken {
+        PersonalAccessToken {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            name: "ci".to_string(),
+            token_prefix: "vkp_abcdefgh".to_string(),
+            scopes: scopes.to_vec(),
+            expires_at: None,
+            last_used_at: None,
+            revoked_at: None,
+            created_at: Utc::now(),
+        }
+    }
 
-    match session_repo.touch(session.id).await {
-        Ok(_) => {}
-        Err(error) => warn!(?error, "failed to update session last-used timestamp"),
+    fn allowed(access: TokenAccess, method: Method, scopes: &[TokenScope]) -> bool {
+        check_token_scope(access, &method, &token(scopes)).is_ok()
     }
 
-    next.run(req).await
+    #[test]
+    fn issue_routes_need_read_or_write_scopes() {
+        let access = TokenAccess::ISSUES;
+        assert!(allowed(access, Method::GET, &[TokenScope::IssuesRead]));
+        assert!(allowed(access, Method::GET, &[TokenScope::IssuesWrite]));
+        assert!(!allowed(access, Method::POST, &[TokenScope::IssuesRead]));
+        assert!(allowed(access, Method::POST, &[TokenScope::IssuesWrite]));
+        assert!(!allowed(access, Method::GET, &[TokenScope::OrgAdmin]));
+    }
+
+    #[test]
+    fn org_admin_token_reads_org_routes() {
+        let access = TokenAccess::ORG_ADMIN;
+        assert!(allowed(access, Method::GET, &[TokenScope::OrgAdmin]));
+        assert!(allowed(access, Method::PATCH, &[TokenScope::OrgAdmin]));
+    }
+
+    #[test]
+    fn issues_read_token_cannot_read_invitations() {
+        // Organization members and invitations are declared org-admin routes
+        let access = TokenAccess::ORG_ADMIN;
+        assert!(!allowed(access, Method::GET, &[TokenScope::IssuesRead]));
+        assert!(!allowed(access, Method::GET, &[TokenScope::IssuesWrite]));
+    }
+
+    #[test]
+    fn session_only_routes_refuse_every_token() {
+        let scopes = [
+            TokenScope::IssuesRead,
+            TokenScope::IssuesWrite,
+            TokenScope::OrgAdmin,
+        ];
+        assert!(!allowed(TokenAccess::SessionOnly, Method::GET, &scopes));
+        assert!(!allowed(TokenAccess::SessionOnly, Method::POST, &scopes));
+    }
 }
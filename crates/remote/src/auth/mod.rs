@@ -2,12 +2,15 @@ mod handoff;
 mod jwt;
 mod middleware;
 mod oauth_token_validator;
+mod oidc;
 mod provider;
 
 pub use handoff::{CallbackResult, HandoffError, OAuthHandoffService};
 pub use jwt::{JwtError, JwtService};
-pub use middleware::{RequestContext, require_session};
+pub use middleware::{RequestContext, TokenAccess, require_session, require_token_scope};
 pub use oauth_token_validator::{OAuthTokenValidationError, OAuthTokenValidator};
+pub use oidc::OidcProvider;
 pub use provider::{
-    GitHubOAuthProvider, GoogleOAuthProvider, ProviderRegistry, ProviderTokenDetails,
+    GitHubOAuthProvider, GitLabOAuthProvider, GoogleOAuthProvider, ProviderRegistry,
+    ProviderTokenDetails,
 };
@@ -0,0 +1,328 @@
+use anyhow::{Context, Result, bail};
+use async_trait::async_trait;
+use reqwest::Client;
+use secrecy::{ExposeSecret, SecretString};
+use serde::Deserialize;
+use serde_json::Value;
+use url::Url;
+
+use super::provider::{
+    AuthorizationGrant, AuthorizationProvider, ProviderTokenDetails, ProviderUser,
+    StandardOAuthClient, TokenValidationError, USER_AGENT,
+};
+use crate::config::{OidcClaimMapping, OidcProviderConfig};
+
+#[derive(Debug, Deserialize)]
+struct DiscoveryDocument {
+    issuer: String,
+    authorization_endpoint: Url,
+    token_endpoint: Url,
+    userinfo_endpoint: Option<Url>,
+    code_challenge_methods_supported: Option<Vec<String>>,
+}
+
+/// Any OpenID Connect identity provider, such as Keycloak or Okta. The profile
+/// is read from the userinfo endpoint, so ID tokens never need to be verified.
+pub struct OidcProvider {
+    oauth: StandardOAuthClient,
+    authorization_endpoint: Url,
+    userinfo_endpoint: Url,
+    scopes: Vec<String>,
+    claims: OidcClaimMapping,
+}
+
+impl OidcProvider {
+    /// Looks up the provider endpoints from its discovery document
+    pub async fn discover(config: &OidcProviderConfig) -> Result<Self> {
+        let client = Client::builder().user_agent(USER_AGENT).build()?;
+        let discovery_url = format!("{}/.well-known/openid-configuration", config.issuer_url());
+        let document: DiscoveryDocument = client
+            .get(&discovery_url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .with_context(|| format!("invalid OpenID discovery document at {discovery_url}"))?;
+
+        if document.issuer.trim_end_matches('/') != config.issuer_url() {
+            bail!(
+                "OpenID issuer `{}` does not match OIDC_ISSUER_URL",
+                document.issuer
+            );
+        }
+        let userinfo_endpoint = document
+            .userinfo_endpoint
+            .context("OpenID provider does not advertise a userinfo endpoint")?;
+        if let Some(methods) = &document.code_challenge_methods_supported
+            && !methods.iter().any(|method| method == "S256")
+        {
+            bail!("OpenID provider does not support S256 PKCE challenges");
+        }
+
+        Ok(Self {
+            oauth: StandardOAuthClient::new(
+                client,
+                "oidc",
+                document.token_endpoint,
+                config.oauth().client_id().to_string(),
+                config.oauth().client_secret().clone(),
+            ),
+            authorization_endpoint: document.authorization_endpoint,
+            userinfo_endpoint,
+            scopes: config.scopes().to_vec(),
+            claims: config.claims().clone(),
+        })
+    }
+}
+
+/// String value of a claim, following dots into nested objects
+fn claim(claims: &Value, path: &str) -> Option<String> {
+    let value = path
+        .split('.')
+        .try_fold(claims, |value, key| value.get(key))?;
+    match value {
+        Value::String(value) if !value.is_empty() => Some(value.clone()),
+        Value::Number(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+/// Some providers send `email_verified` as a string
+fn is_email_verified(claims: &Value) -> bool {
+    match claims.get("email_verified") {
+        Some(Value::Bool(verified)) => *verified,
+        Some(Value::String(verified)) => verified.eq_ignore_ascii_case("true"),
+        _ => false,
+    }
+}
+
+fn profile_from_claims(claims: &Value, mapping: &OidcClaimMapping) -> Result<ProviderUser> {
+    let id = claim(claims, "sub").context("userinfo response is missing the `sub` claim")?;
+    let email = claim(claims, &mapping.email);
+
+    Ok(ProviderUser {
+        id,
+        login: claim(claims, &mapping.username),
+        email_verified: email.is_some() && is_email_verified(claims),
+        email,
+        name: claim(claims, &mapping.name),
+        avatar_url: claim(claims, &mapping.avatar_url),
+    })
+}
+
+#[async_trait]
+impl AuthorizationProvider for OidcProvider {
+    fn name(&self) -> &'static str {
+        "oidc"
+    }
+
+    fn scopes(&self) -> Vec<&str> {
+        self.scopes.iter().map(String::as_str).collect()
+    }
+
+    fn authorize_url(&self, state: &str, redirect_uri: &str, code_challenge: &str) -> Result<Url> {
+        let mut url = self.authorization_endpoint.clone();
+        {
+            let mut qp = url.query_pairs_mut();
+            qp.append_pair("client_id", self.oauth.client_id());
+            qp.append_pair("redirect_uri", redirect_uri);
+            qp.append_pair("response_type", "code");
+            qp.append_pair("scope", &self.scopes().join(" "));
+            qp.append_pair("state", state);
+            qp.append_pair("code_challenge", code_challenge);
+            qp.append_pair("code_challenge_method", "S256");
+        }
+        Ok(url)
+    }
+
+    async fn exchange_code(
+        &self,
+        code: &str,
+        redirect_uri: &str,
+        code_verifier: &str,
+    ) -> Result<AuthorizationGrant> {
+        self.oauth
+            .exchange_code(code, redirect_uri, code_verifier)
+            .await
+    }
+
+    async fn fetch_user(&self, access_token: &SecretString) -> Result<ProviderUser> {
+        let claims: Value = self
+            .oauth
+            .http()
+            .get(self.userinfo_endpoint.clone())
+            .bearer_auth(access_token.expose_secret())
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("failed to parse OpenID userinfo response")?;
+
+        profile_from_claims(&claims, &self.claims)
+    }
+
+    async fn validate_token(
+        &self,
+        token_details: &ProviderTokenDetails,
+        max_retries: u32,
+    ) -> Result<Option<ProviderTokenDetails>, TokenValidationError> {
+        self.oauth
+            .validate_token(&self.userinfo_endpoint, token_details, max_retries)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        Form, Json, Router,
+        extract::State,
+        http::{HeaderMap, StatusCode},
+        routing::{get, post},
+    };
+    use serde_json::json;
+
+    use super::*;
+
+    fn default_mapping() -> OidcClaimMapping {
+        OidcClaimMapping {
+            username: "preferred_username".to_string(),
+            email: "email".to_string(),
+            name: "name".to_string(),
+            avatar_url: "picture".to_string(),
+        }
+    }
+
+    #[test]
+    fn maps_nested_claims() {
+        let claims = json!({
+            "sub": "user-1",
+            "email": "ada@example.com",
+            "email_verified": "true",
+            "profile": { "handle": "ada" },
+        });
+        let mapping = OidcClaimMapping {
+            username: "profile.handle".to_string(),
+            ..default_mapping()
+        };
+
+        let profile = profile_from_claims(&claims, &mapping).unwrap();
+        assert_eq!(profile.id, "user-1");
+        assert_eq!(profile.login.as_deref(), Some("ada"));
+        assert!(profile.email_verified);
+        assert!(profile.name.is_none());
+    }
+
+    /// Minimal identity provider that accepts one code and one verifier
+    async fn start_mock_provider() -> String {
+        async fn discovery(State(issuer): State<String>) -> Json<Value> {
+            Json(json!({
+                "issuer": issuer,
+                "authorization_endpoint": format!("{issuer}/authorize"),
+                "token_endpoint": format!("{issuer}/token"),
+                "userinfo_endpoint": format!("{issuer}/userinfo"),
+                "code_challenge_methods_supported": ["S256"],
+            }))
+        }
+
+        async fn token(
+            Form(form): Form<std::collections::HashMap<String, String>>,
+        ) -> (StatusCode, Json<Value>) {
+            let valid = form.get("code").map(String::as_str) == Some("good-code")
+                && form.get("code_verifier").map(String::as_str) == Some("verifier")
+                && form.get("client_secret").map(String::as_str) == Some("secret");
+            if !valid {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({ "error": "invalid_grant" })),
+                );
+            }
+            (
+                StatusCode::OK,
+                Json(json!({
+                    "access_token": "access",
+                    "token_type": "Bearer",
+                    "expires_in": 300,
+                    "refresh_token": "refresh",
+                    "scope": "openid email",
+                })),
+            )
+        }
+
+        async fn userinfo(headers: HeaderMap) -> (StatusCode, Json<Value>) {
+            if headers.get("authorization").and_then(|v| v.to_str().ok()) != Some("Bearer access") {
+                return (StatusCode::UNAUTHORIZED, Json(json!({})));
+            }
+            (
+                StatusCode::OK,
+                Json(json!({
+                    "sub": "kc-42",
+                    "preferred_username": "ada",
+                    "email": "ada@example.com",
+                    "email_verified": true,
+                    "name": "Ada Lovelace",
+                })),
+            )
+        }
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let issuer = format!("http://{}/realms/test", listener.local_addr().unwrap());
+        let app = Router::new()
+            .route(
+                "/realms/test/.well-known/openid-configuration",
+                get(discovery),
+            )
+            .route("/realms/test/token", post(token))
+            .route("/realms/test/userinfo", get(userinfo))
+            .with_state(issuer.clone());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        issuer
+    }
+
+    #[tokio::test]
+    async fn signs_in_against_mock_provider() {
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        let issuer = start_mock_provider().await;
+        let config = OidcProviderConfig::new(
+            issuer.clone(),
+            "vibe".to_string(),
+            SecretString::new("secret".into()),
+            vec!["openid".to_string(), "email".to_string()],
+            default_mapping(),
+        );
+        let provider = OidcProvider::discover(&config).await.unwrap();
+
+        let url = provider
+            .authorize_url("state", "http://localhost/callback", "challenge")
+            .unwrap();
+        assert!(url.as_str().starts_with(&format!("{issuer}/authorize?")));
+        assert!(
+            url.query_pairs()
+                .any(|(k, v)| k == "code_challenge" && v == "challenge")
+        );
+        assert!(
+            url.query_pairs()
+                .any(|(k, v)| k == "scope" && v == "openid email")
+        );
+
+        assert!(
+            provider
+                .exchange_code("good-code", "http://localhost/callback", "wrong")
+                .await
+                .is_err()
+        );
+        let grant = provider
+            .exchange_code("good-code", "http://localhost/callback", "verifier")
+            .await
+            .unwrap();
+        assert_eq!(grant.scopes, vec!["openid", "email"]);
+
+        let user = provider.fetch_user(&grant.access_token).await.unwrap();
+        assert_eq!(user.id, "kc-42");
+        assert_eq!(user.login.as_deref(), Some("ada"));
+        assert_eq!(user.name.as_deref(), Some("Ada Lovelace"));
+        assert!(user.email_verified);
+    }
+}
@@ -10,7 +10,7 @@ use thiserror::Error;
 use tracing::info;
 use url::Url;
 
-const USER_AGENT: &str = "VibeKanbanRemote/1.0";
+pub(super) const USER_AGENT: &str = "VibeKanbanRemote/1.0";
 
 const TOKEN_EXPIRATION_LEEWAY_SECONDS: i64 = 20;
 pub const VALIDATE_TOKEN_MAX_RETRIES: u32 = 3;
@@ -33,6 +33,8 @@ pub struct ProviderUser {
     pub email: Option<String>,
     pub name: Option<String>,
     pub avatar_url: Option<String>,
+    /// Whether the provider vouches that `email` belongs to the user
+    pub email_verified: bool,
 }
 
 #[derive(Debug, Error)]
@@ -60,9 +62,14 @@ pub struct ProviderTokenDetails {
 #[async_trait]
 pub trait AuthorizationProvider: Send + Sync {
     fn name(&self) -> &'static str;
-    fn scopes(&self) -> &[&str];
-    fn authorize_url(&self, state: &str, redirect_uri: &str) -> Result<Url>;
-    async fn exchange_code(&self, code: &str, redirect_uri: &str) -> Result<AuthorizationGrant>;
+    fn scopes(&self) -> Vec<&str>;
+    fn authorize_url(&self, state: &str, redirect_uri: &str, code_challenge: &str) -> Result<Url>;
+    async fn exchange_code(
+        &self,
+        code: &str,
+        redirect_uri: &str,
+        code_verifier: &str,
+    ) -> Result<AuthorizationGrant>;
     async fn fetch_user(&self, access_token: &SecretString) -> Result<ProviderUser>;
     async fn validate_token(
         &self,
@@ -163,11 +170,11 @@ impl AuthorizationProvider for GitHubOAuthProvider {
         "github"
     }
 
-    fn scopes(&self) -> &[&str] {
-        &["read:user", "user:email"]
+    fn scopes(&self) -> Vec<&str> {
+        vec!["read:user", "user:email"]
     }
 
-    fn authorize_url(&self, state: &str, redirect_uri: &str) -> Result<Url> {
+    fn authorize_url(&self, state: &str, redirect_uri: &str, code_challenge: &str) -> Result<Url> {
         let mut url = Url::parse("https://github.com/login/oauth/authorize")?;
         {
             let mut qp = url.query_pairs_mut();
@@ -176,11 +183,18 @@ impl AuthorizationProvider for GitHubOAuthProvider {
             qp.append_pair("redirect_uri", redirect_uri);
             qp.append_pair("allow_signup", "false");
             qp.append_pair("scope", &self.scopes().join(" "));
+            qp.append_pair("code_challenge", code_challenge);
+            qp.append_pair("code_challenge_method", "S256");
         }
         Ok(url)
     }
 
-    async fn exchange_code(&self, code: &str, redirect_uri: &str) -> Result<AuthorizationGrant> {
+    async fn exchange_code(
+        &self,
+        code: &str,
+        redirect_uri: &str,
+        code_verifier: &str,
+    ) -> Result<AuthorizationGrant> {
         let response = self
             .client
             .post("https://github.com/login/oauth/access_token")
@@ -190,6 +204,7 @@ impl AuthorizationProvider for GitHubOAuthProvider {
                 ("client_secret", self.client_secret.expose_secret()),
                 ("code", code),
                 ("redirect_uri", redirect_uri),
+                ("code_verifier", code_verifier),
             ])
             .send()
             .await?
@@ -232,6 +247,7 @@ impl AuthorizationProvider for GitHubOAuthProvider {
             .json()
             .await?;
 
+        // GitHub only shows verified addresses on public profiles
         let email = if user.email.is_some() {
             user.email
         } else {
@@ -260,6 +276,7 @@ impl AuthorizationProvider for GitHubOAuthProvider {
         Ok(ProviderUser {
             id: user.id.to_string(),
             login: Some(user.login),
+            email_verified: email.is_some(),
             email,
             name: user.name,
             avatar_url: user.avatar_url,
@@ -484,6 +501,8 @@ enum GoogleTokenResponse {
 struct GoogleUser {
     sub: String,
     email: Option<String>,
+    #[serde(default)]
+    email_verified: bool,
     name: Option<String>,
     given_name: Option<String>,
     family_name: Option<String>,
@@ -496,11 +515,11 @@ impl AuthorizationProvider for GoogleOAuthProvider {
         "google"
     }
 
-    fn scopes(&self) -> &[&str] {
-        &["openid", "email", "profile"]
+    fn scopes(&self) -> Vec<&str> {
+        vec!["openid", "email", "profile"]
     }
 
-    fn authorize_url(&self, state: &str, redirect_uri: &str) -> Result<Url> {
+    fn authorize_url(&self, state: &str, redirect_uri: &str, code_challenge: &str) -> Result<Url> {
         let mut url = Url::parse("https://accounts.google.com/o/oauth2/v2/auth")?;
         {
             let mut qp = url.query_pairs_mut();
@@ -511,11 +530,18 @@ impl AuthorizationProvider for GoogleOAuthProvider {
             qp.append_pair("state", state);
             qp.append_pair("access_type", "offline");
             qp.append_pair("prompt", "consent");
+            qp.append_pair("code_challenge", code_challenge);
+            qp.append_pair("code_challenge_method", "S256");
         }
         Ok(url)
     }
 
-    async fn exchange_code(&self, code: &str, redirect_uri: &str) -> Result<AuthorizationGrant> {
+    async fn exchange_code(
+        &self,
+        code: &str,
+        redirect_uri: &str,
+        code_verifier: &str,
+    ) -> Result<AuthorizationGrant> {
         let response = self
             .client
             .post("https://oauth2.googleapis.com/token")
@@ -525,6 +551,7 @@ impl AuthorizationProvider for GoogleOAuthProvider {
                 ("code", code),
                 ("grant_type", "authorization_code"),
                 ("redirect_uri", redirect_uri),
+                ("code_verifier", code_verifier),
             ])
             .send()
             .await?
@@ -596,6 +623,7 @@ impl AuthorizationProvider for GoogleOAuthProvider {
             email: profile.email,
             name,
             avatar_url: profile.picture,
+            email_verified: profile.email_verified,
         })
     }
 
@@ -690,3 +718,356 @@ impl AuthorizationProvider for GoogleOAuthProvider {
         }
     }
 }
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum TokenResponse {
+    Success {
+        access_token: String,
+        token_type: String,
+        scope: Option<String>,
+        expires_in: Option<i64>,
+        refresh_token: Option<String>,
+        id_token: Option<String>,
+    },
+    Error {
+        error: String,
+        error_description: Option<String>,
+    },
+}
+
+/// Token endpoint calls shared by providers that follow the OAuth 2.0 spec
+/// closely enough to need no special handling
+pub(super) struct StandardOAuthClient {
+    client: Client,
+    provider: &'static str,
+    token_url: Url,
+    client_id: String,
+    client_secret: SecretString,
+}
+
+impl StandardOAuthClient {
+    pub(super) fn new(
+        client: Client,
+        provider: &'static str,
+        token_url: Url,
+        client_id: String,
+        client_secret: SecretString,
+    ) -> Self {
+        Self {
+            client,
+            provider,
+            token_url,
+            client_id,
+            client_secret,
+        }
+    }
+
+    pub(super) fn http(&self) -> &Client {
+        &self.client
+    }
+
+    pub(super) fn client_id(&self) -> &str {
+        &self.client_id
+    }
+
+    pub(super) async fn exchange_code(
+        &self,
+        code: &str,
+        redirect_uri: &str,
+        code_verifier: &str,
+    ) -> Result<AuthorizationGrant> {
+        let response = self
+            .client
+            .post(self.token_url.clone())
+            .header("Accept", "application/json")
+            .form(&[
+                ("client_id", self.client_id.as_str()),
+                ("client_secret", self.client_secret.expose_secret()),
+                ("code", code),
+                ("grant_type", "authorization_code"),
+                ("redirect_uri", redirect_uri),
+                ("code_verifier", code_verifier),
+            ])
+            .send()
+            .await?
+            .error_for_status()?;
+
+        match response.json::<TokenResponse>().await? {
+            TokenResponse::Success {
+                access_token,
+                token_type,
+                scope,
+                expires_in,
+                refresh_token,
+                id_token,
+            } => Ok(AuthorizationGrant {
+                access_token: SecretString::new(access_token.into()),
+                token_type,
+                scopes: scope
+                    .unwrap_or_default()
+                    .split_whitespace()
+                    .map(str::to_string)
+                    .collect(),
+                refresh_token: refresh_token.map(|v| SecretString::new(v.into())),
+                expires_in: expires_in.map(Duration::seconds),
+                id_token: id_token.map(|v| SecretString::new(v.into())),
+            }),
+            TokenResponse::Error {
+                error,
+                error_description,
+            } => {
+                let detail = error_description.unwrap_or_else(|| error.clone());
+                anyhow::bail!("{} token exchange failed: {detail}", self.provider)
+            }
+        }
+    }
+
+    async fn try_refresh_access_token(
+        &self,
+        refresh_token: &str,
+    ) -> Result<ProviderTokenDetails, TokenValidationError> {
+        let response = self
+            .client
+            .post(self.token_url.clone())
+            .header("Accept", "application/json")
+            .form(&[
+                ("client_id", self.client_id.as_str()),
+                ("client_secret", self.client_secret.expose_secret()),
+                ("refresh_token", refresh_token),
+                ("grant_type", "refresh_token"),
+            ])
+            .send()
+            .await
+            .map_err(|err| {
+                TokenValidationError::temporary(format!("refresh request failed: {err}"))
+            })?;
+
+        match response.status() {
+            reqwest::StatusCode::OK => {
+                let token = response
+                    .json::<TokenResponse>()
+                    .await
+                    .map_err(|err| TokenValidationError::temporary(format!("{err}")))?;
+                let TokenResponse::Success {
+                    access_token,
+                    expires_in,
+                    refresh_token: new_refresh_token,
+                    ..
+                } = token
+                else {
+                    return Err(TokenValidationError::InvalidOrRevoked);
+                };
+
+                Ok(ProviderTokenDetails {
+                    provider: self.provider.to_string(),
+                    access_token,
+                    refresh_token: Some(
+                        new_refresh_token.unwrap_or_else(|| refresh_token.to_string()),
+                    ),
+                    expires_at: expires_in.map(|secs| chrono::Utc::now().timestamp() + secs),
+                })
+            }
+            reqwest::StatusCode::BAD_REQUEST | reqwest::StatusCode::UNAUTHORIZED => {
+                Err(TokenValidationError::InvalidOrRevoked)
+            }
+            status => Err(TokenValidationError::temporary(format!(
+                "unexpected token refresh status: {status}"
+            ))),
+        }
+    }
+
+    async fn refresh_token(
+        &self,
+        refresh_token: &str,
+        max_retries: u32,
+    ) -> Result<ProviderTokenDetails, TokenValidationError> {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+
+            match self.try_refresh_access_token(refresh_token).await {
+                Err(TokenValidationError::Temporary(_)) if attempt < max_retries => {
+                    tokio::time::sleep(tokio::time::Duration::from_secs(RETRY_INTERVAL_SECONDS))
+                        .await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn refresh_or_revoke(
+        &self,
+        token_details: &ProviderTokenDetails,
+        max_retries: u32,
+    ) -> Result<Option<ProviderTokenDetails>, TokenValidationError> {
+        let Some(refresh_token) = &token_details.refresh_token else {
+            return Err(TokenValidationError::InvalidOrRevoked);
+        };
+        info!(
+            provider = self.provider,
+            "Token expired, attempting refresh"
+        );
+        self.refresh_token(refresh_token, max_retries)
+            .await
+            .map(Some)
+    }
+
+    /// Checks the access token against an endpoint that requires it,
+    /// refreshing the token once it has expired or been rejected
+    pub(super) async fn validate_token(
+        &self,
+        check_url: &Url,
+        token_details: &ProviderTokenDetails,
+        max_retries: u32,
+    ) -> Result<Option<ProviderTokenDetails>, TokenValidationError> {
+        if let Some(expires_at) = token_details.expires_at
+            && chrono::Utc::now().timestamp() >= expires_at - TOKEN_EXPIRATION_LEEWAY_SECONDS
+        {
+            return self.refresh_or_revoke(token_details, max_retries).await;
+        }
+
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+
+            let response = match self
+                .client
+                .get(check_url.clone())
+                .bearer_auth(&token_details.access_token)
+                .send()
+                .await
+            {
+                Ok(resp) => resp,
+                Err(err) => {
+                    if attempt >= max_retries {
+                        return Err(TokenValidationError::temporary(format!(
+                            "request failed: {err}"
+                        )));
+                    }
+                    tokio::time::sleep(tokio::time::Duration::from_secs(RETRY_INTERVAL_SECONDS))
+                        .await;
+                    continue;
+                }
+            };
+
+            match response.status() {
+                status if status.is_success() => return Ok(None),
+                reqwest::StatusCode::UNAUTHORIZED => {
+                    return self.refresh_or_revoke(token_details, max_retries).await;
+                }
+                status => {
+                    if attempt >= max_retries {
+                        return Err(TokenValidationError::temporary(format!(
+                            "unexpected validation status: {status}"
+                        )));
+                    }
+                    tokio::time::sleep(tokio::time::Duration::from_secs(RETRY_INTERVAL_SECONDS))
+                        .await;
+                }
+            }
+        }
+    }
+}
+
+pub struct GitLabOAuthProvider {
+    oauth: StandardOAuthClient,
+    base_url: String,
+}
+
+impl GitLabOAuthProvider {
+    /// `base_url` is the root of the GitLab instance, without a trailing slash
+    pub fn new(base_url: &str, client_id: String, client_secret: SecretString) -> Result<Self> {
+        let client = Client::builder().user_agent(USER_AGENT).build()?;
+        let token_url = Url::parse(&format!("{base_url}/oauth/token"))?;
+        Ok(Self {
+            oauth: StandardOAuthClient::new(client, "gitlab", token_url, client_id, client_secret),
+            base_url: base_url.to_string(),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabUser {
+    id: i64,
+    username: String,
+    name: Option<String>,
+    email: Option<String>,
+    avatar_url: Option<String>,
+    confirmed_at: Option<String>,
+}
+
+#[async_trait]
+impl AuthorizationProvider for GitLabOAuthProvider {
+    fn name(&self) -> &'static str {
+        "gitlab"
+    }
+
+    fn scopes(&self) -> Vec<&str> {
+        vec!["read_user"]
+    }
+
+    fn authorize_url(&self, state: &str, redirect_uri: &str, code_challenge: &str) -> Result<Url> {
+        let mut url = Url::parse(&format!("{}/oauth/authorize", self.base_url))?;
+        {
+            let mut qp = url.query_pairs_mut();
+            qp.append_pair("client_id", self.oauth.client_id());
+            qp.append_pair("redirect_uri", redirect_uri);
+            qp.append_pair("response_type", "code");
+            qp.append_pair("scope", &self.scopes().join(" "));
+            qp.append_pair("state", state);
+            qp.append_pair("code_challenge", code_challenge);
+            qp.append_pair("code_challenge_method", "S256");
+        }
+        Ok(url)
+    }
+
+    async fn exchange_code(
+        &self,
+        code: &str,
+        redirect_uri: &str,
+        code_verifier: &str,
+    ) -> Result<AuthorizationGrant> {
+        self.oauth
+            .exchange_code(code, redirect_uri, code_verifier)
+            .await
+    }
+
+    async fn fetch_user(&self, access_token: &SecretString) -> Result<ProviderUser> {
+        let user: GitLabUser = self
+            .oauth
+            .http()
+            .get(format!("{}/api/v4/user", self.base_url))
+            .bearer_auth(access_token.expose_secret())
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("failed to parse GitLab user response")?;
+
+        // The primary email can only be set to a confirmed address
+        let email_verified = user.email.is_some() && user.confirmed_at.is_some();
+
+        Ok(ProviderUser {
+            id: user.id.to_string(),
+            login: Some(user.username),
+            email: user.email,
+            name: user.name,
+            avatar_url: user.avatar_url,
+            email_verified,
+        })
+    }
+
+    async fn validate_token(
+        &self,
+        token_details: &ProviderTokenDetails,
+        max_retries: u32,
+    ) -> Result<Option<ProviderTokenDetails>, TokenValidationError> {
+        let check_url = Url::parse(&format!("{}/oauth/token/info", self.base_url))
+            .map_err(|err| TokenValidationError::temporary(err.to_string()))?;
+        self.oauth
+            .validate_token(&check_url, token_details, max_retries)
+            .await
+    }
+}
@@ -192,10 +192,170 @@ impl OAuthProviderConfig {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct GitLabProviderConfig {
+    oauth: OAuthProviderConfig,
+    base_url: String,
+}
+
+impl GitLabProviderConfig {
+    fn from_env() -> Result<Option<Self>, ConfigError> {
+        let Some(client_id) = non_empty_var("GITLAB_OAUTH_CLIENT_ID") else {
+            return Ok(None);
+        };
+        let client_secret = non_empty_var("GITLAB_OAUTH_CLIENT_SECRET")
+            .ok_or(ConfigError::MissingVar("GITLAB_OAUTH_CLIENT_SECRET"))?;
+        let base_url = non_empty_var("GITLAB_BASE_URL")
+            .unwrap_or_else(|| "https://gitlab.com".to_string())
+            .trim_end_matches('/')
+            .to_string();
+
+        Ok(Some(Self {
+            oauth: OAuthProviderConfig::new(client_id, SecretString::new(client_secret.into())),
+            base_url,
+        }))
+    }
+
+    pub fn oauth(&self) -> &OAuthProviderConfig {
+        &self.oauth
+    }
+
+    /// Root of the GitLab instance, `https://gitlab.com` unless self-hosted
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+}
+
+/// Names of the userinfo claims used to build the user profile. Dotted names
+/// address nested claims.
+#[derive(Debug, Clone)]
+pub struct OidcClaimMapping {
+    pub username: String,
+    pub email: String,
+    pub name: String,
+    pub avatar_url: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct OidcProviderConfig {
+    oauth: OAuthProviderConfig,
+    issuer_url: String,
+    scopes: Vec<String>,
+    claims: OidcClaimMapping,
+}
+
+impl OidcProviderConfig {
+    fn from_env() -> Result<Option<Self>, ConfigError> {
+        let Some(issuer_url) = non_empty_var("OIDC_ISSUER_URL") else {
+            return Ok(None);
+        };
+        let client_id =
+            non_empty_var("OIDC_CLIENT_ID").ok_or(ConfigError::MissingVar("OIDC_CLIENT_ID"))?;
+        let client_secret = non_empty_var("OIDC_CLIENT_SECRET")
+            .ok_or(ConfigError::MissingVar("OIDC_CLIENT_SECRET"))?;
+
+        let mut scopes: Vec<String> = non_empty_var("OIDC_SCOPES")
+            .unwrap_or_else(|| "openid email profile".to_string())
+            .split([' ', ','])
+            .filter(|scope| !scope.is_empty())
+            .map(str::to_string)
+            .collect();
+        if !scopes.iter().any(|scope| scope == "openid") {
+            scopes.insert(0, "openid".to_string());
+        }
+
+        let claim =
+            |var: &str, default: &str| non_empty_var(var).unwrap_or_else(|| default.to_string());
+        let claims = OidcClaimMapping {
+            username: claim("OIDC_USERNAME_CLAIM", "preferred_username"),
+            email: claim("OIDC_EMAIL_CLAIM", "email"),
+            name: claim("OIDC_NAME_CLAIM", "name"),
+            avatar_url: claim("OIDC_AVATAR_CLAIM", "picture"),
+        };
+
+        Ok(Some(Self::new(
+            issuer_url,
+            client_id,
+            SecretString::new(client_secret.into()),
+            scopes,
+            claims,
+        )))
+    }
+
+    pub fn new(
+        issuer_url: String,
+        client_id: String,
+        client_secret: SecretString,
+        scopes: Vec<String>,
+        claims: OidcClaimMapping,
+    ) -> Self {
+        Self {
+            oauth: OAuthProviderConfig::new(client_id, client_secret),
+            issuer_url: issuer_url.trim_end_matches('/').to_string(),
+            scopes,
+            claims,
+        }
+    }
+
+    pub fn oauth(&self) -> &OAuthProviderConfig {
+        &self.oauth
+    }
+
+    /// Issuer whose `/.well-known/openid-configuration` describes the endpoints
+    pub fn issuer_url(&self) -> &str {
+        &self.issuer_url
+    }
+
+    pub fn scopes(&self) -> &[String] {
+        &self.scopes
+    }
+
+    pub fn claims(&self) -> &OidcClaimMapping {
+        &self.claims
+    }
+}
+
+/// Organization that users with a verified email address at `domain` join
+/// as members when they sign in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmailDomainRule {
+    pub domain: String,
+    pub organization_slug: String,
+}
+
+impl EmailDomainRule {
+    /// Parses `ORG_AUTO_JOIN_DOMAINS`, a comma separated list of
+    /// `domain=organization-slug` pairs
+    fn parse_list(value: &str) -> Result<Vec<Self>, ConfigError> {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                let (domain, slug) = entry
+                    .split_once('=')
+                    .ok_or(ConfigError::InvalidVar("ORG_AUTO_JOIN_DOMAINS"))?;
+                let domain = domain.trim().trim_start_matches('@').to_lowercase();
+                let slug = slug.trim();
+                if domain.is_empty() || slug.is_empty() {
+                    return Err(ConfigError::InvalidVar("ORG_AUTO_JOIN_DOMAINS"));
+                }
+                Ok(Self {
+                    domain,
+                    organization_slug: slug.to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct AuthConfig {
     github: Option<OAuthProviderConfig>,
     google: Option<OAuthProviderConfig>,
+    gitlab: Option<GitLabProviderConfig>,
+    oidc: Option<OidcProviderConfig>,
+    auto_join_domains: Vec<EmailDomainRule>,
     jwt_secret: SecretString,
     public_base_url: String,
 }
@@ -231,16 +391,27 @@ impl AuthConfig {
             Err(_) => None,
         };
 
-        if github.is_none() && google.is_none() {
+        let gitlab = GitLabProviderConfig::from_env()?;
+        let oidc = OidcProviderConfig::from_env()?;
+
+        if github.is_none() && google.is_none() && gitlab.is_none() && oidc.is_none() {
             return Err(ConfigError::NoOAuthProviders);
         }
 
+        let auto_join_domains = match non_empty_var("ORG_AUTO_JOIN_DOMAINS") {
+            Some(value) => EmailDomainRule::parse_list(&value)?,
+            None => Vec::new(),
+        };
+
         let public_base_url =
             env::var("SERVER_PUBLIC_BASE_URL").unwrap_or_else(|_| "http://localhost:8081".into());
 
         Ok(Self {
             github,
             google,
+            gitlab,
+            oidc,
+            auto_join_domains,
             jwt_secret,
             public_base_url,
         })
@@ -254,6 +425,18 @@ impl AuthConfig {
         self.google.as_ref()
     }
 
+    pub fn gitlab(&self) -> Option<&GitLabProviderConfig> {
+        self.gitlab.as_ref()
+    }
+
+    pub fn oidc(&self) -> Option<&OidcProviderConfig> {
+        self.oidc.as_ref()
+    }
+
+    pub fn auto_join_domains(&self) -> &[EmailDomainRule] {
+        &self.auto_join_domains
+    }
+
     pub fn jwt_secret(&self) -> &SecretString {
         &self.jwt_secret
     }
@@ -263,6 +446,10 @@ impl AuthConfig {
     }
 }
 
+fn non_empty_var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.trim().is_empty())
+}
+
 fn validate_jwt_secret(secret: &str) -> Result<(), ConfigError> {
     let decoded = BASE64_STANDARD
         .decode(secret.as_bytes())
@@ -0,0 +1,150 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{Executor, PgPool, Postgres, Type};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "audit_action", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    MemberJoined,
+    MemberRemoved,
+    MemberRoleChanged,
+    InvitationCreated,
+    InvitationRevoked,
+    GithubAppInstalled,
+    GithubAppUninstalled,
+    ProjectDeleted,
+    IssueDeleted,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MemberJoined => "member_joined",
+            Self::MemberRemoved => "member_removed",
+            Self::MemberRoleChanged => "member_role_changed",
+            Self::InvitationCreated => "invitation_created",
+            Self::InvitationRevoked => "invitation_revoked",
+            Self::GithubAppInstalled => "github_app_installed",
+            Self::GithubAppUninstalled => "github_app_uninstalled",
+            Self::ProjectDeleted => "project_deleted",
+            Self::IssueDeleted => "issue_deleted",
+        }
+    }
+}
+
+/// Entry in an organization's audit log, with the actor's current profile
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditEvent {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub actor_user_id: Option<Uuid>,
+    pub actor_email: Option<String>,
+    pub actor_username: Option<String>,
+    pub action: AuditAction,
+    pub subject_id: Option<Uuid>,
+    pub details: Value,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewAuditEvent {
+    pub organization_id: Uuid,
+    /// None for changes made by GitHub rather than a user
+    pub actor_user_id: Option<Uuid>,
+    pub action: AuditAction,
+    pub subject_id: Option<Uuid>,
+    pub details: Value,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AuditEventFilter {
+    pub action: Option<AuditAction>,
+    pub actor_user_id: Option<Uuid>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+/// Appends an event. Callers pass the transaction that makes the change, so
+/// the change and its audit event are committed together.
+pub(crate) async fn record<'e, E>(executor: E, event: NewAuditEvent) -> Result<(), sqlx::Error>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    sqlx::query!(
+        r#"
+        INSERT INTO audit_events (organization_id, actor_user_id, action, subject_id, details)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+        event.organization_id,
+        event.actor_user_id,
+        event.action as AuditAction,
+        event.subject_id,
+        event.details
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+pub struct AuditEventRepository<'a> {
+    pool: &'a PgPool,
+}
+
+impl<'a> AuditEventRepository<'a> {
+    pub fn new(pool: &'a PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Newest first. `before` is the id of the last event of the previous page.
+    pub async fn list(
+        &self,
+        organization_id: Uuid,
+        filter: &AuditEventFilter,
+        before: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<AuditEvent>, sqlx::Error> {
+        sqlx::query_as!(
+            AuditEvent,
+            r#"
+            SELECT
+                e.id              AS "id!: Uuid",
+                e.organization_id AS "organization_id!: Uuid",
+                e.actor_user_id   AS "actor_user_id?: Uuid",
+                u.email           AS "actor_email?",
+                u.username        AS "actor_username?",
+                e.action          AS "action!: AuditAction",
+                e.subject_id      AS "subject_id?: Uuid",
+                e.details         AS "details!: Value",
+                e.created_at      AS "created_at!: DateTime<Utc>"
+            FROM audit_events e
+            LEFT JOIN users u ON u.id = e.actor_user_id
+            WHERE e.organization_id = $1
+              AND ($2::audit_action IS NULL OR e.action = $2)
+              AND ($3::uuid IS NULL OR e.actor_user_id = $3)
+              AND ($4::timestamptz IS NULL OR e.created_at >= $4)
+              AND ($5::timestamptz IS NULL OR e.created_at < $5)
+              AND (
+                $6::uuid IS NULL
+                OR (e.created_at, e.id) < (
+                    SELECT created_at, id FROM audit_events WHERE id = $6
+                )
+              )
+            ORDER BY e.created_at DESC, e.id DESC
+            LIMIT $7
+            "#,
+            organization_id,
+            filter.action as Option<AuditAction>,
+            filter.actor_user_id,
+            filter.since,
+            filter.until,
+            before,
+            limit
+        )
+        .fetch_all(self.pool)
+        .await
+    }
+}
@@ -1,8 +1,14 @@
 use chrono::{DateTime, Utc};
+use serde_json::json;
 use sqlx::{FromRow, PgPool};
 use thiserror::Error;
 use uuid::Uuid;
 
+use super::{
+    Tx,
+    audit_events::{self, AuditAction, NewAuditEvent},
+};
+
 #[derive(Debug, Error)]
 pub enum GitHubAppDbError {
     #[error("database error: {0}")]
@@ -70,6 +76,8 @@ impl<'a> GitHubAppRepository2<'a> {
         repository_selection: &str,
         installed_by_user_id: Uuid,
     ) -> Result<GitHubAppInstallation, GitHubAppDbError> {
+        let mut tx = self.pool.begin().await?;
+
         let installation = sqlx::query_as!(
             GitHubAppInstallation,
             r#"
@@ -109,9 +117,23 @@ impl<'a> GitHubAppRepository2<'a> {
             repository_selection,
             installed_by_user_id
         )
-        .fetch_one(self.pool)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        audit_events::record(
+            &mut *tx,
+            NewAuditEvent {
+                organization_id,
+                actor_user_id: Some(installed_by_user_id),
+                action: AuditAction::GithubAppInstalled,
+                subject_id: Some(installation.id),
+                details: installation_details(&installation),
+            },
+        )
         .await?;
 
+        tx.commit().await?;
+
         Ok(installation)
     }
 
@@ -203,10 +225,14 @@ impl<'a> GitHubAppRepository2<'a> {
         Ok(installation)
     }
 
+    /// Removes an installation that was uninstalled on GitHub
     pub async fn delete_by_github_id(
         &self,
         github_installation_id: i64,
     ) -> Result<(), GitHubAppDbError> {
+        let installation = self.get_by_github_id(github_installation_id).await?;
+        let mut tx = self.pool.begin().await?;
+
         sqlx::query!(
             r#"
             DELETE FROM github_app_installations
@@ -214,16 +240,25 @@ impl<'a> GitHubAppRepository2<'a> {
             "#,
             github_installation_id
         )
-        .execute(self.pool)
+        .execute(&mut *tx)
         .await?;
 
+        if let Some(installation) = installation {
+            record_uninstall(&mut tx, &installation, None).await?;
+        }
+
+        tx.commit().await?;
         Ok(())
     }
 
     pub async fn delete_by_organization(
         &self,
         organization_id: Uuid,
+        removed_by_user_id: Uuid,
     ) -> Result<(), GitHubAppDbError> {
+        let installation = self.get_by_organization(organization_id).await?;
+        let mut tx = self.pool.begin().await?;
+
         sqlx::query!(
             r#"
             DELETE FROM github_app_installations
@@ -231,9 +266,14 @@ impl<'a> GitHubAppRepository2<'a> {
             "#,
             organization_id
         )
-        .execute(self.pool)
+        .execute(&mut *tx)
         .await?;
 
+        if let Some(installation) = installation {
+            record_uninstall(&mut tx, &installation, Some(removed_by_user_id)).await?;
+        }
+
+        tx.commit().await?;
         Ok(())
     }
 
@@ -569,3 +609,28 @@ impl<'a> GitHubAppRepository2<'a> {
         Ok(result.rows_affected())
     }
 }
+
+fn installation_details(installation: &GitHubAppInstallation) -> serde_json::Value {
+    json!({
+        "github_installation_id": installation.github_installation_id,
+        "account": installation.github_account_login,
+    })
+}
+
+async fn record_uninstall(
+    tx: &mut Tx<'_>,
+    installation: &GitHubAppInstallation,
+    actor_user_id: Option<Uuid>,
+) -> Result<(), sqlx::Error> {
+    audit_events::record(
+        &mut **tx,
+        NewAuditEvent {
+            organization_id: installation.organization_id,
+            actor_user_id,
+            action: AuditAction::GithubAppUninstalled,
+            subject_id: Some(installation.id),
+            details: installation_details(installation),
+        },
+    )
+    .await
+}
@@ -1,10 +1,12 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use sqlx::PgPool;
 pub use utils::api::organizations::InvitationStatus;
 use uuid::Uuid;
 
 use super::{
+    audit_events::{self, AuditAction, NewAuditEvent},
     identity_errors::IdentityError,
     organization_members::{MemberRole, add_member, assert_admin},
     organizations::{Organization, OrganizationRepository},
@@ -54,6 +56,8 @@ impl<'a> InvitationRepository<'a> {
             ));
         }
 
+        let mut tx = self.pool.begin().await?;
+
         let invitation = sqlx::query_as!(
             Invitation,
             r#"
@@ -80,7 +84,7 @@ impl<'a> InvitationRepository<'a> {
             token,
             expires_at
         )
-        .fetch_one(self.pool)
+        .fetch_one(&mut *tx)
         .await
         .map_err(|e| {
             if let Some(db_err) = e.as_database_error()
@@ -93,6 +97,20 @@ impl<'a> InvitationRepository<'a> {
             IdentityError::from(e)
         })?;
 
+        audit_events::record(
+            &mut *tx,
+            NewAuditEvent {
+                organization_id,
+                actor_user_id: Some(invited_by_user_id),
+                action: AuditAction::InvitationCreated,
+                subject_id: Some(invitation.id),
+                details: json!({ "email": invitation.email, "role": invitation.role }),
+            },
+        )
+        .await?;
+
+        tx.commit().await?;
+
         Ok(invitation)
     }
 
@@ -171,20 +189,34 @@ impl<'a> InvitationRepository<'a> {
     ) -> Result<(), IdentityError> {
         assert_admin(self.pool, organization_id, requesting_user_id).await?;
 
-        let result = sqlx::query!(
+        let mut tx = self.pool.begin().await?;
+
+        let email = sqlx::query_scalar!(
             r#"
             DELETE FROM organization_invitations
             WHERE id = $1 AND organization_id = $2
+            RETURNING email
             "#,
             invitation_id,
             organization_id
         )
-        .execute(self.pool)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(IdentityError::NotFound)?;
+
+        audit_events::record(
+            &mut *tx,
+            NewAuditEvent {
+                organization_id,
+                actor_user_id: Some(requesting_user_id),
+                action: AuditAction::InvitationRevoked,
+                subject_id: Some(invitation_id),
+                details: json!({ "email": email }),
+            },
+        )
         .await?;
 
-        if result.rows_affected() == 0 {
-            return Err(IdentityError::NotFound);
-        }
+        tx.commit().await?;
 
         Ok(())
     }
@@ -265,6 +297,18 @@ impl<'a> InvitationRepository<'a> {
         )
         .await?;
 
+        audit_events::record(
+            &mut *tx,
+            NewAuditEvent {
+                organization_id: invitation.organization_id,
+                actor_user_id: Some(user_id),
+                action: AuditAction::MemberJoined,
+                subject_id: Some(user_id),
+                details: json!({ "role": invitation.role, "invitation_id": invitation.id }),
+            },
+        )
+        .await?;
+
         sqlx::query!(
             r#"
             UPDATE organization_invitations
@@ -1,12 +1,13 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Value, json};
 use sqlx::PgPool;
 use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
 
 use super::{
+    audit_events::{self, AuditAction, NewAuditEvent},
     get_txid,
     project_statuses::ProjectStatusRepository,
     pull_requests::PullRequestRepository,
@@ -304,13 +305,37 @@ impl IssueRepository {
         Ok(MutationResponse { data, txid })
     }
 
-    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<DeleteResponse, IssueError> {
+    pub async fn delete(
+        pool: &PgPool,
+        id: Uuid,
+        deleted_by_user_id: Uuid,
+    ) -> Result<DeleteResponse, IssueError> {
+        let issue = Self::find_by_id(pool, id).await?;
+        let organization_id = Self::organization_id(pool, id).await?;
         let mut tx = pool.begin().await?;
 
         sqlx::query!("DELETE FROM issues WHERE id = $1", id)
             .execute(&mut *tx)
             .await?;
 
+        if let (Some(issue), Some(organization_id)) = (issue, organization_id) {
+            audit_events::record(
+                &mut *tx,
+                NewAuditEvent {
+                    organization_id,
+                    actor_user_id: Some(deleted_by_user_id),
+                    action: AuditAction::IssueDeleted,
+                    subject_id: Some(id),
+                    details: json!({
+                        "project_id": issue.project_id,
+                        "simple_id": issue.simple_id,
+                        "title": issue.title,
+                    }),
+                },
+            )
+            .await?;
+        }
+
         let txid = get_txid(&mut *tx).await?;
         tx.commit().await?;
 
@@ -1,3 +1,4 @@
+pub mod audit_events;
 pub mod auth;
 pub mod github_app;
 pub mod identity_errors;
@@ -14,6 +15,7 @@ pub mod oauth;
 pub mod oauth_accounts;
 pub mod organization_members;
 pub mod organizations;
+pub mod personal_access_tokens;
 pub mod project_notification_preferences;
 pub mod project_statuses;
 pub mod projects;
@@ -1,11 +1,16 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use sqlx::{Executor, PgPool, Postgres};
 use ts_rs::TS;
 pub use utils::api::organizations::MemberRole;
 use uuid::Uuid;
 
-use super::identity_errors::IdentityError;
+use super::{
+    Tx,
+    audit_events::{self, AuditAction, NewAuditEvent},
+    identity_errors::IdentityError,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export)]
@@ -43,6 +48,73 @@ where
     Ok(())
 }
 
+/// Removes a member on behalf of an admin and records it in the audit log
+pub(crate) async fn remove_member(
+    tx: &mut Tx<'_>,
+    organization_id: Uuid,
+    user_id: Uuid,
+    role: MemberRole,
+    removed_by_user_id: Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        DELETE FROM organization_member_metadata
+        WHERE organization_id = $1 AND user_id = $2
+        "#,
+        organization_id,
+        user_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    audit_events::record(
+        &mut **tx,
+        NewAuditEvent {
+            organization_id,
+            actor_user_id: Some(removed_by_user_id),
+            action: AuditAction::MemberRemoved,
+            subject_id: Some(user_id),
+            details: json!({ "role": role }),
+        },
+    )
+    .await
+}
+
+/// Changes a member's role on behalf of an admin and records it in the audit log
+pub(crate) async fn update_member_role(
+    tx: &mut Tx<'_>,
+    organization_id: Uuid,
+    user_id: Uuid,
+    previous_role: MemberRole,
+    role: MemberRole,
+    changed_by_user_id: Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE organization_member_metadata
+        SET role = $3
+        WHERE organization_id = $1 AND user_id = $2
+        "#,
+        organization_id,
+        user_id,
+        role as MemberRole
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    audit_events::record(
+        &mut **tx,
+        NewAuditEvent {
+            organization_id,
+            actor_user_id: Some(changed_by_user_id),
+            action: AuditAction::MemberRoleChanged,
+            subject_id: Some(user_id),
+            details: json!({ "from": previous_role, "to": role }),
+        },
+    )
+    .await
+}
+
 pub(super) async fn check_user_role(
     pool: &PgPool,
     organization_id: Uuid,
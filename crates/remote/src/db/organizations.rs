@@ -1,8 +1,10 @@
+use serde_json::json;
 use sqlx::{Executor, PgPool, Postgres, query_as};
 pub use utils::api::organizations::{MemberRole, Organization, OrganizationWithRole};
 use uuid::Uuid;
 
 use super::{
+    audit_events::{self, AuditAction, NewAuditEvent},
     identity_errors::IdentityError,
     organization_members::{
         add_member, assert_admin as check_admin, assert_membership as check_membership,
@@ -121,6 +123,47 @@ impl<'a> OrganizationRepository<'a> {
         check_admin(self.pool, organization_id, user_id).await
     }
 
+    /// Adds the user as a member of the shared organization with this slug,
+    /// leaving any existing membership alone. Returns whether they joined.
+    pub async fn join_by_slug(&self, slug: &str, user_id: Uuid) -> Result<bool, IdentityError> {
+        let mut tx = self.pool.begin().await?;
+
+        let organization_id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO organization_member_metadata (organization_id, user_id, role)
+            SELECT id, $2, 'member'
+            FROM organizations
+            WHERE slug = $1 AND is_personal = FALSE
+            ON CONFLICT (organization_id, user_id) DO NOTHING
+            RETURNING organization_id
+            "#,
+            slug,
+            user_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(organization_id) = organization_id else {
+            return Ok(false);
+        };
+
+        audit_events::record(
+            &mut *tx,
+            NewAuditEvent {
+                organization_id,
+                actor_user_id: Some(user_id),
+                action: AuditAction::MemberJoined,
+                subject_id: Some(user_id),
+                details: json!({ "role": MemberRole::Member, "via": "email_domain" }),
+            },
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
     pub async fn create_organization(
         &self,
         name: &str,
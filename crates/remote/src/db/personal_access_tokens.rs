@@ -0,0 +1,301 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use rand::{Rng, distr::Alphanumeric};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::{PgPool, query_as};
+use uuid::Uuid;
+
+/// Prefix that tells personal access tokens apart from session JWTs
+pub const TOKEN_PREFIX: &str = "vkp_";
+const TOKEN_SECRET_LENGTH: usize = 40;
+/// Characters of the token kept in clear text so users can recognise it
+const DISPLAY_PREFIX_LENGTH: usize = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TokenScope {
+    #[serde(rename = "issues:read")]
+    IssuesRead,
+    #[serde(rename = "issues:write")]
+    IssuesWrite,
+    #[serde(rename = "org:admin")]
+    OrgAdmin,
+}
+
+impl TokenScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::IssuesRead => "issues:read",
+            Self::IssuesWrite => "issues:write",
+            Self::OrgAdmin => "org:admin",
+        }
+    }
+
+    /// Whether a token holding this scope may act where `required` is needed.
+    /// Write access includes read access.
+    pub fn grants(&self, required: TokenScope) -> bool {
+        *self == required || (*self == Self::IssuesWrite && required == Self::IssuesRead)
+    }
+}
+
+impl FromStr for TokenScope {
+    type Err = ();
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "issues:read" => Ok(Self::IssuesRead),
+            "issues:write" => Ok(Self::IssuesWrite),
+            "org:admin" => Ok(Self::OrgAdmin),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PersonalAccessToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub token_prefix: String,
+    pub scopes: Vec<TokenScope>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PersonalAccessToken {
+    pub fn has_scope(&self, required: TokenScope) -> bool {
+        self.scopes.iter().any(|scope| scope.grants(required))
+    }
+}
+
+struct PersonalAccessTokenRow {
+    id: Uuid,
+    user_id: Uuid,
+    name: String,
+    token_prefix: String,
+    scopes: Vec<String>,
+    expires_at: Option<DateTime<Utc>>,
+    last_used_at: Option<DateTime<Utc>>,
+    revoked_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<PersonalAccessTokenRow> for PersonalAccessToken {
+    fn from(row: PersonalAccessTokenRow) -> Self {
+        Self {
+            id: row.id,
+            user_id: row.user_id,
+            name: row.name,
+            token_prefix: row.token_prefix,
+            scopes: row
+                .scopes
+                .iter()
+                .filter_map(|scope| scope.parse().ok())
+                .collect(),
+            expires_at: row.expires_at,
+            last_used_at: row.last_used_at,
+            revoked_at: row.revoked_at,
+            created_at: row.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CreatePersonalAccessToken<'a> {
+    pub user_id: Uuid,
+    pub name: &'a str,
+    pub token: &'a str,
+    pub scopes: &'a [TokenScope],
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+pub struct PersonalAccessTokenRepository<'a> {
+    pool: &'a PgPool,
+}
+
+impl<'a> PersonalAccessTokenRepository<'a> {
+    pub fn new(pool: &'a PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(
+        &self,
+        data: CreatePersonalAccessToken<'_>,
+    ) -> Result<PersonalAccessToken, sqlx::Error> {
+        let token_hash = hash_token(data.token);
+        let token_prefix: String = data.token.chars().take(DISPLAY_PREFIX_LENGTH).collect();
+        let scopes: Vec<String> = data
+            .scopes
+            .iter()
+            .map(|scope| scope.as_str().to_string())
+            .collect();
+
+        let row = query_as!(
+            PersonalAccessTokenRow,
+            r#"
+            INSERT INTO personal_access_tokens (
+                user_id,
+                name,
+                token_hash,
+                token_prefix,
+                scopes,
+                expires_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING
+                id           AS "id!: Uuid",
+                user_id      AS "user_id!: Uuid",
+                name         AS "name!",
+                token_prefix AS "token_prefix!",
+                scopes       AS "scopes!",
+                expires_at   AS "expires_at?",
+                last_used_at AS "last_used_at?",
+                revoked_at   AS "revoked_at?",
+                created_at   AS "created_at!"
+            "#,
+            data.user_id,
+            data.name,
+            token_hash,
+            token_prefix,
+            &scopes,
+            data.expires_at
+        )
+        .fetch_one(self.pool)
+        .await?;
+
+        Ok(row.into())
+    }
+
+    /// Tokens that have not been revoked, newest first
+    pub async fn list_for_user(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<PersonalAccessToken>, sqlx::Error> {
+        let rows = query_as!(
+            PersonalAccessTokenRow,
+            r#"
+            SELECT
+                id           AS "id!: Uuid",
+                user_id      AS "user_id!: Uuid",
+                name         AS "name!",
+                token_prefix AS "token_prefix!",
+                scopes       AS "scopes!",
+                expires_at   AS "expires_at?",
+                last_used_at AS "last_used_at?",
+                revoked_at   AS "revoked_at?",
+                created_at   AS "created_at!"
+            FROM personal_access_tokens
+            WHERE user_id = $1 AND revoked_at IS NULL
+            ORDER BY created_at DESC
+            "#,
+            user_id
+        )
+        .fetch_all(self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Token matching the presented secret, unless it is revoked or expired
+    pub async fn find_active(
+        &self,
+        token: &str,
+    ) -> Result<Option<PersonalAccessToken>, sqlx::Error> {
+        let row = query_as!(
+            PersonalAccessTokenRow,
+            r#"
+            SELECT
+                id           AS "id!: Uuid",
+                user_id      AS "user_id!: Uuid",
+                name         AS "name!",
+                token_prefix AS "token_prefix!",
+                scopes       AS "scopes!",
+                expires_at   AS "expires_at?",
+                last_used_at AS "last_used_at?",
+                revoked_at   AS "revoked_at?",
+                created_at   AS "created_at!"
+            FROM personal_access_tokens
+            WHERE token_hash = $1
+              AND revoked_at IS NULL
+              AND (expires_at IS NULL OR expires_at > NOW())
+            "#,
+            hash_token(token)
+        )
+        .fetch_optional(self.pool)
+        .await?;
+
+        Ok(row.map(Into::into))
+    }
+
+    /// Records a use of the token, at most once a minute
+    pub async fn touch(&self, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE personal_access_tokens
+            SET last_used_at = NOW()
+            WHERE id = $1
+              AND (
+                last_used_at IS NULL
+                OR last_used_at < NOW() - INTERVAL '1 minute'
+              )
+            "#,
+            id
+        )
+        .execute(self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns false when the user has no active token with this id
+    pub async fn revoke(&self, id: Uuid, user_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE personal_access_tokens
+            SET revoked_at = NOW()
+            WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL
+            "#,
+            id,
+            user_id
+        )
+        .execute(self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+/// New token secret. Only its hash is stored.
+pub fn generate_token() -> String {
+    let secret: String = rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(TOKEN_SECRET_LENGTH)
+        .map(char::from)
+        .collect();
+    format!("{TOKEN_PREFIX}{secret}")
+}
+
+fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_scope_includes_read() {
+        assert!(TokenScope::IssuesWrite.grants(TokenScope::IssuesRead));
+        assert!(!TokenScope::IssuesRead.grants(TokenScope::IssuesWrite));
+        assert!(!TokenScope::OrgAdmin.grants(TokenScope::IssuesRead));
+    }
+
+    #[test]
+    fn generated_tokens_are_prefixed() {
+        let token = generate_token();
+        assert!(token.starts_with(TOKEN_PREFIX));
+        assert_eq!(token.len(), TOKEN_PREFIX.len() + TOKEN_SECRET_LENGTH);
+        assert_eq!(hash_token(&token).len(), 64);
+    }
+}
@@ -1,11 +1,17 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use sqlx::{Executor, PgPool, Postgres};
 use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
 
-use super::{get_txid, project_statuses::ProjectStatusRepository, tags::TagRepository};
+use super::{
+    audit_events::{self, AuditAction, NewAuditEvent},
+    get_txid,
+    project_statuses::ProjectStatusRepository,
+    tags::TagRepository,
+};
 use crate::mutation_types::{DeleteResponse, MutationResponse};
 
 /// Default color for the initial project created with personal organizations
@@ -176,11 +182,29 @@ impl ProjectRepository {
         Ok(MutationResponse { data, txid })
     }
 
-    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<DeleteResponse, ProjectError> {
+    pub async fn delete(
+        pool: &PgPool,
+        id: Uuid,
+        deleted_by_user_id: Uuid,
+    ) -> Result<DeleteResponse, ProjectError> {
         let mut tx = pool.begin().await?;
+        let project = Self::find_by_id(&mut *tx, id).await?;
         sqlx::query!("DELETE FROM projects WHERE id = $1", id)
             .execute(&mut *tx)
             .await?;
+        if let Some(project) = project {
+            audit_events::record(
+                &mut *tx,
+                NewAuditEvent {
+                    organization_id: project.organization_id,
+                    actor_user_id: Some(deleted_by_user_id),
+                    action: AuditAction::ProjectDeleted,
+                    subject_id: Some(id),
+                    details: json!({ "name": project.name }),
+                },
+            )
+            .await?;
+        }
         let txid = get_txid(&mut *tx).await?;
         tx.commit().await?;
         Ok(DeleteResponse { txid })
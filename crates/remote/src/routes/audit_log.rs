@@ -0,0 +1,244 @@
+use axum::{
+    Json, Router,
+    extract::{Extension, Path, Query, State},
+    http::{StatusCode, header},
+    response::IntoResponse,
+    routing::get,
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{error::ErrorResponse, organization_members::ensure_admin_access};
+use crate::{
+    AppState,
+    auth::RequestContext,
+    db::audit_events::{AuditAction, AuditEvent, AuditEventFilter, AuditEventRepository},
+};
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 200;
+const EXPORT_BATCH_SIZE: i64 = 1000;
+/// Exports are built in memory, so very old history has to be fetched in
+/// date ranges
+const MAX_EXPORT_EVENTS: usize = 100_000;
+
+#[derive(Debug, Deserialize)]
+pub struct AuditLogQuery {
+    pub action: Option<AuditAction>,
+    pub actor_user_id: Option<Uuid>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    /// `next_cursor` from the previous page
+    pub before: Option<Uuid>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Jsonl,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportAuditLogQuery {
+    pub action: Option<AuditAction>,
+    pub actor_user_id: Option<Uuid>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListAuditEventsResponse {
+    pub events: Vec<AuditEvent>,
+    /// Pass as `before` to fetch the next page; absent on the last page
+    pub next_cursor: Option<Uuid>,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/organizations/{org_id}/audit-log", get(list_audit_events))
+        .route(
+            "/organizations/{org_id}/audit-log/export",
+            get(export_audit_events),
+        )
+}
+
+#[instrument(
+    name = "audit_log.list",
+    skip(state, ctx, query),
+    fields(org_id = %org_id, user_id = %ctx.user.id)
+)]
+async fn list_audit_events(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(org_id): Path<Uuid>,
+    Query(query): Query<AuditLogQuery>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    let filter = AuditEventFilter {
+        action: query.action,
+        actor_user_id: query.actor_user_id,
+        since: query.since,
+        until: query.until,
+    };
+    let events = AuditEventRepository::new(state.pool())
+        .list(org_id, &filter, query.before, limit)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to list audit events");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
+        })?;
+
+    let next_cursor = if events.len() as i64 == limit {
+        events.last().map(|event| event.id)
+    } else {
+        None
+    };
+
+    Ok(Json(ListAuditEventsResponse {
+        events,
+        next_cursor,
+    }))
+}
+
+#[instrument(
+    name = "audit_log.export",
+    skip(state, ctx, query),
+    fields(org_id = %org_id, user_id = %ctx.user.id)
+)]
+async fn export_audit_events(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(org_id): Path<Uuid>,
+    Query(query): Query<ExportAuditLogQuery>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
+
+    let filter = AuditEventFilter {
+        action: query.action,
+        actor_user_id: query.actor_user_id,
+        since: query.since,
+        until: query.until,
+    };
+    let repo = AuditEventRepository::new(state.pool());
+    let mut events = Vec::new();
+    let mut before = None;
+    loop {
+        let batch = repo
+            .list(org_id, &filter, before, EXPORT_BATCH_SIZE)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, "failed to export audit events");
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
+            })?;
+        let done = (batch.len() as i64) < EXPORT_BATCH_SIZE;
+        before = batch.last().map(|event| event.id);
+        events.extend(batch);
+
+        if events.len() > MAX_EXPORT_EVENTS {
+            return Err(ErrorResponse::new(
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "Export is limited to {MAX_EXPORT_EVENTS} events; narrow it with `since` and `until`"
+                ),
+            ));
+        }
+        if done {
+            break;
+        }
+    }
+
+    let (body, content_type, extension) = match query.format {
+        ExportFormat::Csv => (to_csv(&events), "text/csv; charset=utf-8", "csv"),
+        ExportFormat::Jsonl => (to_jsonl(&events), "application/x-ndjson", "jsonl"),
+    };
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"audit-log-{org_id}.{extension}\""),
+            ),
+        ],
+        body,
+    ))
+}
+
+fn to_jsonl(events: &[AuditEvent]) -> String {
+    let mut output = String::new();
+    for event in events {
+        // AuditEvent only holds JSON-safe values, so serialization cannot fail
+        output.push_str(&serde_json::to_string(event).unwrap_or_default());
+        output.push('\n');
+    }
+    output
+}
+
+fn to_csv(events: &[AuditEvent]) -> String {
+    let mut output = String::from(
+        "id,created_at,action,actor_user_id,actor_email,actor_username,subject_id,details\n",
+    );
+    for event in events {
+        let fields = [
+            event.id.to_string(),
+            event
+                .created_at
+                .to_rfc3339_opts(SecondsFormat::Millis, true),
+            event.action.as_str().to_string(),
+            event
+                .actor_user_id
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+            event.actor_email.clone().unwrap_or_default(),
+            event.actor_username.clone().unwrap_or_default(),
+            event
+                .subject_id
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+            event.details.to_string(),
+        ];
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        output.push_str(&row.join(","));
+        output.push('\n');
+    }
+    output
+}
+
+/// Quotes a CSV field when needed. Fields starting with a formula character
+/// are prefixed with `'` so spreadsheets don't evaluate them.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{value}")
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_fields_are_escaped() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field(r#"{"a":1,"b":2}"#), r#""{""a"":1,""b"":2}""#);
+        assert_eq!(csv_field("=HYPERLINK()"), "'=HYPERLINK()");
+    }
+}
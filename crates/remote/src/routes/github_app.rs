@@ -271,10 +271,13 @@ pub async fn uninstall(
         })?;
 
     let gh_repo = GitHubAppRepository2::new(state.pool());
-    gh_repo.delete_by_organization(org_id).await.map_err(|e| {
-        error!(?e, "Failed to delete GitHub App installation");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
-    })?;
+    gh_repo
+        .delete_by_organization(org_id, ctx.user.id)
+        .await
+        .map_err(|e| {
+            error!(?e, "Failed to delete GitHub App installation");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
+        })?;
 
     info!(org_id = %org_id, user_id = %ctx.user.id, "GitHub App installation removed");
     Ok(StatusCode::NO_CONTENT)
@@ -164,7 +164,7 @@ async fn delete_issue(
 
     ensure_project_access(state.pool(), ctx.user.id, issue.project_id).await?;
 
-    let response = IssueRepository::delete(state.pool(), issue_id)
+    let response = IssueRepository::delete(state.pool(), issue_id, ctx.user.id)
         .await
         .map_err(|error| {
             tracing::error!(?error, "failed to delete issue");
@@ -12,8 +12,12 @@ use tower_http::{
 };
 use tracing::{Level, field};
 
-use crate::{AppState, auth::require_session};
+use crate::{
+    AppState,
+    auth::{TokenAccess, require_session, require_token_scope},
+};
 
+mod audit_log;
 mod electric_proxy;
 mod error;
 mod github_app;
@@ -29,6 +33,7 @@ mod notifications;
 mod oauth;
 pub(crate) mod organization_members;
 mod organizations;
+mod personal_access_tokens;
 mod project_statuses;
 mod projects;
 mod pull_requests;
@@ -66,14 +71,11 @@ pub fn router(state: AppState) -> Router {
         .merge(review::public_router())
         .merge(github_app::public_router());
 
-    let v1_protected = Router::<AppState>::new()
+    // Personal access tokens only reach routes that declare the scopes they need
+    let v1_issues = Router::<AppState>::new()
         .merge(identity::router())
         .merge(projects::router())
-        .merge(organizations::router())
-        .merge(organization_members::protected_router())
-        .merge(oauth::protected_router())
         .merge(electric_proxy::router())
-        .merge(github_app::protected_router())
         .merge(project_statuses::router())
         .merge(tags::router())
         .merge(issue_comments::router())
@@ -86,6 +88,33 @@ pub fn router(state: AppState) -> Router {
         .merge(pull_requests::router())
         .merge(notifications::router())
         .merge(workspaces::router())
+        .route_layer(middleware::from_fn_with_state(
+            TokenAccess::ISSUES,
+            require_token_scope,
+        ));
+
+    let v1_org_admin = Router::<AppState>::new()
+        .merge(organizations::router())
+        .merge(audit_log::router())
+        .merge(organization_members::protected_router())
+        .merge(github_app::protected_router())
+        .route_layer(middleware::from_fn_with_state(
+            TokenAccess::ORG_ADMIN,
+            require_token_scope,
+        ));
+
+    let v1_session_only = Router::<AppState>::new()
+        .merge(oauth::protected_router())
+        .merge(personal_access_tokens::router())
+        .route_layer(middleware::from_fn_with_state(
+            TokenAccess::SessionOnly,
+            require_token_scope,
+        ));
+
+    let v1_protected = Router::<AppState>::new()
+        .merge(v1_issues)
+        .merge(v1_org_admin)
+        .merge(v1_session_only)
         .layer(middleware::from_fn_with_state(
             state.clone(),
             require_session,
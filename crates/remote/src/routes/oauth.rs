@@ -210,16 +210,19 @@ pub async fn logout(
 ) -> Response {
     use crate::db::auth::{AuthSessionError, AuthSessionRepository};
 
+    let Some(session_id) = ctx.session_id else {
+        return StatusCode::BAD_REQUEST.into_response();
+    };
     let repo = AuthSessionRepository::new(state.pool());
 
-    match repo.revoke(ctx.session_id).await {
+    match repo.revoke(session_id).await {
         Ok(_) | Err(AuthSessionError::NotFound) => StatusCode::NO_CONTENT.into_response(),
         Err(AuthSessionError::Database(error)) => {
-            warn!(?error, session_id = %ctx.session_id, "failed to revoke auth session");
+            warn!(?error, session_id = %session_id, "failed to revoke auth session");
             StatusCode::INTERNAL_SERVER_ERROR.into_response()
         }
         Err(error) => {
-            warn!(?error, session_id = %ctx.session_id, "failed to revoke auth session");
+            warn!(?error, session_id = %session_id, "failed to revoke auth session");
             StatusCode::INTERNAL_SERVER_ERROR.into_response()
         }
     }
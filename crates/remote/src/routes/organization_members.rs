@@ -368,17 +368,9 @@ pub async fn remove_member(
         }
     }
 
-    sqlx::query!(
-        r#"
-        DELETE FROM organization_member_metadata
-        WHERE organization_id = $1 AND user_id = $2
-        "#,
-        org_id,
-        user_id
-    )
-    .execute(&mut *tx)
-    .await
-    .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
+    organization_members::remove_member(&mut tx, org_id, user_id, target.role, user.id)
+        .await
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
 
     tx.commit()
         .await
@@ -465,17 +457,14 @@ pub async fn update_member_role(
         }
     }
 
-    sqlx::query!(
-        r#"
-        UPDATE organization_member_metadata
-        SET role = $3
-        WHERE organization_id = $1 AND user_id = $2
-        "#,
+    organization_members::update_member_role(
+        &mut tx,
         org_id,
         user_id,
-        payload.role as MemberRole
+        target.role,
+        payload.role,
+        user.id,
     )
-    .execute(&mut *tx)
     .await
     .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
 
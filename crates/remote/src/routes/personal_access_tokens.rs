@@ -0,0 +1,161 @@
+use axum::{
+    Json, Router,
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{delete, get},
+};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::error::ErrorResponse;
+use crate::{
+    AppState,
+    auth::RequestContext,
+    db::personal_access_tokens::{
+        CreatePersonalAccessToken, PersonalAccessToken, PersonalAccessTokenRepository, TokenScope,
+        generate_token,
+    },
+};
+
+const MAX_EXPIRY_DAYS: i64 = 365;
+
+#[derive(Debug, Serialize)]
+pub struct ListPersonalAccessTokensResponse {
+    pub tokens: Vec<PersonalAccessToken>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreatePersonalAccessTokenRequest {
+    pub name: String,
+    pub scopes: Vec<TokenScope>,
+    /// Tokens without an expiry stay valid until revoked
+    pub expires_in_days: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreatePersonalAccessTokenResponse {
+    pub token: PersonalAccessToken,
+    /// Shown once; only a hash is kept on the server
+    pub secret: String,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/tokens/personal", get(list_tokens).post(create_token))
+        .route("/tokens/personal/{token_id}", delete(revoke_token))
+}
+
+#[instrument(
+    name = "personal_access_tokens.list",
+    skip(state, ctx),
+    fields(user_id = %ctx.user.id)
+)]
+async fn list_tokens(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let tokens = PersonalAccessTokenRepository::new(state.pool())
+        .list_for_user(ctx.user.id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to list personal access tokens");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
+        })?;
+
+    Ok(Json(ListPersonalAccessTokensResponse { tokens }))
+}
+
+#[instrument(
+    name = "personal_access_tokens.create",
+    skip(state, ctx, payload),
+    fields(user_id = %ctx.user.id)
+)]
+async fn create_token(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Json(payload): Json<CreatePersonalAccessTokenRequest>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let name = payload.name.trim();
+    if name.is_empty() || name.len() > 100 {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "Token name must be between 1 and 100 characters",
+        ));
+    }
+
+    if payload.scopes.is_empty() {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "Token must have at least one scope",
+        ));
+    }
+
+    let expires_at = match payload.expires_in_days {
+        Some(days) if !(1..=MAX_EXPIRY_DAYS).contains(&days) => {
+            return Err(ErrorResponse::new(
+                StatusCode::BAD_REQUEST,
+                format!("Token expiry must be between 1 and {MAX_EXPIRY_DAYS} days"),
+            ));
+        }
+        Some(days) => Some(Utc::now() + Duration::days(days)),
+        None => None,
+    };
+
+    let mut scopes = Vec::with_capacity(payload.scopes.len());
+    for scope in payload.scopes {
+        if !scopes.contains(&scope) {
+            scopes.push(scope);
+        }
+    }
+
+    let secret = generate_token();
+    let token = PersonalAccessTokenRepository::new(state.pool())
+        .create(CreatePersonalAccessToken {
+            user_id: ctx.user.id,
+            name,
+            token: &secret,
+            scopes: &scopes,
+            expires_at,
+        })
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to create personal access token");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
+        })?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreatePersonalAccessTokenResponse { token, secret }),
+    ))
+}
+
+#[instrument(
+    name = "personal_access_tokens.revoke",
+    skip(state, ctx),
+    fields(token_id = %token_id, user_id = %ctx.user.id)
+)]
+async fn revoke_token(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(token_id): Path<Uuid>,
+) -> Result<StatusCode, ErrorResponse> {
+    let revoked = PersonalAccessTokenRepository::new(state.pool())
+        .revoke(token_id, ctx.user.id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to revoke personal access token");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
+        })?;
+
+    if !revoked {
+        return Err(ErrorResponse::new(
+            StatusCode::NOT_FOUND,
+            "Personal access token not found",
+        ));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
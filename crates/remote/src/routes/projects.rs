@@ -164,7 +164,7 @@ async fn delete_project(
 
     ensure_member_access(state.pool(), project.organization_id, ctx.user.id).await?;
 
-    let response = ProjectRepository::delete(state.pool(), project_id)
+    let response = ProjectRepository::delete(state.pool(), project_id, ctx.user.id)
         .await
         .map_err(|error| {
             tracing::error!(?error, "failed to delete project");
@@ -16,6 +16,8 @@ fn generate_types_content() -> String {
         db::models::project::UpdateProject::decl(),
         db::models::project::SearchResult::decl(),
         db::models::project::SearchMatchType::decl(),
+        db::models::project::SearchSymbol::decl(),
+        db::models::project::SymbolKind::decl(),
         db::models::repo::Repo::decl(),
         db::models::repo::UpdateRepo::decl(),
         db::models::project_repo::ProjectRepo::decl(),
@@ -51,6 +53,21 @@ fn generate_types_content() -> String {
         db::models::execution_process::ExecutionProcessStatus::decl(),
         db::models::execution_process::ExecutionProcessRunReason::decl(),
         db::models::execution_process_repo_state::ExecutionProcessRepoState::decl(),
+        db::models::execution_process_logs::LogStorageStats::decl(),
+        db::models::conversation_entry::ConversationSearchParams::decl(),
+        db::models::conversation_entry::ConversationSearchHit::decl(),
+        db::models::review_comment::ReviewSide::decl(),
+        db::models::review_comment::ReviewCommentStatus::decl(),
+        db::models::review_comment::ReviewComment::decl(),
+        db::models::review_comment::CreateReviewComment::decl(),
+        db::models::review_comment::UpdateReviewComment::decl(),
+        db::models::worker_host::WorkerHost::decl(),
+        db::models::worker_host::CreateWorkerHost::decl(),
+        db::models::user::User::decl(),
+        db::models::user::UserRole::decl(),
+        db::models::user::CreateUser::decl(),
+        db::models::activity::Activity::decl(),
+        db::models::activity::ActivityAction::decl(),
         db::models::merge::Merge::decl(),
         db::models::merge::DirectMerge::decl(),
         db::models::merge::PrMerge::decl(),
@@ -61,6 +78,8 @@ fn generate_types_content() -> String {
         utils::approvals::ApprovalResponse::decl(),
         utils::diff::Diff::decl(),
         utils::diff::DiffChangeKind::decl(),
+        utils::diff::DiffPointer::decl(),
+        utils::diff::DiffHunk::decl(),
         utils::response::ApiResponse::<()>::decl(),
         utils::api::oauth::LoginStatus::decl(),
         utils::api::oauth::ProfileResponse::decl(),
@@ -94,7 +113,21 @@ fn generate_types_content() -> String {
         server::routes::projects::LinkToExistingRequest::decl(),
         server::routes::repo::RegisterRepoRequest::decl(),
         server::routes::repo::InitRepoRequest::decl(),
+        server::routes::repo::CloneRepoRequest::decl(),
         server::routes::tags::TagSearchParams::decl(),
+        server::routes::execution_processes::replay::ReplayQuery::decl(),
+        server::routes::execution_processes::replay::ReplayCommand::decl(),
+        services::services::conversation_export::ConversationExportFormat::decl(),
+        server::routes::export::ConversationExportScope::decl(),
+        server::routes::export::ConversationExportQuery::decl(),
+        services::services::session_import::ExternalAgent::decl(),
+        services::services::session_import::ExternalSessionSummary::decl(),
+        server::routes::agent_sessions::ImportAgentSessionRequest::decl(),
+        server::routes::agent_sessions::ImportAgentSessionResponse::decl(),
+        server::routes::terminal::CreateTerminalSessionRequest::decl(),
+        server::routes::terminal::TerminalSession::decl(),
+        server::routes::task_attempts::commits::WorkspaceCommit::decl(),
+        server::routes::task_attempts::review_comments::SendReviewRequest::decl(),
         server::routes::oauth::TokenResponse::decl(),
         server::routes::config::UserSystemInfo::decl(),
         server::routes::config::Environment::decl(),
@@ -132,6 +165,8 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::AbortConflictsRequest::decl(),
         server::routes::task_attempts::GitOperationError::decl(),
         server::routes::task_attempts::PushError::decl(),
+        services::services::container::GateKind::decl(),
+        services::services::container::GateFailure::decl(),
         server::routes::task_attempts::pr::PrError::decl(),
         server::routes::task_attempts::RunScriptError::decl(),
         server::routes::task_attempts::pr::AttachPrResponse::decl(),
@@ -139,6 +174,16 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::pr::PrCommentsResponse::decl(),
         server::routes::task_attempts::pr::GetPrCommentsError::decl(),
         server::routes::task_attempts::pr::GetPrCommentsQuery::decl(),
+        server::routes::task_attempts::pr::ImportPrCommentsRequest::decl(),
+        server::routes::task_attempts::pr::ImportPrCommentsResponse::decl(),
+        server::routes::task_attempts::revert::RevertFileRequest::decl(),
+        server::routes::task_attempts::revert::RevertHunkRequest::decl(),
+        server::routes::task_attempts::revert::FileHunksQuery::decl(),
+        server::routes::task_attempts::revert::RevertResponse::decl(),
+        server::routes::task_attempts::revert::FileHunksResponse::decl(),
+        server::routes::task_attempts::backport::BackportConflictResolution::decl(),
+        server::routes::task_attempts::backport::BackportRequest::decl(),
+        server::routes::task_attempts::backport::BackportResponse::decl(),
         services::services::git_host::UnifiedPrComment::decl(),
         services::services::git_host::ProviderKind::decl(),
         server::routes::task_attempts::RepoBranchStatus::decl(),
@@ -150,6 +195,7 @@ fn generate_types_content() -> String {
         services::services::filesystem::DirectoryEntry::decl(),
         services::services::filesystem::DirectoryListResponse::decl(),
         services::services::file_search::SearchMode::decl(),
+        services::services::file_search::SearchKind::decl(),
         services::services::config::Config::decl(),
         services::services::config::NotificationConfig::decl(),
         services::services::config::ThemeMode::decl(),
@@ -161,6 +207,22 @@ fn generate_types_content() -> String {
         services::services::config::UiLanguage::decl(),
         services::services::config::ShowcaseState::decl(),
         services::services::config::SendMessageShortcut::decl(),
+        services::services::config::RemoteAccessConfig::decl(),
+        services::services::config::AutoBackupConfig::decl(),
+        services::services::config::UserConfigOverrides::decl(),
+        services::services::backup::BackupManifest::decl(),
+        services::services::backup::BackupKind::decl(),
+        services::services::backup::BackupFile::decl(),
+        server::routes::data_management::RestoreBackupResponse::decl(),
+        server::middleware::RemoteAccessRole::decl(),
+        server::routes::remote_access::RemoteAccessStatus::decl(),
+        server::routes::remote_access::RemoteAccessLoginRequest::decl(),
+        server::routes::remote_access::UpdateRemoteAccessRequest::decl(),
+        server::routes::remote_access::UpdateRemoteAccessResponse::decl(),
+        server::routes::remote_access::RemoteAccessTokenResponse::decl(),
+        server::routes::users::CreateUserRequest::decl(),
+        server::routes::users::UpdateUserRequest::decl(),
+        server::routes::worker_hosts::WorkerHostCheckResponse::decl(),
         services::services::git::GitBranch::decl(),
         services::services::queued_message::QueuedMessage::decl(),
         services::services::queued_message::QueueStatus::decl(),
@@ -6,22 +6,26 @@ use axum::{
 };
 use db::models::{
     execution_process::ExecutionProcessError, project::ProjectError,
-    project_repo::ProjectRepoError, repo::RepoError, scratch::ScratchError, session::SessionError,
+    project_repo::ProjectRepoError, repo::RepoError, review_comment::ReviewCommentError,
+    scratch::ScratchError, session::SessionError, user::UserError, worker_host::WorkerHostError,
     workspace::WorkspaceError,
 };
 use deployment::{DeploymentError, RemoteClientNotConfigured};
 use executors::{command::CommandBuildError, executors::ExecutorError};
 use git2::Error as Git2Error;
-use local_deployment::pty::PtyError;
+use local_deployment::{pty::PtyError, worker_host::WorkerSyncError};
 use services::services::{
+    backup::BackupError,
     config::{ConfigError, EditorOpenError},
-    container::ContainerError,
+    container::{ContainerError, GateFailure},
     git::GitServiceError,
     git_host::GitHostError,
     image::ImageError,
+    pr_comment_follow_ups::PrCommentFollowUpError,
     project::ProjectServiceError,
     remote_client::RemoteClientError,
     repo::RepoError as RepoServiceError,
+    session_import::SessionImportError,
     worktree_manager::WorktreeError,
 };
 use thiserror::Error;
@@ -80,6 +84,8 @@ pub enum ApiError {
     CommandBuilder(#[from] CommandBuildError),
     #[error(transparent)]
     Pty(#[from] PtyError),
+    #[error("{} gate failed for {}", .0.gate.label(), .0.repo_name)]
+    GateFailed(GateFailure),
 }
 
 impl From<&'static str> for ApiError {
@@ -102,6 +108,28 @@ impl From<RemoteClientNotConfigured> for ApiError {
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
+        // Gate failures carry the failing process so the UI can link to its logs
+        if let ApiError::GateFailed(failure) = &self {
+            let message = if failure.timed_out {
+                format!(
+                    "{} check for {} timed out and was stopped.",
+                    failure.gate.label(),
+                    failure.repo_name
+                )
+            } else {
+                format!(
+                    "{} check failed for {}. See the gate script logs for details.",
+                    failure.gate.label(),
+                    failure.repo_name
+                )
+            };
+            let response = ApiResponse::<(), GateFailure>::error_with_message_and_data(
+                &message,
+                failure.clone(),
+            );
+            return (StatusCode::PRECONDITION_FAILED, Json(response)).into_response();
+        }
+
         let (status_code, error_type) = match &self {
             ApiError::Project(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ProjectError"),
             ApiError::Repo(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ProjectRepoError"),
@@ -185,8 +213,10 @@ impl IntoResponse for ApiError {
             ApiError::Pty(err) => match err {
                 PtyError::SessionNotFound(_) => (StatusCode::NOT_FOUND, "PtyError"),
                 PtyError::SessionClosed => (StatusCode::GONE, "PtyError"),
+                PtyError::TooManySessions(_) => (StatusCode::CONFLICT, "PtyError"),
                 _ => (StatusCode::INTERNAL_SERVER_ERROR, "PtyError"),
             },
+            ApiError::GateFailed(_) => (StatusCode::PRECONDITION_FAILED, "GateFailed"),
         };
 
         let error_message = match &self {
@@ -277,6 +307,10 @@ impl From<ProjectServiceError> for ApiError {
         match err {
             ProjectServiceError::Database(db_err) => ApiError::Database(db_err),
             ProjectServiceError::Io(io_err) => ApiError::Io(io_err),
+            ProjectServiceError::Project(
+                proj_err @ (ProjectError::InvalidContainerImage(_)
+                | ProjectError::InvalidDevServerPort(_)),
+            ) => ApiError::BadRequest(proj_err.to_string()),
             ProjectServiceError::Project(proj_err) => ApiError::Project(proj_err),
             ProjectServiceError::PathNotFound(path) => {
                 ApiError::BadRequest(format!("Path does not exist: {}", path.display()))
@@ -330,6 +364,9 @@ impl From<RepoServiceError> for ApiError {
             RepoServiceError::InvalidFolderName(name) => {
                 ApiError::BadRequest(format!("Invalid folder name: {}", name))
             }
+            RepoServiceError::InvalidCloneFilter(filter) => {
+                ApiError::BadRequest(format!("Invalid partial clone filter: {}", filter))
+            }
         }
     }
 }
@@ -347,3 +384,73 @@ impl From<ProjectRepoError> for ApiError {
         }
     }
 }
+
+impl From<SessionImportError> for ApiError {
+    fn from(err: SessionImportError) -> Self {
+        match err {
+            SessionImportError::Io(io_err) => ApiError::Io(io_err),
+            other => ApiError::BadRequest(other.to_string()),
+        }
+    }
+}
+
+impl From<ReviewCommentError> for ApiError {
+    fn from(err: ReviewCommentError) -> Self {
+        match err {
+            ReviewCommentError::Database(db_err) => ApiError::Database(db_err),
+            ReviewCommentError::NotFound => {
+                ApiError::BadRequest("Review comment not found".to_string())
+            }
+        }
+    }
+}
+
+impl From<WorkerHostError> for ApiError {
+    fn from(err: WorkerHostError) -> Self {
+        match err {
+            WorkerHostError::Database(db_err) => ApiError::Database(db_err),
+            WorkerHostError::NotFound => ApiError::BadRequest("Worker host not found".to_string()),
+        }
+    }
+}
+
+impl From<UserError> for ApiError {
+    fn from(err: UserError) -> Self {
+        match err {
+            UserError::Database(db_err) => ApiError::Database(db_err),
+            UserError::NotFound => ApiError::BadRequest("User not found".to_string()),
+            UserError::UsernameTaken => ApiError::Conflict(err.to_string()),
+        }
+    }
+}
+
+impl From<BackupError> for ApiError {
+    fn from(err: BackupError) -> Self {
+        match err {
+            BackupError::Io(e) => ApiError::Io(e),
+            BackupError::Database(e) => ApiError::Database(e),
+            other => ApiError::BadRequest(other.to_string()),
+        }
+    }
+}
+
+impl From<WorkerSyncError> for ApiError {
+    fn from(err: WorkerSyncError) -> Self {
+        match err {
+            WorkerSyncError::Io(e) => ApiError::Io(e),
+            WorkerSyncError::Database(e) => ApiError::Database(e),
+            other => ApiError::BadRequest(other.to_string()),
+        }
+    }
+}
+
+impl From<PrCommentFollowUpError> for ApiError {
+    fn from(err: PrCommentFollowUpError) -> Self {
+        match err {
+            PrCommentFollowUpError::GitHost(e) => ApiError::GitHost(e),
+            PrCommentFollowUpError::GitService(e) => ApiError::GitService(e),
+            PrCommentFollowUpError::Database(e) => ApiError::Database(e),
+            other => ApiError::BadRequest(other.to_string()),
+        }
+    }
+}
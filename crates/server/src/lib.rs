@@ -3,6 +3,7 @@ pub mod mcp;
 pub mod middleware;
 pub mod routes;
 pub mod shutdown;
+pub mod tls;
 
 // #[cfg(feature = "cloud")]
 // type DeploymentImpl = vibe_kanban_cloud::deployment::CloudDeployment;
@@ -1,7 +1,9 @@
+use std::net::SocketAddr;
+
 use anyhow::{self, Error as AnyhowError};
 use deployment::{Deployment, DeploymentError};
-use server::{DeploymentImpl, routes, shutdown};
-use services::services::container::ContainerService;
+use server::{DeploymentImpl, routes, shutdown, tls::TlsListener};
+use services::services::{backup, container::ContainerService, remote_access::resolve_tls_files};
 use sqlx::Error as SqlxError;
 use strip_ansi_escapes::strip;
 use thiserror::Error;
@@ -11,8 +13,14 @@ use utils::{
     browser::open_browser,
     port_file::write_port_file,
     sentry::{self as sentry_utils, SentrySource, sentry_layer},
+    telemetry,
 };
 
+/// How often finished logs are compacted and raw log retention is applied
+const LOG_MAINTENANCE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+/// How often to check whether an automatic backup is due
+const BACKUP_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+
 #[derive(Debug, Error)]
 pub enum VibeKanbanError {
     #[error(transparent)]
@@ -39,10 +47,12 @@ async fn main() -> Result<(), VibeKanbanError> {
         "warn,server={level},services={level},db={level},executors={level},deployment={level},local_deployment={level},utils={level}",
         level = log_level
     );
-    let env_filter = EnvFilter::try_new(filter_string).expect("Failed to create tracing filter");
+    let env_filter = EnvFilter::try_new(&filter_string).expect("Failed to create tracing filter");
+    let otel_filter = EnvFilter::try_new(&filter_string).expect("Failed to create tracing filter");
     tracing_subscriber::registry()
         .with(tracing_subscriber::fmt::layer().with_filter(env_filter))
         .with(sentry_layer())
+        .with(telemetry::otel_layer().with_filter(otel_filter))
         .init();
 
     // Create asset directory if it doesn't exist
@@ -83,7 +93,76 @@ async fn main() -> Result<(), VibeKanbanError> {
         }
     });
 
-    let app_router = routes::router(deployment.clone());
+    // Index conversations of runs that predate conversation search
+    let deployment_for_search = deployment.clone();
+    tokio::spawn(async move {
+        match deployment_for_search
+            .container()
+            .backfill_conversation_index()
+            .await
+        {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("Indexed {} conversations for search", count),
+            Err(e) => tracing::warn!("Failed to backfill conversation search index: {}", e),
+        }
+    });
+
+    // Compact finished logs and apply raw log retention periodically
+    let deployment_for_logs = deployment.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(LOG_MAINTENANCE_INTERVAL);
+        loop {
+            interval.tick().await;
+            let retention_days = deployment_for_logs
+                .config()
+                .read()
+                .await
+                .raw_log_retention_days;
+            match deployment_for_logs
+                .container()
+                .run_log_maintenance(retention_days)
+                .await
+            {
+                Ok(summary) if summary.compacted > 0 || summary.purged > 0 => tracing::info!(
+                    "Log maintenance compacted {} and purged raw output of {} processes",
+                    summary.compacted,
+                    summary.purged
+                ),
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Log maintenance failed: {}", e),
+            }
+        }
+    });
+
+    // Take automatic backups when enabled and the newest one is older than the interval
+    let deployment_for_backups = deployment.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(BACKUP_CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            let config = deployment_for_backups
+                .config()
+                .read()
+                .await
+                .auto_backup
+                .clone();
+            match backup::run_scheduled_backup(
+                deployment_for_backups.db(),
+                deployment_for_backups.image().cache_dir(),
+                &config,
+            )
+            .await
+            {
+                Ok(Some(path)) => tracing::info!("Automatic backup written to {}", path.display()),
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Automatic backup failed: {}", e),
+            }
+        }
+    });
+
+    let app_router =
+        routes::router(deployment.clone()).into_make_service_with_connect_info::<SocketAddr>();
+    let remote_access = deployment.config().read().await.remote_access.clone();
 
     let port = std::env::var("BACKEND_PORT")
         .or_else(|_| std::env::var("PORT"))
@@ -94,12 +173,20 @@ async fn main() -> Result<(), VibeKanbanError> {
                 String::from_utf8(strip(s.as_bytes())).expect("UTF-8 after stripping ANSI");
             cleaned.trim().parse::<u16>().ok()
         })
+        .or(remote_access.port.filter(|_| remote_access.enabled))
         .unwrap_or_else(|| {
             tracing::info!("No PORT environment variable set, using port 0 for auto-assignment");
             0
         }); // Use 0 to find free port if no specific port provided
 
-    let host = std::env::var("HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+    // Remote access listens on all interfaces unless HOST says otherwise
+    let host = std::env::var("HOST").unwrap_or_else(|_| {
+        if remote_access.enabled {
+            "0.0.0.0".to_string()
+        } else {
+            "127.0.0.1".to_string()
+        }
+    });
     let listener = tokio::net::TcpListener::bind(format!("{host}:{port}")).await?;
     let actual_port = listener.local_addr()?.port(); // get → 53427 (example)
 
@@ -108,26 +195,46 @@ async fn main() -> Result<(), VibeKanbanError> {
         tracing::warn!("Failed to write port file: {}", e);
     }
 
-    tracing::info!("Server running on http://{host}:{actual_port}");
+    let scheme = if remote_access.enabled { "https" } else { "http" };
+    tracing::info!("Server running on {scheme}://{host}:{actual_port}");
 
     if !cfg!(debug_assertions) {
         tracing::info!("Opening browser...");
         tokio::spawn(async move {
-            if let Err(e) = open_browser(&format!("http://127.0.0.1:{actual_port}")).await {
+            if let Err(e) = open_browser(&format!("{scheme}://127.0.0.1:{actual_port}")).await {
                 tracing::warn!(
-                    "Failed to open browser automatically: {}. Please open http://127.0.0.1:{} manually.",
+                    "Failed to open browser automatically: {}. Please open {}://127.0.0.1:{} manually.",
                     e,
+                    scheme,
                     actual_port
                 );
             }
         });
     }
 
-    axum::serve(listener, app_router)
-        .with_graceful_shutdown(shutdown::shutdown_signal())
-        .await?;
+    if remote_access.enabled {
+        let hostname = std::env::var("HOSTNAME").unwrap_or_else(|_| "localhost".to_string());
+        let tls_files = resolve_tls_files(&remote_access, &hostname)
+            .await
+            .map_err(AnyhowError::from)?;
+        if remote_access.password_hash.is_none() {
+            tracing::warn!("Remote access is enabled without an admin password");
+        }
+        tracing::info!(
+            "Remote access enabled with certificate {}",
+            tls_files.cert_path.display()
+        );
+        axum::serve(TlsListener::new(listener, &tls_files)?, app_router)
+            .with_graceful_shutdown(shutdown::shutdown_signal())
+            .await?;
+    } else {
+        axum::serve(listener, app_router)
+            .with_graceful_shutdown(shutdown::shutdown_signal())
+            .await?;
+    }
 
     perform_cleanup_actions(&deployment).await;
+    telemetry::shutdown();
 
     Ok(())
 }
@@ -1,5 +1,7 @@
 pub mod model_loaders;
 pub mod origin;
+pub mod remote_access;
 
 pub use model_loaders::*;
 pub use origin::*;
+pub use remote_access::*;
@@ -0,0 +1,443 @@
+//! Authentication for remote access mode.
+//!
+//! Connections from the loopback interface are always trusted as admins, so the
+//! desktop experience is unchanged. Remote clients authenticate with a session
+//! cookie from `/remote-access/login`, which browsers also send on WebSocket
+//! upgrades, or with the bearer token. Put a reverse proxy in front only if it
+//! enforces its own authentication, since proxied requests arrive from loopback.
+//!
+//! Prometheus scrapes `/api/metrics` with a separate metrics token sent as a
+//! bearer token. It is accepted nowhere else, and the metrics stay readable by
+//! admins.
+//!
+//! People sharing a server sign in to their own user account, or are identified
+//! by the proxy through `trusted_user_header`, so their actions carry their name.
+
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{LazyLock, RwLock},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    body::Body,
+    extract::{ConnectInfo, FromRequestParts, Request, State},
+    http::{StatusCode, header, request::Parts},
+    middleware::Next,
+    response::Response,
+};
+use db::models::{
+    activity::Actor,
+    user::{User, UserRole},
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::{
+    config::{Config, EditorConfig, UserConfigOverrides},
+    remote_access::{generate_session_id, verify_token},
+};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::DeploymentImpl;
+
+pub const SESSION_COOKIE: &str = "vk_remote_session";
+pub const SESSION_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteAccessRole {
+    /// Logged in with the password, or connected from this machine
+    Admin,
+    /// Logged in with the bearer token
+    Member,
+}
+
+impl From<UserRole> for RemoteAccessRole {
+    fn from(role: UserRole) -> Self {
+        match role {
+            UserRole::Admin => RemoteAccessRole::Admin,
+            UserRole::Member => RemoteAccessRole::Member,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionOwner {
+    /// Logged in with a shared credential
+    Role(RemoteAccessRole),
+    /// Logged in to a user account, whose current role applies
+    User(Uuid),
+}
+
+/// Login sessions, kept in memory so a restart logs everyone out
+#[derive(Default)]
+pub struct RemoteAccessSessions {
+    sessions: RwLock<HashMap<String, (SessionOwner, Instant)>>,
+}
+
+impl RemoteAccessSessions {
+    pub fn create(&self, owner: SessionOwner) -> String {
+        let id = generate_session_id();
+        let mut sessions = self.sessions.write().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        sessions.retain(|_, (_, expires_at)| *expires_at > now);
+        sessions.insert(id.clone(), (owner, now + SESSION_TTL));
+        id
+    }
+
+    pub fn get(&self, key: &str) -> Option<SessionOwner> {
+        let sessions = self.sessions.read().unwrap_or_else(|e| e.into_inner());
+        sessions
+            .get(key)
+            .filter(|(_, expires_at)| *expires_at > Instant::now())
+            .map(|(owner, _)| *owner)
+    }
+
+    pub fn remove(&self, key: &str) {
+        let mut sessions = self.sessions.write().unwrap_or_else(|e| e.into_inner());
+        sessions.remove(key);
+    }
+
+    /// Log out everyone with `role`, after the credential granting it changed
+    pub fn clear_role(&self, role: RemoteAccessRole) {
+        self.clear_owner(SessionOwner::Role(role));
+    }
+
+    /// Log a user out everywhere, after their password changed or the account
+    /// was deleted
+    pub fn clear_user(&self, user_id: Uuid) {
+        self.clear_owner(SessionOwner::User(user_id));
+    }
+
+    fn clear_owner(&self, owner: SessionOwner) {
+        let mut sessions = self.sessions.write().unwrap_or_else(|e| e.into_inner());
+        sessions.retain(|_, (session_owner, _)| *session_owner != owner);
+    }
+}
+
+/// Who is making a request. Handlers outside `require_remote_auth` see the
+/// local admin.
+#[derive(Debug, Clone)]
+pub struct CurrentUser {
+    pub role: RemoteAccessRole,
+    /// None for the local user and for shared credentials
+    pub user: Option<User>,
+}
+
+impl CurrentUser {
+    fn local() -> Self {
+        Self {
+            role: RemoteAccessRole::Admin,
+            user: None,
+        }
+    }
+
+    fn from_user(user: User) -> Self {
+        Self {
+            role: user.role.into(),
+            user: Some(user),
+        }
+    }
+
+    /// Identity stamped on activity records
+    pub fn actor(&self) -> Actor {
+        match &self.user {
+            Some(user) => Actor {
+                user_id: Some(user.id),
+                name: user.name().to_string(),
+            },
+            None => Actor {
+                user_id: None,
+                name: match self.role {
+                    RemoteAccessRole::Admin => "local".to_string(),
+                    RemoteAccessRole::Member => "token".to_string(),
+                },
+            },
+        }
+    }
+
+    pub fn config_overrides(&self) -> UserConfigOverrides {
+        UserConfigOverrides::from_json(
+            self.user
+                .as_ref()
+                .and_then(|user| user.config_overrides.as_deref()),
+        )
+    }
+
+    /// The global config with this user's own settings applied
+    pub fn apply_overrides(&self, config: &mut Config) {
+        self.config_overrides().apply(config);
+    }
+
+    /// Editor to open files with, the user's own when they set one
+    pub fn editor(&self, config: &Config) -> EditorConfig {
+        self.config_overrides()
+            .editor
+            .unwrap_or_else(|| config.editor.clone())
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for CurrentUser {
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts
+            .extensions
+            .get::<CurrentUser>()
+            .cloned()
+            .unwrap_or_else(CurrentUser::local))
+    }
+}
+
+pub fn remote_access_sessions() -> &'static RemoteAccessSessions {
+    static SESSIONS: LazyLock<RemoteAccessSessions> = LazyLock::new(Default::default);
+    &SESSIONS
+}
+
+/// The request's client, or None when it has to log in first
+pub async fn resolve_user<B>(deployment: &DeploymentImpl, req: &Request<B>) -> Option<CurrentUser> {
+    let (enabled, token_hash, user_header) = {
+        let config = deployment.config().read().await;
+        (
+            config.remote_access.enabled,
+            config.remote_access.token_hash.clone(),
+            config.remote_access.trusted_user_header.clone(),
+        )
+    };
+    let pool = &deployment.db().pool;
+    let loopback = is_loopback(req);
+
+    if let Some(username) = proxy_username(req, user_header.as_deref()) {
+        return match User::find_or_create_by_username(pool, username).await {
+            Ok(user) => Some(CurrentUser::from_user(user)),
+            Err(e) => {
+                tracing::error!("Failed to load user {} from proxy header: {}", username, e);
+                None
+            }
+        };
+    }
+
+    let sessions = remote_access_sessions();
+    match session_cookie(req).and_then(|id| sessions.get(id)) {
+        Some(SessionOwner::User(user_id)) => match User::find_by_id(pool, user_id).await {
+            Ok(Some(user)) => return Some(CurrentUser::from_user(user)),
+            Ok(None) => {}
+            Err(e) => {
+                tracing::error!("Failed to load user {}: {}", user_id, e);
+                return None;
+            }
+        },
+        Some(SessionOwner::Role(role)) if enabled && !loopback => {
+            return Some(CurrentUser { role, user: None });
+        }
+        _ => {}
+    }
+
+    if !enabled || loopback {
+        return Some(CurrentUser::local());
+    }
+
+    let token = bearer_token(req)?;
+    token_hash
+        .is_some_and(|hash| verify_token(token, &hash))
+        .then_some(CurrentUser {
+            role: RemoteAccessRole::Member,
+            user: None,
+        })
+}
+
+/// Reject unauthenticated remote clients and record who the client is on the request
+pub async fn require_remote_auth(
+    State(deployment): State<DeploymentImpl>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    match resolve_user(&deployment, &req).await {
+        Some(user) => {
+            req.extensions_mut().insert(user.role);
+            req.extensions_mut().insert(user);
+            next.run(req).await
+        }
+        None => status_response(StatusCode::UNAUTHORIZED),
+    }
+}
+
+/// Restrict a route to admins; runs inside `require_remote_auth`
+pub async fn require_admin(req: Request, next: Next) -> Response {
+    match req.extensions().get::<RemoteAccessRole>() {
+        Some(RemoteAccessRole::Admin) => next.run(req).await,
+        _ => status_response(StatusCode::FORBIDDEN),
+    }
+}
+
+fn is_loopback<B>(req: &Request<B>) -> bool {
+    req.extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .is_some_and(|ConnectInfo(addr)| addr.ip().is_loopback())
+}
+
+pub fn session_cookie<B>(req: &Request<B>) -> Option<&str> {
+    req.headers()
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(name, _)| *name == SESSION_COOKIE)
+        .map(|(_, value)| value)
+}
+
+/// Username asserted by the trusted proxy header. Only connections from this
+/// machine, where the proxy runs, may assert one.
+fn proxy_username<'a, B>(req: &'a Request<B>, user_header: Option<&str>) -> Option<&'a str> {
+    if !is_loopback(req) {
+        return None;
+    }
+    header_value(req, user_header?)
+}
+
+fn header_value<'a, B>(req: &'a Request<B>, name: &str) -> Option<&'a str> {
+    req.headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+fn bearer_token<B>(req: &Request<B>) -> Option<&str> {
+    req.headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+}
+
+/// Whether the request carries the metrics token
+async fn has_metrics_token<B>(deployment: &DeploymentImpl, req: &Request<B>) -> bool {
+    let Some(token) = bearer_token(req) else {
+        return false;
+    };
+    let config = deployment.config().read().await;
+    config
+        .remote_access
+        .metrics_token_hash
+        .as_deref()
+        .is_some_and(|hash| verify_token(token, hash))
+}
+
+/// Who may read the metrics: scrapers with the metrics token, and admins
+fn metrics_access(scraper: bool, user: Option<&CurrentUser>) -> Result<(), StatusCode> {
+    if scraper {
+        return Ok(());
+    }
+    match user {
+        Some(user) if user.role == RemoteAccessRole::Admin => Ok(()),
+        Some(_) => Err(StatusCode::FORBIDDEN),
+        None => Err(StatusCode::UNAUTHORIZED),
+    }
+}
+
+/// Guard `/api/metrics`; runs instead of `require_remote_auth` so the metrics
+/// token does not have to be a login credential
+pub async fn require_metrics_access(
+    State(deployment): State<DeploymentImpl>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let scraper = has_metrics_token(&deployment, &req).await;
+    let user = if scraper {
+        None
+    } else {
+        resolve_user(&deployment, &req).await
+    };
+    match metrics_access(scraper, user.as_ref()) {
+        Ok(()) => next.run(req).await,
+        Err(status) => status_response(status),
+    }
+}
+
+fn status_response(status: StatusCode) -> Response {
+    Response::builder()
+        .status(status)
+        .body(Body::empty())
+        .unwrap_or_else(|_| Response::new(Body::empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_session_cookie_among_others() {
+        let req = Request::builder()
+            .header(header::COOKIE, "theme=dark; vk_remote_session=abc123")
+            .header(header::AUTHORIZATION, "Bearer vkr_token")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(session_cookie(&req), Some("abc123"));
+        assert_eq!(bearer_token(&req), Some("vkr_token"));
+
+        let sessions = RemoteAccessSessions::default();
+        let admin = SessionOwner::Role(RemoteAccessRole::Admin);
+        let admin_session = sessions.create(admin);
+        let member = sessions.create(SessionOwner::Role(RemoteAccessRole::Member));
+        let user_id = Uuid::new_v4();
+        let user = sessions.create(SessionOwner::User(user_id));
+        sessions.clear_role(RemoteAccessRole::Member);
+        sessions.clear_user(user_id);
+        assert_eq!(sessions.get(&admin_session), Some(admin));
+        assert_eq!(sessions.get(&member), None);
+        assert_eq!(sessions.get(&user), None);
+    }
+
+    fn request_from(ip: [u8; 4], user: Option<&str>) -> Request {
+        let mut builder = Request::builder();
+        if let Some(user) = user {
+            builder = builder.header("X-Forwarded-User", user);
+        }
+        let mut req = builder.body(Body::empty()).unwrap();
+        req.extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from((ip, 40000))));
+        req
+    }
+
+    #[test]
+    fn proxy_header_is_only_trusted_from_loopback() {
+        let header = Some("x-forwarded-user");
+        let local = request_from([127, 0, 0, 1], Some(" ada "));
+        assert_eq!(proxy_username(&local, header), Some("ada"));
+        assert_eq!(proxy_username(&local, None), None);
+        assert_eq!(proxy_username(&local, Some("x-remote-user")), None);
+
+        let remote = request_from([192, 168, 1, 20], Some("ada"));
+        assert_eq!(proxy_username(&remote, header), None);
+
+        let blank = request_from([127, 0, 0, 1], Some("  "));
+        assert_eq!(proxy_username(&blank, header), None);
+
+        let mut unknown_peer = request_from([127, 0, 0, 1], Some("ada"));
+        unknown_peer
+            .extensions_mut()
+            .remove::<ConnectInfo<SocketAddr>>();
+        assert_eq!(proxy_username(&unknown_peer, header), None);
+    }
+
+    #[test]
+    fn metrics_are_for_scrapers_and_admins() {
+        let admin = CurrentUser::local();
+        let member = CurrentUser {
+            role: RemoteAccessRole::Member,
+            user: None,
+        };
+        assert_eq!(metrics_access(true, None), Ok(()));
+        assert_eq!(metrics_access(false, Some(&admin)), Ok(()));
+        assert_eq!(
+            metrics_access(false, Some(&member)),
+            Err(StatusCode::FORBIDDEN)
+        );
+        assert_eq!(metrics_access(false, None), Err(StatusCode::UNAUTHORIZED));
+    }
+}
@@ -0,0 +1,99 @@
+use axum::{
+    Extension,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    activity::{Activity, ActivityAction},
+    project::Project,
+    task::Task,
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::CurrentUser};
+
+const DEFAULT_LIMIT: i64 = 100;
+const MAX_LIMIT: i64 = 500;
+
+#[derive(Debug, Deserialize)]
+pub struct ActivityQuery {
+    pub limit: Option<i64>,
+}
+
+impl ActivityQuery {
+    fn limit(&self) -> i64 {
+        self.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT)
+    }
+}
+
+/// What people did on a task, newest first
+pub async fn get_task_activity(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ActivityQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<Activity>>>, ApiError> {
+    let activity = Activity::find_by_task_id(&deployment.db().pool, task.id, query.limit()).await?;
+    Ok(ResponseJson(ApiResponse::success(activity)))
+}
+
+/// What people did across a project's tasks, newest first
+pub async fn get_project_activity(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ActivityQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<Activity>>>, ApiError> {
+    let activity =
+        Activity::find_by_project_id(&deployment.db().pool, project.id, query.limit()).await?;
+    Ok(ResponseJson(ApiResponse::success(activity)))
+}
+
+/// Record a task-level action for the feeds. Failing to record is logged
+/// rather than failing the action itself.
+pub async fn record_for_task(
+    deployment: &DeploymentImpl,
+    current: &CurrentUser,
+    task_id: Uuid,
+    action: ActivityAction,
+    details: Option<&str>,
+) {
+    if let Err(e) = Activity::record_for_task(
+        &deployment.db().pool,
+        &current.actor(),
+        task_id,
+        action,
+        details,
+    )
+    .await
+    {
+        tracing::warn!("Failed to record {:?} on task {}: {}", action, task_id, e);
+    }
+}
+
+/// Record a workspace-level action for the feeds, like [`record_for_task`]
+pub async fn record_for_workspace(
+    deployment: &DeploymentImpl,
+    current: &CurrentUser,
+    workspace_id: Uuid,
+    action: ActivityAction,
+    details: Option<&str>,
+) {
+    if let Err(e) = Activity::record_for_workspace(
+        &deployment.db().pool,
+        &current.actor(),
+        workspace_id,
+        action,
+        details,
+    )
+    .await
+    {
+        tracing::warn!(
+            "Failed to record {:?} on workspace {}: {}",
+            action,
+            workspace_id,
+            e
+        );
+    }
+}
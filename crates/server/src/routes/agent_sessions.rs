@@ -0,0 +1,181 @@
+use std::path::PathBuf;
+
+use axum::{
+    Json, Router,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::{
+    project::{Project, ProjectError},
+    repo::{Repo, RepoError},
+    session::{CreateSession, Session},
+    task::{CreateTask, Task, TaskStatus},
+    workspace::{CreateWorkspace, Workspace},
+    workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::{
+    container::ContainerService,
+    session_import::{self, ExternalAgent, ExternalSessionSummary},
+};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, routes::task_attempts::WorkspaceRepoInput};
+
+#[derive(Debug, Deserialize)]
+pub struct AgentSessionsQuery {
+    pub agent: ExternalAgent,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ImportAgentSessionRequest {
+    pub agent: ExternalAgent,
+    /// Session file path as returned by the session listing
+    pub path: String,
+    pub project_id: Uuid,
+    pub repos: Vec<WorkspaceRepoInput>,
+    /// Task title, defaults to the session's first prompt
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ImportAgentSessionResponse {
+    pub task_id: Uuid,
+    pub workspace_id: Uuid,
+    pub session_id: Uuid,
+    pub execution_process_id: Uuid,
+}
+
+pub async fn list_agent_sessions(
+    Query(query): Query<AgentSessionsQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<ExternalSessionSummary>>>, ApiError> {
+    let sessions =
+        tokio::task::spawn_blocking(move || session_import::discover_sessions(query.agent))
+            .await
+            .map_err(|e| ApiError::BadRequest(format!("Failed to list sessions: {e}")))??;
+    Ok(ResponseJson(ApiResponse::success(sessions)))
+}
+
+pub async fn import_agent_session(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ImportAgentSessionRequest>,
+) -> Result<ResponseJson<ApiResponse<ImportAgentSessionResponse>>, ApiError> {
+    if payload.repos.is_empty() {
+        return Err(ApiError::BadRequest(
+            "At least one repository is required".to_string(),
+        ));
+    }
+
+    let pool = &deployment.db().pool;
+    Project::find_by_id(pool, payload.project_id)
+        .await?
+        .ok_or(ProjectError::ProjectNotFound)?;
+
+    let agent = payload.agent;
+    let path = PathBuf::from(&payload.path);
+    let external = tokio::task::spawn_blocking(move || session_import::read_session(agent, &path))
+        .await
+        .map_err(|e| ApiError::BadRequest(format!("Failed to read session: {e}")))??;
+
+    let title = payload
+        .title
+        .filter(|title| !title.trim().is_empty())
+        .or_else(|| external.first_prompt.clone())
+        .unwrap_or_else(|| format!("Imported {agent} session"));
+    let task = Task::create(
+        pool,
+        &CreateTask::from_title_description(
+            payload.project_id,
+            title,
+            Some(format!(
+                "Imported from {agent} session {}",
+                external.agent_session_id
+            )),
+        ),
+        Uuid::new_v4(),
+    )
+    .await?;
+
+    // Same working dir rules as a regular attempt, so follow-ups resume in the right place
+    let agent_working_dir = if payload.repos.len() == 1 {
+        let repo = Repo::find_by_id(pool, payload.repos[0].repo_id)
+            .await?
+            .ok_or(RepoError::NotFound)?;
+        Some(repo.name)
+    } else {
+        None
+    };
+
+    let workspace_id = Uuid::new_v4();
+    let branch = deployment
+        .container()
+        .git_branch_from_workspace(&workspace_id, &task.title)
+        .await;
+    let workspace = Workspace::create(
+        pool,
+        &CreateWorkspace {
+            branch,
+            agent_working_dir,
+        },
+        workspace_id,
+        task.id,
+    )
+    .await?;
+
+    let workspace_repos: Vec<CreateWorkspaceRepo> = payload
+        .repos
+        .iter()
+        .map(|r| CreateWorkspaceRepo {
+            repo_id: r.repo_id,
+            target_branch: r.target_branch.clone(),
+        })
+        .collect();
+    WorkspaceRepo::create_many(pool, workspace.id, &workspace_repos).await?;
+
+    let session = Session::create(
+        pool,
+        &CreateSession {
+            executor: Some(agent.base_coding_agent().to_string()),
+        },
+        Uuid::new_v4(),
+        workspace.id,
+    )
+    .await?;
+
+    let execution_process = deployment
+        .container()
+        .import_external_session(&workspace, &session, external)
+        .await?;
+    Task::update_status(pool, task.id, TaskStatus::InReview).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "agent_session_imported",
+            serde_json::json!({
+                "agent": agent.to_string(),
+                "task_id": task.id.to_string(),
+                "workspace_id": workspace.id.to_string(),
+                "repository_count": payload.repos.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(
+        ImportAgentSessionResponse {
+            task_id: task.id,
+            workspace_id: workspace.id,
+            session_id: session.id,
+            execution_process_id: execution_process.id,
+        },
+    )))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/agent-sessions", get(list_agent_sessions))
+        .route("/agent-sessions/import", post(import_agent_session))
+}
@@ -5,16 +5,32 @@ use axum::{
     response::Json as ResponseJson,
     routing::post,
 };
+use db::models::{activity::ActivityAction, execution_process::ExecutionProcess, session::Session};
 use deployment::Deployment;
 use utils::{
     approvals::{ApprovalResponse, ApprovalStatus},
     response::ApiResponse,
 };
+use uuid::Uuid;
 
-use crate::DeploymentImpl;
+use crate::{DeploymentImpl, middleware::CurrentUser, routes::activity};
+
+async fn workspace_for_process(
+    deployment: &DeploymentImpl,
+    execution_process_id: Uuid,
+) -> Result<Option<Uuid>, sqlx::Error> {
+    let pool = &deployment.db().pool;
+    let Some(process) = ExecutionProcess::find_by_id(pool, execution_process_id).await? else {
+        return Ok(None);
+    };
+    Ok(Session::find_by_id(pool, process.session_id)
+        .await?
+        .map(|session| session.workspace_id))
+}
 
 pub async fn respond_to_approval(
     State(deployment): State<DeploymentImpl>,
+    current: CurrentUser,
     Path(id): Path<String>,
     ResponseJson(request): ResponseJson<ApprovalResponse>,
 ) -> Result<ResponseJson<ApiResponse<ApprovalStatus>>, StatusCode> {
@@ -34,6 +50,22 @@ pub async fn respond_to_approval(
                 )
                 .await;
 
+            match workspace_for_process(&deployment, context.execution_process_id).await {
+                Ok(Some(workspace_id)) => {
+                    let details = format!("{}: {:?}", context.tool_name, status);
+                    activity::record_for_workspace(
+                        &deployment,
+                        &current,
+                        workspace_id,
+                        ActivityAction::ApprovalResponded,
+                        Some(&details),
+                    )
+                    .await;
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Failed to find workspace for approval {}: {}", id, e),
+            }
+
             Ok(ResponseJson(ApiResponse::success(status)))
         }
         Err(e) => {
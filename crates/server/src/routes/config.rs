@@ -11,6 +11,7 @@ use axum::{
     response::{IntoResponse, Json as ResponseJson, Response},
     routing::{get, put},
 };
+use db::models::user::User;
 use deployment::{Deployment, DeploymentError};
 use executors::{
     executors::{
@@ -23,7 +24,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use services::services::{
     config::{
-        Config, ConfigError, SoundFile,
+        Config, ConfigError, SoundFile, UserConfigOverrides,
         editor::{EditorConfig, EditorType},
         save_config_to_file,
     },
@@ -36,7 +37,7 @@ use utils::assets::{custom_path_config_file, load_custom_path_config};
 use utils::log_msg::LogMsg;
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError};
+use crate::{DeploymentImpl, error::ApiError, middleware::CurrentUser};
 
 pub fn router() -> Router<DeploymentImpl> {
     Router::new()
@@ -90,7 +91,10 @@ impl Environment {
 
 #[derive(Debug, Serialize, Deserialize, TS)]
 pub struct UserSystemInfo {
+    /// The global config with the signed-in user's own settings applied
     pub config: Config,
+    /// Account the client is signed in to, if any
+    pub user: Option<User>,
     pub analytics_user_id: String,
     pub login_status: LoginStatus,
     #[serde(flatten)]
@@ -104,12 +108,18 @@ pub struct UserSystemInfo {
 #[axum::debug_handler]
 async fn get_user_system_info(
     State(deployment): State<DeploymentImpl>,
+    current: CurrentUser,
 ) -> ResponseJson<ApiResponse<UserSystemInfo>> {
     let config = deployment.config().read().await;
     let login_status = deployment.get_login_status().await;
 
+    let mut config = config.clone();
+    config.remote_access = config.remote_access.without_secrets();
+    current.apply_overrides(&mut config);
+
     let user_system_info = UserSystemInfo {
-        config: config.clone(),
+        config,
+        user: current.user,
         analytics_user_id: deployment.user_id().to_string(),
         login_status,
         profiles: ExecutorConfigs::get_cached(),
@@ -131,6 +141,7 @@ async fn get_user_system_info(
 
 async fn update_config(
     State(deployment): State<DeploymentImpl>,
+    current: CurrentUser,
     Json(new_config): Json<Config>,
 ) -> ResponseJson<ApiResponse<Config>> {
     let config_path = config_path();
@@ -144,6 +155,39 @@ async fn update_config(
 
     // Get old config state before updating
     let old_config = deployment.config().read().await.clone();
+    // Remote access is managed through its own admin-only endpoints
+    let mut new_config = Config {
+        remote_access: old_config.remote_access.clone(),
+        ..new_config
+    };
+
+    // A signed-in user's editor, language and sounds are their own
+    let overrides = match &current.user {
+        Some(user) => {
+            let overrides = UserConfigOverrides {
+                editor: Some(new_config.editor.clone()),
+                language: Some(new_config.language.clone()),
+                notifications: Some(new_config.notifications.clone()),
+            };
+            let saved = match serde_json::to_string(&overrides) {
+                Ok(raw) => User::set_config_overrides(&deployment.db().pool, user.id, Some(&raw))
+                    .await
+                    .map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            if let Err(e) = saved {
+                return ResponseJson(ApiResponse::error(&format!(
+                    "Failed to save personal settings: {}",
+                    e
+                )));
+            }
+            new_config.editor = old_config.editor.clone();
+            new_config.language = old_config.language.clone();
+            new_config.notifications = old_config.notifications.clone();
+            Some(overrides)
+        }
+        None => None,
+    };
 
     match save_config_to_file(&new_config, &config_path).await {
         Ok(_) => {
@@ -154,6 +198,10 @@ async fn update_config(
             // Track config events when fields transition from false → true and run side effects
             handle_config_events(&deployment, &old_config, &new_config).await;
 
+            if let Some(overrides) = overrides {
+                overrides.apply(&mut new_config);
+            }
+            new_config.remote_access = new_config.remote_access.without_secrets();
             ResponseJson(ApiResponse::success(new_config))
         }
         Err(e) => ResponseJson(ApiResponse::error(&format!("Failed to save config: {}", e))),
@@ -6,9 +6,15 @@ use ts_rs::TS;
 use uuid::Uuid;
 use std::path::PathBuf;
 use std::io::{Cursor, Read, Write};
+use tokio::io::AsyncWriteExt;
 
-use crate::{DeploymentImpl, error::ApiError};
+use crate::{DeploymentImpl, error::ApiError, middleware::require_admin};
+use axum::{extract::{DefaultBodyLimit, Path}, middleware::from_fn};
+use db::models::execution_process::ExecutionProcess;
+use db::models::execution_process_logs::{ExecutionProcessLogs, LogStorageStats};
 use deployment::Deployment;
+use services::services::backup::{self, BackupFile, BackupKind, BackupManifest};
+use services::services::repo_validator::RepoValidationInfo;
 use utils::response::ApiResponse;
 
 // ===== Session Statistics =====
@@ -894,6 +900,160 @@ pub async fn switch_config_source(
     })))
 }
 
+// ===== Log Storage =====
+
+/// Report log storage usage and the space reclaimable by compaction and retention
+pub async fn get_log_storage_stats(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<Json<ApiResponse<LogStorageStats>>, ApiError> {
+    let retention_days = deployment.config().read().await.raw_log_retention_days;
+    let stats = ExecutionProcessLogs::storage_stats(&deployment.db().pool, retention_days).await?;
+    Ok(Json(ApiResponse::success(stats)))
+}
+
+// ===== Backup/Restore =====
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
+pub struct RestoreBackupQuery {
+    /// Directory the backed-up repositories lived under, replaced by `relink_to`
+    pub relink_from: Option<String>,
+    pub relink_to: Option<String>,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct RestoreBackupResponse {
+    pub manifest: BackupManifest,
+    /// Status of every repository after re-linking, so broken paths can be fixed
+    pub repos: Vec<RepoValidationInfo>,
+}
+
+fn zip_attachment(filename: &str, bytes: Vec<u8>) -> Result<Response, ApiError> {
+    let headers = HeaderMap::from_iter([
+        (
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/zip"),
+        ),
+        (
+            header::CONTENT_DISPOSITION,
+            HeaderValue::from_str(&format!("attachment; filename=\"{}\"", filename))
+                .map_err(|e| ApiError::BadRequest(format!("Invalid header value: {}", e)))?,
+        ),
+    ]);
+    Ok((headers, bytes).into_response())
+}
+
+/// Create a full backup, keep it in the backups directory and download it
+pub async fn create_backup(State(deployment): State<DeploymentImpl>) -> Result<Response, ApiError> {
+    let path = backup::create_backup(
+        deployment.db(),
+        deployment.image().cache_dir(),
+        BackupKind::Manual,
+    )
+    .await?;
+    let filename = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("vibe-kanban-backup.zip")
+        .to_string();
+    let bytes = tokio::fs::read(&path).await?;
+    zip_attachment(&filename, bytes)
+}
+
+/// List stored backups, newest first
+pub async fn list_backups(
+    State(_deployment): State<DeploymentImpl>,
+) -> Result<Json<ApiResponse<Vec<BackupFile>>>, ApiError> {
+    Ok(Json(ApiResponse::success(backup::list_backups()?)))
+}
+
+/// Download a stored backup
+pub async fn download_backup(
+    State(_deployment): State<DeploymentImpl>,
+    Path(name): Path<String>,
+) -> Result<Response, ApiError> {
+    let path = backup::backup_path(&name)?;
+    let bytes = tokio::fs::read(&path).await?;
+    zip_attachment(&name, bytes)
+}
+
+/// Restore a backup archive uploaded as `backup_file`. A backup of the current
+/// state is taken first, so a restore can itself be undone.
+pub async fn restore_backup(
+    State(deployment): State<DeploymentImpl>,
+    Query(params): Query<RestoreBackupQuery>,
+    multipart: axum::extract::Multipart,
+) -> Result<Json<ApiResponse<RestoreBackupResponse>>, ApiError> {
+    if !ExecutionProcess::find_running(&deployment.db().pool)
+        .await?
+        .is_empty()
+    {
+        return Err(ApiError::Conflict(
+            "Stop running processes before restoring a backup".to_string(),
+        ));
+    }
+
+    let backups_dir = backup::backups_dir();
+    tokio::fs::create_dir_all(&backups_dir).await?;
+    let upload = backups_dir.join(format!(".upload-{}.zip", Uuid::new_v4()));
+    let result = restore_upload(&deployment, &upload, params, multipart).await;
+    let _ = tokio::fs::remove_file(&upload).await;
+
+    Ok(Json(ApiResponse::success(result?)))
+}
+
+async fn restore_upload(
+    deployment: &DeploymentImpl,
+    upload: &std::path::Path,
+    params: RestoreBackupQuery,
+    mut multipart: axum::extract::Multipart,
+) -> Result<RestoreBackupResponse, ApiError> {
+    let mut received = false;
+    while let Some(mut field) = multipart.next_field().await? {
+        if field.name() != Some("backup_file") {
+            continue;
+        }
+        let mut file = tokio::fs::File::create(upload).await?;
+        while let Some(chunk) = field.chunk().await? {
+            file.write_all(&chunk).await?;
+        }
+        file.flush().await?;
+        received = true;
+    }
+    if !received {
+        return Err(ApiError::BadRequest("Missing backup_file".to_string()));
+    }
+    // Reject archives this version can't restore before touching anything
+    backup::read_manifest(upload)?;
+
+    let safety = backup::create_backup(
+        deployment.db(),
+        deployment.image().cache_dir(),
+        BackupKind::Safety,
+    )
+    .await?;
+    tracing::info!(
+        "Backed up current state to {} before restoring",
+        safety.display()
+    );
+
+    let manifest =
+        backup::restore_backup(deployment.db(), deployment.image().cache_dir(), upload).await?;
+    *deployment.config().write().await =
+        services::services::config::load_config_from_file(&utils::assets::config_path()).await;
+    executors::profile::ExecutorConfigs::reload();
+
+    let relink_from = params.relink_from.as_deref().map(std::path::Path::new);
+    let relink_to = params.relink_to.as_deref().map(std::path::Path::new);
+    let repos = backup::relink_repos(&deployment.db().pool, relink_from, relink_to).await?;
+    tracing::info!(
+        "Restored backup made by Vibe Kanban {}",
+        manifest.app_version
+    );
+    Ok(RestoreBackupResponse { manifest, repos })
+}
+
 pub fn router() -> Router<DeploymentImpl> {
     Router::new()
         .route("/sessions/stats", axum::routing::get(get_session_stats))
@@ -907,4 +1067,18 @@ pub fn router() -> Router<DeploymentImpl> {
         .route("/reload", axum::routing::post(reload_config))
         .route("/export/config", axum::routing::get(export_config))
         .route("/import/config", axum::routing::post(import_config))
+        .route("/logs/storage", axum::routing::get(get_log_storage_stats))
+        .merge(backup_router())
+}
+
+fn backup_router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/backup", axum::routing::post(create_backup))
+        .route("/backups", axum::routing::get(list_backups))
+        .route("/backups/{name}", axum::routing::get(download_backup))
+        .route(
+            "/restore",
+            axum::routing::post(restore_backup).layer(DefaultBodyLimit::disable()),
+        )
+        .layer(from_fn(require_admin))
 }
@@ -1,3 +1,5 @@
+pub mod replay;
+
 use anyhow;
 use axum::{
     Extension, Router,
@@ -250,6 +252,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/repo-states", get(get_execution_process_repo_states))
         .route("/raw-logs/ws", get(stream_raw_logs_ws))
         .route("/normalized-logs/ws", get(stream_normalized_logs_ws))
+        .route("/replay/ws", get(replay::replay_execution_ws))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_execution_process_middleware,
@@ -0,0 +1,693 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use axum::{
+    Extension,
+    extract::{
+        Query, State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    response::IntoResponse,
+};
+use chrono::{DateTime, Utc};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessError, ExecutionProcessStatus},
+    execution_process_logs::ExecutionProcessLogs,
+    execution_process_repo_state::ExecutionProcessRepoState,
+    repo::Repo,
+    workspace::Workspace,
+    workspace_repo::WorkspaceRepo,
+};
+use deployment::Deployment;
+use executors::logs::{
+    ActionType, NormalizedEntryType,
+    utils::patch::{ConversationPatch, extract_normalized_entry_from_patch},
+};
+use futures_util::{SinkExt, StreamExt, stream::BoxStream};
+use json_patch::Patch;
+use serde::Deserialize;
+use serde_json::json;
+use services::services::{container::ContainerService, git::DiffTarget};
+use tokio::time::Instant;
+use ts_rs::TS;
+use utils::{diff::Diff, log_msg::LogMsg, msg_store::MsgStore};
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Longest pause replayed between two log lines; longer idle periods (e.g. waiting
+/// for an approval) are shortened to this
+const MAX_REPLAY_GAP: Duration = Duration::from_secs(5);
+const MIN_REPLAY_SPEED: f64 = 0.1;
+const MAX_REPLAY_SPEED: f64 = 100.0;
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ReplayQuery {
+    /// Playback speed multiplier; 1.0 replays at the original pace
+    pub speed: Option<f64>,
+    /// Fast-forward to this entry index before playing at normal speed
+    pub entry_index: Option<usize>,
+}
+
+/// Controls a client can send over the replay socket
+#[derive(Debug, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReplayCommand {
+    Seek { entry_index: usize },
+    Speed { speed: f64 },
+    Pause,
+    Resume,
+}
+
+pub async fn replay_execution_ws(
+    ws: WebSocketUpgrade,
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ReplayQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    if execution_process.status == ExecutionProcessStatus::Running {
+        return Err(ApiError::BadRequest(
+            "Cannot replay an execution that is still running".to_string(),
+        ));
+    }
+
+    let speed = match query.speed {
+        Some(speed) => replay_speed(speed).ok_or_else(|| {
+            ApiError::BadRequest("Replay speed must be a finite number".to_string())
+        })?,
+        None => 1.0,
+    };
+
+    let pool = &deployment.db().pool;
+    let records = ExecutionProcessLogs::find_by_execution_id(pool, execution_process.id).await?;
+    let frames = ExecutionProcessLogs::parse_timed_logs(&records)
+        .map_err(|e| ApiError::BadRequest(format!("Failed to parse stored logs: {e}")))?;
+    if frames.is_empty() {
+        return Err(ApiError::BadRequest(
+            "No logs were recorded for this execution".to_string(),
+        ));
+    }
+
+    let (workspace, _session) = execution_process
+        .parent_workspace_and_session(pool)
+        .await?
+        .ok_or(ExecutionProcessError::ExecutionProcessNotFound)?;
+    let diffs = FileEditDiffs::load(&deployment, &execution_process, &workspace).await?;
+
+    let state = PlaybackState::start(&deployment, &execution_process, &workspace, &frames);
+    let replay = Replay {
+        deployment,
+        process: execution_process,
+        workspace,
+        frames,
+        seek_target: query.entry_index,
+        diffs,
+        state,
+        clock: PlaybackClock::new(speed),
+    };
+
+    Ok(ws.on_upgrade(move |socket| async move {
+        if let Err(e) = replay.run(socket).await {
+            tracing::warn!("replay WS closed: {}", e);
+        }
+    }))
+}
+
+/// Clamp a requested playback speed, rejecting NaN and infinities
+fn replay_speed(speed: f64) -> Option<f64> {
+    speed
+        .is_finite()
+        .then(|| speed.clamp(MIN_REPLAY_SPEED, MAX_REPLAY_SPEED))
+}
+
+/// Pause between the frame at `position` and the next one before speed is
+/// applied, or None after the last frame
+fn frame_gap(frames: &[(DateTime<Utc>, LogMsg)], position: usize) -> Option<Duration> {
+    let (at, _) = frames.get(position)?;
+    let (next_at, _) = frames.get(position + 1)?;
+    Some(
+        (*next_at - *at)
+            .to_std()
+            .unwrap_or_default()
+            .min(MAX_REPLAY_GAP),
+    )
+}
+
+/// When the next frame is due, given the playback speed and pauses
+struct PlaybackClock {
+    speed: f64,
+    paused: bool,
+    next_due: Instant,
+    paused_remaining: Duration,
+}
+
+impl PlaybackClock {
+    fn new(speed: f64) -> Self {
+        Self {
+            speed,
+            paused: false,
+            next_due: Instant::now(),
+            paused_remaining: Duration::ZERO,
+        }
+    }
+
+    /// Make the next frame due right away
+    fn due_now(&mut self) {
+        self.next_due = Instant::now();
+    }
+
+    /// Make the next frame due after `gap` of original time
+    fn schedule(&mut self, gap: Duration) {
+        self.next_due = Instant::now() + gap.div_f64(self.speed);
+    }
+
+    /// Change speed, stretching or shrinking the wait for the pending frame
+    fn set_speed(&mut self, speed: f64) {
+        let remaining = self.next_due.saturating_duration_since(Instant::now());
+        self.next_due = Instant::now() + remaining.mul_f64(self.speed / speed);
+        self.speed = speed;
+    }
+
+    fn pause(&mut self) {
+        if !self.paused {
+            self.paused = true;
+            self.paused_remaining = self.next_due.saturating_duration_since(Instant::now());
+        }
+    }
+
+    fn resume(&mut self) {
+        if self.paused {
+            self.paused = false;
+            self.next_due = Instant::now() + self.paused_remaining;
+        }
+    }
+}
+
+/// How a seek to an entry is carried out
+#[derive(Debug, PartialEq, Eq)]
+enum SeekPlan {
+    /// The entry was already played; start over and fast-forward to it
+    Restart,
+    /// Fast-forward from the current position
+    FastForward,
+    /// Playback already reached the end without producing the entry
+    Finished,
+}
+
+fn seek_plan(last_entry_index: Option<usize>, done: bool, entry_index: usize) -> SeekPlan {
+    if last_entry_index.is_some_and(|last| entry_index <= last) {
+        SeekPlan::Restart
+    } else if done {
+        SeekPlan::Finished
+    } else {
+        SeekPlan::FastForward
+    }
+}
+
+/// One pass over the stored frames: a fresh message store with the executor's
+/// normalizer attached, fed frame by frame
+struct PlaybackState {
+    store: Option<Arc<MsgStore>>,
+    patches: BoxStream<'static, Result<LogMsg, std::io::Error>>,
+    normalized: bool,
+    effective_dir: Option<PathBuf>,
+    position: usize,
+    raw_entry_count: usize,
+    last_entry_index: Option<usize>,
+    diffed_entries: HashSet<usize>,
+    done: bool,
+}
+
+struct Replay {
+    deployment: DeploymentImpl,
+    process: ExecutionProcess,
+    workspace: Workspace,
+    frames: Vec<(DateTime<Utc>, LogMsg)>,
+    seek_target: Option<usize>,
+    diffs: FileEditDiffs,
+    state: PlaybackState,
+    clock: PlaybackClock,
+}
+
+impl PlaybackState {
+    /// Start playback from the first frame on a fresh store
+    fn start(
+        deployment: &DeploymentImpl,
+        process: &ExecutionProcess,
+        workspace: &Workspace,
+        frames: &[(DateTime<Utc>, LogMsg)],
+    ) -> Self {
+        let store = Arc::new(MsgStore::new());
+        let patches = store
+            .history_plus_stream()
+            .filter(|msg| futures_util::future::ready(matches!(msg, Ok(LogMsg::JsonPatch(_)))))
+            .boxed();
+        let has_raw_output = frames
+            .iter()
+            .any(|(_, msg)| matches!(msg, LogMsg::Stdout(_) | LogMsg::Stderr(_)));
+        let effective_dir = if has_raw_output {
+            deployment
+                .container()
+                .spawn_log_normalizer(process, workspace, store.clone())
+        } else {
+            None
+        };
+
+        Self {
+            store: Some(store),
+            patches,
+            normalized: effective_dir.is_some(),
+            effective_dir,
+            position: 0,
+            raw_entry_count: 0,
+            last_entry_index: None,
+            diffed_entries: HashSet::new(),
+            done: false,
+        }
+    }
+}
+
+impl Replay {
+    async fn run(mut self, socket: WebSocket) -> anyhow::Result<()> {
+        let (mut sender, mut receiver) = socket.split();
+        sender
+            .send(LogMsg::JsonPatch(reset_patch()).to_ws_message_unchecked())
+            .await?;
+
+        loop {
+            let state = &mut self.state;
+            let advance = state.position < self.frames.len()
+                && (!self.clock.paused || self.seek_target.is_some());
+            let streaming = !state.done;
+            let due = if self.seek_target.is_some() {
+                Instant::now()
+            } else {
+                self.clock.next_due
+            };
+
+            tokio::select! {
+                biased;
+
+                item = state.patches.next(), if streaming => match item {
+                    Some(Ok(LogMsg::JsonPatch(patch))) => {
+                        self.forward_patch(&mut sender, patch).await?;
+                    }
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => return Err(e.into()),
+                    None => {
+                        state.done = true;
+                        self.seek_target = None;
+                        sender.send(LogMsg::Finished.to_ws_message_unchecked()).await?;
+                    }
+                },
+                message = receiver.next() => match message {
+                    Some(Ok(Message::Text(text))) => {
+                        if let Ok(command) = serde_json::from_str::<ReplayCommand>(&text) {
+                            self.apply(command, &mut sender).await?;
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => {}
+                },
+                _ = tokio::time::sleep_until(due), if advance => {
+                    self.push_next_frame();
+                    if self.seek_target.is_some() {
+                        // Give the normalizer a chance to catch up so seeks stop close to the target
+                        tokio::task::yield_now().await;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Start playback over from the first frame on a fresh store
+    fn restart(&mut self) {
+        self.state = PlaybackState::start(
+            &self.deployment,
+            &self.process,
+            &self.workspace,
+            &self.frames,
+        );
+        self.clock.due_now();
+    }
+
+    fn push_next_frame(&mut self) {
+        let state = &mut self.state;
+        let Some(store) = state.store.as_ref() else {
+            return;
+        };
+        let (_, msg) = &self.frames[state.position];
+        match msg {
+            LogMsg::Stdout(_) | LogMsg::Stderr(_) if state.normalized => store.push(msg.clone()),
+            // Without a normalizer, raw output is shown the way the raw logs stream shows it
+            LogMsg::Stdout(content) => {
+                store.push_patch(ConversationPatch::add_stdout(
+                    state.raw_entry_count,
+                    content.clone(),
+                ));
+                state.raw_entry_count += 1;
+            }
+            LogMsg::Stderr(content) => {
+                store.push_patch(ConversationPatch::add_stderr(
+                    state.raw_entry_count,
+                    content.clone(),
+                ));
+                state.raw_entry_count += 1;
+            }
+            LogMsg::JsonPatch(_) => store.push(msg.clone()),
+            LogMsg::SessionId(_) | LogMsg::Ready | LogMsg::Finished => {}
+        }
+        match frame_gap(&self.frames, state.position) {
+            Some(gap) => self.clock.schedule(gap),
+            None => {
+                // Let the patch stream end once the normalizer has drained the store
+                if let Some(store) = state.store.take() {
+                    store.push_finished();
+                }
+            }
+        }
+        state.position += 1;
+    }
+
+    async fn forward_patch(
+        &mut self,
+        sender: &mut (impl SinkExt<Message, Error = axum::Error> + Unpin),
+        patch: Patch,
+    ) -> anyhow::Result<()> {
+        sender
+            .send(LogMsg::JsonPatch(patch.clone()).to_ws_message_unchecked())
+            .await?;
+
+        let state = &mut self.state;
+        if let Some(index) = entry_index_of(&patch) {
+            state.last_entry_index = state.last_entry_index.max(Some(index));
+            if self
+                .seek_target
+                .is_some_and(|target| state.last_entry_index >= Some(target))
+            {
+                self.seek_target = None;
+                self.clock.due_now();
+            }
+        }
+
+        if let Some((index, entry)) = extract_normalized_entry_from_patch(&patch)
+            && let NormalizedEntryType::ToolUse {
+                action_type: ActionType::FileEdit { path, .. },
+                ..
+            } = &entry.entry_type
+            && state.diffed_entries.insert(index)
+        {
+            let diffs = self
+                .diffs
+                .for_path(&self.deployment, state.effective_dir.as_deref(), path);
+            if !diffs.is_empty() {
+                sender
+                    .send(
+                        LogMsg::JsonPatch(file_edit_diff_patch(index, diffs))
+                            .to_ws_message_unchecked(),
+                    )
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn apply(
+        &mut self,
+        command: ReplayCommand,
+        sender: &mut (impl SinkExt<Message, Error = axum::Error> + Unpin),
+    ) -> anyhow::Result<()> {
+        match command {
+            ReplayCommand::Seek { entry_index } => {
+                match seek_plan(self.state.last_entry_index, self.state.done, entry_index) {
+                    SeekPlan::Restart => {
+                        sender
+                            .send(LogMsg::JsonPatch(reset_patch()).to_ws_message_unchecked())
+                            .await?;
+                        self.restart();
+                    }
+                    SeekPlan::FastForward => {}
+                    // Already played to the end; nothing left to fast-forward through
+                    SeekPlan::Finished => return Ok(()),
+                }
+                self.seek_target = Some(entry_index);
+            }
+            ReplayCommand::Speed { speed } => {
+                // Ignore speeds that would stall or break the clock, e.g. NaN
+                if let Some(speed) = replay_speed(speed) {
+                    self.clock.set_speed(speed);
+                }
+            }
+            ReplayCommand::Pause => self.clock.pause(),
+            ReplayCommand::Resume => self.clock.resume(),
+        }
+        Ok(())
+    }
+}
+
+/// Clear the client's conversation state before (re)starting playback
+fn reset_patch() -> Patch {
+    serde_json::from_value(json!([
+        {"op": "add", "path": "/entries", "value": []},
+        {"op": "add", "path": "/file_edit_diffs", "value": {}},
+    ]))
+    .expect("reset patch should be valid")
+}
+
+fn file_edit_diff_patch(entry_index: usize, diffs: Vec<Diff>) -> Patch {
+    serde_json::from_value(json!([{
+        "op": "add",
+        "path": format!("/file_edit_diffs/{entry_index}"),
+        "value": diffs,
+    }]))
+    .expect("file edit diff patch should be valid")
+}
+
+fn entry_index_of(patch: &Patch) -> Option<usize> {
+    let value = serde_json::to_value(patch).ok()?;
+    value
+        .as_array()?
+        .iter()
+        .filter_map(|op| {
+            op.get("path")?
+                .as_str()?
+                .strip_prefix("/entries/")?
+                .parse::<usize>()
+                .ok()
+        })
+        .max()
+}
+
+/// Net change to a file made by the replayed execution, per workspace repo: from
+/// the execution's `before_head_commit` (or the previous execution's
+/// `after_head_commit`) to its `after_head_commit`
+struct FileEditDiffs {
+    workspace_root: PathBuf,
+    repos: Vec<(Repo, String, String)>,
+    cache: HashMap<String, Vec<Diff>>,
+}
+
+impl FileEditDiffs {
+    async fn load(
+        deployment: &DeploymentImpl,
+        process: &ExecutionProcess,
+        workspace: &Workspace,
+    ) -> Result<Self, ApiError> {
+        let pool = &deployment.db().pool;
+        let states =
+            ExecutionProcessRepoState::find_by_execution_process_id(pool, process.id).await?;
+        let mut repos = Vec::new();
+        for repo in WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await? {
+            let state = states.iter().find(|s| s.repo_id == repo.id);
+            let Some(after) = state.and_then(|s| s.after_head_commit.clone()) else {
+                continue;
+            };
+            let before = match state.and_then(|s| s.before_head_commit.clone()) {
+                Some(before) => Some(before),
+                None => {
+                    ExecutionProcess::find_prev_after_head_commit(
+                        pool,
+                        process.session_id,
+                        process.id,
+                        repo.id,
+                    )
+                    .await?
+                }
+            };
+            if let Some(before) = before
+                && before != after
+            {
+                repos.push((repo, before, after));
+            }
+        }
+
+        Ok(Self {
+            workspace_root: deployment.container().workspace_to_current_dir(workspace),
+            repos,
+            cache: HashMap::new(),
+        })
+    }
+
+    fn for_path(
+        &mut self,
+        deployment: &DeploymentImpl,
+        effective_dir: Option<&Path>,
+        path: &str,
+    ) -> Vec<Diff> {
+        if let Some(diffs) = self.cache.get(path) {
+            return diffs.clone();
+        }
+
+        let full_path = effective_dir.unwrap_or(&self.workspace_root).join(path);
+        let mut diffs = Vec::new();
+        for (repo, before, after) in &self.repos {
+            let rel_path = match full_path.strip_prefix(self.workspace_root.join(&repo.name)) {
+                Ok(rel) => rel.to_string_lossy().to_string(),
+                Err(_) if self.repos.len() == 1 && Path::new(path).is_relative() => {
+                    path.to_string()
+                }
+                Err(_) => continue,
+            };
+            match deployment.git().get_diffs(
+                DiffTarget::CommitRange {
+                    repo_path: &repo.path,
+                    from_commit: before,
+                    to_commit: after,
+                },
+                Some(&[rel_path.as_str()]),
+            ) {
+                Ok(repo_diffs) => diffs.extend(repo_diffs.into_iter().map(|mut diff| {
+                    diff.repo_id = Some(repo.id);
+                    diff
+                })),
+                Err(e) => tracing::warn!(
+                    "Failed to diff {} in repo {} for replay: {}",
+                    rel_path,
+                    repo.name,
+                    e
+                ),
+            }
+        }
+
+        self.cache.insert(path.to_string(), diffs.clone());
+        diffs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use uuid::Uuid;
+
+    use super::*;
+
+    /// Frames as read back from a stored log: a burst of output, a long idle
+    /// period and a final line
+    fn stored_frames() -> Vec<(DateTime<Utc>, LogMsg)> {
+        let execution_id = Uuid::new_v4();
+        let at = |secs| Utc.timestamp_opt(1_700_000_000 + secs, 0).unwrap();
+        let line = |msg: LogMsg| serde_json::to_string(&msg).unwrap() + "\n";
+        let records = vec![
+            ExecutionProcessLogs {
+                execution_id,
+                logs: line(LogMsg::Stdout("one".to_string()))
+                    + &line(LogMsg::Stdout("two".to_string())),
+                byte_size: 0,
+                inserted_at: at(0),
+            },
+            ExecutionProcessLogs {
+                execution_id,
+                logs: line(LogMsg::Stderr("three".to_string())),
+                byte_size: 0,
+                inserted_at: at(2),
+            },
+            ExecutionProcessLogs {
+                execution_id,
+                logs: line(LogMsg::Stdout("four".to_string())),
+                byte_size: 0,
+                inserted_at: at(600),
+            },
+        ];
+        ExecutionProcessLogs::parse_timed_logs(&records).unwrap()
+    }
+
+    fn remaining(clock: &PlaybackClock) -> Duration {
+        clock.next_due.saturating_duration_since(Instant::now())
+    }
+
+    fn assert_close(actual: Duration, expected: Duration) {
+        let tolerance = Duration::from_millis(250);
+        assert!(
+            actual <= expected + tolerance && actual + tolerance >= expected,
+            "expected about {expected:?}, got {actual:?}"
+        );
+    }
+
+    #[test]
+    fn frame_gaps_follow_recorded_times_and_are_capped() {
+        let frames = stored_frames();
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frame_gap(&frames, 0), Some(Duration::ZERO));
+        assert_eq!(frame_gap(&frames, 1), Some(Duration::from_secs(2)));
+        assert_eq!(frame_gap(&frames, 2), Some(MAX_REPLAY_GAP));
+        assert_eq!(frame_gap(&frames, 3), None);
+    }
+
+    #[test]
+    fn speed_scales_the_gap_and_the_pending_wait() {
+        let frames = stored_frames();
+        let gap = frame_gap(&frames, 1).unwrap();
+
+        let mut clock = PlaybackClock::new(2.0);
+        clock.schedule(gap);
+        assert_close(remaining(&clock), Duration::from_secs(1));
+
+        clock.set_speed(0.5);
+        assert_close(remaining(&clock), Duration::from_secs(4));
+        assert_eq!(clock.speed, 0.5);
+    }
+
+    #[test]
+    fn pause_holds_the_remaining_wait_until_resume() {
+        let frames = stored_frames();
+        let mut clock = PlaybackClock::new(1.0);
+        clock.schedule(frame_gap(&frames, 2).unwrap());
+
+        clock.pause();
+        assert!(clock.paused);
+        assert_close(clock.paused_remaining, MAX_REPLAY_GAP);
+
+        // Pausing twice keeps the first remaining time
+        clock.paused_remaining = Duration::from_secs(3);
+        clock.pause();
+        assert_eq!(clock.paused_remaining, Duration::from_secs(3));
+
+        clock.resume();
+        assert!(!clock.paused);
+        assert_close(remaining(&clock), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn seek_restarts_fast_forwards_or_stops_at_the_end() {
+        assert_eq!(seek_plan(None, false, 0), SeekPlan::FastForward);
+        assert_eq!(seek_plan(Some(2), false, 1), SeekPlan::Restart);
+        assert_eq!(seek_plan(Some(2), false, 2), SeekPlan::Restart);
+        assert_eq!(seek_plan(Some(2), false, 3), SeekPlan::FastForward);
+        assert_eq!(seek_plan(Some(2), true, 3), SeekPlan::Finished);
+        assert_eq!(seek_plan(Some(2), true, 0), SeekPlan::Restart);
+    }
+
+    #[test]
+    fn non_finite_speeds_are_rejected() {
+        assert_eq!(replay_speed(f64::NAN), None);
+        assert_eq!(replay_speed(f64::INFINITY), None);
+        assert_eq!(replay_speed(f64::NEG_INFINITY), None);
+        assert_eq!(replay_speed(0.0), Some(MIN_REPLAY_SPEED));
+        assert_eq!(replay_speed(1e9), Some(MAX_REPLAY_SPEED));
+        assert_eq!(replay_speed(1.5), Some(1.5));
+    }
+}
@@ -0,0 +1,279 @@
+use std::{
+    io::{Cursor, Write},
+    path::PathBuf,
+};
+
+use axum::{
+    Router,
+    extract::{Query, State},
+    http::{HeaderMap, HeaderValue, header},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use chrono::Utc;
+use db::models::{
+    execution_process::{
+        ExecutionProcess, ExecutionProcessError, ExecutionProcessRunReason, ExecutionProcessStatus,
+    },
+    project::{Project, ProjectError},
+    session::{Session, SessionError},
+    task::Task,
+    workspace::{Workspace, WorkspaceError},
+    workspace_repo::WorkspaceRepo,
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::{
+    container::ContainerService,
+    conversation_export::{
+        self, ConversationExport, ConversationExportFormat, ExportedSession, RedactionOptions,
+        Redactor,
+    },
+};
+use ts_rs::TS;
+use uuid::Uuid;
+use zip::{ZipWriter, write::FileOptions};
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Clone, Copy, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ConversationExportScope {
+    Execution,
+    Session,
+    Task,
+    Project,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ConversationExportQuery {
+    pub scope: ConversationExportScope,
+    /// ID of the execution process, session, task or project to export
+    pub id: Uuid,
+    #[serde(default)]
+    pub format: ConversationExportFormat,
+    /// Bundle one file per session into a ZIP archive
+    #[serde(default)]
+    pub archive: bool,
+    /// Replace environment variable values and secrets with a placeholder
+    #[serde(default)]
+    pub redact_env: bool,
+    /// Replace workspace, repository and home directory paths with placeholders
+    #[serde(default)]
+    pub redact_paths: bool,
+}
+
+async fn sessions_of_task(
+    deployment: &DeploymentImpl,
+    task_id: Uuid,
+) -> Result<Vec<(Session, Option<Uuid>)>, ApiError> {
+    let pool = &deployment.db().pool;
+    let mut sessions = Vec::new();
+    for workspace in Workspace::fetch_all(pool, Some(task_id)).await? {
+        for session in Session::find_by_workspace_id(pool, workspace.id).await? {
+            sessions.push((session, None));
+        }
+    }
+    Ok(sessions)
+}
+
+/// Sessions to export, each optionally limited to a single execution
+async fn sessions_in_scope(
+    deployment: &DeploymentImpl,
+    scope: ConversationExportScope,
+    id: Uuid,
+) -> Result<(String, Vec<(Session, Option<Uuid>)>), ApiError> {
+    let pool = &deployment.db().pool;
+    match scope {
+        ConversationExportScope::Execution => {
+            let process = ExecutionProcess::find_by_id(pool, id)
+                .await?
+                .ok_or(ExecutionProcessError::ExecutionProcessNotFound)?;
+            let session = Session::find_by_id(pool, process.session_id)
+                .await?
+                .ok_or(SessionError::NotFound)?;
+            Ok((
+                format!("Execution {}", &id.to_string()[..8]),
+                vec![(session, Some(id))],
+            ))
+        }
+        ConversationExportScope::Session => {
+            let session = Session::find_by_id(pool, id)
+                .await?
+                .ok_or(SessionError::NotFound)?;
+            Ok((
+                format!("Session {}", &id.to_string()[..8]),
+                vec![(session, None)],
+            ))
+        }
+        ConversationExportScope::Task => {
+            let task = Task::find_by_id(pool, id)
+                .await?
+                .ok_or(WorkspaceError::TaskNotFound)?;
+            Ok((
+                format!("Task: {}", task.title),
+                sessions_of_task(deployment, id).await?,
+            ))
+        }
+        ConversationExportScope::Project => {
+            let project = Project::find_by_id(pool, id)
+                .await?
+                .ok_or(ProjectError::ProjectNotFound)?;
+            let mut sessions = Vec::new();
+            for task in Task::find_by_project_id_with_attempt_status(pool, id).await? {
+                sessions.extend(sessions_of_task(deployment, task.id).await?);
+            }
+            Ok((format!("Project: {}", project.name), sessions))
+        }
+    }
+}
+
+pub async fn export_conversations(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ConversationExportQuery>,
+) -> Result<Response, ApiError> {
+    let pool = &deployment.db().pool;
+    let (title, sessions) = sessions_in_scope(&deployment, query.scope, query.id).await?;
+
+    let mut path_roots = Vec::new();
+    let mut exported = Vec::new();
+    for (session, only_execution) in sessions {
+        let Some(workspace) = Workspace::find_by_id(pool, session.workspace_id).await? else {
+            continue;
+        };
+        let Some(task) = Task::find_by_id(pool, workspace.task_id).await? else {
+            continue;
+        };
+        let project_name = Project::find_by_id(pool, task.project_id)
+            .await?
+            .map(|project| project.name)
+            .unwrap_or_default();
+
+        if let Some(container_ref) = &workspace.container_ref {
+            path_roots.push((PathBuf::from(container_ref), "<workspace>".to_string()));
+        }
+        for repo in WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await? {
+            path_roots.push((repo.path.clone(), format!("<repo:{}>", repo.name)));
+        }
+
+        let mut executions = Vec::new();
+        for process in ExecutionProcess::find_by_session_id(pool, session.id, false).await? {
+            if only_execution.is_some_and(|id| id != process.id)
+                || process.run_reason != ExecutionProcessRunReason::CodingAgent
+                || process.status == ExecutionProcessStatus::Running
+            {
+                continue;
+            }
+            match deployment.container().export_execution(&process).await {
+                Some(execution) => executions.push(execution),
+                None => tracing::warn!(
+                    "Skipping execution {} in export: logs could not be normalized",
+                    process.id
+                ),
+            }
+        }
+        if executions.is_empty() {
+            continue;
+        }
+
+        exported.push(ExportedSession {
+            session_id: session.id,
+            workspace_id: workspace.id,
+            task_id: task.id,
+            task_title: task.title,
+            project_name,
+            branch: workspace.branch,
+            executor: session.executor,
+            created_at: session.created_at,
+            executions,
+        });
+    }
+
+    if exported.is_empty() {
+        return Err(ApiError::BadRequest(
+            "No finished agent conversations to export".to_string(),
+        ));
+    }
+
+    let mut export = ConversationExport {
+        title,
+        exported_at: Utc::now(),
+        sessions: exported,
+    };
+    let redactor = Redactor::new(
+        RedactionOptions {
+            env_values: query.redact_env,
+            absolute_paths: query.redact_paths,
+        },
+        path_roots,
+    );
+    redactor
+        .redact_export(&mut export)
+        .map_err(|e| ApiError::BadRequest(format!("Failed to redact export: {e}")))?;
+
+    let file_stem = conversation_export::sanitize_file_name(&export.title);
+    if query.archive {
+        let bytes = archive(&export, query.format)?;
+        return attachment(bytes, "application/zip", &format!("{file_stem}.zip"));
+    }
+
+    let body = conversation_export::render(&export, query.format)
+        .map_err(|e| ApiError::BadRequest(format!("Failed to render export: {e}")))?;
+    attachment(
+        body.into_bytes(),
+        query.format.content_type(),
+        &format!("{file_stem}.{}", query.format.extension()),
+    )
+}
+
+/// ZIP with one file per session
+fn archive(
+    export: &ConversationExport,
+    format: ConversationExportFormat,
+) -> Result<Vec<u8>, ApiError> {
+    let mut buffer = Cursor::new(Vec::new());
+    {
+        let mut zip = ZipWriter::new(&mut buffer);
+        let options = FileOptions::<()>::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .unix_permissions(0o644);
+
+        for session in &export.sessions {
+            let single = ConversationExport {
+                title: format!("{} — {}", export.title, session.task_title),
+                exported_at: export.exported_at,
+                sessions: vec![session.clone()],
+            };
+            let body = conversation_export::render(&single, format)
+                .map_err(|e| ApiError::BadRequest(format!("Failed to render export: {e}")))?;
+            let path = conversation_export::archive_path(session, format);
+            zip.start_file(path.to_string_lossy(), options)
+                .map_err(|e| ApiError::BadRequest(format!("Failed to create ZIP entry: {e}")))?;
+            zip.write_all(body.as_bytes())?;
+        }
+
+        zip.finish()
+            .map_err(|e| ApiError::BadRequest(format!("Failed to finalize ZIP: {e}")))?;
+    }
+    Ok(buffer.into_inner())
+}
+
+fn attachment(bytes: Vec<u8>, content_type: &str, filename: &str) -> Result<Response, ApiError> {
+    let headers = HeaderMap::from_iter([
+        (
+            header::CONTENT_TYPE,
+            HeaderValue::from_str(content_type)
+                .map_err(|e| ApiError::BadRequest(format!("Invalid header value: {e}")))?,
+        ),
+        (
+            header::CONTENT_DISPOSITION,
+            HeaderValue::from_str(&format!("attachment; filename=\"{filename}\""))
+                .map_err(|e| ApiError::BadRequest(format!("Invalid header value: {e}")))?,
+        ),
+    ]);
+    Ok((headers, bytes).into_response())
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/export/conversations", get(export_conversations))
+}
@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+
+use axum::{
+    Router,
+    extract::State,
+    http::{HeaderValue, header},
+    middleware::from_fn_with_state,
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use db::models::{execution_process::ExecutionProcess, session::Session};
+use deployment::Deployment;
+use services::services::container::ContainerService;
+use utils::metrics::{metrics, write_gauge};
+
+use crate::{DeploymentImpl, error::ApiError, middleware::require_metrics_access};
+
+/// Prometheus text exposition of the recorded metrics plus gauges read now
+pub async fn get_metrics(State(deployment): State<DeploymentImpl>) -> Result<Response, ApiError> {
+    let pool = &deployment.db().pool;
+    let mut out = String::new();
+    metrics().render(&mut out);
+
+    let running = ExecutionProcess::count_running_by_executor(pool)
+        .await?
+        .into_iter()
+        .map(|(executor, run_reason, count)| (vec![executor, run_reason], count as f64))
+        .collect::<Vec<_>>();
+    write_gauge(
+        &mut out,
+        "executions_running",
+        "Execution processes currently running",
+        &["executor", "run_reason"],
+        &running,
+    );
+
+    let mut queued: BTreeMap<String, f64> = BTreeMap::new();
+    for session_id in deployment.queued_message_service().queued_session_ids() {
+        let executor = Session::find_by_id(pool, session_id)
+            .await?
+            .and_then(|session| session.executor)
+            .unwrap_or_else(|| "none".to_string());
+        *queued.entry(executor).or_default() += 1.0;
+    }
+    let queued = queued
+        .into_iter()
+        .map(|(executor, count)| (vec![executor], count))
+        .collect::<Vec<_>>();
+    write_gauge(
+        &mut out,
+        "follow_ups_queued",
+        "Follow-up messages waiting for the running agent to finish",
+        &["executor"],
+        &queued,
+    );
+
+    let (stores, bytes) = {
+        let msg_stores = deployment.container().msg_stores().read().await;
+        let bytes: usize = msg_stores.values().map(|store| store.history_bytes()).sum();
+        (msg_stores.len(), bytes)
+    };
+    write_gauge(
+        &mut out,
+        "msg_stores",
+        "Execution log stores held in memory",
+        &[],
+        &[(vec![], stores as f64)],
+    );
+    write_gauge(
+        &mut out,
+        "msg_store_history_bytes",
+        "Bytes of log history held by execution log stores",
+        &[],
+        &[(vec![], bytes as f64)],
+    );
+
+    write_gauge(
+        &mut out,
+        "db_pool_connections",
+        "Open database connections",
+        &["state"],
+        &[
+            (vec!["open".to_string()], pool.size() as f64),
+            (vec!["idle".to_string()], pool.num_idle() as f64),
+        ],
+    );
+
+    write_gauge(
+        &mut out,
+        "event_stream_subscribers",
+        "Clients subscribed to the event stream",
+        &[],
+        &[(
+            vec![],
+            deployment.events().msg_store().subscriber_count() as f64,
+        )],
+    );
+
+    Ok((
+        [(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("text/plain; version=0.0.4"),
+        )],
+        out,
+    )
+        .into_response())
+}
+
+/// Mounted outside `require_remote_auth`: Prometheus authenticates with the
+/// metrics token, other clients need to be admins
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/metrics", get(get_metrics))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            require_metrics_access,
+        ))
+}
@@ -1,11 +1,15 @@
 use axum::{
     Router,
-    routing::{IntoMakeService, get},
+    extract::Request,
+    middleware::{from_fn, from_fn_with_state},
+    routing::get,
 };
-use tower_http::validate_request::ValidateRequestHeaderLayer;
+use tower_http::{trace::TraceLayer, validate_request::ValidateRequestHeaderLayer};
 
 use crate::{DeploymentImpl, middleware};
 
+pub mod activity;
+pub mod agent_sessions;
 pub mod approvals;
 pub mod app_management;
 pub mod config;
@@ -15,21 +19,27 @@ pub mod filesystem;
 // pub mod github;
 pub mod events;
 pub mod execution_processes;
+pub mod export;
 pub mod frontend;
 pub mod health;
 pub mod images;
+pub mod metrics;
 pub mod oauth;
 pub mod organizations;
 pub mod projects;
+pub mod remote_access;
 pub mod repo;
 pub mod scratch;
+pub mod search;
 pub mod sessions;
 pub mod tags;
 pub mod task_attempts;
 pub mod tasks;
 pub mod terminal;
+pub mod users;
+pub mod worker_hosts;
 
-pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
+pub fn router(deployment: DeploymentImpl) -> Router {
     // Create routers with different middleware layers
     let base_routes = Router::new()
         .route("/health", get(health::health_check))
@@ -42,25 +52,52 @@ pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
         .merge(tags::router(&deployment))
         .merge(oauth::router())
         .merge(organizations::router())
-        .merge(filesystem::router())
+        .merge(filesystem::router().layer(from_fn(middleware::require_admin)))
         .merge(repo::router())
         .merge(events::router(&deployment))
         .merge(approvals::router())
         .merge(scratch::router(&deployment))
+        .merge(search::router())
+        .merge(export::router())
+        .merge(agent_sessions::router())
         .merge(sessions::router(&deployment))
         .merge(terminal::router())
+        .merge(worker_hosts::router())
+        .merge(users::router())
         .nest("/data-management", data_management::router())
         .nest("/images", images::routes())
         // App management routes
-        .nest("/app", app_management::router())
+        .nest(
+            "/app",
+            app_management::router().layer(from_fn(middleware::require_admin)),
+        )
+        .merge(remote_access::router())
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            middleware::require_remote_auth,
+        ))
+        .merge(remote_access::public_router())
+        .merge(metrics::router(&deployment))
         .layer(ValidateRequestHeaderLayer::custom(
             middleware::validate_origin,
         ))
+        .layer(
+            TraceLayer::new_for_http()
+                .make_span_with(|request: &Request| {
+                    tracing::info_span!(
+                        "http_request",
+                        method = %request.method(),
+                        path = %request.uri().path(),
+                    )
+                })
+                .on_request(())
+                .on_response(())
+                .on_failure(()),
+        )
         .with_state(deployment);
 
     Router::new()
         .route("/", get(frontend::serve_frontend_root))
         .route("/{*path}", get(frontend::serve_frontend))
         .nest("/api", base_routes)
-        .into_make_service()
 }
@@ -31,7 +31,12 @@ use utils::{
 };
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{CurrentUser, load_project_middleware},
+    routes::activity,
+};
 
 #[derive(Deserialize, TS)]
 pub struct LinkToExistingRequest {
@@ -325,6 +330,7 @@ pub struct OpenEditorResponse {
 pub async fn open_project_in_editor(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
+    current: CurrentUser,
     Json(payload): Json<Option<OpenEditorRequest>>,
 ) -> Result<ResponseJson<ApiResponse<OpenEditorResponse>>, ApiError> {
     let path = if let Some(ref req) = payload
@@ -346,7 +352,7 @@ pub async fn open_project_in_editor(
     let editor_config = {
         let config = deployment.config().read().await;
         let editor_type_str = payload.as_ref().and_then(|req| req.editor_type.as_deref());
-        config.editor.with_override(editor_type_str)
+        current.editor(&config).with_override(editor_type_str)
     };
 
     match editor_config.open_file(&path).await {
@@ -576,6 +582,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         )
         .route("/remote/members", get(get_project_remote_members))
         .route("/search", get(search_project_files))
+        .route("/activity", get(activity::get_project_activity))
         .route("/open-editor", post(open_project_in_editor))
         .route(
             "/link",
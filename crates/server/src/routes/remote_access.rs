@@ -0,0 +1,321 @@
+use axum::{
+    Json, Router,
+    extract::{Request, State},
+    http::{HeaderMap, HeaderValue, header},
+    middleware::from_fn,
+    response::{IntoResponse, Json as ResponseJson, Response},
+    routing::{get, post, put},
+};
+use db::models::user::User;
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::{
+    config::{RemoteAccessConfig, save_config_to_file},
+    remote_access::{generate_token, hash_secret, hash_token, verify_secret, verify_token},
+};
+use ts_rs::TS;
+use utils::{assets::config_path, response::ApiResponse};
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{
+        RemoteAccessRole, SESSION_COOKIE, SESSION_TTL, SessionOwner, remote_access_sessions,
+        require_admin, resolve_user, session_cookie,
+    },
+};
+
+/// Slows down password and token guessing
+const FAILED_LOGIN_DELAY: std::time::Duration = std::time::Duration::from_secs(1);
+pub(crate) const MIN_PASSWORD_LEN: usize = 12;
+
+#[derive(Debug, Serialize, TS)]
+pub struct RemoteAccessStatus {
+    pub enabled: bool,
+    /// None when the client has to log in
+    pub role: Option<RemoteAccessRole>,
+    /// Account the client is signed in to, if any
+    pub user: Option<User>,
+    pub has_password: bool,
+    pub has_token: bool,
+    pub has_metrics_token: bool,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct RemoteAccessLoginRequest {
+    /// Signs in to this user account with `password` instead of as the admin
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateRemoteAccessRequest {
+    pub enabled: bool,
+    pub port: Option<u16>,
+    pub tls_cert_path: Option<String>,
+    pub tls_key_path: Option<String>,
+    pub trusted_user_header: Option<String>,
+    /// Replaces the admin password when set
+    pub password: Option<String>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct UpdateRemoteAccessResponse {
+    pub config: RemoteAccessConfig,
+    /// Listening address and TLS changes apply after a restart
+    pub restart_required: bool,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct RemoteAccessTokenResponse {
+    /// Shown once; only its hash is stored
+    pub token: String,
+}
+
+/// Routes reachable before logging in
+pub fn public_router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/remote-access/status", get(get_status))
+        .route("/remote-access/login", post(login))
+        .route("/remote-access/logout", post(logout))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/remote-access", put(update_remote_access))
+        .route(
+            "/remote-access/token",
+            post(regenerate_token).delete(revoke_token),
+        )
+        .route(
+            "/remote-access/metrics-token",
+            post(regenerate_metrics_token).delete(revoke_metrics_token),
+        )
+        .layer(from_fn(require_admin))
+}
+
+async fn get_status(
+    State(deployment): State<DeploymentImpl>,
+    req: Request,
+) -> ResponseJson<ApiResponse<RemoteAccessStatus>> {
+    let current = resolve_user(&deployment, &req).await;
+    let config = deployment.config().read().await;
+    ResponseJson(ApiResponse::success(RemoteAccessStatus {
+        enabled: config.remote_access.enabled,
+        role: current.as_ref().map(|current| current.role),
+        user: current.and_then(|current| current.user),
+        has_password: config.remote_access.password_hash.is_some(),
+        has_token: config.remote_access.token_hash.is_some(),
+        has_metrics_token: config.remote_access.metrics_token_hash.is_some(),
+    }))
+}
+
+async fn login(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<RemoteAccessLoginRequest>,
+) -> Result<Response, ApiError> {
+    if let Some(username) = payload.username {
+        return login_user(&deployment, &username, payload.password.unwrap_or_default()).await;
+    }
+
+    let (password_hash, token_hash) = {
+        let config = deployment.config().read().await;
+        (
+            config.remote_access.password_hash.clone(),
+            config.remote_access.token_hash.clone(),
+        )
+    };
+
+    let role = tokio::task::spawn_blocking(move || match (payload.password, payload.token) {
+        (Some(password), _) => password_hash
+            .filter(|hash| verify_secret(&password, hash))
+            .map(|_| RemoteAccessRole::Admin),
+        (None, Some(token)) => token_hash
+            .filter(|hash| verify_token(token.trim(), hash))
+            .map(|_| RemoteAccessRole::Member),
+        (None, None) => None,
+    })
+    .await
+    .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let Some(role) = role else {
+        tokio::time::sleep(FAILED_LOGIN_DELAY).await;
+        return Err(ApiError::Unauthorized);
+    };
+
+    session_response(SessionOwner::Role(role), role)
+}
+
+async fn login_user(
+    deployment: &DeploymentImpl,
+    username: &str,
+    password: String,
+) -> Result<Response, ApiError> {
+    let user = User::find_by_username(&deployment.db().pool, username.trim()).await?;
+    let verified = match user.as_ref().and_then(|user| user.password_hash.clone()) {
+        Some(hash) => tokio::task::spawn_blocking(move || verify_secret(&password, &hash))
+            .await
+            .map_err(|e| ApiError::BadRequest(e.to_string()))?,
+        None => false,
+    };
+    match user {
+        Some(user) if verified => session_response(SessionOwner::User(user.id), user.role.into()),
+        _ => {
+            tokio::time::sleep(FAILED_LOGIN_DELAY).await;
+            Err(ApiError::Unauthorized)
+        }
+    }
+}
+
+fn session_response(owner: SessionOwner, role: RemoteAccessRole) -> Result<Response, ApiError> {
+    let session_id = remote_access_sessions().create(owner);
+    let cookie = format!(
+        "{SESSION_COOKIE}={session_id}; Path=/; HttpOnly; Secure; SameSite=Strict; Max-Age={}",
+        SESSION_TTL.as_secs()
+    );
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::SET_COOKIE,
+        HeaderValue::from_str(&cookie).map_err(|e| ApiError::BadRequest(e.to_string()))?,
+    );
+    Ok((headers, ResponseJson(ApiResponse::<_, ()>::success(role))).into_response())
+}
+
+async fn logout(req: Request) -> Response {
+    if let Some(session_id) = session_cookie(&req) {
+        remote_access_sessions().remove(session_id);
+    }
+    (
+        [(
+            header::SET_COOKIE,
+            format!("{SESSION_COOKIE}=; Path=/; HttpOnly; Secure; SameSite=Strict; Max-Age=0"),
+        )],
+        ResponseJson(ApiResponse::<(), ()>::success(())),
+    )
+        .into_response()
+}
+
+async fn save_remote_access(
+    deployment: &DeploymentImpl,
+    remote_access: RemoteAccessConfig,
+) -> Result<(), ApiError> {
+    let mut config = deployment.config().write().await;
+    let mut updated = config.clone();
+    updated.remote_access = remote_access;
+    save_config_to_file(&updated, &config_path()).await?;
+    *config = updated;
+    Ok(())
+}
+
+async fn update_remote_access(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateRemoteAccessRequest>,
+) -> Result<ResponseJson<ApiResponse<UpdateRemoteAccessResponse>>, ApiError> {
+    let current = deployment.config().read().await.remote_access.clone();
+    if payload.tls_cert_path.is_some() != payload.tls_key_path.is_some() {
+        return Err(ApiError::BadRequest(
+            "Set both a TLS certificate and key path, or neither".to_string(),
+        ));
+    }
+
+    let password_hash = match payload.password {
+        Some(password) if password.chars().count() < MIN_PASSWORD_LEN => {
+            return Err(ApiError::BadRequest(format!(
+                "The password must be at least {MIN_PASSWORD_LEN} characters"
+            )));
+        }
+        Some(password) => Some(
+            tokio::task::spawn_blocking(move || hash_secret(&password))
+                .await
+                .map_err(|e| ApiError::BadRequest(e.to_string()))?,
+        ),
+        None => current.password_hash.clone(),
+    };
+    if payload.enabled && password_hash.is_none() {
+        return Err(ApiError::BadRequest(
+            "Set an admin password before enabling remote access".to_string(),
+        ));
+    }
+
+    let updated = RemoteAccessConfig {
+        enabled: payload.enabled,
+        port: payload.port,
+        tls_cert_path: payload.tls_cert_path,
+        tls_key_path: payload.tls_key_path,
+        trusted_user_header: payload
+            .trusted_user_header
+            .map(|header| header.trim().to_string())
+            .filter(|header| !header.is_empty()),
+        token_hash: current.token_hash.clone(),
+        metrics_token_hash: current.metrics_token_hash.clone(),
+        password_hash,
+    };
+    let restart_required = updated.enabled != current.enabled
+        || updated.port != current.port
+        || updated.tls_cert_path != current.tls_cert_path
+        || updated.tls_key_path != current.tls_key_path;
+    if updated.password_hash != current.password_hash {
+        remote_access_sessions().clear_role(RemoteAccessRole::Admin);
+    }
+    save_remote_access(&deployment, updated.clone()).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "remote_access_updated",
+            serde_json::json!({ "enabled": updated.enabled }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(
+        UpdateRemoteAccessResponse {
+            config: updated.without_secrets(),
+            restart_required,
+        },
+    )))
+}
+
+async fn regenerate_token(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<RemoteAccessTokenResponse>>, ApiError> {
+    let token = generate_token();
+    let mut remote_access = deployment.config().read().await.remote_access.clone();
+    remote_access.token_hash = Some(hash_token(&token));
+    save_remote_access(&deployment, remote_access).await?;
+    remote_access_sessions().clear_role(RemoteAccessRole::Member);
+
+    Ok(ResponseJson(ApiResponse::success(
+        RemoteAccessTokenResponse { token },
+    )))
+}
+
+async fn revoke_token(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let mut remote_access = deployment.config().read().await.remote_access.clone();
+    remote_access.token_hash = None;
+    save_remote_access(&deployment, remote_access).await?;
+    remote_access_sessions().clear_role(RemoteAccessRole::Member);
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+async fn regenerate_metrics_token(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<RemoteAccessTokenResponse>>, ApiError> {
+    let token = generate_token();
+    let mut remote_access = deployment.config().read().await.remote_access.clone();
+    remote_access.metrics_token_hash = Some(hash_token(&token));
+    save_remote_access(&deployment, remote_access).await?;
+
+    Ok(ResponseJson(ApiResponse::success(
+        RemoteAccessTokenResponse { token },
+    )))
+}
+
+async fn revoke_metrics_token(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let mut remote_access = deployment.config().read().await.remote_access.clone();
+    remote_access.metrics_token_hash = None;
+    save_remote_access(&deployment, remote_access).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
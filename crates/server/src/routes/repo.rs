@@ -11,7 +11,7 @@ use db::models::{
 };
 use deployment::Deployment;
 use serde::{Deserialize, Serialize};
-use services::services::{file_search::SearchQuery, git::GitBranch, repo_validator::{RepoPathValidator, ValidationResult}};
+use services::services::{file_ranker::FileRanker, file_search::SearchQuery, git::GitBranch, repo::CloneOptions, repo_validator::{RepoPathValidator, ValidationResult}};
 use std::path::PathBuf;
 use ts_rs::TS;
 use utils::response::ApiResponse;
@@ -20,6 +20,7 @@ use uuid::Uuid;
 use crate::{
     DeploymentImpl,
     error::ApiError,
+    middleware::CurrentUser,
     routes::projects::{OpenEditorRequest, OpenEditorResponse},
 };
 
@@ -37,6 +38,18 @@ pub struct InitRepoRequest {
     pub folder_name: String,
 }
 
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
+pub struct CloneRepoRequest {
+    pub url: String,
+    pub parent_path: String,
+    pub folder_name: String,
+    /// Partial clone filter, e.g. `blob:none`
+    pub partial_clone_filter: Option<String>,
+    /// Shallow clone depth
+    pub depth: Option<u32>,
+}
+
 #[derive(Debug, Deserialize, TS)]
 #[ts(export)]
 pub struct BatchRepoRequest {
@@ -76,6 +89,28 @@ pub async fn init_repo(
     Ok(ResponseJson(ApiResponse::success(repo)))
 }
 
+pub async fn clone_repo(
+    State(deployment): State<DeploymentImpl>,
+    ResponseJson(payload): ResponseJson<CloneRepoRequest>,
+) -> Result<ResponseJson<ApiResponse<Repo>>, ApiError> {
+    let repo = deployment
+        .repo()
+        .clone_repo(
+            &deployment.db().pool,
+            deployment.git(),
+            &payload.url,
+            &payload.parent_path,
+            &payload.folder_name,
+            &CloneOptions {
+                filter: payload.partial_clone_filter,
+                depth: payload.depth,
+            },
+        )
+        .await?;
+
+    Ok(ResponseJson(ApiResponse::success(repo)))
+}
+
 pub async fn get_repo_branches(
     State(deployment): State<DeploymentImpl>,
     Path(repo_id): Path<Uuid>,
@@ -121,11 +156,13 @@ pub async fn update_repo(
     ResponseJson(payload): ResponseJson<UpdateRepo>,
 ) -> Result<ResponseJson<ApiResponse<Repo>>, ApiError> {
     let repo = Repo::update(&deployment.db().pool, repo_id, &payload).await?;
+    FileRanker::apply_repo_settings(&repo);
     Ok(ResponseJson(ApiResponse::success(repo)))
 }
 
 pub async fn open_repo_in_editor(
     State(deployment): State<DeploymentImpl>,
+    current: CurrentUser,
     Path(repo_id): Path<Uuid>,
     ResponseJson(payload): ResponseJson<Option<OpenEditorRequest>>,
 ) -> Result<ResponseJson<ApiResponse<OpenEditorResponse>>, ApiError> {
@@ -137,7 +174,7 @@ pub async fn open_repo_in_editor(
     let editor_config = {
         let config = deployment.config().read().await;
         let editor_type_str = payload.as_ref().and_then(|req| req.editor_type.as_deref());
-        config.editor.with_override(editor_type_str)
+        current.editor(&config).with_override(editor_type_str)
     };
 
     match editor_config.open_file(&repo.path).await {
@@ -194,9 +231,10 @@ pub async fn search_repo(
         }
     };
 
+    FileRanker::apply_repo_settings(&repo);
     match deployment
         .file_search_cache()
-        .search_repo(&repo.path, &search_query.q, search_query.mode)
+        .search_repo(&repo.path, &search_query)
         .await
     {
         Ok(results) => Ok(ResponseJson(ApiResponse::success(results))),
@@ -307,6 +345,7 @@ pub fn router() -> Router<DeploymentImpl> {
     Router::new()
         .route("/repos", get(get_repos).post(register_repo))
         .route("/repos/init", post(init_repo))
+        .route("/repos/clone", post(clone_repo))
         .route("/repos/batch", post(get_repos_batch))
         .route("/repos/validate-all", post(validate_all_repos))
         .route("/repos/{repo_id}", get(get_repo).put(update_repo))
@@ -0,0 +1,25 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use db::models::conversation_entry::{
+    ConversationEntry, ConversationSearchHit, ConversationSearchParams,
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+pub async fn search_conversations(
+    State(deployment): State<DeploymentImpl>,
+    Query(params): Query<ConversationSearchParams>,
+) -> Result<ResponseJson<ApiResponse<Vec<ConversationSearchHit>>>, ApiError> {
+    let hits = ConversationEntry::search(&deployment.db().pool, &params).await?;
+    Ok(ResponseJson(ApiResponse::success(hits)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/search/conversations", get(search_conversations))
+}
@@ -9,6 +9,7 @@ use axum::{
     routing::{get, post},
 };
 use db::models::{
+    activity::ActivityAction,
     execution_process::{ExecutionProcess, ExecutionProcessRunReason},
     scratch::{Scratch, ScratchType},
     session::{CreateSession, Session, SessionError},
@@ -29,8 +30,10 @@ use utils::response::ApiResponse;
 use uuid::Uuid;
 
 use crate::{
-    DeploymentImpl, error::ApiError, middleware::load_session_middleware,
-    routes::task_attempts::util::restore_worktrees_to_process,
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{CurrentUser, load_session_middleware},
+    routes::{activity, task_attempts::util::restore_worktrees_to_process},
 };
 
 #[derive(Debug, Deserialize)]
@@ -97,6 +100,7 @@ pub struct CreateFollowUpAttempt {
 pub async fn follow_up(
     Extension(session): Extension<Session>,
     State(deployment): State<DeploymentImpl>,
+    current: CurrentUser,
     Json(payload): Json<CreateFollowUpAttempt>,
 ) -> Result<ResponseJson<ApiResponse<ExecutionProcess>>, ApiError> {
     let pool = &deployment.db().pool;
@@ -216,6 +220,14 @@ pub async fn follow_up(
             &ExecutionProcessRunReason::CodingAgent,
         )
         .await?;
+    activity::record_for_workspace(
+        &deployment,
+        &current,
+        workspace.id,
+        ActivityAction::FollowUpSent,
+        None,
+    )
+    .await;
 
     // Clear the draft follow-up scratch on successful spawn
     // This ensures the scratch is wiped even if the user navigates away quickly
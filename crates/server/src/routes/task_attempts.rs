@@ -1,8 +1,12 @@
+pub mod backport;
 pub mod codex_setup;
+pub mod commits;
 pub mod cursor_setup;
 pub mod gh_cli_setup;
 pub mod images;
 pub mod pr;
+pub mod revert;
+pub mod review_comments;
 pub mod util;
 pub mod workspace_summary;
 
@@ -23,12 +27,15 @@ use axum::{
     routing::{get, post, put},
 };
 use db::models::{
+    activity::ActivityAction,
     coding_agent_turn::CodingAgentTurn,
-    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    execution_process::{
+        ExecutionProcess, ExecutionProcessError, ExecutionProcessRunReason, ExecutionProcessStatus,
+    },
     merge::{Merge, MergeStatus, PrMerge, PullRequestInfo},
     project::SearchResult,
     repo::{Repo, RepoError},
-    session::{CreateSession, Session},
+    session::{CreateSession, Session, SessionError},
     task::{Task, TaskRelationships, TaskStatus},
     workspace::{CreateWorkspace, Workspace, WorkspaceError},
     workspace_repo::{CreateWorkspaceRepo, RepoWithTargetBranch, WorkspaceRepo},
@@ -45,7 +52,8 @@ use executors::{
 use git2::BranchType;
 use serde::{Deserialize, Serialize};
 use services::services::{
-    container::ContainerService,
+    container::{ContainerService, GateKind},
+    diff_stream::DiffStreamTarget,
     file_search::SearchQuery,
     git::{ConflictOp, GitCliError, GitServiceError},
     workspace_manager::WorkspaceManager,
@@ -56,8 +64,13 @@ use utils::response::ApiResponse;
 use uuid::Uuid;
 
 use crate::{
-    DeploymentImpl, error::ApiError, middleware::load_workspace_middleware,
-    routes::task_attempts::gh_cli_setup::GhCliSetupError,
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{CurrentUser, load_workspace_middleware},
+    routes::{
+        activity,
+        task_attempts::{gh_cli_setup::GhCliSetupError, util::ensure_gate_passes},
+    },
 };
 
 #[derive(Debug, Deserialize, Serialize, TS)]
@@ -94,6 +107,12 @@ pub struct TaskAttemptQuery {
 pub struct DiffStreamQuery {
     #[serde(default)]
     pub stats_only: bool,
+    /// Only the changes made by this execution process
+    pub execution_process_id: Option<Uuid>,
+    /// Only the changes made by this commit
+    pub commit: Option<String>,
+    /// Repository of `commit`; may be omitted for single-repo workspaces
+    pub repo_id: Option<Uuid>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -294,22 +313,66 @@ pub async fn stream_task_attempt_diff_ws(
     Query(params): Query<DiffStreamQuery>,
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
-) -> impl IntoResponse {
-    let _ = Workspace::touch(&deployment.db().pool, workspace.id).await;
+) -> Result<impl IntoResponse, ApiError> {
+    let pool = &deployment.db().pool;
+    let _ = Workspace::touch(pool, workspace.id).await;
+
+    let target = match (params.execution_process_id, params.commit) {
+        (Some(_), Some(_)) => {
+            return Err(ApiError::BadRequest(
+                "Specify either execution_process_id or commit, not both".to_string(),
+            ));
+        }
+        (Some(execution_process_id), None) => {
+            let process = ExecutionProcess::find_by_id(pool, execution_process_id)
+                .await?
+                .ok_or(ExecutionProcessError::ExecutionProcessNotFound)?;
+            let session = Session::find_by_id(pool, process.session_id)
+                .await?
+                .ok_or(SessionError::NotFound)?;
+            if session.workspace_id != workspace.id {
+                return Err(ApiError::BadRequest(
+                    "Execution process does not belong to this workspace".to_string(),
+                ));
+            }
+            DiffStreamTarget::Execution(execution_process_id)
+        }
+        (None, Some(sha)) => {
+            let repo_id = match params.repo_id {
+                Some(repo_id) => repo_id,
+                None => match WorkspaceRepo::find_by_workspace_id(pool, workspace.id)
+                    .await?
+                    .as_slice()
+                {
+                    [workspace_repo] => workspace_repo.repo_id,
+                    _ => {
+                        return Err(ApiError::BadRequest(
+                            "repo_id is required for workspaces with several repositories"
+                                .to_string(),
+                        ));
+                    }
+                },
+            };
+            DiffStreamTarget::Commit { repo_id, sha }
+        }
+        (None, None) => DiffStreamTarget::Workspace,
+    };
 
     let stats_only = params.stats_only;
-    ws.on_upgrade(move |socket| async move {
-        if let Err(e) = handle_task_attempt_diff_ws(socket, deployment, workspace, stats_only).await
+    Ok(ws.on_upgrade(move |socket| async move {
+        if let Err(e) =
+            handle_task_attempt_diff_ws(socket, deployment, workspace, target, stats_only).await
         {
             tracing::warn!("diff WS closed: {}", e);
         }
-    })
+    }))
 }
 
 async fn handle_task_attempt_diff_ws(
     socket: WebSocket,
     deployment: DeploymentImpl,
     workspace: Workspace,
+    target: DiffStreamTarget,
     stats_only: bool,
 ) -> anyhow::Result<()> {
     use futures_util::{SinkExt, StreamExt, TryStreamExt};
@@ -317,7 +380,7 @@ async fn handle_task_attempt_diff_ws(
 
     let stream = deployment
         .container()
-        .stream_diff(&workspace, stats_only)
+        .stream_diff(&workspace, &target, stats_only)
         .await?;
 
     let mut stream = stream.map_ok(|msg: LogMsg| msg.to_ws_message_unchecked());
@@ -421,6 +484,7 @@ pub struct PushTaskAttemptRequest {
 pub async fn merge_task_attempt(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
+    current: CurrentUser,
     Json(request): Json<MergeTaskAttemptRequest>,
 ) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
     let pool = &deployment.db().pool;
@@ -445,6 +509,8 @@ pub async fn merge_task_attempt(
         ));
     }
 
+    ensure_gate_passes(&deployment, &workspace, &repo, GateKind::PreMerge).await?;
+
     let container_ref = deployment
         .container()
         .ensure_container_exists(&workspace)
@@ -485,6 +551,17 @@ pub async fn merge_task_attempt(
         &merge_commit_id,
     )
     .await?;
+    activity::record_for_workspace(
+        &deployment,
+        &current,
+        workspace.id,
+        ActivityAction::Merged,
+        Some(&format!(
+            "{} into {}",
+            merge_commit_id, workspace_repo.target_branch
+        )),
+    )
+    .await;
     Task::update_status(pool, task.id, TaskStatus::Done).await?;
     if !workspace.pinned {
         Workspace::set_archived(pool, workspace.id, true).await?;
@@ -544,6 +621,8 @@ pub async fn push_task_attempt_branch(
         .await?
         .ok_or(RepoError::NotFound)?;
 
+    ensure_gate_passes(&deployment, &workspace, &repo, GateKind::PrePush).await?;
+
     let container_ref = deployment
         .container()
         .ensure_container_exists(&workspace)
@@ -579,6 +658,8 @@ pub async fn force_push_task_attempt_branch(
         .await?
         .ok_or(RepoError::NotFound)?;
 
+    ensure_gate_passes(&deployment, &workspace, &repo, GateKind::PrePush).await?;
+
     let container_ref = deployment
         .container()
         .ensure_container_exists(&workspace)
@@ -613,6 +694,7 @@ pub struct OpenEditorResponse {
 pub async fn open_task_attempt_in_editor(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
+    current: CurrentUser,
     Json(payload): Json<OpenEditorRequest>,
 ) -> Result<ResponseJson<ApiResponse<OpenEditorResponse>>, ApiError> {
     let container_ref = deployment
@@ -643,7 +725,7 @@ pub async fn open_task_attempt_in_editor(
     let editor_config = {
         let config = deployment.config().read().await;
         let editor_type_str = payload.editor_type.as_deref();
-        config.editor.with_override(editor_type_str)
+        current.editor(&config).with_override(editor_type_str)
     };
 
     match editor_config.open_file(path.as_path()).await {
@@ -1678,6 +1760,11 @@ pub async fn delete_workspace(
         }
     }
 
+    deployment
+        .pty()
+        .close_workspace_sessions(workspace.id)
+        .await;
+
     // Gather data needed for background cleanup
     let workspace_dir = workspace.container_ref.clone().map(PathBuf::from);
     let repositories = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
@@ -1768,14 +1855,30 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/run-cleanup-script", post(run_cleanup_script))
         .route("/branch-status", get(get_task_attempt_branch_status))
         .route("/diff/ws", get(stream_task_attempt_diff_ws))
+        .route("/commits", get(commits::get_workspace_commits))
+        .route(
+            "/review-comments",
+            get(review_comments::list_review_comments).post(review_comments::create_review_comment),
+        )
+        .route(
+            "/review-comments/{comment_id}",
+            put(review_comments::update_review_comment)
+                .delete(review_comments::delete_review_comment),
+        )
+        .route("/review-comments/send", post(review_comments::send_review))
         .route("/merge", post(merge_task_attempt))
         .route("/push", post(push_task_attempt_branch))
         .route("/push/force", post(force_push_task_attempt_branch))
         .route("/rebase", post(rebase_task_attempt))
         .route("/conflicts/abort", post(abort_conflicts_task_attempt))
+        .route("/backport", post(backport::backport_task_attempt))
+        .route("/revert/hunks", get(revert::get_file_hunks))
+        .route("/revert/file", post(revert::revert_file))
+        .route("/revert/hunk", post(revert::revert_hunk))
         .route("/pr", post(pr::create_pr))
         .route("/pr/attach", post(pr::attach_existing_pr))
         .route("/pr/comments", get(pr::get_pr_comments))
+        .route("/pr/comments/import", post(pr::import_pr_comments))
         .route("/open-editor", post(open_task_attempt_in_editor))
         .route("/children", get(get_task_attempt_children))
         .route("/stop", post(stop_task_attempt_execution))
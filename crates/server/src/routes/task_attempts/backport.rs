@@ -0,0 +1,452 @@
+use std::path::{Path, PathBuf};
+
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    merge::Merge,
+    repo::{Repo, RepoError},
+    session::{CreateSession, Session},
+    workspace::{CreateWorkspace, Workspace},
+    workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
+};
+use deployment::Deployment;
+use executors::{
+    actions::{
+        ExecutorAction, ExecutorActionType, coding_agent_initial::CodingAgentInitialRequest,
+    },
+    profile::ExecutorProfileId,
+};
+use serde::{Deserialize, Serialize};
+use services::services::{
+    container::{ContainerService, GateKind},
+    git::GitServiceError,
+    git_host::{self, CreatePrRequest, GitHostProvider},
+};
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum BackportConflictResolution {
+    /// Start a coding agent in the new workspace to resolve the conflicts
+    #[default]
+    Agent,
+    /// Leave the cherry-pick in progress for the user to resolve
+    Manual,
+}
+
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct BackportRequest {
+    pub repo_id: Uuid,
+    /// Branch to backport onto, e.g. `release/1.2`
+    pub target_branch: String,
+    /// Executor used for conflict resolution; defaults to the source workspace's latest one
+    pub executor_profile_id: Option<ExecutorProfileId>,
+    #[serde(default)]
+    pub conflict_resolution: BackportConflictResolution,
+    /// Push the new branch and open a PR against `target_branch` when the cherry-pick is clean
+    #[serde(default)]
+    pub create_pr: bool,
+    pub pr_title: Option<String>,
+    pub pr_body: Option<String>,
+    pub draft: Option<bool>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct BackportResponse {
+    /// Workspace created on the backport branch
+    pub workspace: Workspace,
+    /// Commits from the source workspace that were cherry-picked, oldest first
+    pub cherry_picked: Vec<String>,
+    /// Files left in conflict; empty when the cherry-pick applied cleanly
+    pub conflicted_files: Vec<String>,
+    /// Whether a coding agent was started to resolve the conflicts
+    pub conflict_resolution_started: bool,
+    pub pr_url: Option<String>,
+    /// Why the PR could not be opened, if `create_pr` was requested and failed
+    pub pr_error: Option<String>,
+}
+
+const BACKPORT_CONFLICT_PROMPT: &str = r#"This workspace backports changes onto the branch `{target_branch}`.
+Cherry-picking the original commits stopped with conflicts in:
+{conflicted_files}
+
+Resolve the conflicts so the original intent of the changes is preserved on this branch, stage the resolved files, and finish the cherry-pick with `git cherry-pick --continue` (repeat until no cherry-pick is in progress)."#;
+
+/// Commits to backport: the squash commit of a direct merge when one was recorded,
+/// otherwise the workspace branch commits that are not on its target branch.
+async fn source_commits(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    workspace_repo: &WorkspaceRepo,
+    repo: &Repo,
+) -> Result<Vec<String>, ApiError> {
+    let pool = &deployment.db().pool;
+    let direct_merge = Merge::find_by_workspace_and_repo_id(pool, workspace.id, repo.id)
+        .await?
+        .into_iter()
+        .find_map(|merge| match merge {
+            Merge::Direct(direct) => Some(direct.merge_commit),
+            Merge::Pr(_) => None,
+        });
+    if let Some(commit) = direct_merge {
+        return Ok(vec![commit]);
+    }
+
+    Ok(deployment.git().get_branch_commits(
+        &repo.path,
+        &workspace.branch,
+        &workspace_repo.target_branch,
+    )?)
+}
+
+async fn start_conflict_resolution(
+    deployment: &DeploymentImpl,
+    source_workspace: &Workspace,
+    workspace: &Workspace,
+    executor_profile_id: Option<ExecutorProfileId>,
+    target_branch: &str,
+    conflicted_files: &[String],
+) -> Result<bool, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let executor_profile_id = match executor_profile_id {
+        Some(profile) => Some(profile),
+        None => match Session::find_latest_by_workspace_id(pool, source_workspace.id).await? {
+            Some(session) => {
+                ExecutionProcess::latest_executor_profile_for_session(pool, session.id).await?
+            }
+            None => None,
+        },
+    };
+    let Some(executor_profile_id) = executor_profile_id else {
+        tracing::warn!(
+            "No executor profile found for workspace {}, leaving backport conflicts for manual resolution",
+            source_workspace.id
+        );
+        return Ok(false);
+    };
+
+    let session = Session::create(
+        pool,
+        &CreateSession {
+            executor: Some(executor_profile_id.executor.to_string()),
+        },
+        Uuid::new_v4(),
+        workspace.id,
+    )
+    .await?;
+
+    let files = conflicted_files
+        .iter()
+        .map(|f| format!("- {f}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let prompt = BACKPORT_CONFLICT_PROMPT
+        .replace("{target_branch}", target_branch)
+        .replace("{conflicted_files}", &files);
+
+    let working_dir = workspace
+        .agent_working_dir
+        .as_ref()
+        .filter(|dir| !dir.is_empty())
+        .cloned();
+
+    let action = ExecutorAction::new(
+        ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+            prompt,
+            executor_profile_id,
+            working_dir,
+        }),
+        None,
+    );
+
+    deployment
+        .container()
+        .start_execution(
+            workspace,
+            &session,
+            &action,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?;
+
+    Ok(true)
+}
+
+/// Push the backport branch and open a PR against `target_branch`.
+/// Returns the PR URL, or a user-facing reason the PR could not be opened.
+async fn open_backport_pr(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    repo: &Repo,
+    worktree_path: &Path,
+    target_branch: &str,
+    request: &BackportRequest,
+    default_title: String,
+) -> Result<Result<String, String>, ApiError> {
+    let git = deployment.git();
+    let push_remote = git.resolve_remote_name_for_branch(&repo.path, &workspace.branch)?;
+    let (target_remote, base_branch) =
+        match git.get_remote_name_from_branch_name(&repo.path, target_branch) {
+            Ok(remote) => {
+                let branch = target_branch
+                    .strip_prefix(&format!("{remote}/"))
+                    .unwrap_or(target_branch);
+                (remote, branch.to_string())
+            }
+            Err(_) => (push_remote.clone(), target_branch.to_string()),
+        };
+    let push_remote_url = git.get_remote_url(&repo.path, &push_remote)?;
+    let target_remote_url = git.get_remote_url(&repo.path, &target_remote)?;
+
+    if let Some(failure) = deployment
+        .container()
+        .run_gate(workspace, repo, GateKind::PrePush)
+        .await?
+    {
+        return Ok(Err(ApiError::GateFailed(failure).to_string()));
+    }
+
+    if let Err(e) = git.push_to_remote(worktree_path, &workspace.branch, false) {
+        tracing::error!("Failed to push backport branch to remote: {}", e);
+        return Ok(Err(format!("Failed to push branch: {e}")));
+    }
+
+    let git_host = match git_host::GitHostService::from_url(&target_remote_url) {
+        Ok(host) => host,
+        Err(e) => return Ok(Err(e.to_string())),
+    };
+
+    let pr_request = CreatePrRequest {
+        title: request.pr_title.clone().unwrap_or(default_title),
+        body: request.pr_body.clone(),
+        head_branch: workspace.branch.clone(),
+        base_branch: base_branch.clone(),
+        draft: request.draft,
+        head_repo_url: Some(push_remote_url),
+    };
+
+    match git_host
+        .create_pr(&repo.path, &target_remote_url, &pr_request)
+        .await
+    {
+        Ok(pr_info) => {
+            if let Err(e) = Merge::create_pr(
+                &deployment.db().pool,
+                workspace.id,
+                repo.id,
+                &base_branch,
+                pr_info.number,
+                &pr_info.url,
+            )
+            .await
+            {
+                tracing::error!("Failed to record backport PR: {}", e);
+            }
+            Ok(Ok(pr_info.url))
+        }
+        Err(e) => {
+            tracing::error!("Failed to create backport PR: {}", e);
+            Ok(Err(e.to_string()))
+        }
+    }
+}
+
+/// Remove a backport workspace that failed part-way through, along with its
+/// worktree, so a failed request leaves nothing behind
+async fn discard_backport_workspace(deployment: &DeploymentImpl, workspace_id: Uuid) {
+    let pool = &deployment.db().pool;
+    match Workspace::find_by_id(pool, workspace_id).await {
+        Ok(Some(workspace)) => {
+            if let Err(e) = deployment.container().delete(&workspace).await {
+                tracing::error!(
+                    "Failed to clean up backport workspace {}: {}",
+                    workspace_id,
+                    e
+                );
+            }
+        }
+        Ok(None) => return,
+        Err(e) => tracing::error!("Failed to load backport workspace {}: {}", workspace_id, e),
+    }
+
+    if let Err(e) = Workspace::delete(pool, workspace_id).await {
+        tracing::error!(
+            "Failed to delete backport workspace {}: {}",
+            workspace_id,
+            e
+        );
+    }
+}
+
+/// Create a new workspace on `target_branch` for the same task and cherry-pick this
+/// workspace's changes onto it. Conflicts are handed to a coding agent or left for
+/// the user; a clean backport can optionally be opened as a PR.
+#[axum::debug_handler]
+pub async fn backport_task_attempt(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(request): Json<BackportRequest>,
+) -> Result<ResponseJson<ApiResponse<BackportResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let workspace_repo =
+        WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, request.repo_id)
+            .await?
+            .ok_or(RepoError::NotFound)?;
+    let repo = Repo::find_by_id(pool, request.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+
+    if !deployment
+        .git()
+        .check_branch_exists(&repo.path, &request.target_branch)?
+    {
+        return Ok(ResponseJson(ApiResponse::error(
+            format!(
+                "Branch '{}' does not exist in the repository",
+                request.target_branch
+            )
+            .as_str(),
+        )));
+    }
+
+    let commits = source_commits(&deployment, &workspace, &workspace_repo, &repo).await?;
+    if commits.is_empty() {
+        return Ok(ResponseJson(ApiResponse::error(
+            "This workspace has no commits to backport",
+        )));
+    }
+
+    let task = workspace
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    let backport_id = Uuid::new_v4();
+    let branch = deployment
+        .container()
+        .git_branch_from_workspace(&backport_id, &task.title)
+        .await;
+    let created = Workspace::create(
+        pool,
+        &CreateWorkspace {
+            branch,
+            agent_working_dir: Some(repo.name.clone()),
+        },
+        backport_id,
+        task.id,
+    )
+    .await?;
+
+    let outcome = async {
+        WorkspaceRepo::create_many(
+            pool,
+            created.id,
+            &[CreateWorkspaceRepo {
+                repo_id: repo.id,
+                target_branch: request.target_branch.clone(),
+            }],
+        )
+        .await?;
+
+        let container_ref = deployment.container().create(&created).await?;
+        let worktree_path = PathBuf::from(container_ref).join(&repo.name);
+        let backport_workspace = Workspace::find_by_id(pool, created.id)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+
+        let conflicted_files = match deployment
+            .git()
+            .cherry_pick_commits(&worktree_path, &commits)
+        {
+            Ok(_) => Vec::new(),
+            Err(GitServiceError::MergeConflicts {
+                conflicted_files, ..
+            }) => conflicted_files,
+            Err(e) => return Err(ApiError::GitService(e)),
+        };
+
+        let mut conflict_resolution_started = false;
+        if !conflicted_files.is_empty()
+            && request.conflict_resolution == BackportConflictResolution::Agent
+        {
+            conflict_resolution_started = start_conflict_resolution(
+                &deployment,
+                &workspace,
+                &backport_workspace,
+                request.executor_profile_id.clone(),
+                &request.target_branch,
+                &conflicted_files,
+            )
+            .await?;
+        }
+
+        let (pr_url, pr_error) = if request.create_pr && conflicted_files.is_empty() {
+            match open_backport_pr(
+                &deployment,
+                &backport_workspace,
+                &repo,
+                &worktree_path,
+                &request.target_branch,
+                &request,
+                format!("{} (backport to {})", task.title, request.target_branch),
+            )
+            .await?
+            {
+                Ok(url) => (Some(url), None),
+                Err(reason) => (None, Some(reason)),
+            }
+        } else {
+            (None, None)
+        };
+
+        Ok((
+            backport_workspace,
+            conflicted_files,
+            conflict_resolution_started,
+            pr_url,
+            pr_error,
+        ))
+    }
+    .await;
+
+    let (backport_workspace, conflicted_files, conflict_resolution_started, pr_url, pr_error) =
+        match outcome {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                discard_backport_workspace(&deployment, created.id).await;
+                return Err(e);
+            }
+        };
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_backported",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "backport_workspace_id": backport_workspace.id.to_string(),
+                "repo_id": repo.id.to_string(),
+                "commit_count": commits.len(),
+                "had_conflicts": !conflicted_files.is_empty(),
+                "conflict_resolution_started": conflict_resolution_started,
+                "pr_created": pr_url.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(BackportResponse {
+        workspace: backport_workspace,
+        cherry_picked: commits,
+        conflicted_files,
+        conflict_resolution_started,
+        pr_url,
+        pr_error,
+    })))
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use axum::{Extension, extract::State, response::Json as ResponseJson};
+use chrono::{DateTime, Utc};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    execution_process_repo_state::ExecutionProcessRepoState,
+    session::Session,
+    workspace::Workspace,
+    workspace_repo::WorkspaceRepo,
+};
+use deployment::Deployment;
+use serde::Serialize;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Serialize, TS)]
+pub struct WorkspaceCommit {
+    pub repo_id: Uuid,
+    pub repo_name: String,
+    pub sha: String,
+    pub subject: String,
+    pub author_name: String,
+    #[ts(type = "Date")]
+    pub committed_at: DateTime<Utc>,
+    pub is_merge: bool,
+    /// Coding agent turn that produced this commit, if any
+    pub execution_process_id: Option<Uuid>,
+}
+
+/// (repo, commit sha) -> coding agent execution whose before..after range contains it
+async fn commit_authors(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    repo_paths: &HashMap<Uuid, std::path::PathBuf>,
+) -> Result<HashMap<(Uuid, String), Uuid>, ApiError> {
+    let pool = &deployment.db().pool;
+    let mut authors = HashMap::new();
+    for session in Session::find_by_workspace_id(pool, workspace.id).await? {
+        for process in ExecutionProcess::find_by_session_id(pool, session.id, false).await? {
+            if process.run_reason != ExecutionProcessRunReason::CodingAgent {
+                continue;
+            }
+            for state in
+                ExecutionProcessRepoState::find_by_execution_process_id(pool, process.id).await?
+            {
+                let (Some(before), Some(after), Some(repo_path)) = (
+                    state.before_head_commit.as_deref(),
+                    state.after_head_commit.as_deref(),
+                    repo_paths.get(&state.repo_id),
+                ) else {
+                    continue;
+                };
+                match deployment.git().get_commit_range(repo_path, before, after) {
+                    Ok(shas) => {
+                        for sha in shas {
+                            authors.insert((state.repo_id, sha), process.id);
+                        }
+                    }
+                    Err(e) => {
+                        tracing::debug!("Skipping commit range of execution {}: {}", process.id, e)
+                    }
+                }
+            }
+        }
+    }
+    Ok(authors)
+}
+
+/// Commits on the workspace branch that are not on the target branch, newest first
+pub async fn get_workspace_commits(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<WorkspaceCommit>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let workspace_repos = WorkspaceRepo::find_by_workspace_id(pool, workspace.id).await?;
+    let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
+    let repo_paths: HashMap<_, _> = repos
+        .iter()
+        .map(|repo| (repo.id, repo.path.clone()))
+        .collect();
+    let authors = commit_authors(&deployment, &workspace, &repo_paths).await?;
+
+    let mut commits = Vec::new();
+    for repo in &repos {
+        let Some(workspace_repo) = workspace_repos.iter().find(|wr| wr.repo_id == repo.id) else {
+            continue;
+        };
+        let log = deployment.git().get_branch_commit_log(
+            &repo.path,
+            &workspace.branch,
+            &workspace_repo.target_branch,
+        )?;
+        commits.extend(log.into_iter().map(|commit| WorkspaceCommit {
+            repo_id: repo.id,
+            repo_name: repo.name.clone(),
+            execution_process_id: authors.get(&(repo.id, commit.sha.clone())).copied(),
+            sha: commit.sha,
+            subject: commit.subject,
+            author_name: commit.author_name,
+            committed_at: commit.committed_at,
+            is_merge: commit.is_merge,
+        }));
+    }
+    if repos.len() > 1 {
+        commits.sort_by(|a, b| b.committed_at.cmp(&a.committed_at));
+    }
+
+    Ok(ResponseJson(ApiResponse::success(commits)))
+}
@@ -6,8 +6,10 @@ use axum::{
     response::Json as ResponseJson,
 };
 use db::models::{
+    activity::ActivityAction,
     execution_process::{ExecutionProcess, ExecutionProcessRunReason},
     merge::{Merge, MergeStatus},
+    pr_comment_follow_up::PrCommentFollowUp,
     repo::{Repo, RepoError},
     session::{CreateSession, Session},
     task::{Task, TaskStatus},
@@ -21,17 +23,23 @@ use executors::actions::{
 };
 use serde::{Deserialize, Serialize};
 use services::services::{
-    container::ContainerService,
+    container::{ContainerService, GateKind},
     git::{GitCliError, GitServiceError},
     git_host::{
         self, CreatePrRequest, GitHostError, GitHostProvider, ProviderKind, UnifiedPrComment,
     },
+    pr_comment_follow_ups::{self, PrCommentFollowUpError},
 };
 use ts_rs::TS;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::CurrentUser,
+    routes::{activity, task_attempts::util::ensure_gate_passes},
+};
 
 #[derive(Debug, Deserialize, Serialize, TS)]
 pub struct CreatePrApiRequest {
@@ -88,6 +96,19 @@ pub struct GetPrCommentsQuery {
     pub repo_id: Uuid,
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct ImportPrCommentsRequest {
+    pub repo_id: Uuid,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ImportPrCommentsResponse {
+    pub comment_count: usize,
+    /// The follow-up turn, when the workspace was idle. Otherwise the comments wait
+    /// in the session's queue for the running execution to finish.
+    pub execution_process_id: Option<Uuid>,
+}
+
 pub const DEFAULT_PR_DESCRIPTION_PROMPT: &str = r#"Update the PR that was just created with a better title and description.
 The PR number is #{pr_number} and the URL is {pr_url}.
 
@@ -195,6 +216,7 @@ async fn trigger_pr_description_follow_up(
 pub async fn create_pr(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
+    current: CurrentUser,
     Json(request): Json<CreatePrApiRequest>,
 ) -> Result<ResponseJson<ApiResponse<String, PrError>>, ApiError> {
     let pool = &deployment.db().pool;
@@ -263,6 +285,8 @@ pub async fn create_pr(
         Ok(true) => {}
     }
 
+    ensure_gate_passes(&deployment, &workspace, &repo, GateKind::PrePush).await?;
+
     if let Err(e) = git.push_to_remote(&worktree_path, &workspace.branch, false) {
         tracing::error!("Failed to push branch to remote: {}", e);
         match e {
@@ -325,6 +349,14 @@ pub async fn create_pr(
             {
                 tracing::error!("Failed to update workspace PR status: {}", e);
             }
+            activity::record_for_workspace(
+                &deployment,
+                &current,
+                workspace.id,
+                ActivityAction::PrCreated,
+                Some(&pr_info.url),
+            )
+            .await;
 
             // Auto-open PR in browser
             if let Err(e) = utils::browser::open_browser(&pr_info.url).await {
@@ -580,3 +612,77 @@ pub async fn get_pr_comments(
         }
     }
 }
+
+/// Queue the PR comments not yet sent to the agent as one follow-up, starting it
+/// right away when the workspace is idle
+pub async fn import_pr_comments(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(request): Json<ImportPrCommentsRequest>,
+) -> Result<ResponseJson<ApiResponse<ImportPrCommentsResponse, GetPrCommentsError>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let repo = Repo::find_by_id(pool, request.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+    let merges = Merge::find_by_workspace_and_repo_id(pool, workspace.id, request.repo_id).await?;
+    let pr_merge = match merges.into_iter().next() {
+        Some(Merge::Pr(pr_merge)) => pr_merge,
+        _ => {
+            return Ok(ResponseJson(ApiResponse::error_with_data(
+                GetPrCommentsError::NoPrAttached,
+            )));
+        }
+    };
+
+    let comments =
+        match pr_comment_follow_ups::fetch_pr_comments(deployment.git(), &repo, &pr_merge).await {
+            Ok(comments) => comments,
+            Err(PrCommentFollowUpError::GitHost(GitHostError::CliNotInstalled { provider })) => {
+                return Ok(ResponseJson(ApiResponse::error_with_data(
+                    GetPrCommentsError::CliNotInstalled { provider },
+                )));
+            }
+            Err(e) => return Err(e.into()),
+        };
+    let tracked = PrCommentFollowUp::find_by_merge_id(pool, pr_merge.id).await?;
+    let pending = pr_comment_follow_ups::pending_comments(
+        comments,
+        &tracked,
+        deployment.queued_message_service(),
+    );
+    if pending.is_empty() {
+        return Err(ApiError::BadRequest(
+            "There are no new PR comments to import".to_string(),
+        ));
+    }
+
+    let session_id = pr_comment_follow_ups::queue_follow_up(
+        pool,
+        deployment.queued_message_service(),
+        &pr_merge,
+        &pending,
+    )
+    .await?;
+    let execution_process = deployment
+        .container()
+        .start_queued_message(session_id)
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "pr_comments_imported",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "comment_count": pending.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(
+        ImportPrCommentsResponse {
+            comment_count: pending.len(),
+            execution_process_id: execution_process.map(|process| process.id),
+        },
+    )))
+}
@@ -0,0 +1,265 @@
+use std::path::PathBuf;
+
+use axum::{
+    Extension, Json,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessError},
+    execution_process_repo_state::ExecutionProcessRepoState,
+    repo::{Repo, RepoError},
+    workspace::Workspace,
+    workspace_repo::WorkspaceRepo,
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::container::ContainerService;
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use utils::{diff::DiffHunk, response::ApiResponse, text::short_uuid};
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct RevertFileRequest {
+    pub repo_id: Uuid,
+    /// Path relative to the repository root
+    pub path: String,
+    /// Revert to this execution's `before_head_commit` instead of the workspace base commit
+    pub execution_process_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct RevertHunkRequest {
+    pub repo_id: Uuid,
+    /// Path relative to the repository root
+    pub path: String,
+    /// Index into the hunks returned by `GET /revert/hunks`
+    pub hunk_index: usize,
+    /// Revert to this execution's `before_head_commit` instead of the workspace base commit
+    pub execution_process_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct FileHunksQuery {
+    pub repo_id: Uuid,
+    pub path: String,
+    pub execution_process_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct RevertResponse {
+    /// Commit the file or hunk was reverted to
+    pub source_commit: String,
+    /// New commit recording the revert; None if the file already matched
+    pub commit_sha: Option<String>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct FileHunksResponse {
+    pub source_commit: String,
+    pub hunks: Vec<DiffHunk>,
+}
+
+struct RevertTarget {
+    worktree_path: PathBuf,
+    source_commit: String,
+}
+
+/// Resolve the repo worktree and the commit to revert to: the given execution's
+/// `before_head_commit` when provided, otherwise the workspace base commit.
+async fn resolve_revert_target(
+    deployment: &DeploymentImpl,
+    pool: &SqlitePool,
+    workspace: &Workspace,
+    repo_id: Uuid,
+    execution_process_id: Option<Uuid>,
+) -> Result<RevertTarget, ApiError> {
+    let workspace_repo = WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+    let repo = Repo::find_by_id(pool, repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+
+    let container_ref = deployment
+        .container()
+        .ensure_container_exists(workspace)
+        .await?;
+    let worktree_path = PathBuf::from(container_ref).join(&repo.name);
+
+    let source_commit = match execution_process_id {
+        Some(process_id) => {
+            let process = ExecutionProcess::find_by_id(pool, process_id)
+                .await?
+                .ok_or(ExecutionProcessError::ExecutionProcessNotFound)?;
+            let belongs_to_workspace = process
+                .parent_workspace_and_session(pool)
+                .await?
+                .is_some_and(|(ws, _)| ws.id == workspace.id);
+            if !belongs_to_workspace {
+                return Err(ApiError::BadRequest(
+                    "Execution process does not belong to this workspace".to_string(),
+                ));
+            }
+
+            let before = ExecutionProcessRepoState::find_by_execution_process_id(pool, process.id)
+                .await?
+                .into_iter()
+                .find(|s| s.repo_id == repo_id)
+                .and_then(|s| s.before_head_commit);
+            let before = match before {
+                Some(oid) => Some(oid),
+                None => {
+                    ExecutionProcess::find_prev_after_head_commit(
+                        pool,
+                        process.session_id,
+                        process.id,
+                        repo_id,
+                    )
+                    .await?
+                }
+            };
+            before.ok_or_else(|| {
+                ApiError::BadRequest(
+                    "No recorded commit before this execution for the repository".to_string(),
+                )
+            })?
+        }
+        None => deployment
+            .git()
+            .get_base_commit(&repo.path, &workspace.branch, &workspace_repo.target_branch)?
+            .to_string(),
+    };
+
+    Ok(RevertTarget {
+        worktree_path,
+        source_commit,
+    })
+}
+
+fn short_sha(sha: &str) -> &str {
+    sha.get(..7).unwrap_or(sha)
+}
+
+pub async fn get_file_hunks(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<FileHunksQuery>,
+) -> Result<ResponseJson<ApiResponse<FileHunksResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let target = resolve_revert_target(
+        &deployment,
+        pool,
+        &workspace,
+        query.repo_id,
+        query.execution_process_id,
+    )
+    .await?;
+
+    let hunks =
+        deployment
+            .git()
+            .file_hunks(&target.worktree_path, &query.path, &target.source_commit)?;
+
+    Ok(ResponseJson(ApiResponse::success(FileHunksResponse {
+        hunks,
+        source_commit: target.source_commit,
+    })))
+}
+
+#[axum::debug_handler]
+pub async fn revert_file(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(request): Json<RevertFileRequest>,
+) -> Result<ResponseJson<ApiResponse<RevertResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let target = resolve_revert_target(
+        &deployment,
+        pool,
+        &workspace,
+        request.repo_id,
+        request.execution_process_id,
+    )
+    .await?;
+
+    let message = format!(
+        "Revert {} to {} (vibe-kanban {})",
+        request.path,
+        short_sha(&target.source_commit),
+        short_uuid(&workspace.id)
+    );
+    let commit_sha = deployment.git().revert_file(
+        &target.worktree_path,
+        &request.path,
+        &target.source_commit,
+        &message,
+    )?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_file_reverted",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "repo_id": request.repo_id.to_string(),
+                "to_execution": request.execution_process_id.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(RevertResponse {
+        source_commit: target.source_commit,
+        commit_sha,
+    })))
+}
+
+#[axum::debug_handler]
+pub async fn revert_hunk(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(request): Json<RevertHunkRequest>,
+) -> Result<ResponseJson<ApiResponse<RevertResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let target = resolve_revert_target(
+        &deployment,
+        pool,
+        &workspace,
+        request.repo_id,
+        request.execution_process_id,
+    )
+    .await?;
+
+    let message = format!(
+        "Revert hunk {} of {} to {} (vibe-kanban {})",
+        request.hunk_index + 1,
+        request.path,
+        short_sha(&target.source_commit),
+        short_uuid(&workspace.id)
+    );
+    let commit_sha = deployment.git().revert_hunk(
+        &target.worktree_path,
+        &request.path,
+        &target.source_commit,
+        request.hunk_index,
+        &message,
+    )?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_hunk_reverted",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "repo_id": request.repo_id.to_string(),
+                "to_execution": request.execution_process_id.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(RevertResponse {
+        source_commit: target.source_commit,
+        commit_sha,
+    })))
+}
@@ -0,0 +1,305 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    execution_process::ExecutionProcess,
+    repo::{Repo, RepoError},
+    review_comment::{
+        CreateReviewComment, ReviewComment, ReviewCommentStatus, UpdateReviewComment,
+    },
+    session::{Session, SessionError},
+    workspace::Workspace,
+    workspace_repo::WorkspaceRepo,
+};
+use deployment::Deployment;
+use executors::profile::ExecutorProfileId;
+use serde::Deserialize;
+use services::services::{container::ContainerService, review_comments};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::sessions::{CreateFollowUpAttempt, follow_up},
+};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct SendReviewRequest {
+    /// Session to continue, defaults to the workspace's latest session
+    pub session_id: Option<Uuid>,
+    /// Defaults to the executor of the session's latest coding agent turn
+    pub executor_profile_id: Option<ExecutorProfileId>,
+}
+
+/// Worktree and diff base of a workspace repository
+struct RepoSides {
+    repo: Repo,
+    worktree_path: PathBuf,
+    base_commit: String,
+}
+
+async fn repo_sides(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    repo_id: Uuid,
+) -> Result<RepoSides, ApiError> {
+    let pool = &deployment.db().pool;
+    let workspace_repo = WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+    let repo = Repo::find_by_id(pool, repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+    let container_ref = deployment
+        .container()
+        .ensure_container_exists(workspace)
+        .await?;
+    let base_commit = deployment
+        .git()
+        .get_base_commit(&repo.path, &workspace.branch, &workspace_repo.target_branch)?
+        .to_string();
+
+    Ok(RepoSides {
+        worktree_path: PathBuf::from(container_ref).join(&repo.name),
+        repo,
+        base_commit,
+    })
+}
+
+/// Follow unresolved comments to their lines in the current diff, persisting any
+/// move. Returns each comment with its repository name.
+async fn reanchor_comments(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    comments: Vec<ReviewComment>,
+) -> Result<Vec<(String, ReviewComment)>, ApiError> {
+    let pool = &deployment.db().pool;
+    let mut sides: HashMap<Uuid, RepoSides> = HashMap::new();
+    let mut anchored = Vec::with_capacity(comments.len());
+
+    for mut comment in comments {
+        if !sides.contains_key(&comment.repo_id) {
+            let repo_sides = repo_sides(deployment, workspace, comment.repo_id).await?;
+            sides.insert(comment.repo_id, repo_sides);
+        }
+        let repo_sides = &sides[&comment.repo_id];
+
+        if comment.status != ReviewCommentStatus::Resolved {
+            let current = review_comments::side_content(
+                deployment.git(),
+                &repo_sides.worktree_path,
+                &repo_sides.base_commit,
+                comment.side,
+                &comment.file_path,
+            )?;
+            let anchor = review_comments::reanchor(
+                deployment.git(),
+                &repo_sides.worktree_path,
+                &comment,
+                current.as_deref(),
+            )?;
+            if anchor.line != comment.line
+                || anchor.outdated != comment.outdated
+                || anchor.anchor_blob != comment.anchor_blob
+            {
+                ReviewComment::update_anchor(pool, comment.id, &anchor).await?;
+                comment.line = anchor.line;
+                comment.anchor_blob = anchor.anchor_blob;
+                comment.line_content = anchor.line_content;
+                comment.outdated = anchor.outdated;
+            }
+        }
+        anchored.push((repo_sides.repo.name.clone(), comment));
+    }
+
+    Ok(anchored)
+}
+
+pub async fn list_review_comments(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ReviewComment>>>, ApiError> {
+    let comments = ReviewComment::find_by_workspace_id(&deployment.db().pool, workspace.id).await?;
+    let comments = reanchor_comments(&deployment, &workspace, comments)
+        .await?
+        .into_iter()
+        .map(|(_, comment)| comment)
+        .collect();
+    Ok(ResponseJson(ApiResponse::success(comments)))
+}
+
+pub async fn create_review_comment(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateReviewComment>,
+) -> Result<ResponseJson<ApiResponse<ReviewComment>>, ApiError> {
+    if payload.body.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Comment body must not be empty".to_string(),
+        ));
+    }
+    let repo_sides = repo_sides(&deployment, &workspace, payload.repo_id).await?;
+    let content = review_comments::side_content(
+        deployment.git(),
+        &repo_sides.worktree_path,
+        &repo_sides.base_commit,
+        payload.side,
+        &payload.file_path,
+    )?
+    .ok_or_else(|| {
+        ApiError::BadRequest(format!(
+            "{} does not exist on that side of the diff",
+            payload.file_path
+        ))
+    })?;
+    let anchor = review_comments::anchor_line(
+        deployment.git(),
+        &repo_sides.worktree_path,
+        &content,
+        payload.line,
+    )?;
+    if anchor.outdated {
+        return Err(ApiError::BadRequest(format!(
+            "Line {} is outside of {}",
+            payload.line, payload.file_path
+        )));
+    }
+
+    let comment = ReviewComment::create(
+        &deployment.db().pool,
+        Uuid::new_v4(),
+        workspace.id,
+        &payload,
+        &anchor,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(comment)))
+}
+
+async fn find_workspace_comment(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    comment_id: Uuid,
+) -> Result<ReviewComment, ApiError> {
+    ReviewComment::find_by_id(&deployment.db().pool, comment_id)
+        .await?
+        .filter(|comment| comment.workspace_id == workspace.id)
+        .ok_or_else(|| ApiError::BadRequest("Review comment not found".to_string()))
+}
+
+pub async fn update_review_comment(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_id, comment_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateReviewComment>,
+) -> Result<ResponseJson<ApiResponse<ReviewComment>>, ApiError> {
+    find_workspace_comment(&deployment, &workspace, comment_id).await?;
+    if payload
+        .body
+        .as_ref()
+        .is_some_and(|body| body.trim().is_empty())
+    {
+        return Err(ApiError::BadRequest(
+            "Comment body must not be empty".to_string(),
+        ));
+    }
+    let comment = ReviewComment::update(&deployment.db().pool, comment_id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(comment)))
+}
+
+pub async fn delete_review_comment(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_id, comment_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    find_workspace_comment(&deployment, &workspace, comment_id).await?;
+    ReviewComment::delete(&deployment.db().pool, comment_id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Send every open comment to the agent as one follow-up prompt
+pub async fn send_review(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SendReviewRequest>,
+) -> Result<ResponseJson<ApiResponse<ExecutionProcess>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let session = match payload.session_id {
+        Some(session_id) => Session::find_by_id(pool, session_id)
+            .await?
+            .filter(|session| session.workspace_id == workspace.id)
+            .ok_or(SessionError::NotFound)?,
+        None => Session::find_latest_by_workspace_id(pool, workspace.id)
+            .await?
+            .ok_or(SessionError::NotFound)?,
+    };
+    if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, workspace.id)
+        .await?
+    {
+        return Err(ApiError::Conflict(
+            "Wait for the running process to finish before sending the review".to_string(),
+        ));
+    }
+    let executor_profile_id = match payload.executor_profile_id {
+        Some(profile) => profile,
+        None => ExecutionProcess::latest_executor_profile_for_session(pool, session.id)
+            .await?
+            .ok_or_else(|| ApiError::BadRequest("No executor to send the review to".to_string()))?,
+    };
+
+    let open: Vec<_> = ReviewComment::find_by_workspace_id(pool, workspace.id)
+        .await?
+        .into_iter()
+        .filter(|comment| comment.status == ReviewCommentStatus::Open)
+        .collect();
+    if open.is_empty() {
+        return Err(ApiError::BadRequest(
+            "There are no open review comments to send".to_string(),
+        ));
+    }
+    let anchored = reanchor_comments(&deployment, &workspace, open).await?;
+    let prompt = review_comments::build_review_prompt(
+        &anchored
+            .iter()
+            .map(|(repo_name, comment)| (repo_name.clone(), comment))
+            .collect::<Vec<_>>(),
+    );
+
+    let ResponseJson(response) = follow_up(
+        Extension(session),
+        State(deployment.clone()),
+        Json(CreateFollowUpAttempt {
+            prompt,
+            executor_profile_id,
+            retry_process_id: None,
+            force_when_dirty: None,
+            perform_git_reset: None,
+        }),
+    )
+    .await?;
+    let execution_process = response
+        .into_data()
+        .ok_or_else(|| ApiError::BadRequest("Failed to start the review turn".to_string()))?;
+
+    let comment_ids: Vec<Uuid> = anchored.iter().map(|(_, comment)| comment.id).collect();
+    ReviewComment::mark_sent(pool, &comment_ids, execution_process.id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "review_comments_sent",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "comment_count": comment_ids.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(execution_process)))
+}
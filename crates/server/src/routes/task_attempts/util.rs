@@ -2,15 +2,36 @@ use std::path::PathBuf;
 
 use db::models::{
     execution_process::ExecutionProcess, execution_process_repo_state::ExecutionProcessRepoState,
-    workspace::Workspace, workspace_repo::WorkspaceRepo,
+    repo::Repo, workspace::Workspace, workspace_repo::WorkspaceRepo,
 };
 use deployment::Deployment;
-use services::services::{container::ContainerService, git::WorktreeResetOptions};
+use services::services::{
+    container::{ContainerService, GateKind},
+    git::WorktreeResetOptions,
+};
 use sqlx::SqlitePool;
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError};
 
+/// Run the repo's gate script for `gate` and fail with a structured error linking
+/// to the gate process when it does not pass.
+pub async fn ensure_gate_passes(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    repo: &Repo,
+    gate: GateKind,
+) -> Result<(), ApiError> {
+    match deployment
+        .container()
+        .run_gate(workspace, repo, gate)
+        .await?
+    {
+        Some(failure) => Err(ApiError::GateFailed(failure)),
+        None => Ok(()),
+    }
+}
+
 /// Reset all repository worktrees to the state before the given process.
 /// For each repo, finds the before_head_commit from the target process,
 /// or falls back to the previous process's after_head_commit.
@@ -13,6 +13,7 @@ use axum::{
     routing::{delete, get, post, put},
 };
 use db::models::{
+    activity::ActivityAction,
     image::TaskImage,
     repo::{Repo, RepoError},
     task::{CreateTask, Task, TaskWithAttemptStatus, UpdateTask},
@@ -30,8 +31,10 @@ use utils::response::ApiResponse;
 use uuid::Uuid;
 
 use crate::{
-    DeploymentImpl, error::ApiError, middleware::load_task_middleware,
-    routes::task_attempts::WorkspaceRepoInput,
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{CurrentUser, load_task_middleware},
+    routes::{activity, task_attempts::WorkspaceRepoInput},
 };
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -106,6 +109,7 @@ pub async fn get_task(
 
 pub async fn create_task(
     State(deployment): State<DeploymentImpl>,
+    current: CurrentUser,
     Json(payload): Json<CreateTask>,
 ) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
     let id = Uuid::new_v4();
@@ -121,6 +125,14 @@ pub async fn create_task(
     if let Some(image_ids) = &payload.image_ids {
         TaskImage::associate_many_dedup(&deployment.db().pool, task.id, image_ids).await?;
     }
+    activity::record_for_task(
+        &deployment,
+        &current,
+        task.id,
+        ActivityAction::TaskCreated,
+        None,
+    )
+    .await;
 
     deployment
         .track_if_analytics_allowed(
@@ -146,6 +158,7 @@ pub struct CreateAndStartTaskRequest {
 
 pub async fn create_task_and_start(
     State(deployment): State<DeploymentImpl>,
+    current: CurrentUser,
     Json(payload): Json<CreateAndStartTaskRequest>,
 ) -> Result<ResponseJson<ApiResponse<TaskWithAttemptStatus>>, ApiError> {
     if payload.repos.is_empty() {
@@ -162,6 +175,14 @@ pub async fn create_task_and_start(
     if let Some(image_ids) = &payload.task.image_ids {
         TaskImage::associate_many_dedup(pool, task.id, image_ids).await?;
     }
+    activity::record_for_task(
+        &deployment,
+        &current,
+        task.id,
+        ActivityAction::TaskCreated,
+        None,
+    )
+    .await;
 
     deployment
         .track_if_analytics_allowed(
@@ -439,7 +460,8 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/", put(update_task))
         .route("/", delete(delete_task))
         .route("/save-session", post(save_task_session))
-        .route("/sessions", get(get_task_sessions));
+        .route("/sessions", get(get_task_sessions))
+        .route("/activity", get(activity::get_task_activity));
 
     let task_id_router = Router::new()
         .route("/", get(get_task))
@@ -1,32 +1,94 @@
 use std::path::PathBuf;
 
 use axum::{
-    Router,
+    Json, Router,
     extract::{
-        Query, State,
+        Path, Query, State,
         ws::{Message, WebSocket, WebSocketUpgrade},
     },
-    response::IntoResponse,
-    routing::get,
+    response::{IntoResponse, Json as ResponseJson},
+    routing::{delete, get},
 };
 use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use chrono::{DateTime, Utc};
 use db::models::{workspace::Workspace, workspace_repo::WorkspaceRepo};
 use deployment::Deployment;
 use futures_util::{SinkExt, StreamExt};
+use local_deployment::{
+    oci::{self, WorkspaceContainer},
+    pty::{PtyError, PtySessionInfo},
+};
 use serde::{Deserialize, Serialize};
+use services::services::container::ContainerError;
+use tokio::sync::broadcast::error::RecvError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{CurrentUser, RemoteAccessRole},
+};
 
 #[derive(Debug, Deserialize)]
 pub struct TerminalQuery {
     pub workspace_id: Uuid,
+    /// Attach to an existing session instead of starting a throwaway shell
+    pub session_id: Option<Uuid>,
+    /// Watch the session without sending input or resizing it. Viewers other
+    /// than the session's creator are always read-only.
+    #[serde(default)]
+    pub read_only: bool,
+    #[serde(default = "default_cols")]
+    pub cols: u16,
+    #[serde(default = "default_rows")]
+    pub rows: u16,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TerminalSessionsQuery {
+    pub workspace_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateTerminalSessionRequest {
+    pub workspace_id: Uuid,
+    pub name: Option<String>,
     #[serde(default = "default_cols")]
     pub cols: u16,
     #[serde(default = "default_rows")]
     pub rows: u16,
 }
 
+#[derive(Debug, Serialize, TS)]
+pub struct TerminalSession {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub name: String,
+    /// Persistent sessions survive disconnects until they are killed
+    pub persistent: bool,
+    pub created_at: DateTime<Utc>,
+    /// Number of WebSockets currently attached
+    pub viewers: usize,
+    /// The shell has exited; only its scrollback remains
+    pub exited: bool,
+}
+
+impl From<PtySessionInfo> for TerminalSession {
+    fn from(info: PtySessionInfo) -> Self {
+        Self {
+            id: info.id,
+            workspace_id: info.workspace_id,
+            name: info.name,
+            persistent: info.persistent,
+            created_at: info.created_at.into(),
+            viewers: info.viewers,
+            exited: info.exited,
+        }
+    }
+}
+
 fn default_cols() -> u16 {
     80
 }
@@ -46,20 +108,23 @@ enum TerminalCommand {
 #[serde(tag = "type", rename_all = "snake_case")]
 enum TerminalMessage {
     Output { data: String },
+    Exit,
     Error { message: String },
 }
 
-pub async fn terminal_ws(
-    ws: WebSocketUpgrade,
-    State(deployment): State<DeploymentImpl>,
-    Query(query): Query<TerminalQuery>,
-) -> Result<impl IntoResponse, ApiError> {
-    let attempt = Workspace::find_by_id(&deployment.db().pool, query.workspace_id)
+/// Directory the shell starts in, and the workspace's container when its project
+/// runs workspaces in one
+async fn resolve_working_dir(
+    deployment: &DeploymentImpl,
+    workspace_id: Uuid,
+) -> Result<(PathBuf, Option<WorkspaceContainer>), ApiError> {
+    let attempt = Workspace::find_by_id(&deployment.db().pool, workspace_id)
         .await?
         .ok_or_else(|| ApiError::BadRequest("Attempt not found".to_string()))?;
 
     let container_ref = attempt
         .container_ref
+        .clone()
         .ok_or_else(|| ApiError::BadRequest("Attempt has no workspace directory".to_string()))?;
 
     let base_dir = PathBuf::from(&container_ref);
@@ -70,7 +135,7 @@ pub async fn terminal_ws(
     }
 
     let mut working_dir = base_dir.clone();
-    match WorkspaceRepo::find_repos_for_workspace(&deployment.db().pool, query.workspace_id).await {
+    match WorkspaceRepo::find_repos_for_workspace(&deployment.db().pool, workspace_id).await {
         Ok(repos) if repos.len() == 1 => {
             let repo_dir = base_dir.join(&repos[0].name);
             if repo_dir.exists() {
@@ -87,26 +152,120 @@ pub async fn terminal_ws(
         }
     }
 
-    Ok(ws.on_upgrade(move |socket| {
-        handle_terminal_ws(socket, deployment, working_dir, query.cols, query.rows)
+    let container = oci::ensure_workspace_container(&deployment.db().pool, &attempt, &base_dir)
+        .await
+        .map_err(|e| ContainerError::Other(e.into()))?;
+
+    Ok((working_dir, container))
+}
+
+/// Identity recorded as a session's creator: the user account, or the role of
+/// a shared credential
+fn creator_of(user: &CurrentUser) -> String {
+    match (&user.user, user.role) {
+        (Some(user), _) => format!("user:{}", user.id),
+        (None, RemoteAccessRole::Admin) => "role:admin".to_string(),
+        (None, RemoteAccessRole::Member) => "role:member".to_string(),
+    }
+}
+
+/// Which session a socket attaches to
+enum TerminalTarget {
+    Existing {
+        session_id: Uuid,
+        read_only: bool,
+    },
+    /// A shell that lives only as long as the socket, started once it is open
+    Throwaway {
+        working_dir: PathBuf,
+        container: Option<WorkspaceContainer>,
+    },
+}
+
+pub async fn terminal_ws(
+    ws: WebSocketUpgrade,
+    State(deployment): State<DeploymentImpl>,
+    current_user: CurrentUser,
+    Query(query): Query<TerminalQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let creator = creator_of(&current_user);
+    let target = match query.session_id {
+        Some(session_id) => {
+            let session = deployment
+                .pty()
+                .get_session(session_id)
+                .filter(|session| session.workspace_id == query.workspace_id)
+                .ok_or(PtyError::SessionNotFound(session_id))?;
+            TerminalTarget::Existing {
+                session_id,
+                read_only: query.read_only || session.creator != creator,
+            }
+        }
+        None => {
+            let (working_dir, container) =
+                resolve_working_dir(&deployment, query.workspace_id).await?;
+            TerminalTarget::Throwaway {
+                working_dir,
+                container,
+            }
+        }
+    };
+
+    Ok(ws.on_upgrade(move |socket| async move {
+        let (session_id, read_only, close_on_disconnect) = match target {
+            TerminalTarget::Existing {
+                session_id,
+                read_only,
+            } => (session_id, read_only, false),
+            TerminalTarget::Throwaway {
+                working_dir,
+                container,
+            } => {
+                let created = deployment
+                    .pty()
+                    .create_session(
+                        query.workspace_id,
+                        None,
+                        false,
+                        creator,
+                        working_dir,
+                        container,
+                        query.cols,
+                        query.rows,
+                    )
+                    .await;
+                match created {
+                    Ok(session) => (session.id, query.read_only, true),
+                    Err(e) => {
+                        tracing::error!("Failed to start terminal session: {}", e);
+                        let _ = send_error(socket, &e.to_string()).await;
+                        return;
+                    }
+                }
+            }
+        };
+        handle_terminal_ws(
+            socket,
+            deployment,
+            session_id,
+            read_only,
+            close_on_disconnect,
+        )
+        .await
     }))
 }
 
 async fn handle_terminal_ws(
     socket: WebSocket,
     deployment: DeploymentImpl,
-    working_dir: PathBuf,
-    cols: u16,
-    rows: u16,
+    session_id: Uuid,
+    read_only: bool,
+    close_on_disconnect: bool,
 ) {
-    let (session_id, mut output_rx) = match deployment
-        .pty()
-        .create_session(working_dir, cols, rows)
-        .await
-    {
-        Ok(result) => result,
+    let mut attachment = match deployment.pty().attach(session_id) {
+        Ok(attachment) => attachment,
         Err(e) => {
-            tracing::error!("Failed to create PTY session: {}", e);
+            tracing::error!("Failed to attach to PTY session: {}", e);
             let _ = send_error(socket, &e.to_string()).await;
             return;
         }
@@ -115,36 +274,54 @@ async fn handle_terminal_ws(
     let (mut ws_sender, mut ws_receiver) = socket.split();
 
     let pty_service = deployment.pty().clone();
-    let session_id_for_input = session_id;
 
     let output_task = tokio::spawn(async move {
-        while let Some(data) = output_rx.recv().await {
-            let msg = TerminalMessage::Output {
-                data: BASE64.encode(&data),
-            };
-            let json = match serde_json::to_string(&msg) {
-                Ok(j) => j,
-                Err(_) => continue,
-            };
-            if ws_sender.send(Message::Text(json.into())).await.is_err() {
-                break;
+        let scrollback = std::mem::take(&mut attachment.scrollback);
+        if !scrollback.is_empty() && send_output(&mut ws_sender, &scrollback).await.is_err() {
+            return;
+        }
+
+        if let Some(mut output_rx) = attachment.output.take() {
+            loop {
+                match output_rx.recv().await {
+                    Ok(data) => {
+                        if send_output(&mut ws_sender, &data).await.is_err() {
+                            return;
+                        }
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::debug!(
+                            "Terminal viewer of {} fell behind, dropped {} chunks",
+                            session_id,
+                            skipped
+                        );
+                    }
+                    Err(RecvError::Closed) => break,
+                }
             }
         }
-        ws_sender
+
+        let json = serde_json::to_string(&TerminalMessage::Exit).unwrap_or_default();
+        let _ = ws_sender.send(Message::Text(json.into())).await;
+        let _ = ws_sender.close().await;
+        drop(attachment);
     });
 
     while let Some(Ok(msg)) = ws_receiver.next().await {
         match msg {
             Message::Text(text) => {
+                if read_only {
+                    continue;
+                }
                 if let Ok(cmd) = serde_json::from_str::<TerminalCommand>(&text) {
                     match cmd {
                         TerminalCommand::Input { data } => {
                             if let Ok(bytes) = BASE64.decode(&data) {
-                                let _ = pty_service.write(session_id_for_input, &bytes).await;
+                                let _ = pty_service.write(session_id, &bytes).await;
                             }
                         }
                         TerminalCommand::Resize { cols, rows } => {
-                            let _ = pty_service.resize(session_id_for_input, cols, rows).await;
+                            let _ = pty_service.resize(session_id, cols, rows).await;
                         }
                     }
                 }
@@ -154,10 +331,69 @@ async fn handle_terminal_ws(
         }
     }
 
-    let _ = deployment.pty().close_session(session_id).await;
+    if close_on_disconnect {
+        let _ = deployment.pty().close_session(session_id).await;
+    }
     output_task.abort();
 }
 
+async fn send_output<S>(ws_sender: &mut S, data: &[u8]) -> Result<(), axum::Error>
+where
+    S: SinkExt<Message, Error = axum::Error> + Unpin,
+{
+    let msg = TerminalMessage::Output {
+        data: BASE64.encode(data),
+    };
+    let json = serde_json::to_string(&msg).unwrap_or_default();
+    ws_sender.send(Message::Text(json.into())).await
+}
+
+pub async fn list_terminal_sessions(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TerminalSessionsQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<TerminalSession>>>, ApiError> {
+    let sessions = deployment
+        .pty()
+        .list_sessions(query.workspace_id)
+        .into_iter()
+        .map(TerminalSession::from)
+        .collect();
+    Ok(ResponseJson(ApiResponse::success(sessions)))
+}
+
+pub async fn create_terminal_session(
+    State(deployment): State<DeploymentImpl>,
+    current_user: CurrentUser,
+    Json(payload): Json<CreateTerminalSessionRequest>,
+) -> Result<ResponseJson<ApiResponse<TerminalSession>>, ApiError> {
+    let (working_dir, container) = resolve_working_dir(&deployment, payload.workspace_id).await?;
+    let session = deployment
+        .pty()
+        .create_session(
+            payload.workspace_id,
+            payload.name,
+            true,
+            creator_of(&current_user),
+            working_dir,
+            container,
+            payload.cols,
+            payload.rows,
+        )
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(session.into())))
+}
+
+pub async fn kill_terminal_session(
+    State(deployment): State<DeploymentImpl>,
+    Path(session_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    if deployment.pty().get_session(session_id).is_none() {
+        return Err(PtyError::SessionNotFound(session_id).into());
+    }
+    deployment.pty().close_session(session_id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
 async fn send_error(mut socket: WebSocket, message: &str) -> Result<(), axum::Error> {
     let msg = TerminalMessage::Error {
         message: message.to_string(),
@@ -169,5 +405,59 @@ async fn send_error(mut socket: WebSocket, message: &str) -> Result<(), axum::Er
 }
 
 pub fn router() -> Router<DeploymentImpl> {
-    Router::new().route("/terminal/ws", get(terminal_ws))
+    Router::new()
+        .route("/terminal/ws", get(terminal_ws))
+        .route(
+            "/terminal/sessions",
+            get(list_terminal_sessions).post(create_terminal_session),
+        )
+        .route(
+            "/terminal/sessions/{session_id}",
+            delete(kill_terminal_session),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use db::models::user::{User, UserRole};
+
+    use super::*;
+
+    fn account(role: UserRole) -> CurrentUser {
+        let now = Utc::now();
+        let user = User {
+            id: Uuid::new_v4(),
+            username: "ada".to_string(),
+            display_name: None,
+            password_hash: None,
+            role,
+            config_overrides: None,
+            created_at: now,
+            updated_at: now,
+        };
+        CurrentUser {
+            role: user.role.into(),
+            user: Some(user),
+        }
+    }
+
+    #[test]
+    fn only_the_creator_counts_as_the_creator() {
+        let admin = CurrentUser {
+            role: RemoteAccessRole::Admin,
+            user: None,
+        };
+        let member = CurrentUser {
+            role: RemoteAccessRole::Member,
+            user: None,
+        };
+        let ada = account(UserRole::Admin);
+        let other = account(UserRole::Admin);
+
+        assert_eq!(creator_of(&ada), creator_of(&ada.clone()));
+        assert_ne!(creator_of(&ada), creator_of(&other));
+        assert_ne!(creator_of(&ada), creator_of(&admin));
+        assert_ne!(creator_of(&admin), creator_of(&member));
+    }
 }
@@ -0,0 +1,139 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    middleware::from_fn,
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::user::{CreateUser, User, UserError, UserRole};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::{config::UserConfigOverrides, remote_access::hash_secret};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{CurrentUser, remote_access_sessions, require_admin},
+    routes::remote_access::MIN_PASSWORD_LEN,
+};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateUserRequest {
+    #[serde(flatten)]
+    pub user: CreateUser,
+    /// Accounts without a password can only be used through the proxy header
+    pub password: Option<String>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateUserRequest {
+    pub display_name: Option<String>,
+    pub role: UserRole,
+    /// Replaces the password when set
+    pub password: Option<String>,
+}
+
+async fn hash_password(password: Option<String>) -> Result<Option<String>, ApiError> {
+    let Some(password) = password else {
+        return Ok(None);
+    };
+    if password.chars().count() < MIN_PASSWORD_LEN {
+        return Err(ApiError::BadRequest(format!(
+            "The password must be at least {MIN_PASSWORD_LEN} characters"
+        )));
+    }
+    tokio::task::spawn_blocking(move || hash_secret(&password))
+        .await
+        .map(Some)
+        .map_err(|e| ApiError::BadRequest(e.to_string()))
+}
+
+pub async fn get_users(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<User>>>, ApiError> {
+    let users = User::find_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(users)))
+}
+
+pub async fn create_user(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateUserRequest>,
+) -> Result<ResponseJson<ApiResponse<User>>, ApiError> {
+    let username = payload.user.username.trim();
+    if username.is_empty() || username.contains(char::is_whitespace) {
+        return Err(ApiError::BadRequest("Invalid username".to_string()));
+    }
+    let password_hash = hash_password(payload.password).await?;
+    let user = User::create(
+        &deployment.db().pool,
+        Uuid::new_v4(),
+        &CreateUser {
+            username: username.to_string(),
+            ..payload.user
+        },
+        password_hash.as_deref(),
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed("user_created", serde_json::json!({ "role": user.role }))
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(user)))
+}
+
+pub async fn update_user(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateUserRequest>,
+) -> Result<ResponseJson<ApiResponse<User>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let password_hash = hash_password(payload.password).await?;
+    let user = User::update(pool, id, payload.display_name.as_deref(), payload.role).await?;
+    if let Some(hash) = password_hash {
+        User::set_password_hash(pool, id, Some(&hash)).await?;
+        remote_access_sessions().clear_user(id);
+    }
+    Ok(ResponseJson(ApiResponse::success(user)))
+}
+
+pub async fn delete_user(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    if User::delete(&deployment.db().pool, id).await? == 0 {
+        return Err(UserError::NotFound.into());
+    }
+    remote_access_sessions().clear_user(id);
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Replace the signed-in user's own editor, language and notification settings
+pub async fn update_my_config(
+    State(deployment): State<DeploymentImpl>,
+    current: CurrentUser,
+    Json(overrides): Json<UserConfigOverrides>,
+) -> Result<ResponseJson<ApiResponse<UserConfigOverrides>>, ApiError> {
+    let Some(user) = current.user else {
+        return Err(ApiError::BadRequest(
+            "Sign in to a user account to keep personal settings".to_string(),
+        ));
+    };
+    let raw = serde_json::to_string(&overrides).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    User::set_config_overrides(&deployment.db().pool, user.id, Some(&raw)).await?;
+    Ok(ResponseJson(ApiResponse::success(overrides)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    let admin_routes = Router::new()
+        .route("/users", get(get_users).post(create_user))
+        .route("/users/{id}", put(update_user).delete(delete_user))
+        .layer(from_fn(require_admin));
+
+    Router::new()
+        .route("/users/me/config", put(update_my_config))
+        .merge(admin_routes)
+}
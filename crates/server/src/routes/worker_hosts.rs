@@ -0,0 +1,121 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    middleware::from_fn,
+    response::Json as ResponseJson,
+    routing::{get, post, put},
+};
+use db::models::worker_host::{CreateWorkerHost, WorkerHost};
+use deployment::Deployment;
+use local_deployment::worker_host;
+use serde::Serialize;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::require_admin};
+
+#[derive(Debug, Serialize, TS)]
+pub struct WorkerHostCheckResponse {
+    /// `git --version` output from the host
+    pub git_version: String,
+}
+
+pub async fn get_worker_hosts(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<WorkerHost>>>, ApiError> {
+    let hosts = WorkerHost::find_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(hosts)))
+}
+
+/// Values end up on the `ssh` command line, so nothing may pass as an option
+fn validate(payload: &CreateWorkerHost) -> Result<(), ApiError> {
+    if payload.name.trim().is_empty() {
+        return Err(ApiError::BadRequest("Name is required".to_string()));
+    }
+    if payload.host.trim().is_empty()
+        || payload.host.starts_with('-')
+        || payload.host.contains(char::is_whitespace)
+    {
+        return Err(ApiError::BadRequest("Invalid host".to_string()));
+    }
+    if payload
+        .username
+        .as_deref()
+        .is_some_and(|user| user.is_empty() || user.starts_with('-') || user.contains('@'))
+    {
+        return Err(ApiError::BadRequest("Invalid username".to_string()));
+    }
+    if !payload.base_dir.starts_with('/') {
+        return Err(ApiError::BadRequest(
+            "The base directory must be an absolute path".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub async fn create_worker_host(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateWorkerHost>,
+) -> Result<ResponseJson<ApiResponse<WorkerHost>>, ApiError> {
+    validate(&payload)?;
+    let host = WorkerHost::create(&deployment.db().pool, Uuid::new_v4(), &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "worker_host_created",
+            serde_json::json!({ "worker_host_id": host.id.to_string() }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(host)))
+}
+
+pub async fn update_worker_host(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateWorkerHost>,
+) -> Result<ResponseJson<ApiResponse<WorkerHost>>, ApiError> {
+    validate(&payload)?;
+    let host = WorkerHost::update(&deployment.db().pool, id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(host)))
+}
+
+pub async fn delete_worker_host(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected = WorkerHost::delete(&deployment.db().pool, id).await?;
+    if rows_affected == 0 {
+        Err(ApiError::Database(sqlx::Error::RowNotFound))
+    } else {
+        Ok(ResponseJson(ApiResponse::success(())))
+    }
+}
+
+pub async fn check_worker_host(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<WorkerHostCheckResponse>>, ApiError> {
+    let host = WorkerHost::find_by_id(&deployment.db().pool, id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let git_version = worker_host::check_host(&host).await?;
+    Ok(ResponseJson(ApiResponse::success(
+        WorkerHostCheckResponse { git_version },
+    )))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route(
+            "/worker-hosts",
+            get(get_worker_hosts).post(create_worker_host),
+        )
+        .route(
+            "/worker-hosts/{id}",
+            put(update_worker_host).delete(delete_worker_host),
+        )
+        .route("/worker-hosts/{id}/check", post(check_worker_host))
+        .layer(from_fn(require_admin))
+}
@@ -25,6 +25,7 @@ use tokio::sync::{RwLock, oneshot};
 use utils::{
     approvals::{ApprovalRequest, ApprovalResponse, ApprovalStatus},
     log_msg::LogMsg,
+    metrics::metrics,
     msg_store::MsgStore,
 };
 use uuid::Uuid;
@@ -35,6 +36,7 @@ struct PendingApproval {
     entry: NormalizedEntry,
     execution_process_id: Uuid,
     tool_name: String,
+    requested_at: chrono::DateTime<chrono::Utc>,
     response_tx: oneshot::Sender<ApprovalStatus>,
 }
 
@@ -110,6 +112,7 @@ impl Approvals {
                         entry: matching_tool,
                         execution_process_id: request.execution_process_id,
                         tool_name: request.tool_name.clone(),
+                        requested_at: request.created_at,
                         response_tx: tx,
                     },
                 );
@@ -145,6 +148,7 @@ impl Approvals {
         req: ApprovalResponse,
     ) -> Result<(ApprovalStatus, ToolContext), ApprovalError> {
         if let Some((_, p)) = self.pending.remove(id) {
+            record_wait(&p, &req.status);
             self.completed.insert(id.to_string(), req.status.clone());
             let _ = p.response_tx.send(req.status.clone());
 
@@ -222,6 +226,7 @@ impl Approvals {
             completed.insert(id.clone(), status.clone());
 
             if is_timeout && let Some((_, pending_approval)) = pending.remove(&id) {
+                record_wait(&pending_approval, &status);
                 if pending_approval.response_tx.send(status.clone()).is_err() {
                     tracing::debug!("approval '{}' timeout notification receiver dropped", id);
                 }
@@ -282,6 +287,19 @@ impl Approvals {
     }
 }
 
+fn record_wait(pending: &PendingApproval, status: &ApprovalStatus) {
+    let outcome = match status {
+        ApprovalStatus::Pending => return,
+        ApprovalStatus::Approved => "approved",
+        ApprovalStatus::Denied { .. } => "denied",
+        ApprovalStatus::TimedOut => "timed_out",
+    };
+    let waited = (chrono::Utc::now() - pending.requested_at)
+        .to_std()
+        .unwrap_or_default();
+    metrics().approval_wait.observe(&[outcome], waited);
+}
+
 pub(crate) async fn ensure_task_in_review(pool: &SqlitePool, execution_process_id: Uuid) {
     if let Ok(ctx) = ExecutionProcess::load_context(pool, execution_process_id).await
         && ctx.task.status == TaskStatus::InProgress
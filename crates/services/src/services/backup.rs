@@ -0,0 +1,761 @@
+//! Full backups of the local instance: a consistent database snapshot, the
+//! config and profiles files and the image cache, packed into one ZIP archive
+//! with a manifest describing where it came from.
+
+use std::{
+    fs::{self, File},
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use db::DBService;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use ts_rs::TS;
+use utils::assets::{asset_dir, config_path, profiles_path};
+use uuid::Uuid;
+use zip::{ZipArchive, ZipWriter, write::SimpleFileOptions};
+
+use crate::services::{
+    config::AutoBackupConfig,
+    repo_validator::{RepoPathValidator, RepoValidationInfo, ValidationError, ValidationResult},
+};
+
+const MANIFEST_ENTRY: &str = "manifest.json";
+const DATABASE_ENTRY: &str = "db.sqlite";
+const CONFIG_ENTRY: &str = "config.json";
+const PROFILES_ENTRY: &str = "profiles.json";
+const IMAGES_PREFIX: &str = "images/";
+const FILE_PREFIX: &str = "vibe-kanban-backup-";
+
+#[derive(Debug, Error)]
+pub enum BackupError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Zip(#[from] zip::result::ZipError),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Validation(#[from] ValidationError),
+    #[error("Not a Vibe Kanban backup: {0}")]
+    InvalidArchive(String),
+    #[error(
+        "The backup was made by Vibe Kanban {0}, whose database is newer than this version supports"
+    )]
+    NewerSchema(String),
+    #[error("Backup not found")]
+    NotFound,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct BackupManifest {
+    pub app_version: String,
+    /// Newest database migration applied when the snapshot was taken
+    #[ts(type = "number")]
+    pub schema_version: i64,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    pub image_count: usize,
+}
+
+/// Why a backup was taken. Only automatic backups are rotated away.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupKind {
+    /// Requested from the settings
+    Manual,
+    /// Taken on the configured schedule
+    Auto,
+    /// Taken before a restore, so the restore can be undone
+    Safety,
+}
+
+impl BackupKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            BackupKind::Manual => "manual",
+            BackupKind::Auto => "auto",
+            BackupKind::Safety => "safety",
+        }
+    }
+
+    /// Kind recorded at the end of an archive name; older names carry none and
+    /// count as manual, so rotation never deletes them
+    fn from_name(name: &str) -> Self {
+        match name
+            .strip_suffix(".zip")
+            .and_then(|stem| stem.rsplit_once('-'))
+            .map(|(_, kind)| kind)
+        {
+            Some("auto") => BackupKind::Auto,
+            Some("safety") => BackupKind::Safety,
+            _ => BackupKind::Manual,
+        }
+    }
+}
+
+/// Archive stored in the backups directory
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct BackupFile {
+    pub name: String,
+    pub kind: BackupKind,
+    #[ts(type = "number")]
+    pub size: u64,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+pub fn backups_dir() -> PathBuf {
+    asset_dir().join("backups")
+}
+
+/// Whether `name` is a backup archive name that stays inside the backups directory
+fn is_backup_name(name: &str) -> bool {
+    name.starts_with(FILE_PREFIX)
+        && name.ends_with(".zip")
+        && !name.contains(['/', '\\'])
+        && !name.contains("..")
+}
+
+/// Unique archive name; it starts with the creation time, so names sort
+/// chronologically
+fn backup_name(created_at: DateTime<Utc>, kind: BackupKind) -> String {
+    let suffix = Uuid::new_v4().simple().to_string();
+    format!(
+        "{FILE_PREFIX}{}-{}-{}.zip",
+        created_at.format("%Y%m%d-%H%M%S-%3f"),
+        &suffix[..8],
+        kind.as_str()
+    )
+}
+
+/// Config and profiles files carried by a backup, with their archive entries
+fn settings_files() -> Vec<(&'static str, PathBuf)> {
+    vec![
+        (CONFIG_ENTRY, config_path()),
+        (PROFILES_ENTRY, profiles_path()),
+    ]
+}
+
+pub fn backup_path(name: &str) -> Result<PathBuf, BackupError> {
+    let path = backups_dir().join(name);
+    if !is_backup_name(name) || !path.is_file() {
+        return Err(BackupError::NotFound);
+    }
+    Ok(path)
+}
+
+/// Stored backups, newest first
+pub fn list_backups() -> Result<Vec<BackupFile>, BackupError> {
+    list_backups_in(&backups_dir())
+}
+
+fn list_backups_in(dir: &Path) -> Result<Vec<BackupFile>, BackupError> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if is_backup_name(&name) {
+            let metadata = entry.metadata()?;
+            backups.push(BackupFile {
+                kind: BackupKind::from_name(&name),
+                name,
+                size: metadata.len(),
+                created_at: metadata.modified()?.into(),
+            });
+        }
+    }
+    // Names embed the creation time, so they sort chronologically
+    backups.sort_by(|a, b| b.name.cmp(&a.name));
+    Ok(backups)
+}
+
+/// Delete all but the `keep` newest automatic backups. Manual and safety
+/// backups are left for the user to delete.
+pub fn rotate_backups(keep: usize) -> Result<usize, BackupError> {
+    rotate_backups_in(&backups_dir(), keep)
+}
+
+fn rotate_backups_in(dir: &Path, keep: usize) -> Result<usize, BackupError> {
+    let stale = list_backups_in(dir)?
+        .into_iter()
+        .filter(|backup| backup.kind == BackupKind::Auto)
+        .skip(keep)
+        .collect::<Vec<_>>();
+    for backup in &stale {
+        fs::remove_file(dir.join(&backup.name))?;
+    }
+    Ok(stale.len())
+}
+
+/// Take an automatic backup if the newest stored one is older than the
+/// configured interval, then apply rotation. Returns the new archive, if any.
+pub async fn run_scheduled_backup(
+    db: &DBService,
+    image_dir: &Path,
+    config: &AutoBackupConfig,
+) -> Result<Option<PathBuf>, BackupError> {
+    if !config.enabled {
+        return Ok(None);
+    }
+    let interval = chrono::Duration::hours(config.interval_hours.into());
+    if list_backups()?
+        .iter()
+        .find(|backup| backup.kind == BackupKind::Auto)
+        .is_some_and(|latest| Utc::now() - latest.created_at < interval)
+    {
+        return Ok(None);
+    }
+    let path = create_backup(db, image_dir, BackupKind::Auto).await?;
+    rotate_backups(config.keep.max(1) as usize)?;
+    Ok(Some(path))
+}
+
+/// Write a new archive to the backups directory and return its path
+pub async fn create_backup(
+    db: &DBService,
+    image_dir: &Path,
+    kind: BackupKind,
+) -> Result<PathBuf, BackupError> {
+    create_backup_in(db, &backups_dir(), image_dir, kind, settings_files()).await
+}
+
+async fn create_backup_in(
+    db: &DBService,
+    dir: &Path,
+    image_dir: &Path,
+    kind: BackupKind,
+    files: Vec<(&'static str, PathBuf)>,
+) -> Result<PathBuf, BackupError> {
+    let dir = dir.to_path_buf();
+    fs::create_dir_all(&dir)?;
+
+    let snapshot = dir.join(format!(".snapshot-{}.sqlite", Uuid::new_v4()));
+    let schema_version = db.applied_migration_version().await?;
+    let result = match db.backup_to(&snapshot).await {
+        Ok(()) => {
+            let image_dir = image_dir.to_path_buf();
+            let snapshot = snapshot.clone();
+            tokio::task::spawn_blocking(move || {
+                write_archive(&dir, &snapshot, &image_dir, kind, &files, schema_version)
+            })
+            .await
+            .map_err(io::Error::other)?
+        }
+        Err(e) => Err(e.into()),
+    };
+    let _ = fs::remove_file(&snapshot);
+    result
+}
+
+fn write_archive(
+    dir: &Path,
+    snapshot: &Path,
+    image_dir: &Path,
+    kind: BackupKind,
+    files: &[(&'static str, PathBuf)],
+    schema_version: i64,
+) -> Result<PathBuf, BackupError> {
+    let created_at = Utc::now();
+    let name = backup_name(created_at, kind);
+    let partial = dir.join(format!(".{name}.partial"));
+
+    let mut zip = ZipWriter::new(File::create(&partial)?);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    zip.start_file(DATABASE_ENTRY, options)?;
+    io::copy(&mut File::open(snapshot)?, &mut zip)?;
+
+    for (entry, path) in files {
+        if path.exists() {
+            zip.start_file(entry, options)?;
+            io::copy(&mut File::open(path)?, &mut zip)?;
+        }
+    }
+
+    let mut image_count = 0;
+    if image_dir.exists() {
+        for entry in walkdir::WalkDir::new(image_dir) {
+            let entry = entry.map_err(io::Error::other)?;
+            let Ok(relative) = entry.path().strip_prefix(image_dir) else {
+                continue;
+            };
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            zip.start_file(format!("{IMAGES_PREFIX}{relative}"), options)?;
+            io::copy(&mut File::open(entry.path())?, &mut zip)?;
+            image_count += 1;
+        }
+    }
+
+    let manifest = BackupManifest {
+        app_version: utils::version::APP_VERSION.to_string(),
+        schema_version,
+        created_at,
+        image_count,
+    };
+    zip.start_file(MANIFEST_ENTRY, options)?;
+    serde_json::to_writer_pretty(&mut zip, &manifest)?;
+    zip.finish()?;
+
+    let path = dir.join(name);
+    fs::rename(&partial, &path)?;
+    Ok(path)
+}
+
+/// Read and check the manifest of an archive without restoring anything
+pub fn read_manifest(archive: &Path) -> Result<BackupManifest, BackupError> {
+    let mut zip = ZipArchive::new(File::open(archive)?)?;
+    let manifest: BackupManifest = match zip.by_name(MANIFEST_ENTRY) {
+        Ok(entry) => serde_json::from_reader(entry)?,
+        Err(zip::result::ZipError::FileNotFound) => {
+            return Err(BackupError::InvalidArchive("missing manifest".to_string()));
+        }
+        Err(e) => return Err(e.into()),
+    };
+    if zip.index_for_name(DATABASE_ENTRY).is_none() {
+        return Err(BackupError::InvalidArchive("missing database".to_string()));
+    }
+    if manifest.schema_version > db::latest_migration_version() {
+        return Err(BackupError::NewerSchema(manifest.app_version));
+    }
+    Ok(manifest)
+}
+
+/// Replace the database, config, profiles and images with the archive's
+/// contents. The database is migrated to this version's schema afterwards, and
+/// the caller is responsible for reloading config it holds in memory.
+///
+/// Everything is unpacked before anything is replaced, and the replaced files
+/// are put back if the database restore fails.
+pub async fn restore_backup(
+    db: &DBService,
+    image_dir: &Path,
+    archive: &Path,
+) -> Result<BackupManifest, BackupError> {
+    restore_backup_in(db, &backups_dir(), image_dir, archive, settings_files()).await
+}
+
+async fn restore_backup_in(
+    db: &DBService,
+    dir: &Path,
+    image_dir: &Path,
+    archive: &Path,
+    files: Vec<(&'static str, PathBuf)>,
+) -> Result<BackupManifest, BackupError> {
+    let manifest = read_manifest(archive)?;
+
+    fs::create_dir_all(dir)?;
+    let staging = Staging::new(dir, image_dir, files);
+    let result = restore_staged(db, archive, &staging).await;
+    staging.remove();
+    result.map(|()| manifest)
+}
+
+async fn restore_staged(
+    db: &DBService,
+    archive: &Path,
+    staging: &Staging,
+) -> Result<(), BackupError> {
+    {
+        let archive = archive.to_path_buf();
+        let staging = staging.clone();
+        tokio::task::spawn_blocking(move || staging.extract(&archive))
+            .await
+            .map_err(io::Error::other)??;
+    }
+
+    let mut swapped = Vec::new();
+    for (staged, live) in staging.replacements() {
+        match swap_in(&staged, &live) {
+            Ok(swap) => swapped.push(swap),
+            Err(e) => {
+                roll_back(swapped);
+                return Err(e.into());
+            }
+        }
+    }
+    if let Err(e) = db.restore_from(&staging.database).await {
+        roll_back(swapped);
+        return Err(e.into());
+    }
+    for swap in swapped {
+        if let Some(previous) = swap.previous {
+            let _ = remove_path(&previous);
+        }
+    }
+    Ok(())
+}
+
+/// Where a restore unpacks the archive. Files are staged next to the ones they
+/// replace, so swapping them in is a rename on the same filesystem.
+#[derive(Clone)]
+struct Staging {
+    database: PathBuf,
+    /// Archive entry, staged path and live path of each settings file
+    files: Vec<(&'static str, PathBuf, PathBuf)>,
+    images: PathBuf,
+    image_dir: PathBuf,
+}
+
+impl Staging {
+    fn new(dir: &Path, image_dir: &Path, files: Vec<(&'static str, PathBuf)>) -> Self {
+        let id = Uuid::new_v4();
+        Self {
+            database: dir.join(format!(".restore-{id}.sqlite")),
+            files: files
+                .into_iter()
+                .map(|(entry, live)| (entry, sibling(&live, &format!("restore-{id}")), live))
+                .collect(),
+            images: sibling(image_dir, &format!("restore-{id}")),
+            image_dir: image_dir.to_path_buf(),
+        }
+    }
+
+    fn extract(&self, archive: &Path) -> Result<(), BackupError> {
+        let mut zip = ZipArchive::new(File::open(archive)?)?;
+        io::copy(
+            &mut zip.by_name(DATABASE_ENTRY)?,
+            &mut File::create(&self.database)?,
+        )?;
+
+        for (entry, staged, _) in &self.files {
+            let mut contents = Vec::new();
+            match zip.by_name(entry) {
+                Ok(mut file) => file.read_to_end(&mut contents)?,
+                Err(zip::result::ZipError::FileNotFound) => continue,
+                Err(e) => return Err(e.into()),
+            };
+            fs::write(staged, contents)?;
+        }
+
+        fs::create_dir_all(&self.images)?;
+        for i in 0..zip.len() {
+            let mut file = zip.by_index(i)?;
+            let Some(relative) = file
+                .enclosed_name()
+                .and_then(|name| name.strip_prefix(IMAGES_PREFIX).ok().map(Path::to_path_buf))
+            else {
+                continue;
+            };
+            if file.is_dir() || relative.as_os_str().is_empty() {
+                continue;
+            }
+            let path = self.images.join(relative);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            io::copy(&mut file, &mut File::create(path)?)?;
+        }
+        Ok(())
+    }
+
+    /// Staged paths with the live paths they replace. Settings files missing
+    /// from the archive keep their current contents.
+    fn replacements(&self) -> Vec<(PathBuf, PathBuf)> {
+        self.files
+            .iter()
+            .filter(|(_, staged, _)| staged.exists())
+            .map(|(_, staged, live)| (staged.clone(), live.clone()))
+            .chain([(self.images.clone(), self.image_dir.clone())])
+            .collect()
+    }
+
+    /// Delete whatever was staged but not swapped in
+    fn remove(&self) {
+        let _ = fs::remove_file(&self.database);
+        for (_, staged, _) in &self.files {
+            let _ = remove_path(staged);
+        }
+        let _ = remove_path(&self.images);
+    }
+}
+
+/// Live file or directory replaced during a restore, with where its previous
+/// contents were moved
+struct Swapped {
+    live: PathBuf,
+    previous: Option<PathBuf>,
+}
+
+fn swap_in(staged: &Path, live: &Path) -> io::Result<Swapped> {
+    let previous = live
+        .exists()
+        .then(|| sibling(live, &format!("previous-{}", Uuid::new_v4())));
+    if let Some(previous) = &previous {
+        fs::rename(live, previous)?;
+    }
+    if let Err(e) = fs::rename(staged, live) {
+        if let Some(previous) = &previous {
+            let _ = fs::rename(previous, live);
+        }
+        return Err(e);
+    }
+    Ok(Swapped {
+        live: live.to_path_buf(),
+        previous,
+    })
+}
+
+/// Put back what `swap_in` replaced, newest first
+fn roll_back(swapped: Vec<Swapped>) {
+    for swap in swapped.into_iter().rev() {
+        let restored = remove_path(&swap.live).and_then(|()| match &swap.previous {
+            Some(previous) => fs::rename(previous, &swap.live),
+            None => Ok(()),
+        });
+        if let Err(e) = restored {
+            tracing::error!("Failed to put back {}: {}", swap.live.display(), e);
+        }
+    }
+}
+
+/// Hidden path next to `path`, tagged so concurrent restores don't collide
+fn sibling(path: &Path, tag: &str) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{name}.{tag}"))
+}
+
+fn remove_path(path: &Path) -> io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(path),
+        Ok(_) => fs::remove_file(path),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Point repositories whose path starts with `from` at the same location under
+/// `to`, when that location is a valid repository, then report every
+/// repository's status so the rest can be fixed by hand.
+pub async fn relink_repos(
+    pool: &SqlitePool,
+    from: Option<&Path>,
+    to: Option<&Path>,
+) -> Result<Vec<RepoValidationInfo>, BackupError> {
+    let validator = RepoPathValidator::new();
+    if let (Some(from), Some(to)) = (from, to) {
+        for repo in db::models::repo::Repo::all(pool).await? {
+            let Ok(rest) = repo.path.strip_prefix(from) else {
+                continue;
+            };
+            let candidate = to.join(rest);
+            if matches!(
+                validator.validate_repo(&candidate, None).await,
+                Ok(ValidationResult::Valid)
+            ) && let Err(e) = validator.fix_repo_path(pool, repo.id, &candidate).await
+            {
+                tracing::warn!("Failed to relink repo {}: {}", repo.id, e);
+            }
+        }
+    }
+    Ok(validator.validate_all_repos(pool).await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::sqlite::SqlitePoolOptions;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    async fn test_db() -> DBService {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("../db/migrations").run(&pool).await.unwrap();
+        DBService { pool }
+    }
+
+    /// Backups directory, image cache and settings files inside `root`
+    fn layout(root: &Path) -> (PathBuf, PathBuf, Vec<(&'static str, PathBuf)>) {
+        (
+            root.join("backups"),
+            root.join("images"),
+            vec![
+                (CONFIG_ENTRY, root.join("config.json")),
+                (PROFILES_ENTRY, root.join("profiles.json")),
+            ],
+        )
+    }
+
+    async fn project_name(db: &DBService) -> String {
+        sqlx::query_scalar("SELECT name FROM projects")
+            .fetch_one(&db.pool)
+            .await
+            .unwrap()
+    }
+
+    /// Names in `root` that a restore left behind
+    fn leftovers(root: &Path) -> Vec<String> {
+        fs::read_dir(root)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .filter(|name| name.contains(".restore-") || name.contains(".previous-"))
+            .collect()
+    }
+
+    #[test]
+    fn backup_names_stay_inside_the_directory() {
+        assert!(is_backup_name("vibe-kanban-backup-20260101-120000.zip"));
+        assert!(!is_backup_name("../vibe-kanban-backup-x.zip"));
+        assert!(!is_backup_name("vibe-kanban-backup-../../db.zip"));
+        assert!(!is_backup_name("vibe-kanban-backup-x/y.zip"));
+        assert!(!is_backup_name("db.sqlite"));
+    }
+
+    #[test]
+    fn backup_names_are_unique_and_record_their_kind() {
+        let now = Utc::now();
+        let first = backup_name(now, BackupKind::Auto);
+        let second = backup_name(now, BackupKind::Auto);
+        assert_ne!(first, second);
+        assert!(is_backup_name(&first));
+        assert_eq!(BackupKind::from_name(&first), BackupKind::Auto);
+        assert_eq!(
+            BackupKind::from_name(&backup_name(now, BackupKind::Safety)),
+            BackupKind::Safety
+        );
+        assert_eq!(
+            BackupKind::from_name("vibe-kanban-backup-20260101-120000.zip"),
+            BackupKind::Manual
+        );
+    }
+
+    #[test]
+    fn rotation_only_deletes_old_automatic_backups() {
+        let tmp = TempDir::new().unwrap();
+        let names = [
+            "vibe-kanban-backup-20260101-120000-000-00000001-auto.zip",
+            "vibe-kanban-backup-20260101-130000-000-00000002-manual.zip",
+            "vibe-kanban-backup-20260101-140000-000-00000003-safety.zip",
+            "vibe-kanban-backup-20260101-150000-000-00000004-auto.zip",
+            "vibe-kanban-backup-20260101-160000-000-00000005-auto.zip",
+        ];
+        for name in names {
+            fs::write(tmp.path().join(name), b"zip").unwrap();
+        }
+
+        assert_eq!(rotate_backups_in(tmp.path(), 2).unwrap(), 1);
+        let mut kept = list_backups_in(tmp.path())
+            .unwrap()
+            .into_iter()
+            .map(|backup| backup.name)
+            .collect::<Vec<_>>();
+        kept.sort();
+        assert_eq!(kept, names[1..]);
+    }
+
+    #[tokio::test]
+    async fn restore_brings_back_what_was_backed_up() {
+        let tmp = TempDir::new().unwrap();
+        let (dir, images, files) = layout(tmp.path());
+        let db = test_db().await;
+        sqlx::query("INSERT INTO projects (id, name) VALUES (?, 'before')")
+            .bind(Uuid::new_v4())
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        fs::write(&files[0].1, r#"{"theme":"light"}"#).unwrap();
+        fs::write(&files[1].1, r#"{"executors":{}}"#).unwrap();
+        fs::create_dir_all(images.join("nested")).unwrap();
+        fs::write(images.join("nested/one.png"), b"one").unwrap();
+
+        let archive = create_backup_in(&db, &dir, &images, BackupKind::Manual, files.clone())
+            .await
+            .unwrap();
+        assert_eq!(read_manifest(&archive).unwrap().image_count, 1);
+
+        sqlx::query("UPDATE projects SET name = 'after'")
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        fs::write(&files[0].1, r#"{"theme":"dark"}"#).unwrap();
+        fs::remove_file(&files[1].1).unwrap();
+        fs::remove_file(images.join("nested/one.png")).unwrap();
+        fs::write(images.join("two.png"), b"two").unwrap();
+
+        restore_backup_in(&db, &dir, &images, &archive, files.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(project_name(&db).await, "before");
+        assert_eq!(
+            fs::read_to_string(&files[0].1).unwrap(),
+            r#"{"theme":"light"}"#
+        );
+        assert_eq!(
+            fs::read_to_string(&files[1].1).unwrap(),
+            r#"{"executors":{}}"#
+        );
+        assert_eq!(fs::read(images.join("nested/one.png")).unwrap(), b"one");
+        assert!(!images.join("two.png").exists());
+        assert!(leftovers(tmp.path()).is_empty());
+        assert!(leftovers(&dir).is_empty());
+    }
+
+    #[tokio::test]
+    async fn failed_database_restore_puts_the_files_back() {
+        let tmp = TempDir::new().unwrap();
+        let (dir, images, files) = layout(tmp.path());
+        let db = test_db().await;
+        fs::write(&files[0].1, r#"{"theme":"dark"}"#).unwrap();
+        fs::create_dir_all(&images).unwrap();
+        fs::write(images.join("current.png"), b"current").unwrap();
+
+        fs::create_dir_all(&dir).unwrap();
+        let archive = dir.join("broken.zip");
+        let mut zip = ZipWriter::new(File::create(&archive).unwrap());
+        let options = SimpleFileOptions::default();
+        zip.start_file(DATABASE_ENTRY, options).unwrap();
+        io::Write::write_all(&mut zip, b"not a database").unwrap();
+        zip.start_file(CONFIG_ENTRY, options).unwrap();
+        io::Write::write_all(&mut zip, br#"{"theme":"light"}"#).unwrap();
+        zip.start_file(format!("{IMAGES_PREFIX}restored.png"), options)
+            .unwrap();
+        io::Write::write_all(&mut zip, b"restored").unwrap();
+        zip.start_file(MANIFEST_ENTRY, options).unwrap();
+        serde_json::to_writer(
+            &mut zip,
+            &BackupManifest {
+                app_version: "0.0.0".to_string(),
+                schema_version: 0,
+                created_at: Utc::now(),
+                image_count: 1,
+            },
+        )
+        .unwrap();
+        zip.finish().unwrap();
+
+        assert!(
+            restore_backup_in(&db, &dir, &images, &archive, files.clone())
+                .await
+                .is_err()
+        );
+
+        assert_eq!(
+            fs::read_to_string(&files[0].1).unwrap(),
+            r#"{"theme":"dark"}"#
+        );
+        assert!(!files[1].1.exists());
+        assert_eq!(fs::read(images.join("current.png")).unwrap(), b"current");
+        assert!(!images.join("restored.png").exists());
+        assert!(leftovers(tmp.path()).is_empty());
+        assert!(leftovers(&dir).is_empty());
+    }
+}
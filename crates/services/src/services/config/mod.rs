@@ -1,6 +1,8 @@
 use std::path::PathBuf;
 
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use ts_rs::TS;
 
 pub mod editor;
 mod versions;
@@ -27,6 +29,41 @@ pub type GitHubConfig = versions::v8::GitHubConfig;
 pub type UiLanguage = versions::v8::UiLanguage;
 pub type ShowcaseState = versions::v8::ShowcaseState;
 pub type SendMessageShortcut = versions::v8::SendMessageShortcut;
+pub type RemoteAccessConfig = versions::v8::RemoteAccessConfig;
+pub type AutoBackupConfig = versions::v8::AutoBackupConfig;
+
+/// A user's own settings on a shared server, layered over the global config
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct UserConfigOverrides {
+    pub editor: Option<EditorConfig>,
+    pub language: Option<UiLanguage>,
+    pub notifications: Option<NotificationConfig>,
+}
+
+impl UserConfigOverrides {
+    /// Parse the JSON stored on a user; unreadable overrides are ignored
+    pub fn from_json(raw: Option<&str>) -> Self {
+        raw.and_then(|raw| {
+            serde_json::from_str(raw)
+                .inspect_err(|e| tracing::warn!("Ignoring invalid user config overrides: {}", e))
+                .ok()
+        })
+        .unwrap_or_default()
+    }
+
+    pub fn apply(&self, config: &mut Config) {
+        if let Some(editor) = &self.editor {
+            config.editor = editor.clone();
+        }
+        if let Some(language) = &self.language {
+            config.language = language.clone();
+        }
+        if let Some(notifications) = &self.notifications {
+            config.notifications = notifications.clone();
+        }
+    }
+}
 
 /// Will always return config, trying old schemas or eventually returning default
 pub async fn load_config_from_file(config_path: &PathBuf) -> Config {
@@ -24,6 +24,66 @@ pub enum SendMessageShortcut {
     Enter,
 }
 
+/// Access to the server from other machines, always served over HTTPS. The
+/// credential hashes are only changed through the remote access endpoints and
+/// never sent to the frontend.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct RemoteAccessConfig {
+    pub enabled: bool,
+    /// Port to listen on; the PORT environment variable takes precedence
+    pub port: Option<u16>,
+    /// PEM certificate chain; a self-signed certificate is generated when unset
+    pub tls_cert_path: Option<String>,
+    pub tls_key_path: Option<String>,
+    /// Hash of the admin password
+    #[ts(skip)]
+    pub password_hash: Option<String>,
+    /// Hash of the bearer token, which grants non-admin access
+    #[ts(skip)]
+    pub token_hash: Option<String>,
+    /// Hash of the bearer token Prometheus scrapes `/api/metrics` with, which
+    /// grants nothing else
+    #[ts(skip)]
+    pub metrics_token_hash: Option<String>,
+    /// Header carrying the username set by an authenticating reverse proxy.
+    /// Only read on connections from this machine, where the proxy runs.
+    pub trusted_user_header: Option<String>,
+}
+
+impl RemoteAccessConfig {
+    /// The config as sent to clients, with the credential hashes removed
+    pub fn without_secrets(self) -> Self {
+        Self {
+            password_hash: None,
+            token_hash: None,
+            metrics_token_hash: None,
+            ..self
+        }
+    }
+}
+
+/// Periodic full backups written to the backups directory
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct AutoBackupConfig {
+    pub enabled: bool,
+    pub interval_hours: u32,
+    /// Number of automatic backups kept; older ones are deleted after each new
+    /// automatic backup
+    pub keep: u32,
+}
+
+impl Default for AutoBackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_hours: 24,
+            keep: 7,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, TS)]
 pub struct Config {
     pub config_version: String,
@@ -56,6 +116,19 @@ pub struct Config {
     pub commit_reminder: bool,
     #[serde(default)]
     pub send_message_shortcut: SendMessageShortcut,
+    /// Days to keep raw agent output before only normalized entries are kept; None keeps it forever
+    #[serde(default)]
+    pub raw_log_retention_days: Option<u32>,
+    /// Queue new PR comments as follow-ups for the workspace's agent automatically
+    #[serde(default)]
+    pub pr_comment_follow_ups_enabled: bool,
+    /// Reply to PR comments with the commit that addressed them
+    #[serde(default)]
+    pub pr_comment_replies_enabled: bool,
+    #[serde(default)]
+    pub remote_access: RemoteAccessConfig,
+    #[serde(default)]
+    pub auto_backup: AutoBackupConfig,
 }
 
 impl Config {
@@ -85,6 +158,11 @@ impl Config {
             beta_workspaces_invitation_sent: false,
             commit_reminder: false,
             send_message_shortcut: SendMessageShortcut::default(),
+            raw_log_retention_days: None,
+            pr_comment_follow_ups_enabled: false,
+            pr_comment_replies_enabled: false,
+            remote_access: RemoteAccessConfig::default(),
+            auto_backup: AutoBackupConfig::default(),
         }
     }
 
@@ -139,6 +217,11 @@ impl Default for Config {
             beta_workspaces_invitation_sent: false,
             commit_reminder: false,
             send_message_shortcut: SendMessageShortcut::default(),
+            raw_log_retention_days: None,
+            pr_comment_follow_ups_enabled: false,
+            pr_comment_replies_enabled: false,
+            remote_access: RemoteAccessConfig::default(),
+            auto_backup: AutoBackupConfig::default(),
         }
     }
 }
@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -10,6 +10,7 @@ use db::{
     DBService,
     models::{
         coding_agent_turn::{CodingAgentTurn, CreateCodingAgentTurn},
+        conversation_entry::ConversationEntry,
         execution_process::{
             CreateExecutionProcess, ExecutionContext, ExecutionProcess, ExecutionProcessError,
             ExecutionProcessRunReason, ExecutionProcessStatus,
@@ -36,14 +37,19 @@ use executors::{
         script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
     },
     executors::{ExecutorError, StandardCodingAgentExecutor},
-    logs::{NormalizedEntry, NormalizedEntryError, NormalizedEntryType, utils::ConversationPatch},
+    logs::{
+        NormalizedConversation, NormalizedEntry, NormalizedEntryError, NormalizedEntryType,
+        utils::{ConversationPatch, patch::extract_normalized_entry_from_patch},
+    },
     profile::ExecutorProfileId,
 };
 use futures::{StreamExt, future, stream::BoxStream};
 use json_patch::Patch;
+use serde::{Deserialize, Serialize};
 use sqlx::Error as SqlxError;
 use thiserror::Error;
 use tokio::{sync::RwLock, task::JoinHandle};
+use ts_rs::TS;
 use utils::{
     log_msg::LogMsg,
     msg_store::MsgStore,
@@ -52,13 +58,106 @@ use utils::{
 use uuid::Uuid;
 
 use crate::services::{
+    conversation_export::{ExportedExecution, entries_from_patches},
+    conversation_search::ConversationIndexer,
+    diff_stream::DiffStreamTarget,
     git::{GitService, GitServiceError},
     notification::NotificationService,
+    session_import::{ExternalAgent, ExternalSession, install_claude_session},
     workspace_manager::WorkspaceError as WorkspaceManagerError,
     worktree_manager::WorktreeError,
 };
 pub type ContainerRef = String;
 
+/// Processes indexed per query while backfilling conversation search
+const BACKFILL_BATCH_SIZE: i64 = 20;
+
+/// Upper bound on re-normalizing the stored logs of one finished process
+const NORMALIZE_STORED_LOGS_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Processes compacted or purged per query during log maintenance
+const LOG_MAINTENANCE_BATCH_SIZE: i64 = 50;
+
+/// How long a gate script may run when the repo doesn't set a timeout
+const DEFAULT_GATE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30 * 60);
+
+/// Processes handled by one log maintenance run
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogMaintenanceSummary {
+    /// Processes whose logs were moved into compressed chunks
+    pub compacted: usize,
+    /// Processes whose raw output was replaced by normalized entries
+    pub purged: usize,
+}
+
+/// Git operation guarded by a repo gate script
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum GateKind {
+    PreMerge,
+    PrePush,
+}
+
+impl GateKind {
+    pub fn script(self, repo: &Repo) -> Option<&str> {
+        let script = match self {
+            GateKind::PreMerge => repo.pre_merge_script.as_deref(),
+            GateKind::PrePush => repo.pre_push_script.as_deref(),
+        };
+        script.filter(|s| !s.trim().is_empty())
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GateKind::PreMerge => "Pre-merge",
+            GateKind::PrePush => "Pre-push",
+        }
+    }
+
+    /// How long the repo's gate scripts may run before they are killed
+    pub fn timeout(repo: &Repo) -> std::time::Duration {
+        repo.gate_timeout_secs
+            .filter(|secs| *secs > 0)
+            .map(|secs| std::time::Duration::from_secs(secs as u64))
+            .unwrap_or(DEFAULT_GATE_TIMEOUT)
+    }
+}
+
+/// A gate script that did not pass; the process logs explain why
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct GateFailure {
+    pub gate: GateKind,
+    pub repo_id: Uuid,
+    pub repo_name: String,
+    pub execution_process_id: Uuid,
+    pub status: ExecutionProcessStatus,
+    pub exit_code: Option<i64>,
+    /// The script was killed after running past the repo's gate timeout
+    pub timed_out: bool,
+}
+
+/// Whether a finished gate process passed
+fn gate_passed(status: &ExecutionProcessStatus, exit_code: Option<i64>, timed_out: bool) -> bool {
+    !timed_out && *status == ExecutionProcessStatus::Completed && exit_code == Some(0)
+}
+
+/// Wait until a process's log stream reports it finished. Returns false when
+/// `timeout` elapses first.
+async fn wait_for_finished(msg_store: Arc<MsgStore>, timeout: std::time::Duration) -> bool {
+    // Only the stream is kept so the store can be dropped once the process exits
+    let mut stream = msg_store.history_plus_stream();
+    drop(msg_store);
+
+    let finished = async {
+        while let Some(msg) = stream.next().await {
+            if matches!(msg, Ok(LogMsg::Finished)) {
+                break;
+            }
+        }
+    };
+    tokio::time::timeout(timeout, finished).await.is_ok()
+}
+
 #[derive(Debug, Error)]
 pub enum ContainerError {
     #[error(transparent)]
@@ -180,14 +279,14 @@ pub trait ContainerService {
 
     /// A context is finalized when
     /// - Always when the execution process has failed or been killed
-    /// - Never when the run reason is DevServer
+    /// - Never when the run reason is DevServer or GateScript
     /// - Never when a setup script has no next_action (parallel mode)
     /// - The next action is None (no follow-up actions)
     fn should_finalize(&self, ctx: &ExecutionContext) -> bool {
-        // Never finalize DevServer processes
+        // Never finalize DevServer or gate processes
         if matches!(
             ctx.execution_process.run_reason,
-            ExecutionProcessRunReason::DevServer
+            ExecutionProcessRunReason::DevServer | ExecutionProcessRunReason::GateScript
         ) {
             return false;
         }
@@ -509,6 +608,81 @@ pub trait ContainerService {
         chained
     }
 
+    /// Run the repo's gate script in the workspace worktree and wait for it to exit.
+    /// Scripts that outlive the repo's gate timeout are killed and fail the gate.
+    /// Returns None when the gate passed or no script is configured.
+    async fn run_gate(
+        &self,
+        workspace: &Workspace,
+        repo: &Repo,
+        gate: GateKind,
+    ) -> Result<Option<GateFailure>, ContainerError> {
+        let Some(script) = gate.script(repo) else {
+            return Ok(None);
+        };
+
+        self.ensure_container_exists(workspace).await?;
+        let pool = &self.db().pool;
+        // Each run gets its own session so gate logs stay out of the agent's conversation
+        let session = Session::create(
+            pool,
+            &CreateSession { executor: None },
+            Uuid::new_v4(),
+            workspace.id,
+        )
+        .await?;
+
+        let action = ExecutorAction::new(
+            ExecutorActionType::ScriptRequest(ScriptRequest {
+                script: script.to_string(),
+                language: ScriptRequestLanguage::Bash,
+                context: ScriptContext::GateScript,
+                working_dir: Some(repo.name.clone()),
+            }),
+            None,
+        );
+        let process = self
+            .start_execution(
+                workspace,
+                &session,
+                &action,
+                &ExecutionProcessRunReason::GateScript,
+            )
+            .await?;
+
+        // A missing store means the process already exited and was cleaned up
+        let timed_out = match self.get_msg_store_by_id(&process.id).await {
+            Some(msg_store) => !wait_for_finished(msg_store, GateKind::timeout(repo)).await,
+            None => false,
+        };
+        if timed_out {
+            tracing::warn!(
+                "{} gate for {} timed out; stopping process {}",
+                gate.label(),
+                repo.name,
+                process.id
+            );
+            self.stop_execution(&process, ExecutionProcessStatus::Killed)
+                .await?;
+        }
+
+        let process = ExecutionProcess::find_by_id(pool, process.id)
+            .await?
+            .ok_or(ExecutionProcessError::ExecutionProcessNotFound)?;
+        if gate_passed(&process.status, process.exit_code, timed_out) {
+            return Ok(None);
+        }
+        Ok(Some(GateFailure {
+            gate,
+            repo_id: repo.id,
+            repo_name: repo.name.clone(),
+            execution_process_id: process.id,
+            status: process.status,
+            exit_code: process.exit_code,
+            timed_out,
+        }))
+    }
+
     async fn try_stop(&self, workspace: &Workspace, include_dev_server: bool) {
         // stop execution processes for this workspace's sessions
         let sessions = match Session::find_by_workspace_id(&self.db().pool, workspace.id).await {
@@ -566,6 +740,14 @@ pub trait ContainerService {
 
     async fn try_commit_changes(&self, ctx: &ExecutionContext) -> Result<bool, ContainerError>;
 
+    /// Start the message queued on an idle session. Returns None when nothing is
+    /// queued or the workspace is busy, in which case the message waits for the
+    /// running execution to exit.
+    async fn start_queued_message(
+        &self,
+        session_id: Uuid,
+    ) -> Result<Option<ExecutionProcess>, ContainerError>;
+
     async fn copy_project_files(
         &self,
         source_dir: &Path,
@@ -577,6 +759,7 @@ pub trait ContainerService {
     async fn stream_diff(
         &self,
         workspace: &Workspace,
+        target: &DiffStreamTarget,
         stats_only: bool,
     ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, ContainerError>;
 
@@ -667,159 +850,467 @@ pub trait ContainerService {
             )
         } else {
             // Fallback: load from DB and normalize
-            let log_records =
-                match ExecutionProcessLogs::find_by_execution_id(&self.db().pool, *id).await {
-                    Ok(records) if !records.is_empty() => records,
-                    Ok(_) => return None, // No logs exist
-                    Err(e) => {
-                        tracing::error!("Failed to fetch logs for execution {}: {}", id, e);
-                        return None;
-                    }
-                };
+            let temp_store = self.normalize_stored_logs(id, true).await?;
+            Some(
+                temp_store
+                    .history_plus_stream()
+                    .filter(|msg| future::ready(matches!(msg, Ok(LogMsg::JsonPatch(..)))))
+                    .chain(futures::stream::once(async {
+                        Ok::<_, std::io::Error>(LogMsg::Finished)
+                    }))
+                    .boxed(),
+            )
+        }
+    }
 
-            let raw_messages = match ExecutionProcessLogs::parse_logs(&log_records) {
-                Ok(msgs) => msgs,
+    /// Rebuild a message store from the persisted raw logs of a process and run the
+    /// executor's normalizer on it. The returned store receives the normalized patches.
+    async fn normalize_stored_logs(
+        &self,
+        id: &Uuid,
+        recreate_worktree: bool,
+    ) -> Option<Arc<MsgStore>> {
+        let log_records =
+            match ExecutionProcessLogs::find_by_execution_id(&self.db().pool, *id).await {
+                Ok(records) if !records.is_empty() => records,
+                Ok(_) => return None, // No logs exist
                 Err(e) => {
-                    tracing::error!("Failed to parse logs for execution {}: {}", id, e);
+                    tracing::error!("Failed to fetch logs for execution {}: {}", id, e);
                     return None;
                 }
             };
 
-            // Create temporary store and populate
-            // Include JsonPatch messages (already normalized) and Stdout/Stderr (need normalization)
-            let temp_store = Arc::new(MsgStore::new());
-            for msg in raw_messages {
-                if matches!(
-                    msg,
-                    LogMsg::Stdout(_) | LogMsg::Stderr(_) | LogMsg::JsonPatch(_)
-                ) {
-                    temp_store.push(msg);
-                }
+        let raw_messages = match ExecutionProcessLogs::parse_logs(&log_records) {
+            Ok(msgs) => msgs,
+            Err(e) => {
+                tracing::error!("Failed to parse logs for execution {}: {}", id, e);
+                return None;
             }
-            temp_store.push_finished();
+        };
 
-            let process = match ExecutionProcess::find_by_id(&self.db().pool, *id).await {
-                Ok(Some(process)) => process,
+        // Create temporary store and populate
+        // Include JsonPatch messages (already normalized) and Stdout/Stderr (need normalization)
+        let has_raw_output = raw_messages
+            .iter()
+            .any(|msg| matches!(msg, LogMsg::Stdout(_) | LogMsg::Stderr(_)));
+        let temp_store = Arc::new(MsgStore::new());
+        for msg in raw_messages {
+            if matches!(
+                msg,
+                LogMsg::Stdout(_) | LogMsg::Stderr(_) | LogMsg::JsonPatch(_)
+            ) {
+                temp_store.push(msg);
+            }
+        }
+        temp_store.push_finished();
+
+        // Raw output past retention has been replaced by its normalized entries
+        if !has_raw_output {
+            return Some(temp_store);
+        }
+
+        let process = match ExecutionProcess::find_by_id(&self.db().pool, *id).await {
+            Ok(Some(process)) => process,
+            Ok(None) => {
+                tracing::error!("No execution process found for ID: {}", id);
+                return None;
+            }
+            Err(e) => {
+                tracing::error!("Failed to fetch execution process {}: {}", id, e);
+                return None;
+            }
+        };
+
+        // Get the workspace to determine correct directory
+        let (workspace, _session) =
+            match process.parent_workspace_and_session(&self.db().pool).await {
+                Ok(Some((workspace, session))) => (workspace, session),
                 Ok(None) => {
-                    tracing::error!("No execution process found for ID: {}", id);
+                    tracing::error!(
+                        "No workspace/session found for session ID: {}",
+                        process.session_id
+                    );
                     return None;
                 }
                 Err(e) => {
-                    tracing::error!("Failed to fetch execution process {}: {}", id, e);
+                    tracing::error!(
+                        "Failed to fetch workspace for session {}: {}",
+                        process.session_id,
+                        e
+                    );
                     return None;
                 }
             };
 
-            // Get the workspace to determine correct directory
-            let (workspace, _session) =
-                match process.parent_workspace_and_session(&self.db().pool).await {
-                    Ok(Some((workspace, session))) => (workspace, session),
-                    Ok(None) => {
-                        tracing::error!(
-                            "No workspace/session found for session ID: {}",
-                            process.session_id
-                        );
-                        return None;
-                    }
-                    Err(e) => {
-                        tracing::error!(
-                            "Failed to fetch workspace for session {}: {}",
-                            process.session_id,
-                            e
-                        );
-                        return None;
-                    }
-                };
+        if recreate_worktree && let Err(err) = self.ensure_container_exists(&workspace).await {
+            tracing::warn!(
+                "Failed to recreate worktree before log normalization for workspace {}: {}",
+                workspace.id,
+                err
+            );
+        }
 
-            if let Err(err) = self.ensure_container_exists(&workspace).await {
-                tracing::warn!(
-                    "Failed to recreate worktree before log normalization for workspace {}: {}",
-                    workspace.id,
-                    err
-                );
-            }
+        self.spawn_log_normalizer(&process, &workspace, temp_store.clone())?;
+        Some(temp_store)
+    }
 
-            let current_dir = self.workspace_to_current_dir(&workspace);
+    /// Start the executor's log normalizer for a process on the given store. Returns
+    /// the directory paths in the normalized entries are relative to, or None if the
+    /// process does not produce normalized logs.
+    fn spawn_log_normalizer(
+        &self,
+        process: &ExecutionProcess,
+        workspace: &Workspace,
+        store: Arc<MsgStore>,
+    ) -> Option<PathBuf> {
+        let current_dir = self.workspace_to_current_dir(workspace);
 
-            let executor_action = if let Ok(executor_action) = process.executor_action() {
-                executor_action
-            } else {
-                tracing::error!(
-                    "Failed to parse executor action: {:?}",
-                    process.executor_action()
-                );
-                return None;
-            };
+        let executor_action = if let Ok(executor_action) = process.executor_action() {
+            executor_action
+        } else {
+            tracing::error!(
+                "Failed to parse executor action: {:?}",
+                process.executor_action()
+            );
+            return None;
+        };
 
-            // Spawn normalizer on populated store
-            match executor_action.typ() {
-                ExecutorActionType::CodingAgentInitialRequest(request) => {
-                    #[cfg(feature = "qa-mode")]
-                    {
-                        let executor = QaMockExecutor;
-                        executor.normalize_logs(
-                            temp_store.clone(),
-                            &request.effective_dir(&current_dir),
-                        );
-                    }
-                    #[cfg(not(feature = "qa-mode"))]
-                    {
-                        let executor = ExecutorConfigs::get_cached()
-                            .get_coding_agent_or_default(&request.executor_profile_id);
-                        executor.normalize_logs(
-                            temp_store.clone(),
-                            &request.effective_dir(&current_dir),
-                        );
-                    }
+        match executor_action.typ() {
+            ExecutorActionType::CodingAgentInitialRequest(request) => {
+                let effective_dir = request.effective_dir(&current_dir);
+                #[cfg(feature = "qa-mode")]
+                {
+                    let executor = QaMockExecutor;
+                    executor.normalize_logs(store, &effective_dir);
                 }
-                ExecutorActionType::CodingAgentFollowUpRequest(request) => {
-                    #[cfg(feature = "qa-mode")]
-                    {
-                        let executor = QaMockExecutor;
-                        executor.normalize_logs(
-                            temp_store.clone(),
-                            &request.effective_dir(&current_dir),
-                        );
-                    }
-                    #[cfg(not(feature = "qa-mode"))]
-                    {
-                        let executor = ExecutorConfigs::get_cached()
-                            .get_coding_agent_or_default(&request.executor_profile_id);
-                        executor.normalize_logs(
-                            temp_store.clone(),
-                            &request.effective_dir(&current_dir),
-                        );
-                    }
+                #[cfg(not(feature = "qa-mode"))]
+                {
+                    let executor = ExecutorConfigs::get_cached()
+                        .get_coding_agent_or_default(&request.executor_profile_id);
+                    executor.normalize_logs(store, &effective_dir);
                 }
+                Some(effective_dir)
+            }
+            ExecutorActionType::CodingAgentFollowUpRequest(request) => {
+                let effective_dir = request.effective_dir(&current_dir);
                 #[cfg(feature = "qa-mode")]
-                ExecutorActionType::ReviewRequest(_request) => {
+                {
                     let executor = QaMockExecutor;
-                    executor.normalize_logs(temp_store.clone(), &current_dir);
+                    executor.normalize_logs(store, &effective_dir);
                 }
                 #[cfg(not(feature = "qa-mode"))]
-                ExecutorActionType::ReviewRequest(request) => {
+                {
                     let executor = ExecutorConfigs::get_cached()
                         .get_coding_agent_or_default(&request.executor_profile_id);
-                    executor.normalize_logs(temp_store.clone(), &current_dir);
+                    executor.normalize_logs(store, &effective_dir);
                 }
-                _ => {
-                    tracing::debug!(
-                        "Executor action doesn't support log normalization: {:?}",
-                        process.executor_action()
-                    );
-                    return None;
+                Some(effective_dir)
+            }
+            #[cfg(feature = "qa-mode")]
+            ExecutorActionType::ReviewRequest(_request) => {
+                let executor = QaMockExecutor;
+                executor.normalize_logs(store, &current_dir);
+                Some(current_dir)
+            }
+            #[cfg(not(feature = "qa-mode"))]
+            ExecutorActionType::ReviewRequest(request) => {
+                let executor = ExecutorConfigs::get_cached()
+                    .get_coding_agent_or_default(&request.executor_profile_id);
+                executor.normalize_logs(store, &current_dir);
+                Some(current_dir)
+            }
+            _ => {
+                tracing::debug!(
+                    "Executor action doesn't support log normalization: {:?}",
+                    process.executor_action()
+                );
+                None
+            }
+        }
+    }
+
+    /// Normalize the stored logs of a finished process and collect the resulting
+    /// patches. None if the logs are missing, unsupported or normalization timed out.
+    async fn collect_normalized_patches(&self, id: &Uuid) -> Option<Vec<Patch>> {
+        let store = self.normalize_stored_logs(id, false).await?;
+        let mut stream = store.history_plus_stream();
+        // The stream ends once the normalizer drops its handle on the store
+        drop(store);
+
+        let mut patches = Vec::new();
+        let collect = async {
+            while let Some(Ok(msg)) = stream.next().await {
+                if let LogMsg::JsonPatch(patch) = msg {
+                    patches.push(patch);
                 }
             }
-            Some(
-                temp_store
-                    .history_plus_stream()
-                    .filter(|msg| future::ready(matches!(msg, Ok(LogMsg::JsonPatch(..)))))
-                    .chain(futures::stream::once(async {
-                        Ok::<_, std::io::Error>(LogMsg::Finished)
-                    }))
-                    .boxed(),
+        };
+        if tokio::time::timeout(NORMALIZE_STORED_LOGS_TIMEOUT, collect)
+            .await
+            .is_err()
+        {
+            tracing::warn!("Timed out normalizing stored logs of execution {}", id);
+            return None;
+        }
+        Some(patches)
+    }
+
+    /// Rebuild the normalized conversation of a finished coding agent or review run
+    /// for export. None for other processes or when the logs cannot be normalized.
+    async fn export_execution(&self, process: &ExecutionProcess) -> Option<ExportedExecution> {
+        let action = process.executor_action().ok()?;
+        let (executor, prompt) = match action.typ() {
+            ExecutorActionType::CodingAgentInitialRequest(request) => {
+                (request.base_executor(), request.prompt.clone())
+            }
+            ExecutorActionType::CodingAgentFollowUpRequest(request) => {
+                (request.base_executor(), request.prompt.clone())
+            }
+            ExecutorActionType::ReviewRequest(request) => {
+                (request.base_executor(), request.prompt.clone())
+            }
+            ExecutorActionType::ScriptRequest(_) => return None,
+        };
+
+        let patches = self.collect_normalized_patches(&process.id).await?;
+        let turn = CodingAgentTurn::find_by_execution_process_id(&self.db().pool, process.id)
+            .await
+            .ok()
+            .flatten();
+
+        Some(ExportedExecution {
+            execution_process_id: process.id,
+            run_reason: process.run_reason.clone(),
+            started_at: process.started_at,
+            completed_at: process.completed_at,
+            conversation: NormalizedConversation {
+                entries: entries_from_patches(&patches),
+                session_id: turn.as_ref().and_then(|t| t.agent_session_id.clone()),
+                executor_type: executor.to_string(),
+                prompt: Some(prompt),
+                summary: turn.and_then(|t| t.summary),
+            },
+        })
+    }
+
+    /// Record a session started outside Vibe Kanban as a finished coding agent run
+    /// of `session`, so it renders like any other run and follow-ups resume it.
+    async fn import_external_session(
+        &self,
+        workspace: &Workspace,
+        session: &Session,
+        external: ExternalSession,
+    ) -> Result<ExecutionProcess, ContainerError> {
+        let pool = &self.db().pool;
+        let workspace_root = PathBuf::from(self.ensure_container_exists(workspace).await?);
+        let working_dir = workspace
+            .agent_working_dir
+            .as_ref()
+            .filter(|dir| !dir.is_empty())
+            .cloned();
+
+        if external.agent == ExternalAgent::ClaudeCode {
+            let agent_dir = match &working_dir {
+                Some(dir) => workspace_root.join(dir),
+                None => workspace_root.clone(),
+            };
+            if let Err(e) = install_claude_session(&external.path, &agent_dir) {
+                tracing::warn!(
+                    "Failed to make Claude session {} resumable from {}: {}",
+                    external.agent_session_id,
+                    agent_dir.display(),
+                    e
+                );
+            }
+        }
+
+        let repo_states = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id)
+            .await?
+            .into_iter()
+            .map(|repo| {
+                let head = self
+                    .git()
+                    .get_head_info(&workspace_root.join(&repo.name))
+                    .ok()
+                    .map(|h| h.oid);
+                CreateExecutionProcessRepoState {
+                    repo_id: repo.id,
+                    before_head_commit: head.clone(),
+                    after_head_commit: head,
+                    merge_commit: None,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let prompt = external.first_prompt.clone().unwrap_or_default();
+        let action = ExecutorAction::new(
+            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                prompt: prompt.clone(),
+                executor_profile_id: ExecutorProfileId::new(external.agent.base_coding_agent()),
+                working_dir,
+            }),
+            None,
+        );
+        let process = ExecutionProcess::create(
+            pool,
+            &CreateExecutionProcess {
+                session_id: session.id,
+                executor_action: action,
+                run_reason: ExecutionProcessRunReason::CodingAgent,
+            },
+            Uuid::new_v4(),
+            &repo_states,
+        )
+        .await?;
+
+        for line in external.stdout_lines {
+            let jsonl_line = serde_json::to_string(&LogMsg::Stdout(format!("{line}\n")))
+                .map_err(|e| ContainerError::Other(anyhow!(e)))?;
+            ExecutionProcessLogs::append_log_line(pool, process.id, &format!("{jsonl_line}\n"))
+                .await?;
+        }
+        ExecutionProcess::update_completion(
+            pool,
+            process.id,
+            ExecutionProcessStatus::Completed,
+            Some(0),
+        )
+        .await?;
+
+        CodingAgentTurn::create(
+            pool,
+            &CreateCodingAgentTurn {
+                execution_process_id: process.id,
+                prompt: Some(prompt),
+            },
+            Uuid::new_v4(),
+        )
+        .await?;
+        CodingAgentTurn::update_agent_session_id(pool, process.id, &external.agent_session_id)
+            .await?;
+
+        // Normalize the imported history once to index it and pick up its summary
+        let mut indexer = ConversationIndexer::new(process.id);
+        let mut summary = None;
+        for patch in self
+            .collect_normalized_patches(&process.id)
+            .await
+            .unwrap_or_default()
+        {
+            indexer.push_patch(&patch);
+            if let Some((_, entry)) = extract_normalized_entry_from_patch(&patch)
+                && matches!(entry.entry_type, NormalizedEntryType::AssistantMessage)
+            {
+                summary = Some(entry.content);
+            }
+        }
+        indexer.finish(pool).await?;
+        if let Some(summary) = summary {
+            CodingAgentTurn::update_summary(pool, process.id, &summary).await?;
+        }
+        ExecutionProcessLogs::compact(pool, process.id).await?;
+
+        Ok(ExecutionProcess::find_by_id(pool, process.id)
+            .await?
+            .ok_or(ExecutionProcessError::ExecutionProcessNotFound)?)
+    }
+
+    /// Compact the logs of finished processes into compressed chunks and, when a
+    /// retention period is set, replace expired raw output of coding agent runs
+    /// with their normalized conversation entries.
+    async fn run_log_maintenance(
+        &self,
+        raw_log_retention_days: Option<u32>,
+    ) -> Result<LogMaintenanceSummary, ContainerError> {
+        let pool = &self.db().pool;
+        let mut summary = LogMaintenanceSummary::default();
+
+        loop {
+            let ids = ExecutionProcessLogs::find_uncompacted_execution_ids(
+                pool,
+                LOG_MAINTENANCE_BATCH_SIZE,
+            )
+            .await?;
+            if ids.is_empty() {
+                break;
+            }
+            for id in ids {
+                ExecutionProcessLogs::compact(pool, id).await?;
+                summary.compacted += 1;
+            }
+        }
+
+        let Some(retention_days) = raw_log_retention_days else {
+            return Ok(summary);
+        };
+        let mut skipped = HashSet::new();
+        loop {
+            let ids = ExecutionProcessLogs::find_expired_raw_execution_ids(
+                pool,
+                retention_days,
+                LOG_MAINTENANCE_BATCH_SIZE + skipped.len() as i64,
             )
+            .await?;
+            let ids: Vec<Uuid> = ids.into_iter().filter(|id| !skipped.contains(id)).collect();
+            if ids.is_empty() {
+                break;
+            }
+            for id in ids {
+                let patches = self.collect_normalized_patches(&id).await;
+                match patches {
+                    Some(patches) if !patches.is_empty() => {
+                        let messages: Vec<LogMsg> =
+                            patches.into_iter().map(LogMsg::JsonPatch).collect();
+                        ExecutionProcessLogs::replace_with_normalized(pool, id, &messages).await?;
+                        summary.purged += 1;
+                    }
+                    // Keep raw output that could not be normalized rather than lose it
+                    _ => {
+                        skipped.insert(id);
+                    }
+                }
+            }
         }
+
+        Ok(summary)
+    }
+
+    /// Index the conversations of finished processes that ran before conversation
+    /// search existed, or whose live indexing was interrupted.
+    async fn backfill_conversation_index(&self) -> Result<usize, ContainerError> {
+        let pool = &self.db().pool;
+        let mut indexed = 0;
+        // Processes that failed stay unindexed; skip them for the rest of this run
+        let mut failed = HashSet::new();
+        loop {
+            let ids = ConversationEntry::find_unindexed_process_ids(
+                pool,
+                BACKFILL_BATCH_SIZE + failed.len() as i64,
+            )
+            .await?;
+            let ids: Vec<Uuid> = ids.into_iter().filter(|id| !failed.contains(id)).collect();
+            if ids.is_empty() {
+                break;
+            }
+            for id in ids {
+                let mut indexer = ConversationIndexer::new(id);
+                for patch in self
+                    .collect_normalized_patches(&id)
+                    .await
+                    .unwrap_or_default()
+                {
+                    indexer.push_patch(&patch);
+                }
+                // Mark even processes without logs so they are not retried forever
+                if let Err(e) = indexer.finish(pool).await {
+                    tracing::error!("Failed to index conversation for execution {}: {}", id, e);
+                    failed.insert(id);
+                    continue;
+                }
+                indexed += 1;
+            }
+        }
+        Ok(indexed)
     }
 
     fn spawn_stream_raw_logs_to_db(&self, execution_id: &Uuid) -> JoinHandle<()> {
@@ -836,6 +1327,7 @@ pub trait ContainerService {
 
             if let Some(store) = store {
                 let mut stream = store.history_plus_stream();
+                let mut indexer = ConversationIndexer::new(execution_id);
 
                 while let Some(Ok(msg)) = stream.next().await {
                     match &msg {
@@ -886,16 +1378,45 @@ pub trait ContainerService {
                                 );
                             }
                         }
+                        LogMsg::JsonPatch(patch) => {
+                            indexer.push_patch(patch);
+                            if indexer.should_flush()
+                                && let Err(e) = indexer.flush(&db.pool).await
+                            {
+                                tracing::error!(
+                                    "Failed to index conversation for execution {}: {}",
+                                    execution_id,
+                                    e
+                                );
+                            }
+                        }
                         LogMsg::Finished => {
                             break;
                         }
-                        LogMsg::JsonPatch(_) | LogMsg::Ready => continue,
+                        LogMsg::Ready => continue,
                     }
                 }
+
+                if let Err(e) = indexer.finish(&db.pool).await {
+                    tracing::error!(
+                        "Failed to finish conversation index for execution {}: {}",
+                        execution_id,
+                        e
+                    );
+                }
+
+                if let Err(e) = ExecutionProcessLogs::compact(&db.pool, execution_id).await {
+                    tracing::error!(
+                        "Failed to compact logs for execution {}: {}",
+                        execution_id,
+                        e
+                    );
+                }
             }
         })
     }
 
+    #[tracing::instrument(skip_all, fields(workspace_id = %workspace.id))]
     async fn start_workspace(
         &self,
         workspace: &Workspace,
@@ -988,6 +1509,10 @@ pub trait ContainerService {
         Ok(execution_process)
     }
 
+    #[tracing::instrument(
+        skip_all,
+        fields(workspace_id = %workspace.id, ?run_reason, execution_id)
+    )]
     async fn start_execution(
         &self,
         workspace: &Workspace,
@@ -1001,7 +1526,10 @@ pub trait ContainerService {
             .await?
             .ok_or(SqlxError::RowNotFound)?;
         if task.status != TaskStatus::InProgress
-            && run_reason != &ExecutionProcessRunReason::DevServer
+            && !matches!(
+                run_reason,
+                ExecutionProcessRunReason::DevServer | ExecutionProcessRunReason::GateScript
+            )
         {
             Task::update_status(&self.db().pool, task.id, TaskStatus::InProgress).await?;
         }
@@ -1045,6 +1573,10 @@ pub trait ContainerService {
             &repo_states,
         )
         .await?;
+        tracing::Span::current().record(
+            "execution_id",
+            tracing::field::display(execution_process.id),
+        );
 
         Workspace::set_archived(&self.db().pool, workspace.id, false).await?;
 
@@ -1153,6 +1685,7 @@ pub trait ContainerService {
                 _ => None,
             }
         {
+            let _span = tracing::info_span!("normalize_logs").entered();
             #[cfg(feature = "qa-mode")]
             {
                 let executor = QaMockExecutor;
@@ -1212,3 +1745,56 @@ pub trait ContainerService {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn gate_passes_when_script_exits_zero() {
+        let msg_store = Arc::new(MsgStore::new());
+        msg_store.push_stdout("ok\n");
+        msg_store.push_finished();
+
+        let finished = wait_for_finished(msg_store, Duration::from_secs(5)).await;
+        assert!(finished);
+        assert!(gate_passed(
+            &ExecutionProcessStatus::Completed,
+            Some(0),
+            !finished
+        ));
+    }
+
+    #[tokio::test]
+    async fn gate_fails_when_script_exits_non_zero() {
+        let msg_store = Arc::new(MsgStore::new());
+        let waiter = tokio::spawn(wait_for_finished(msg_store.clone(), Duration::from_secs(5)));
+        msg_store.push_stderr("lint failed\n");
+        msg_store.push_finished();
+
+        let finished = waiter.await.unwrap();
+        assert!(finished);
+        assert!(!gate_passed(
+            &ExecutionProcessStatus::Failed,
+            Some(1),
+            !finished
+        ));
+    }
+
+    #[tokio::test]
+    async fn gate_times_out_when_script_keeps_running() {
+        let msg_store = Arc::new(MsgStore::new());
+        msg_store.push_stdout("still running\n");
+
+        let finished = wait_for_finished(msg_store.clone(), Duration::from_millis(50)).await;
+        assert!(!finished);
+        // A killed gate fails even if the process reported success on the way out
+        assert!(!gate_passed(
+            &ExecutionProcessStatus::Completed,
+            Some(0),
+            !finished
+        ));
+    }
+}
@@ -0,0 +1,135 @@
+//! ripgrep-style search over file contents, walking the repository with the
+//! same ignore rules as path search.
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Read},
+    path::Path,
+};
+
+use db::models::project::{SearchMatchType, SearchResult};
+use regex::{Regex, RegexBuilder};
+
+use super::file_search::{FileSearchCache, SearchMode};
+
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+const MAX_PREVIEW_CHARS: usize = 200;
+/// Bytes inspected for NUL to detect binary files
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+
+/// Compile the query as a regex, falling back to a literal match when it is not
+/// valid. Case-insensitive unless the query contains an uppercase letter.
+fn build_matcher(query: &str) -> Result<Regex, String> {
+    let case_insensitive = !query.chars().any(char::is_uppercase);
+    RegexBuilder::new(query)
+        .case_insensitive(case_insensitive)
+        .build()
+        .or_else(|_| {
+            RegexBuilder::new(&regex::escape(query))
+                .case_insensitive(case_insensitive)
+                .build()
+        })
+        .map_err(|e| format!("Invalid search pattern: {e}"))
+}
+
+fn is_binary(path: &Path) -> bool {
+    let mut head = [0u8; BINARY_SNIFF_BYTES];
+    match File::open(path).and_then(|mut file| file.read(&mut head)) {
+        Ok(n) => head[..n].contains(&0),
+        Err(_) => true,
+    }
+}
+
+/// Matching lines in repository order, at most `limit`
+pub fn search_content(
+    repo_path: &Path,
+    query: &str,
+    mode: &SearchMode,
+    limit: usize,
+) -> Result<Vec<SearchResult>, String> {
+    if !repo_path.exists() {
+        return Err(format!("Path not found: {:?}", repo_path));
+    }
+    let matcher = build_matcher(query)?;
+    let mut results = Vec::new();
+
+    for entry in FileSearchCache::walker(repo_path, mode).filter_map(|e| e.ok()) {
+        if !entry.file_type().is_some_and(|t| t.is_file())
+            || !entry
+                .metadata()
+                .is_ok_and(|metadata| metadata.len() <= MAX_FILE_BYTES)
+        {
+            continue;
+        }
+        let path = entry.path();
+        if is_binary(path) {
+            continue;
+        }
+        let Ok(relative_path) = path.strip_prefix(repo_path) else {
+            continue;
+        };
+        let Ok(file) = File::open(path) else {
+            continue;
+        };
+
+        for (index, line) in BufReader::new(file).lines().enumerate() {
+            // Stop at the first non-UTF-8 line, the rest is unlikely to be text
+            let Ok(line) = line else {
+                break;
+            };
+            if !matcher.is_match(&line) {
+                continue;
+            }
+            results.push(SearchResult {
+                path: relative_path.to_string_lossy().to_string(),
+                is_file: true,
+                match_type: SearchMatchType::Content,
+                score: 0,
+                line: Some(index as u32 + 1),
+                preview: Some(line.trim().chars().take(MAX_PREVIEW_CHARS).collect()),
+                symbol: None,
+            });
+            if results.len() >= limit {
+                return Ok(results);
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_lines_and_respects_gitignore() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        // The ignore crate only applies .gitignore inside a git repository
+        std::fs::create_dir(root.join(".git")).unwrap();
+        std::fs::write(root.join(".gitignore"), "ignored.txt\n").unwrap();
+        std::fs::write(root.join("main.rs"), "fn main() {\n    run_server();\n}\n").unwrap();
+        std::fs::write(root.join("ignored.txt"), "run_server\n").unwrap();
+        std::fs::write(root.join("blob.bin"), b"run_server\0").unwrap();
+
+        let results = search_content(root, r"run_\w+\(", &SearchMode::TaskForm, 10).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].path, "main.rs");
+        assert_eq!(results[0].line, Some(2));
+        assert_eq!(results[0].preview.as_deref(), Some("run_server();"));
+
+        // Invalid regex falls back to a literal, uppercase makes it case-sensitive
+        assert_eq!(
+            search_content(root, "main(", &SearchMode::TaskForm, 10)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(
+            search_content(root, "MAIN", &SearchMode::TaskForm, 10)
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
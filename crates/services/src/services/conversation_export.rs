@@ -0,0 +1,700 @@
+use std::{
+    fmt::Write as _,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+use chrono::{DateTime, Utc};
+use db::models::execution_process::ExecutionProcessRunReason;
+use executors::logs::{
+    ActionType, FileChange, NormalizedConversation, NormalizedEntry, NormalizedEntryType,
+    ToolStatus,
+};
+use json_patch::Patch;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Minimum length of an environment variable value before its literal
+/// occurrences are redacted; shorter values produce too many false positives
+const MIN_SECRET_ENV_VALUE_LEN: usize = 8;
+
+static ENV_ASSIGNMENT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\b([A-Z][A-Z0-9_]*)=("[^"]*"|'[^']*'|[^\s"'`;&|]+)"#).unwrap());
+static HOME_PATH: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:/(?:Users|home)/[^/\s]+|[A-Za-z]:\\Users\\[^\\\s]+)").unwrap()
+});
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ConversationExportFormat {
+    #[default]
+    Json,
+    Markdown,
+    Html,
+}
+
+impl ConversationExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Markdown => "md",
+            Self::Html => "html",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::Markdown => "text/markdown; charset=utf-8",
+            Self::Html => "text/html; charset=utf-8",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RedactionOptions {
+    /// Replace values assigned to environment variables and the literal values of
+    /// secret-looking variables set in this process
+    pub env_values: bool,
+    /// Replace workspace, repository and home directory paths with placeholders
+    pub absolute_paths: bool,
+}
+
+/// One execution of a coding agent with its normalized conversation
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedExecution {
+    pub execution_process_id: Uuid,
+    pub run_reason: ExecutionProcessRunReason,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub conversation: NormalizedConversation,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedSession {
+    pub session_id: Uuid,
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub task_title: String,
+    pub project_name: String,
+    pub branch: String,
+    pub executor: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub executions: Vec<ExportedExecution>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConversationExport {
+    pub title: String,
+    pub exported_at: DateTime<Utc>,
+    pub sessions: Vec<ExportedSession>,
+}
+
+/// Rebuild the normalized entries of a conversation from its JSON patches, the
+/// same way the frontend does. Raw stdout/stderr and diff entries are skipped.
+pub fn entries_from_patches(patches: &[Patch]) -> Vec<NormalizedEntry> {
+    let mut doc = json!({ "entries": [] });
+    for patch in patches {
+        if let Err(e) = json_patch::patch(&mut doc, &patch.0) {
+            tracing::debug!("Skipping conversation patch that does not apply: {}", e);
+        }
+    }
+
+    let Value::Array(entries) = doc["entries"].take() else {
+        return Vec::new();
+    };
+    entries
+        .into_iter()
+        .filter(|entry| entry.get("type").and_then(Value::as_str) == Some("NORMALIZED_ENTRY"))
+        .filter_map(|mut entry| serde_json::from_value(entry["content"].take()).ok())
+        .collect()
+}
+
+/// Strips secrets and machine-specific paths from exported conversations
+pub struct Redactor {
+    options: RedactionOptions,
+    /// Known directories and their placeholders, longest first
+    path_roots: Vec<(String, String)>,
+    secret_values: Vec<String>,
+}
+
+impl Redactor {
+    /// `path_roots` maps directories such as workspace or repository roots to the
+    /// placeholder that replaces them
+    pub fn new(options: RedactionOptions, path_roots: Vec<(PathBuf, String)>) -> Self {
+        let mut roots: Vec<(String, String)> = path_roots
+            .into_iter()
+            .map(|(path, placeholder)| (path.to_string_lossy().to_string(), placeholder))
+            .filter(|(path, _)| path.len() > 1)
+            .collect();
+        if let Some(home) = dirs::home_dir() {
+            roots.push((home.to_string_lossy().to_string(), "~".to_string()));
+        }
+        roots.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+
+        let secret_values = if options.env_values {
+            std::env::vars()
+                .filter(|(name, value)| {
+                    is_secret_env_name(name) && value.len() >= MIN_SECRET_ENV_VALUE_LEN
+                })
+                .map(|(_, value)| value)
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Self {
+            options,
+            path_roots: roots,
+            secret_values,
+        }
+    }
+
+    pub fn is_noop(&self) -> bool {
+        !self.options.env_values && !self.options.absolute_paths
+    }
+
+    pub fn redact(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        if self.options.env_values {
+            for value in &self.secret_values {
+                text = text.replace(value.as_str(), REDACTED);
+            }
+            text = ENV_ASSIGNMENT
+                .replace_all(&text, |caps: &Captures| format!("{}={REDACTED}", &caps[1]))
+                .into_owned();
+        }
+        if self.options.absolute_paths {
+            for (root, placeholder) in &self.path_roots {
+                text = text.replace(root.as_str(), placeholder);
+            }
+            text = HOME_PATH.replace_all(&text, "~").into_owned();
+        }
+        text
+    }
+
+    /// Redact every string of a serializable value, keeping its structure intact
+    pub fn redact_value<T>(&self, value: &T) -> Result<T, serde_json::Error>
+    where
+        T: Serialize + for<'de> Deserialize<'de>,
+    {
+        let mut json = serde_json::to_value(value)?;
+        self.redact_json(&mut json);
+        serde_json::from_value(json)
+    }
+
+    fn redact_json(&self, value: &mut Value) {
+        match value {
+            Value::String(s) => *s = self.redact(s),
+            Value::Array(items) => items.iter_mut().for_each(|item| self.redact_json(item)),
+            Value::Object(map) => map.values_mut().for_each(|item| self.redact_json(item)),
+            _ => {}
+        }
+    }
+
+    pub fn redact_export(&self, export: &mut ConversationExport) -> Result<(), serde_json::Error> {
+        if self.is_noop() {
+            return Ok(());
+        }
+        for session in &mut export.sessions {
+            session.branch = self.redact(&session.branch);
+            for execution in &mut session.executions {
+                execution.conversation = self.redact_value(&execution.conversation)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_secret_env_name(name: &str) -> bool {
+    let name = name.to_ascii_uppercase();
+    [
+        "TOKEN",
+        "SECRET",
+        "PASSWORD",
+        "PASSWD",
+        "API_KEY",
+        "ACCESS_KEY",
+        "PRIVATE_KEY",
+    ]
+    .iter()
+    .any(|marker| name.contains(marker))
+}
+
+pub fn render(
+    export: &ConversationExport,
+    format: ConversationExportFormat,
+) -> Result<String, serde_json::Error> {
+    match format {
+        ConversationExportFormat::Json => serde_json::to_string_pretty(export),
+        ConversationExportFormat::Markdown => Ok(render_markdown(export)),
+        ConversationExportFormat::Html => Ok(render_html(export)),
+    }
+}
+
+/// A body section of a collapsed tool call: optional code language and text
+type ToolSection = (Option<&'static str>, String);
+
+fn tool_summary(tool_name: &str, action_type: &ActionType, status: &ToolStatus) -> String {
+    let target = match action_type {
+        ActionType::FileRead { path } | ActionType::FileEdit { path, .. } => path.as_str(),
+        ActionType::CommandRun { command, .. } => command.lines().next().unwrap_or_default(),
+        ActionType::Search { query } => query.as_str(),
+        ActionType::WebFetch { url } => url.as_str(),
+        ActionType::TaskCreate { description } | ActionType::Other { description } => {
+            description.lines().next().unwrap_or_default()
+        }
+        ActionType::PlanPresentation { .. } => "plan",
+        ActionType::TodoManagement { operation, .. } => operation.as_str(),
+        ActionType::Tool { .. } => "",
+    };
+    let status = match status {
+        ToolStatus::Created | ToolStatus::Success => None,
+        ToolStatus::Failed => Some("failed"),
+        ToolStatus::Denied { .. } => Some("denied"),
+        ToolStatus::PendingApproval { .. } => Some("pending approval"),
+        ToolStatus::TimedOut => Some("timed out"),
+    };
+
+    let mut summary = tool_name.to_string();
+    if !target.is_empty() {
+        let _ = write!(summary, ": {target}");
+    }
+    if let Some(status) = status {
+        let _ = write!(summary, " ({status})");
+    }
+    summary
+}
+
+fn tool_sections(action_type: &ActionType, content: &str) -> Vec<ToolSection> {
+    let mut sections = Vec::new();
+    match action_type {
+        ActionType::CommandRun { command, result } => {
+            sections.push((Some("sh"), command.clone()));
+            if let Some(output) = result.as_ref().and_then(|r| r.output.as_ref())
+                && !output.trim().is_empty()
+            {
+                sections.push((None, output.clone()));
+            }
+        }
+        ActionType::FileEdit { path, changes } => {
+            for change in changes {
+                match change {
+                    FileChange::Write { content } => sections.push((None, content.clone())),
+                    FileChange::Delete => sections.push((None, format!("Deleted {path}"))),
+                    FileChange::Rename { new_path } => {
+                        sections.push((None, format!("Renamed {path} to {new_path}")))
+                    }
+                    FileChange::Edit { unified_diff, .. } => {
+                        sections.push((Some("diff"), unified_diff.clone()))
+                    }
+                }
+            }
+        }
+        ActionType::Tool {
+            arguments, result, ..
+        } => {
+            if let Some(arguments) = arguments {
+                sections.push((
+                    Some("json"),
+                    serde_json::to_string_pretty(arguments).unwrap_or_default(),
+                ));
+            }
+            if let Some(result) = result {
+                let text = match &result.value {
+                    Value::String(s) => s.clone(),
+                    other => serde_json::to_string_pretty(other).unwrap_or_default(),
+                };
+                sections.push((None, text));
+            }
+        }
+        ActionType::PlanPresentation { plan } => sections.push((None, plan.clone())),
+        ActionType::TodoManagement { todos, .. } => sections.push((
+            None,
+            todos
+                .iter()
+                .map(|todo| format!("[{}] {}", todo.status, todo.content))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )),
+        _ => {}
+    }
+    if sections.is_empty() && !content.trim().is_empty() {
+        sections.push((None, content.to_string()));
+    }
+    sections
+}
+
+fn session_heading(session: &ExportedSession) -> String {
+    format!("{} ({})", session.task_title, session.branch)
+}
+
+fn execution_heading(index: usize, execution: &ExportedExecution) -> String {
+    let run = if index == 0 {
+        "Initial run"
+    } else {
+        "Follow-up"
+    };
+    format!(
+        "{run} · {} · {}",
+        execution.conversation.executor_type,
+        execution.started_at.format("%Y-%m-%d %H:%M:%S UTC")
+    )
+}
+
+/// A code fence longer than any run of backticks in `text`
+fn fence_for(text: &str) -> String {
+    let longest = text
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    "`".repeat(longest.max(2) + 1)
+}
+
+fn push_code_block(md: &mut String, lang: Option<&str>, text: &str) {
+    let fence = fence_for(text);
+    let _ = writeln!(md, "{fence}{}", lang.unwrap_or_default());
+    md.push_str(text.trim_end_matches('\n'));
+    let _ = write!(md, "\n{fence}\n\n");
+}
+
+fn quote(text: &str) -> String {
+    text.lines()
+        .map(|line| format!("> {line}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn render_markdown(export: &ConversationExport) -> String {
+    let mut md = String::new();
+    let _ = writeln!(md, "# {}\n", export.title);
+    let _ = writeln!(
+        md,
+        "_Exported {}_\n",
+        export.exported_at.format("%Y-%m-%d %H:%M:%S UTC")
+    );
+
+    for session in &export.sessions {
+        let _ = writeln!(md, "## {}\n", session_heading(session));
+        let _ = writeln!(md, "- **Project**: {}", session.project_name);
+        if let Some(executor) = &session.executor {
+            let _ = writeln!(md, "- **Executor**: {executor}");
+        }
+        let _ = writeln!(md, "- **Session**: {}\n", session.session_id);
+
+        for (index, execution) in session.executions.iter().enumerate() {
+            let _ = writeln!(md, "### {}\n", execution_heading(index, execution));
+            for entry in &execution.conversation.entries {
+                push_markdown_entry(&mut md, entry);
+            }
+        }
+    }
+    md
+}
+
+fn push_markdown_entry(md: &mut String, entry: &NormalizedEntry) {
+    match &entry.entry_type {
+        NormalizedEntryType::UserMessage => {
+            let _ = writeln!(md, "**User**\n\n{}\n", entry.content.trim_end());
+        }
+        NormalizedEntryType::UserFeedback { denied_tool } => {
+            let _ = writeln!(
+                md,
+                "**User feedback** (denied `{denied_tool}`)\n\n{}\n",
+                entry.content.trim_end()
+            );
+        }
+        NormalizedEntryType::AssistantMessage => {
+            let _ = writeln!(md, "**Assistant**\n\n{}\n", entry.content.trim_end());
+        }
+        NormalizedEntryType::Thinking => {
+            let _ = writeln!(
+                md,
+                "<details>\n<summary>Thinking</summary>\n\n{}\n\n</details>\n",
+                entry.content.trim_end()
+            );
+        }
+        NormalizedEntryType::ToolUse {
+            tool_name,
+            action_type,
+            status,
+        } => {
+            let summary = tool_summary(tool_name, action_type, status);
+            let _ = writeln!(
+                md,
+                "<details>\n<summary>{}</summary>\n",
+                html_escape(&summary)
+            );
+            for (lang, text) in tool_sections(action_type, &entry.content) {
+                push_code_block(md, lang, &text);
+            }
+            md.push_str("</details>\n\n");
+        }
+        NormalizedEntryType::SystemMessage => {
+            let _ = writeln!(md, "{}\n", quote(&entry.content));
+        }
+        NormalizedEntryType::ErrorMessage { .. } => {
+            let _ = writeln!(md, "{}\n", quote(&format!("**Error:** {}", entry.content)));
+        }
+        NormalizedEntryType::Loading
+        | NormalizedEntryType::NextAction { .. }
+        | NormalizedEntryType::TokenUsageInfo(_) => {}
+    }
+}
+
+fn html_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+const HTML_STYLE: &str = r#"
+body { font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", sans-serif; max-width: 960px; margin: 2rem auto; padding: 0 1rem; color: #1f2328; line-height: 1.5; }
+h1, h2, h3 { line-height: 1.25; }
+.meta { color: #59636e; font-size: 0.9rem; }
+.entry { margin: 0.75rem 0; padding: 0.5rem 0.75rem; border-radius: 6px; white-space: pre-wrap; overflow-wrap: anywhere; }
+.user { background: #ddf4ff; }
+.assistant { background: #f6f8fa; }
+.system { color: #59636e; border-left: 3px solid #d1d9e0; }
+.error { background: #ffebe9; }
+details { margin: 0.5rem 0; border: 1px solid #d1d9e0; border-radius: 6px; padding: 0.25rem 0.75rem; }
+summary { cursor: pointer; font-family: ui-monospace, SFMono-Regular, Menlo, monospace; font-size: 0.85rem; }
+pre { background: #f6f8fa; padding: 0.75rem; border-radius: 6px; overflow-x: auto; font-size: 0.8rem; }
+pre.diff .add { color: #1a7f37; }
+pre.diff .del { color: #d1242f; }
+"#;
+
+pub fn render_html(export: &ConversationExport) -> String {
+    let mut html = String::new();
+    let title = html_escape(&export.title);
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{title}</title>\n<style>{HTML_STYLE}</style>\n</head>\n<body>\n"
+    );
+    let _ = writeln!(html, "<h1>{title}</h1>");
+    let _ = writeln!(
+        html,
+        "<p class=\"meta\">Exported {}</p>",
+        export.exported_at.format("%Y-%m-%d %H:%M:%S UTC")
+    );
+
+    for session in &export.sessions {
+        let _ = writeln!(html, "<h2>{}</h2>", html_escape(&session_heading(session)));
+        let _ = write!(
+            html,
+            "<p class=\"meta\">Project: {}",
+            html_escape(&session.project_name)
+        );
+        if let Some(executor) = &session.executor {
+            let _ = write!(html, " · Executor: {}", html_escape(executor));
+        }
+        let _ = writeln!(html, " · Session: {}</p>", session.session_id);
+
+        for (index, execution) in session.executions.iter().enumerate() {
+            let _ = writeln!(
+                html,
+                "<h3>{}</h3>",
+                html_escape(&execution_heading(index, execution))
+            );
+            for entry in &execution.conversation.entries {
+                push_html_entry(&mut html, entry);
+            }
+        }
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn push_html_entry(html: &mut String, entry: &NormalizedEntry) {
+    let content = html_escape(entry.content.trim_end());
+    match &entry.entry_type {
+        NormalizedEntryType::UserMessage | NormalizedEntryType::UserFeedback { .. } => {
+            let _ = writeln!(html, "<div class=\"entry user\">{content}</div>");
+        }
+        NormalizedEntryType::AssistantMessage => {
+            let _ = writeln!(html, "<div class=\"entry assistant\">{content}</div>");
+        }
+        NormalizedEntryType::Thinking => {
+            let _ = writeln!(
+                html,
+                "<details><summary>Thinking</summary><div class=\"entry\">{content}</div></details>"
+            );
+        }
+        NormalizedEntryType::ToolUse {
+            tool_name,
+            action_type,
+            status,
+        } => {
+            let _ = write!(
+                html,
+                "<details><summary>{}</summary>",
+                html_escape(&tool_summary(tool_name, action_type, status))
+            );
+            for (lang, text) in tool_sections(action_type, &entry.content) {
+                if lang == Some("diff") {
+                    html.push_str("<pre class=\"diff\">");
+                    for line in text.lines() {
+                        let class = match line.chars().next() {
+                            Some('+') if !line.starts_with("+++") => Some("add"),
+                            Some('-') if !line.starts_with("---") => Some("del"),
+                            _ => None,
+                        };
+                        match class {
+                            Some(class) => {
+                                let _ = writeln!(
+                                    html,
+                                    "<span class=\"{class}\">{}</span>",
+                                    html_escape(line)
+                                );
+                            }
+                            None => {
+                                let _ = writeln!(html, "{}", html_escape(line));
+                            }
+                        }
+                    }
+                    html.push_str("</pre>");
+                } else {
+                    let _ = write!(html, "<pre>{}</pre>", html_escape(&text));
+                }
+            }
+            html.push_str("</details>\n");
+        }
+        NormalizedEntryType::SystemMessage => {
+            let _ = writeln!(html, "<div class=\"entry system\">{content}</div>");
+        }
+        NormalizedEntryType::ErrorMessage { .. } => {
+            let _ = writeln!(html, "<div class=\"entry error\">{content}</div>");
+        }
+        NormalizedEntryType::Loading
+        | NormalizedEntryType::NextAction { .. }
+        | NormalizedEntryType::TokenUsageInfo(_) => {}
+    }
+}
+
+/// File name for a session inside a project or task archive
+pub fn archive_path(session: &ExportedSession, format: ConversationExportFormat) -> PathBuf {
+    let task_dir = format!(
+        "{}-{}",
+        sanitize_file_name(&session.task_title),
+        &session.task_id.to_string()[..8]
+    );
+    Path::new(&task_dir).join(format!(
+        "session-{}-{}.{}",
+        session.created_at.format("%Y%m%d-%H%M%S"),
+        &session.session_id.to_string()[..8],
+        format.extension()
+    ))
+}
+
+pub fn sanitize_file_name(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    sanitized.chars().take(60).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use executors::logs::utils::ConversationPatch;
+
+    use super::*;
+
+    fn entry(entry_type: NormalizedEntryType, content: &str) -> NormalizedEntry {
+        NormalizedEntry {
+            timestamp: None,
+            entry_type,
+            content: content.to_string(),
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn rebuilds_entries_from_patches() {
+        let patches = vec![
+            ConversationPatch::add_normalized_entry(
+                0,
+                entry(NormalizedEntryType::UserMessage, "Fix the build"),
+            ),
+            ConversationPatch::add_stdout(1, "raw output".to_string()),
+            ConversationPatch::add_normalized_entry(
+                2,
+                entry(NormalizedEntryType::AssistantMessage, "Working on it"),
+            ),
+            ConversationPatch::replace(2, entry(NormalizedEntryType::AssistantMessage, "Done")),
+        ];
+
+        let entries = entries_from_patches(&patches);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].content, "Fix the build");
+        assert_eq!(entries[1].content, "Done");
+    }
+
+    #[test]
+    fn redacts_env_values_and_paths() {
+        let redactor = Redactor::new(
+            RedactionOptions {
+                env_values: true,
+                absolute_paths: true,
+            },
+            vec![(
+                PathBuf::from("/var/tmp/vibe-kanban/worktrees/abcd-fix-build"),
+                "<workspace>".to_string(),
+            )],
+        );
+
+        let redacted = redactor.redact(
+            "GITHUB_TOKEN=ghp_abc123 cargo test --manifest-path \
+             /var/tmp/vibe-kanban/worktrees/abcd-fix-build/app/Cargo.toml",
+        );
+        assert_eq!(
+            redacted,
+            "GITHUB_TOKEN=[REDACTED] cargo test --manifest-path <workspace>/app/Cargo.toml"
+        );
+        assert_eq!(redactor.redact("cat /home/alice/.bashrc"), "cat ~/.bashrc");
+    }
+
+    #[test]
+    fn markdown_collapses_tool_calls() {
+        let tool = entry(
+            NormalizedEntryType::ToolUse {
+                tool_name: "bash".to_string(),
+                action_type: ActionType::CommandRun {
+                    command: "ls".to_string(),
+                    result: None,
+                },
+                status: ToolStatus::Failed,
+            },
+            "ls",
+        );
+        let mut md = String::new();
+        push_markdown_entry(&mut md, &tool);
+
+        assert!(md.starts_with("<details>\n<summary>bash: ls (failed)</summary>"));
+        assert!(md.contains("```sh\nls\n```"));
+        assert!(md.trim_end().ends_with("</details>"));
+    }
+}
@@ -0,0 +1,174 @@
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+use db::models::conversation_entry::{ConversationEntry, ConversationEntryText};
+use executors::logs::{
+    ActionType, NormalizedEntry, NormalizedEntryType,
+    utils::patch::extract_normalized_entry_from_patch,
+};
+use json_patch::Patch;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+/// How long streamed entries are buffered before being written to the index
+const FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Extract the searchable text of a normalized entry, or None for entries that
+/// carry nothing worth searching (loading indicators, token usage, next actions).
+pub fn entry_text(entry_index: usize, entry: &NormalizedEntry) -> Option<ConversationEntryText> {
+    let mut parts: Vec<&str> = Vec::new();
+    match &entry.entry_type {
+        NormalizedEntryType::Loading
+        | NormalizedEntryType::TokenUsageInfo(_)
+        | NormalizedEntryType::NextAction { .. } => return None,
+        NormalizedEntryType::ToolUse {
+            tool_name,
+            action_type,
+            ..
+        } => {
+            parts.push(tool_name);
+            match action_type {
+                ActionType::FileRead { path } | ActionType::FileEdit { path, .. } => {
+                    parts.push(path)
+                }
+                ActionType::CommandRun { command, .. } => parts.push(command),
+                ActionType::Search { query } => parts.push(query),
+                ActionType::WebFetch { url } => parts.push(url),
+                ActionType::TaskCreate { description } | ActionType::Other { description } => {
+                    parts.push(description)
+                }
+                ActionType::PlanPresentation { plan } => parts.push(plan),
+                ActionType::Tool { .. } | ActionType::TodoManagement { .. } => {}
+            }
+        }
+        NormalizedEntryType::UserFeedback { denied_tool } => parts.push(denied_tool),
+        _ => {}
+    }
+    parts.push(&entry.content);
+
+    let content = parts
+        .into_iter()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    if content.is_empty() {
+        return None;
+    }
+
+    let entry_type = serde_json::to_value(&entry.entry_type)
+        .ok()?
+        .get("type")?
+        .as_str()?
+        .to_string();
+
+    Some(ConversationEntryText {
+        entry_index: entry_index as i64,
+        entry_type,
+        content,
+    })
+}
+
+/// Searchable text carried by a conversation patch, if any
+pub fn entry_text_from_patch(patch: &Patch) -> Option<ConversationEntryText> {
+    let (entry_index, entry) = extract_normalized_entry_from_patch(patch)?;
+    entry_text(entry_index, &entry)
+}
+
+/// Buffers conversation entries of one execution process as they stream in and
+/// writes them to the search index in batches. Later patches for the same entry
+/// index replace earlier ones, so only the final text of an entry is stored.
+pub struct ConversationIndexer {
+    execution_process_id: Uuid,
+    pending: BTreeMap<i64, ConversationEntryText>,
+    last_flush: Instant,
+}
+
+impl ConversationIndexer {
+    pub fn new(execution_process_id: Uuid) -> Self {
+        Self {
+            execution_process_id,
+            pending: BTreeMap::new(),
+            last_flush: Instant::now(),
+        }
+    }
+
+    pub fn push_patch(&mut self, patch: &Patch) {
+        if let Some(entry) = entry_text_from_patch(patch) {
+            self.pending.insert(entry.entry_index, entry);
+        }
+    }
+
+    pub fn should_flush(&self) -> bool {
+        !self.pending.is_empty() && self.last_flush.elapsed() >= FLUSH_INTERVAL
+    }
+
+    pub async fn flush(&mut self, pool: &SqlitePool) -> Result<(), sqlx::Error> {
+        self.last_flush = Instant::now();
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let entries: Vec<ConversationEntryText> =
+            std::mem::take(&mut self.pending).into_values().collect();
+        ConversationEntry::upsert_many(pool, self.execution_process_id, &entries).await
+    }
+
+    /// Write any remaining entries and mark the process as fully indexed
+    pub async fn finish(mut self, pool: &SqlitePool) -> Result<(), sqlx::Error> {
+        self.flush(pool).await?;
+        ConversationEntry::mark_indexed(pool, self.execution_process_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use executors::logs::{ToolStatus, utils::ConversationPatch};
+
+    use super::*;
+
+    fn entry(entry_type: NormalizedEntryType, content: &str) -> NormalizedEntry {
+        NormalizedEntry {
+            timestamp: None,
+            entry_type,
+            content: content.to_string(),
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn tool_use_includes_command_and_type() {
+        let patch = ConversationPatch::add_normalized_entry(
+            3,
+            entry(
+                NormalizedEntryType::ToolUse {
+                    tool_name: "bash".to_string(),
+                    action_type: ActionType::CommandRun {
+                        command: "cargo test -p services".to_string(),
+                        result: None,
+                    },
+                    status: ToolStatus::Success,
+                },
+                "Run tests",
+            ),
+        );
+
+        let text = entry_text_from_patch(&patch).expect("tool use should be indexed");
+        assert_eq!(text.entry_index, 3);
+        assert_eq!(text.entry_type, "tool_use");
+        assert_eq!(text.content, "bash\ncargo test -p services\nRun tests");
+    }
+
+    #[test]
+    fn skips_non_searchable_entries() {
+        let loading = ConversationPatch::add_normalized_entry(
+            0,
+            entry(NormalizedEntryType::Loading, "Loading..."),
+        );
+        assert!(entry_text_from_patch(&loading).is_none());
+
+        let stdout = ConversationPatch::add_stdout(1, "plain output".to_string());
+        assert!(entry_text_from_patch(&stdout).is_none());
+    }
+}
@@ -90,6 +90,18 @@ impl DiffStreamHandle {
     }
 }
 
+/// What a workspace diff stream compares
+#[derive(Debug, Clone, Default)]
+pub enum DiffStreamTarget {
+    /// Base of the workspace branch against the live worktree
+    #[default]
+    Workspace,
+    /// Changes made by one execution process, from its before to its after head commit
+    Execution(Uuid),
+    /// A single commit against its first parent
+    Commit { repo_id: Uuid, sha: String },
+}
+
 #[derive(Clone)]
 pub struct DiffStreamArgs {
     pub git_service: GitService,
@@ -103,6 +115,21 @@ pub struct DiffStreamArgs {
     pub base_commit: Commit,
     pub stats_only: bool,
     pub path_prefix: Option<String>,
+    /// Keep `base_commit` instead of following the target branch's merge base
+    pub fixed_base: bool,
+}
+
+/// Diff between two fixed commits, which never changes once sent
+#[derive(Clone)]
+pub struct SnapshotDiffArgs {
+    pub git_service: GitService,
+    pub repo_id: Uuid,
+    pub repo_path: PathBuf,
+    /// `None` diffs `to_commit` against its first parent
+    pub from_commit: Option<String>,
+    pub to_commit: String,
+    pub stats_only: bool,
+    pub path_prefix: Option<String>,
 }
 
 struct DiffStreamManager {
@@ -139,6 +166,50 @@ pub async fn create(args: DiffStreamArgs) -> Result<DiffStreamHandle, DiffStream
     ))
 }
 
+pub async fn create_snapshot(args: SnapshotDiffArgs) -> Result<DiffStreamHandle, DiffStreamError> {
+    let git = args.git_service.clone();
+    let repo_path = args.repo_path.clone();
+    let from_commit = args.from_commit.clone();
+    let to_commit = args.to_commit.clone();
+    let diffs = tokio::task::spawn_blocking(move || {
+        let target = match from_commit.as_deref() {
+            Some(from_commit) => DiffTarget::CommitRange {
+                repo_path: &repo_path,
+                from_commit,
+                to_commit: &to_commit,
+            },
+            None => DiffTarget::Commit {
+                repo_path: &repo_path,
+                commit_sha: &to_commit,
+            },
+        };
+        git.get_diffs(target, None)
+    })
+    .await??;
+
+    let cumulative = Arc::new(AtomicUsize::new(0));
+    let mut messages: Vec<Result<LogMsg, io::Error>> = diffs
+        .into_iter()
+        .map(|mut diff| {
+            apply_stream_omit_policy(&mut diff, &cumulative, args.stats_only);
+            Ok(add_diff_msg(
+                diff,
+                args.path_prefix.as_deref(),
+                args.repo_id,
+            ))
+        })
+        .collect();
+    messages.push(Ok(LogMsg::Ready));
+
+    // Stay open like a live stream so clients don't treat the end as a disconnect
+    Ok(DiffStreamHandle::new(
+        futures::stream::iter(messages)
+            .chain(futures::stream::pending())
+            .boxed(),
+        None,
+    ))
+}
+
 impl DiffStreamManager {
     fn new(args: DiffStreamArgs, tx: mpsc::Sender<Result<LogMsg, io::Error>>) -> Self {
         Self {
@@ -196,6 +267,7 @@ impl DiffStreamManager {
                         return Err(io::Error::other(format!("{e:?}")).into());
                     }
                 },
+                DiffEvent::GitStateChange | DiffEvent::CheckTarget if self.args.fixed_base => {}
                 DiffEvent::GitStateChange => {
                     self.handle_git_state_change().await?;
                 }
@@ -257,7 +329,7 @@ impl DiffStreamManager {
     }
 
     async fn send_diffs(&self, diffs: Vec<Diff>) -> Result<(), DiffStreamError> {
-        for mut diff in diffs {
+        for diff in diffs {
             let raw_path = GitService::diff_path(&diff);
 
             {
@@ -267,21 +339,11 @@ impl DiffStreamManager {
 
             if !diff.content_omitted {
                 let mut guard = self.full_sent.write().unwrap();
-                guard.insert(raw_path.clone());
+                guard.insert(raw_path);
             }
 
-            let prefixed_entry = prefix_path(raw_path, self.args.path_prefix.as_deref());
-            if let Some(old) = diff.old_path {
-                diff.old_path = Some(prefix_path(old, self.args.path_prefix.as_deref()));
-            }
-            if let Some(new) = diff.new_path {
-                diff.new_path = Some(prefix_path(new, self.args.path_prefix.as_deref()));
-            }
-            diff.repo_id = Some(self.args.repo_id);
-
-            let patch =
-                ConversationPatch::add_diff(escape_json_pointer_segment(&prefixed_entry), diff);
-            if self.tx.send(Ok(LogMsg::JsonPatch(patch))).await.is_err() {
+            let msg = add_diff_msg(diff, self.args.path_prefix.as_deref(), self.args.repo_id);
+            if self.tx.send(Ok(msg)).await.is_err() {
                 return Ok(());
             }
         }
@@ -390,6 +452,22 @@ fn prefix_path(path: String, prefix: Option<&str>) -> String {
     }
 }
 
+fn add_diff_msg(mut diff: Diff, path_prefix: Option<&str>, repo_id: Uuid) -> LogMsg {
+    let prefixed_entry = prefix_path(GitService::diff_path(&diff), path_prefix);
+    if let Some(old) = diff.old_path {
+        diff.old_path = Some(prefix_path(old, path_prefix));
+    }
+    if let Some(new) = diff.new_path {
+        diff.new_path = Some(prefix_path(new, path_prefix));
+    }
+    diff.repo_id = Some(repo_id);
+
+    LogMsg::JsonPatch(ConversationPatch::add_diff(
+        escape_json_pointer_segment(&prefixed_entry),
+        diff,
+    ))
+}
+
 pub fn apply_stream_omit_policy(diff: &mut Diff, sent_bytes: &Arc<AtomicUsize>, stats_only: bool) {
     if stats_only {
         omit_diff_contents(diff);
@@ -6,7 +6,10 @@ use std::{
 
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
-use db::models::project::{SearchMatchType, SearchResult};
+use db::models::{
+    project::{SearchMatchType, SearchResult},
+    repo::Repo,
+};
 use once_cell::sync::Lazy;
 use tokio::task;
 
@@ -36,6 +39,9 @@ struct RepoHistoryCache {
 /// Global cache for file ranking statistics
 static FILE_STATS_CACHE: Lazy<DashMap<PathBuf, RepoHistoryCache>> = Lazy::new(DashMap::new);
 
+/// Per-repository limits on how many commits are scanned, set from repo settings
+static HISTORY_DEPTH_OVERRIDES: Lazy<DashMap<PathBuf, usize>> = Lazy::new(DashMap::new);
+
 /// Configuration constants for ranking algorithm
 const DEFAULT_COMMIT_LIMIT: usize = 100;
 const BASE_MATCH_SCORE_FILENAME: i64 = 100;
@@ -63,6 +69,36 @@ impl FileRanker {
         }
     }
 
+    /// Limit the commits scanned for `repo_path`; `None` restores the default.
+    /// Cached stats are dropped when the limit changes.
+    pub fn set_history_depth(repo_path: &Path, depth: Option<usize>) {
+        let previous = match depth {
+            Some(depth) => HISTORY_DEPTH_OVERRIDES.insert(repo_path.to_path_buf(), depth),
+            None => HISTORY_DEPTH_OVERRIDES
+                .remove(repo_path)
+                .map(|(_, depth)| depth),
+        };
+        if previous != depth {
+            FILE_STATS_CACHE.remove(repo_path);
+        }
+    }
+
+    /// Apply the repo's `file_ranker_history_depth` setting
+    pub fn apply_repo_settings(repo: &Repo) {
+        let depth = repo
+            .file_ranker_history_depth
+            .and_then(|depth| usize::try_from(depth).ok())
+            .filter(|depth| *depth > 0);
+        Self::set_history_depth(&repo.path, depth);
+    }
+
+    fn commit_limit(repo_path: &Path) -> usize {
+        HISTORY_DEPTH_OVERRIDES
+            .get(repo_path)
+            .map(|depth| *depth)
+            .unwrap_or(DEFAULT_COMMIT_LIMIT)
+    }
+
     /// Get file statistics for a repository, using cache when possible
     pub async fn get_stats(&self, repo_path: &Path) -> Result<Arc<FileStats>, GitServiceError> {
         let repo_path = repo_path.to_path_buf();
@@ -96,7 +132,9 @@ impl FileRanker {
         let base_score = match result.match_type {
             SearchMatchType::FileName => BASE_MATCH_SCORE_FILENAME,
             SearchMatchType::DirectoryName => BASE_MATCH_SCORE_DIRNAME,
-            SearchMatchType::FullPath => BASE_MATCH_SCORE_FULLPATH,
+            SearchMatchType::FullPath | SearchMatchType::Content | SearchMatchType::Symbol => {
+                BASE_MATCH_SCORE_FULLPATH
+            }
         };
 
         if let Some(stat) = stats.get(&result.path) {
@@ -116,10 +154,11 @@ impl FileRanker {
         let repo_path = repo_path.to_path_buf();
         let repo_path_for_error = repo_path.clone();
         let git_service = self.git_service.clone();
+        let commit_limit = Self::commit_limit(&repo_path);
 
         // Run git analysis in blocking task to avoid blocking async runtime
         let stats = task::spawn_blocking(move || {
-            git_service.collect_recent_file_stats(&repo_path, DEFAULT_COMMIT_LIMIT)
+            git_service.collect_recent_file_stats(&repo_path, commit_limit)
         })
         .await
         .map_err(|e| GitServiceError::InvalidRepository(format!("Task join error: {e}")))?;
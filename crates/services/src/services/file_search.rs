@@ -20,10 +20,13 @@ use thiserror::Error;
 use tokio::sync::mpsc;
 use tracing::{error, info, warn};
 use ts_rs::TS;
+use utils::metrics::metrics;
 
 use super::{
+    content_search,
     file_ranker::{FileRanker, FileStats},
     git::GitService,
+    symbol_index::SymbolIndex,
 };
 
 /// Search mode for different use cases
@@ -36,12 +39,34 @@ pub enum SearchMode {
     Settings, // Include ignored files (for project config like .env)
 }
 
+/// What a search query is matched against
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchKind {
+    #[default]
+    Files, // File and directory paths
+    Content, // Regex over file contents
+    Symbols, // Function, type and class definitions
+}
+
+impl SearchKind {
+    pub fn result_limit(self) -> usize {
+        match self {
+            SearchKind::Files => 10,
+            SearchKind::Content => 50,
+            SearchKind::Symbols => 20,
+        }
+    }
+}
+
 /// Search query parameters for typed Axum extraction
 #[derive(Debug, Clone, Deserialize)]
 pub struct SearchQuery {
     pub q: String,
     #[serde(default)]
     pub mode: SearchMode,
+    #[serde(default)]
+    pub kind: SearchKind,
 }
 
 /// FST-indexed file search result
@@ -80,6 +105,7 @@ pub struct CachedRepo {
     pub head_sha: String,
     pub fst_index: Map<Vec<u8>>,
     pub indexed_files: Vec<IndexedFile>,
+    pub symbols: Arc<SymbolIndex>,
     pub stats: Arc<FileStats>,
     pub build_ts: Instant,
 }
@@ -151,10 +177,12 @@ impl FileSearchCache {
             && head_info.oid == cached.head_sha
         {
             // Cache hit - perform fast search with mode-based filtering
+            metrics().file_search_cache_lookups.inc(&["hit"]);
             return Ok(self.search_in_cache(&cached, query, mode).await);
         }
 
         // Cache miss - trigger background refresh and return error
+        metrics().file_search_cache_lookups.inc(&["miss"]);
         if let Err(e) = self.build_queue.send(repo_path_buf) {
             warn!("Failed to enqueue cache build: {}", e);
         }
@@ -259,6 +287,9 @@ impl FileSearchCache {
                     is_file: indexed_file.is_file,
                     match_type: indexed_file.match_type.clone(),
                     score: 0,
+                    line: None,
+                    preview: None,
+                    symbol: None,
                 });
             }
         }
@@ -276,18 +307,69 @@ impl FileSearchCache {
         results
     }
 
-    /// Search files in a single repository with cache + fallback
+    /// Search a single repository by path, content or symbol
     pub async fn search_repo(
         &self,
         repo_path: &Path,
-        query: &str,
-        mode: SearchMode,
+        query: &SearchQuery,
     ) -> Result<Vec<SearchResult>, String> {
-        let query = query.trim();
-        if query.is_empty() {
+        let q = query.q.trim();
+        if q.is_empty() {
             return Ok(vec![]);
         }
 
+        match query.kind {
+            SearchKind::Files => self.search_paths(repo_path, q, query.mode.clone()).await,
+            SearchKind::Content => {
+                let repo_path = repo_path.to_path_buf();
+                let q = q.to_string();
+                let mode = query.mode.clone();
+                tokio::task::spawn_blocking(move || {
+                    content_search::search_content(
+                        &repo_path,
+                        &q,
+                        &mode,
+                        SearchKind::Content.result_limit(),
+                    )
+                })
+                .await
+                .map_err(|e| format!("Content search task failed: {e}"))?
+            }
+            SearchKind::Symbols => Ok(self.search_symbols(repo_path, q).await),
+        }
+    }
+
+    /// Search definitions, building the index on the spot when the cache is cold
+    async fn search_symbols(&self, repo_path: &Path, query: &str) -> Vec<SearchResult> {
+        let limit = SearchKind::Symbols.result_limit();
+        let repo_path_buf = repo_path.to_path_buf();
+
+        if let Some(cached) = self.cache.get(&repo_path_buf).await
+            && let Ok(head_info) = self.git_service.get_head_info(&repo_path_buf)
+            && head_info.oid == cached.head_sha
+        {
+            return cached.symbols.search(query, limit);
+        }
+
+        if let Err(e) = self.build_queue.send(repo_path_buf.clone()) {
+            warn!("Failed to enqueue cache build: {}", e);
+        }
+        match tokio::task::spawn_blocking(move || Self::build_symbol_index(&repo_path_buf)).await {
+            Ok(index) => index.search(query, limit),
+            Err(e) => {
+                warn!("Symbol index build failed: {}", e);
+                vec![]
+            }
+        }
+    }
+
+    /// Search file paths with cache + fallback
+    async fn search_paths(
+        &self,
+        repo_path: &Path,
+        query: &str,
+        mode: SearchMode,
+    ) -> Result<Vec<SearchResult>, String> {
         // Try cache first
         match self.search(repo_path, query, mode.clone()).await {
             Ok(results) => Ok(results),
@@ -312,37 +394,7 @@ impl FileSearchCache {
         let mut results = Vec::new();
         let query_lower = query.to_lowercase();
 
-        let walker = match mode {
-            SearchMode::Settings => {
-                // Settings mode: Include ignored files but exclude performance killers
-                WalkBuilder::new(repo_path)
-                    .git_ignore(false)
-                    .git_global(false)
-                    .git_exclude(false)
-                    .hidden(false)
-                    .filter_entry(|entry| {
-                        let name = entry.file_name().to_string_lossy();
-                        name != ".git"
-                            && name != "node_modules"
-                            && name != "target"
-                            && name != "dist"
-                            && name != "build"
-                    })
-                    .build()
-            }
-            SearchMode::TaskForm => WalkBuilder::new(repo_path)
-                .git_ignore(true)
-                .git_global(true)
-                .git_exclude(true)
-                .hidden(false)
-                .filter_entry(|entry| {
-                    let name = entry.file_name().to_string_lossy();
-                    name != ".git"
-                })
-                .build(),
-        };
-
-        for result in walker {
+        for result in Self::walker(repo_path, &mode) {
             let entry = match result {
                 Ok(e) => e,
                 Err(_) => continue,
@@ -371,6 +423,9 @@ impl FileSearchCache {
                     is_file: path.is_file(),
                     match_type: SearchMatchType::FileName,
                     score: 0,
+                    line: None,
+                    preview: None,
+                    symbol: None,
                 });
             } else if relative_path_str.contains(&query_lower) {
                 let match_type = if path
@@ -390,6 +445,9 @@ impl FileSearchCache {
                     is_file: path.is_file(),
                     match_type,
                     score: 0,
+                    line: None,
+                    preview: None,
+                    symbol: None,
                 });
             }
         }
@@ -423,6 +481,55 @@ impl FileSearchCache {
         Ok(results)
     }
 
+    /// Filesystem walker honouring the ignore rules of the search mode
+    pub(crate) fn walker(repo_path: &Path, mode: &SearchMode) -> ignore::Walk {
+        match mode {
+            SearchMode::Settings => {
+                // Settings mode: Include ignored files but exclude performance killers
+                WalkBuilder::new(repo_path)
+                    .git_ignore(false)
+                    .git_global(false)
+                    .git_exclude(false)
+                    .hidden(false)
+                    .filter_entry(|entry| {
+                        let name = entry.file_name().to_string_lossy();
+                        name != ".git"
+                            && name != "node_modules"
+                            && name != "target"
+                            && name != "dist"
+                            && name != "build"
+                    })
+                    .build()
+            }
+            SearchMode::TaskForm => WalkBuilder::new(repo_path)
+                .git_ignore(true)
+                .git_global(true)
+                .git_exclude(true)
+                .hidden(false)
+                .filter_entry(|entry| {
+                    let name = entry.file_name().to_string_lossy();
+                    name != ".git"
+                })
+                .build(),
+        }
+    }
+
+    /// Symbol index over the repository's non-ignored files
+    fn build_symbol_index(repo_path: &Path) -> SymbolIndex {
+        let files: Vec<String> = Self::walker(repo_path, &SearchMode::TaskForm)
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+            .filter_map(|entry| {
+                entry
+                    .path()
+                    .strip_prefix(repo_path)
+                    .ok()
+                    .map(|p| p.to_string_lossy().to_string())
+            })
+            .collect();
+        SymbolIndex::build(repo_path, files.iter().map(String::as_str))
+    }
+
     /// Build cache entry for a repository
     async fn build_repo_cache(&self, repo_path: &Path) -> Result<CachedRepo, String> {
         let repo_path_buf = repo_path.to_path_buf();
@@ -446,10 +553,25 @@ impl FileSearchCache {
         let file_index = Self::build_file_index(repo_path)
             .map_err(|e| format!("Failed to build file index: {e}"))?;
 
+        // Parse the same non-ignored files the path index already walked
+        let symbol_files: Vec<String> = file_index
+            .files
+            .iter()
+            .filter(|file| file.is_file && !file.is_ignored)
+            .map(|file| file.path.clone())
+            .collect();
+        let symbol_repo_path = repo_path_buf.clone();
+        let symbols = tokio::task::spawn_blocking(move || {
+            SymbolIndex::build(&symbol_repo_path, symbol_files.iter().map(String::as_str))
+        })
+        .await
+        .map_err(|e| format!("Failed to build symbol index: {e}"))?;
+
         Ok(CachedRepo {
             head_sha: head_info.oid,
             fst_index: file_index.map,
             indexed_files: file_index.files,
+            symbols: Arc::new(symbols),
             stats,
             build_ts: Instant::now(),
         })
@@ -594,12 +716,19 @@ impl FileSearchCache {
                 watchers: DashMap::new(),
             };
 
+            let started = Instant::now();
             match cache_builder.build_repo_cache(&repo_path).await {
                 Ok(cached_repo) => {
+                    metrics()
+                        .file_search_cache_build
+                        .observe(&["success"], started.elapsed());
                     cache.insert(repo_path.clone(), cached_repo).await;
                     info!("Successfully cached repo: {:?}", repo_path);
                 }
                 Err(e) => {
+                    metrics()
+                        .file_search_cache_build
+                        .observe(&["error"], started.elapsed());
                     error!("Failed to cache repo {:?}: {}", repo_path, e);
                 }
             }
@@ -2,13 +2,16 @@ use std::{collections::HashMap, path::Path};
 
 use chrono::{DateTime, Utc};
 use git2::{
-    BranchType, Delta, DiffFindOptions, DiffOptions, Error as GitError, Reference, Remote,
-    Repository, Sort,
+    AttrCheckFlags, BranchType, Delta, DiffFindOptions, DiffOptions, Error as GitError, FileMode,
+    ObjectType, Reference, Remote, Repository, Sort,
 };
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use ts_rs::TS;
-use utils::diff::{Diff, DiffChangeKind, FileDiffDetails, compute_line_change_counts};
+use utils::diff::{
+    Diff, DiffChangeKind, DiffHunk, DiffPointer, FileDiffDetails, compute_diff_hunks,
+    compute_line_change_counts, parse_lfs_pointer, revert_diff_hunk,
+};
 
 mod cli;
 
@@ -68,6 +71,15 @@ pub struct GitBranch {
     pub last_commit_date: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone)]
+pub struct CommitSummary {
+    pub sha: String,
+    pub subject: String,
+    pub author_name: String,
+    pub committed_at: DateTime<Utc>,
+    pub is_merge: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct HeadInfo {
     pub branch: String,
@@ -81,6 +93,11 @@ impl Commit {
     pub fn new(id: git2::Oid) -> Self {
         Self(id)
     }
+    pub fn from_sha(sha: &str) -> Result<Self, GitServiceError> {
+        git2::Oid::from_str(sha)
+            .map(Self)
+            .map_err(|_| GitServiceError::InvalidRepository(format!("Invalid commit SHA: {sha}")))
+    }
     pub fn as_oid(&self) -> git2::Oid {
         self.0
     }
@@ -140,6 +157,12 @@ pub enum DiffTarget<'p> {
         repo_path: &'p Path,
         commit_sha: &'p str,
     },
+    /// Changes between two commits, e.g. before and after an execution
+    CommitRange {
+        repo_path: &'p Path,
+        from_commit: &'p str,
+        to_commit: &'p str,
+    },
 }
 
 impl Default for GitService {
@@ -410,6 +433,31 @@ impl GitService {
                 let mut find_opts = git2::DiffFindOptions::new();
                 diff.find_similar(Some(&mut find_opts))?;
 
+                self.convert_diff_to_file_diffs(diff, &repo)
+            }
+            DiffTarget::CommitRange {
+                repo_path,
+                from_commit,
+                to_commit,
+            } => {
+                let repo = self.open_repo(repo_path)?;
+                let from_tree = Self::find_commit_by_sha(&repo, from_commit)?.tree()?;
+                let to_tree = Self::find_commit_by_sha(&repo, to_commit)?.tree()?;
+
+                let mut diff_opts = DiffOptions::new();
+                diff_opts.include_typechange(true);
+                if let Some(paths) = path_filter {
+                    for path in paths {
+                        diff_opts.pathspec(*path);
+                    }
+                }
+
+                let mut diff =
+                    repo.diff_tree_to_tree(Some(&from_tree), Some(&to_tree), Some(&mut diff_opts))?;
+
+                let mut find_opts = DiffFindOptions::new();
+                diff.find_similar(Some(&mut find_opts))?;
+
                 self.convert_diff_to_file_diffs(diff, &repo)
             }
         }
@@ -432,6 +480,27 @@ impl GitService {
 
                 let status = delta.status();
 
+                // Submodules (gitlinks) have no blob contents; report the recorded commits
+                let old_is_submodule = delta.old_file().mode() == FileMode::Commit;
+                let new_is_submodule = delta.new_file().mode() == FileMode::Commit;
+                if old_is_submodule || new_is_submodule {
+                    let path_of =
+                        |file: git2::DiffFile| file.path().map(|p| p.to_string_lossy().to_string());
+                    file_diffs.push(Self::submodule_diff(
+                        Self::delta_change_kind(status),
+                        (!matches!(status, Delta::Added))
+                            .then(|| path_of(delta.old_file()))
+                            .flatten(),
+                        (!matches!(status, Delta::Deleted))
+                            .then(|| path_of(delta.new_file()))
+                            .flatten(),
+                        old_is_submodule.then(|| delta.old_file().id().to_string()),
+                        new_is_submodule.then(|| delta.new_file().id().to_string()),
+                    ));
+                    delta_index += 1;
+                    return true;
+                }
+
                 // Decide if we should omit content due to size
                 let mut content_omitted = false;
                 // Check old blob size when applicable
@@ -500,14 +569,15 @@ impl GitService {
                     }
                 };
 
-                let mut change = match status {
-                    Delta::Added => DiffChangeKind::Added,
-                    Delta::Deleted => DiffChangeKind::Deleted,
-                    Delta::Modified => DiffChangeKind::Modified,
-                    Delta::Renamed => DiffChangeKind::Renamed,
-                    Delta::Copied => DiffChangeKind::Copied,
-                    Delta::Untracked => DiffChangeKind::Added,
-                    _ => DiffChangeKind::Modified,
+                let mut change = Self::delta_change_kind(status);
+
+                // LFS pointers are shown as an object summary rather than pointer text
+                let pointer =
+                    Self::lfs_pointer(old_content.as_deref(), new_content.as_deref(), false, None);
+                let (old_content, new_content) = if pointer.is_some() {
+                    (None, None)
+                } else {
+                    (old_content, new_content)
                 };
 
                 // Detect pure mode changes (e.g., chmod +/-x) and classify as PermissionChange
@@ -522,8 +592,8 @@ impl GitService {
                 }
 
                 // Always compute line stats via libgit2 Patch
-                let (additions, deletions) = if let Ok(Some(patch)) =
-                    git2::Patch::from_diff(&diff, delta_index)
+                let (additions, deletions) = if pointer.is_none()
+                    && let Ok(Some(patch)) = git2::Patch::from_diff(&diff, delta_index)
                     && let Ok((_ctx, adds, dels)) = patch.line_stats()
                 {
                     (Some(adds), Some(dels))
@@ -541,6 +611,7 @@ impl GitService {
                     additions,
                     deletions,
                     repo_id: None,
+                    pointer,
                 });
 
                 delta_index += 1;
@@ -562,6 +633,75 @@ impl GitService {
             .unwrap_or_default()
     }
 
+    fn delta_change_kind(status: Delta) -> DiffChangeKind {
+        match status {
+            Delta::Added => DiffChangeKind::Added,
+            Delta::Deleted => DiffChangeKind::Deleted,
+            Delta::Modified => DiffChangeKind::Modified,
+            Delta::Renamed => DiffChangeKind::Renamed,
+            Delta::Copied => DiffChangeKind::Copied,
+            Delta::Untracked => DiffChangeKind::Added,
+            _ => DiffChangeKind::Modified,
+        }
+    }
+
+    /// Diff entry for a submodule whose recorded commit was added, removed or moved
+    fn submodule_diff(
+        change: DiffChangeKind,
+        old_path: Option<String>,
+        new_path: Option<String>,
+        old_commit: Option<String>,
+        new_commit: Option<String>,
+    ) -> Diff {
+        Diff {
+            change,
+            old_path,
+            new_path,
+            old_content: None,
+            new_content: None,
+            content_omitted: false,
+            additions: None,
+            deletions: None,
+            repo_id: None,
+            pointer: Some(DiffPointer::Submodule {
+                old_commit,
+                new_commit,
+            }),
+        }
+    }
+
+    /// LFS summary when either side is an LFS pointer file, or when the path is
+    /// LFS-tracked and the working copy holds the downloaded content instead.
+    fn lfs_pointer(
+        old: Option<&str>,
+        new: Option<&str>,
+        lfs_tracked: bool,
+        new_size_on_disk: Option<usize>,
+    ) -> Option<DiffPointer> {
+        let old_pointer = old.and_then(parse_lfs_pointer);
+        let new_pointer = new.and_then(parse_lfs_pointer);
+        if old_pointer.is_none() && new_pointer.is_none() && !lfs_tracked {
+            return None;
+        }
+        Some(DiffPointer::Lfs {
+            old_oid: old_pointer.as_ref().map(|p| p.oid.clone()),
+            old_size: old_pointer.map(|p| p.size),
+            new_oid: new_pointer.as_ref().map(|p| p.oid.clone()),
+            new_size: new_pointer.map(|p| p.size).or(new_size_on_disk),
+        })
+    }
+
+    fn is_lfs_tracked(repo: &Repository, rel_path: &str) -> bool {
+        repo.get_attr(
+            Path::new(rel_path),
+            "filter",
+            AttrCheckFlags::FILE_THEN_INDEX,
+        )
+        .ok()
+        .flatten()
+            == Some("lfs")
+    }
+
     /// Helper function to convert blob to string content
     fn blob_to_string(blob: &git2::Blob) -> Option<String> {
         if blob.is_binary() {
@@ -673,6 +813,36 @@ impl GitService {
             ChangeType::Unknown(_) => (e.old_path.clone(), Some(e.path.clone())),
         };
 
+        // Submodules: compare the commit in the base tree with the checked out one
+        let old_commit = old_path_opt.as_ref().and_then(|oldp| {
+            base_tree
+                .get_path(Path::new(oldp))
+                .ok()
+                .filter(|entry| entry.kind() == Some(ObjectType::Commit))
+                .map(|entry| entry.id().to_string())
+        });
+        let new_commit = new_path_opt.as_ref().and_then(|newp| {
+            repo.find_submodule(newp)
+                .ok()
+                .and_then(|sm| sm.workdir_id().or_else(|| sm.index_id()))
+                .map(|oid| oid.to_string())
+        });
+        if old_commit.is_some() || new_commit.is_some() {
+            return Self::submodule_diff(
+                change,
+                old_path_opt,
+                new_path_opt,
+                old_commit,
+                new_commit,
+            );
+        }
+
+        // LFS-tracked files: summarize pointer changes instead of loading contents
+        let lfs_tracked = new_path_opt
+            .as_deref()
+            .or(old_path_opt.as_deref())
+            .is_some_and(|p| Self::is_lfs_tracked(repo, p));
+
         // Decide if we should omit content by size (either side)
         let mut content_omitted = false;
         // Old side (from base tree)
@@ -727,6 +897,33 @@ impl GitService {
             (old_content, new_content)
         };
 
+        let new_size_on_disk = new_path_opt.as_ref().and_then(|newp| {
+            let workdir = repo.workdir()?;
+            std::fs::metadata(workdir.join(newp))
+                .ok()
+                .map(|md| md.len() as usize)
+        });
+        let pointer = Self::lfs_pointer(
+            old_content.as_deref(),
+            new_content.as_deref(),
+            lfs_tracked,
+            new_size_on_disk,
+        );
+        if pointer.is_some() {
+            return Diff {
+                change,
+                old_path: old_path_opt,
+                new_path: new_path_opt,
+                old_content: None,
+                new_content: None,
+                content_omitted: false,
+                additions: None,
+                deletions: None,
+                repo_id: None,
+                pointer,
+            };
+        }
+
         // If reported as Modified but content is identical, treat as a permission-only change
         if matches!(change, DiffChangeKind::Modified)
             && old_content.is_some()
@@ -763,6 +960,7 @@ impl GitService {
             additions,
             deletions,
             repo_id: None,
+            pointer: None,
         }
     }
 
@@ -1177,6 +1375,223 @@ impl GitService {
         Ok(())
     }
 
+    /// Read a text file as it exists at `commit_sha`. Returns None when the path
+    /// is absent from that commit.
+    pub fn read_file_at_commit(
+        &self,
+        repo_path: &Path,
+        commit_sha: &str,
+        rel_path: &str,
+    ) -> Result<Option<String>, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let commit = Self::find_commit_by_sha(&repo, commit_sha)?;
+        let entry = match commit.tree()?.get_path(Path::new(rel_path)) {
+            Ok(entry) => entry,
+            Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let blob = repo.find_blob(entry.id())?;
+        Self::blob_to_string(&blob).map(Some).ok_or_else(|| {
+            GitServiceError::InvalidRepository(format!("{rel_path} is not a text file"))
+        })
+    }
+
+    /// Store text in the repository object database without referencing it,
+    /// returning the blob SHA. Used to keep file snapshots across rebases.
+    pub fn write_blob(&self, repo_path: &Path, content: &str) -> Result<String, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        Ok(repo.blob(content.as_bytes())?.to_string())
+    }
+
+    /// Read a text blob written by `write_blob`. Returns None when it has been
+    /// garbage collected or is not valid UTF-8.
+    pub fn read_blob(
+        &self,
+        repo_path: &Path,
+        blob_sha: &str,
+    ) -> Result<Option<String>, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let oid = git2::Oid::from_str(blob_sha).map_err(|_| {
+            GitServiceError::InvalidRepository(format!("Invalid blob SHA: {blob_sha}"))
+        })?;
+        match repo.find_blob(oid) {
+            Ok(blob) => Ok(Self::blob_to_string(&blob)),
+            Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Revert a single file in the worktree to its content at `source_commit` and
+    /// commit just that path. Files absent from `source_commit` are deleted.
+    /// Returns the new commit SHA, or None if the file already matched HEAD.
+    pub fn revert_file(
+        &self,
+        worktree_path: &Path,
+        rel_path: &str,
+        source_commit: &str,
+        message: &str,
+    ) -> Result<Option<String>, GitServiceError> {
+        Self::validate_worktree_rel_path(rel_path)?;
+        self.ensure_no_conflict_op(worktree_path)?;
+
+        let repo = self.open_repo(worktree_path)?;
+        let commit = Self::find_commit_by_sha(&repo, source_commit)?;
+        let in_source = commit.tree()?.get_path(Path::new(rel_path)).is_ok();
+
+        let git = GitCli::new();
+        if in_source {
+            git.checkout_path_from_commit(worktree_path, &commit.id().to_string(), rel_path)?;
+        } else {
+            let abs_path = worktree_path.join(rel_path);
+            if abs_path.is_file() {
+                std::fs::remove_file(&abs_path)?;
+            }
+            git.remove_cached_path(worktree_path, rel_path)?;
+        }
+
+        self.commit_single_path(worktree_path, rel_path, message)
+    }
+
+    /// Hunks of the diff between `source_commit` and the worktree copy of
+    /// `rel_path`, in the order `revert_hunk` indexes them.
+    pub fn file_hunks(
+        &self,
+        worktree_path: &Path,
+        rel_path: &str,
+        source_commit: &str,
+    ) -> Result<Vec<DiffHunk>, GitServiceError> {
+        Self::validate_worktree_rel_path(rel_path)?;
+        let (old_content, new_content) =
+            self.read_hunk_sides(worktree_path, rel_path, source_commit)?;
+        Ok(compute_diff_hunks(&old_content, &new_content))
+    }
+
+    /// Revert one hunk of the diff between `source_commit` and the worktree copy of
+    /// `rel_path`, keeping every other change, then commit just that path.
+    /// Hunks are indexed as returned by `file_hunks`.
+    pub fn revert_hunk(
+        &self,
+        worktree_path: &Path,
+        rel_path: &str,
+        source_commit: &str,
+        hunk_index: usize,
+        message: &str,
+    ) -> Result<Option<String>, GitServiceError> {
+        Self::validate_worktree_rel_path(rel_path)?;
+        self.ensure_no_conflict_op(worktree_path)?;
+
+        let (old_content, new_content) =
+            self.read_hunk_sides(worktree_path, rel_path, source_commit)?;
+        let abs_path = worktree_path.join(rel_path);
+
+        let reverted =
+            revert_diff_hunk(&old_content, &new_content, hunk_index).ok_or_else(|| {
+                GitServiceError::InvalidRepository(format!(
+                    "Hunk {hunk_index} not found in {rel_path}; the diff may have changed"
+                ))
+            })?;
+
+        let repo = self.open_repo(worktree_path)?;
+        let commit = Self::find_commit_by_sha(&repo, source_commit)?;
+        let in_source = commit.tree()?.get_path(Path::new(rel_path)).is_ok();
+
+        if !in_source && reverted.is_empty() {
+            // Reverting what remains of an added file removes it, as `revert_file` does
+            if abs_path.is_file() {
+                std::fs::remove_file(&abs_path)?;
+            }
+            GitCli::new().remove_cached_path(worktree_path, rel_path)?;
+        } else {
+            if let Some(parent) = abs_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&abs_path, reverted)?;
+        }
+
+        self.commit_single_path(worktree_path, rel_path, message)
+    }
+
+    /// Content of `rel_path` at `source_commit` and in the worktree; missing
+    /// files read as empty. Both hunk listing and hunk revert use this so hunk
+    /// indexes stay in sync.
+    fn read_hunk_sides(
+        &self,
+        worktree_path: &Path,
+        rel_path: &str,
+        source_commit: &str,
+    ) -> Result<(String, String), GitServiceError> {
+        let old_content = self
+            .read_file_at_commit(worktree_path, source_commit, rel_path)?
+            .unwrap_or_default();
+        let new_content = if worktree_path.join(rel_path).is_file() {
+            let repo = self.open_repo(worktree_path)?;
+            Self::read_file_to_string(&repo, Path::new(rel_path)).ok_or_else(|| {
+                GitServiceError::InvalidRepository(format!(
+                    "{rel_path} is binary or too large to revert by hunk"
+                ))
+            })?
+        } else {
+            String::new()
+        };
+        Ok((old_content, new_content))
+    }
+
+    /// Stage and commit a single path, leaving the rest of the index untouched.
+    fn commit_single_path(
+        &self,
+        worktree_path: &Path,
+        rel_path: &str,
+        message: &str,
+    ) -> Result<Option<String>, GitServiceError> {
+        let git = GitCli::new();
+        if !git.path_has_changes(worktree_path, rel_path)? {
+            return Ok(None);
+        }
+        if worktree_path.join(rel_path).exists() {
+            git.add_path(worktree_path, rel_path)?;
+        }
+        self.ensure_cli_commit_identity(worktree_path)?;
+        git.commit_paths(worktree_path, message, &[rel_path])?;
+        Ok(Some(self.get_head_info(worktree_path)?.oid))
+    }
+
+    fn find_commit_by_sha<'a>(
+        repo: &'a Repository,
+        commit_sha: &str,
+    ) -> Result<git2::Commit<'a>, GitServiceError> {
+        let oid = git2::Oid::from_str(commit_sha).map_err(|_| {
+            GitServiceError::InvalidRepository(format!("Invalid commit SHA: {commit_sha}"))
+        })?;
+        Ok(repo.find_commit(oid)?)
+    }
+
+    /// Reject absolute paths and parent traversal for paths supplied by API callers.
+    fn validate_worktree_rel_path(rel_path: &str) -> Result<(), GitServiceError> {
+        let path = Path::new(rel_path);
+        let is_safe = !rel_path.is_empty()
+            && path
+                .components()
+                .all(|c| matches!(c, std::path::Component::Normal(_)));
+        if is_safe {
+            Ok(())
+        } else {
+            Err(GitServiceError::InvalidRepository(format!(
+                "Invalid file path: {rel_path}"
+            )))
+        }
+    }
+
+    /// Refuse to touch a worktree that is mid-rebase/merge/cherry-pick/revert.
+    fn ensure_no_conflict_op(&self, worktree_path: &Path) -> Result<(), GitServiceError> {
+        match self.detect_conflict_op(worktree_path)? {
+            None => Ok(()),
+            Some(ConflictOp::Rebase) => Err(GitServiceError::RebaseInProgress),
+            Some(op) => Err(GitServiceError::InvalidRepository(format!(
+                "A {op:?} is in progress; resolve or abort it before retrying"
+            ))),
+        }
+    }
+
     /// Add a worktree for a branch, optionally creating the branch
     pub fn add_worktree(
         &self,
@@ -1191,6 +1606,55 @@ impl GitService {
         Ok(())
     }
 
+    /// Add a worktree that only checks out `sparse_paths` (cone mode).
+    /// Falls back to a regular worktree when `sparse_paths` is empty.
+    pub fn add_sparse_worktree(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+        branch: &str,
+        sparse_paths: &[String],
+    ) -> Result<(), GitServiceError> {
+        if sparse_paths.is_empty() {
+            return self.add_worktree(repo_path, worktree_path, branch, false);
+        }
+        let git = GitCli::new();
+        git.worktree_add_sparse(repo_path, worktree_path, branch, sparse_paths)
+            .map_err(|e| GitServiceError::InvalidRepository(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Clone a repository with the git CLI, optionally as a partial (`filter`)
+    /// and/or shallow (`depth`) clone.
+    pub fn clone_with_filter(
+        &self,
+        clone_url: &str,
+        target_path: &Path,
+        filter: Option<&str>,
+        depth: Option<u32>,
+    ) -> Result<(), GitServiceError> {
+        if let Some(parent) = target_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let git = GitCli::new();
+        git.clone(clone_url, target_path, filter, depth)?;
+        Ok(())
+    }
+
+    /// Initialize submodules in a worktree so agents see their contents
+    pub fn init_submodules(&self, worktree_path: &Path) -> Result<(), GitServiceError> {
+        let git = GitCli::new();
+        git.submodule_update_init(worktree_path)?;
+        Ok(())
+    }
+
+    /// Replace LFS pointer files in a worktree with their real contents
+    pub fn lfs_pull(&self, worktree_path: &Path) -> Result<(), GitServiceError> {
+        let git = GitCli::new();
+        git.lfs_pull(worktree_path)?;
+        Ok(())
+    }
+
     /// Remove a worktree
     pub fn remove_worktree(
         &self,
@@ -1432,6 +1896,158 @@ impl GitService {
         Ok(final_commit.id().to_string())
     }
 
+    /// List commits on `branch_name` that are not on `base_branch`, oldest first.
+    /// Merge commits are skipped so the result can be cherry-picked in order.
+    pub fn get_branch_commits(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+        base_branch: &str,
+    ) -> Result<Vec<String>, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let branch_oid = Self::find_branch(&repo, branch_name)?
+            .get()
+            .peel_to_commit()?
+            .id();
+        let base_oid = Self::find_branch(&repo, base_branch)?
+            .get()
+            .peel_to_commit()?
+            .id();
+
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(branch_oid)?;
+        revwalk.hide(base_oid)?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+
+        let mut commits = Vec::new();
+        for oid in revwalk {
+            let commit = repo.find_commit(oid?)?;
+            if commit.parent_count() <= 1 {
+                commits.push(commit.id().to_string());
+            }
+        }
+        Ok(commits)
+    }
+
+    /// Commits on `branch_name` that are not on `base_branch`, newest first,
+    /// including merge commits.
+    pub fn get_branch_commit_log(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+        base_branch: &str,
+    ) -> Result<Vec<CommitSummary>, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let branch_oid = Self::find_branch(&repo, branch_name)?
+            .get()
+            .peel_to_commit()?
+            .id();
+        let base_oid = Self::find_branch(&repo, base_branch)?
+            .get()
+            .peel_to_commit()?
+            .id();
+
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(branch_oid)?;
+        revwalk.hide(base_oid)?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+
+        let mut commits = Vec::new();
+        for oid in revwalk {
+            let commit = repo.find_commit(oid?)?;
+            commits.push(CommitSummary {
+                sha: commit.id().to_string(),
+                subject: commit.summary().unwrap_or("(no subject)").to_string(),
+                author_name: commit.author().name().unwrap_or_default().to_string(),
+                committed_at: DateTime::from_timestamp(commit.time().seconds(), 0)
+                    .unwrap_or_default(),
+                is_merge: commit.parent_count() > 1,
+            });
+        }
+        Ok(commits)
+    }
+
+    /// SHAs reachable from `to_commit` but not from `from_commit`
+    pub fn get_commit_range(
+        &self,
+        repo_path: &Path,
+        from_commit: &str,
+        to_commit: &str,
+    ) -> Result<Vec<String>, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let parse = |sha: &str| {
+            git2::Oid::from_str(sha).map_err(|_| {
+                GitServiceError::InvalidRepository(format!("Invalid commit SHA: {sha}"))
+            })
+        };
+
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(parse(to_commit)?)?;
+        revwalk.hide(parse(from_commit)?)?;
+        revwalk
+            .map(|oid| Ok(oid?.to_string()))
+            .collect::<Result<Vec<_>, GitServiceError>>()
+    }
+
+    /// Cherry-pick `commits` onto the branch checked out in `worktree_path`.
+    /// On conflict the cherry-pick is left in progress (so it can be resolved and
+    /// continued, or aborted via `abort_conflicts`) and MergeConflicts is returned.
+    /// Returns the resulting HEAD commit.
+    pub fn cherry_pick_commits(
+        &self,
+        worktree_path: &Path,
+        commits: &[String],
+    ) -> Result<String, GitServiceError> {
+        let worktree_repo = self.open_repo(worktree_path)?;
+        self.check_worktree_clean(&worktree_repo)?;
+        self.ensure_no_conflict_op(worktree_path)?;
+
+        if commits.is_empty() {
+            return Ok(self.get_head_info(worktree_path)?.oid);
+        }
+
+        self.ensure_cli_commit_identity(worktree_path)?;
+        let git = GitCli::new();
+        match git.cherry_pick(worktree_path, commits) {
+            Ok(()) => {}
+            Err(GitCliError::CommandFailed(stderr)) => {
+                let conflicted_files = git.get_conflicted_files(worktree_path).unwrap_or_default();
+                let in_progress = git
+                    .is_cherry_pick_in_progress(worktree_path)
+                    .unwrap_or(false);
+                if in_progress || !conflicted_files.is_empty() {
+                    let branch = worktree_repo
+                        .head()
+                        .ok()
+                        .and_then(|h| h.shorthand().map(|s| s.to_string()))
+                        .unwrap_or_else(|| "(unknown)".to_string());
+                    let files_part = if conflicted_files.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" Conflicted files: {}.", conflicted_files.join(", "))
+                    };
+                    return Err(GitServiceError::MergeConflicts {
+                        message: format!(
+                            "Cherry-pick onto '{branch}' encountered conflicts.{files_part} Resolve conflicts and then continue or abort."
+                        ),
+                        conflicted_files,
+                    });
+                }
+                return Err(GitServiceError::InvalidRepository(format!(
+                    "Cherry-pick failed: {}",
+                    stderr.lines().next().unwrap_or("")
+                )));
+            }
+            Err(e) => {
+                return Err(GitServiceError::InvalidRepository(format!(
+                    "git cherry-pick failed: {e}"
+                )));
+            }
+        }
+
+        Ok(self.get_head_info(worktree_path)?.oid)
+    }
+
     pub fn find_branch_type(
         &self,
         repo_path: &Path,
@@ -107,6 +107,82 @@ impl GitCli {
         Ok(())
     }
 
+    /// Add a worktree that only materializes `sparse_paths` using a cone-mode sparse
+    /// checkout. The worktree is added without a checkout so excluded directories are
+    /// never written to disk; the sparse config is scoped to this worktree.
+    pub fn worktree_add_sparse(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+        branch: &str,
+        sparse_paths: &[String],
+    ) -> Result<(), GitCliError> {
+        self.ensure_available()?;
+
+        let args: Vec<OsString> = vec![
+            "worktree".into(),
+            "add".into(),
+            "--no-checkout".into(),
+            worktree_path.as_os_str().into(),
+            OsString::from(branch),
+        ];
+        self.git(repo_path, args)?;
+
+        let mut args: Vec<OsString> = vec!["sparse-checkout".into(), "set".into(), "--cone".into()];
+        args.push("--".into());
+        args.extend(sparse_paths.iter().map(OsString::from));
+        self.git(worktree_path, args)?;
+
+        self.git(worktree_path, ["checkout", branch])?;
+        Ok(())
+    }
+
+    /// Clone `url` into `target_path`. `filter` enables a partial clone (e.g. `blob:none`)
+    /// and `depth` a shallow clone; both keep clones of very large repositories fast.
+    pub fn clone(
+        &self,
+        url: &str,
+        target_path: &Path,
+        filter: Option<&str>,
+        depth: Option<u32>,
+    ) -> Result<(), GitCliError> {
+        self.ensure_available()?;
+
+        let mut args: Vec<OsString> = vec!["clone".into()];
+        if let Some(filter) = filter {
+            args.push(format!("--filter={filter}").into());
+        }
+        if let Some(depth) = depth {
+            args.push("--depth".into());
+            args.push(depth.to_string().into());
+            args.push("--no-single-branch".into());
+        }
+        args.push("--".into());
+        args.push(url.into());
+        args.push(target_path.as_os_str().into());
+
+        let cwd = target_path.parent().unwrap_or(Path::new("."));
+        self.git(cwd, args)?;
+        Ok(())
+    }
+
+    /// Initialize and update submodules (recursively) in `worktree_path`.
+    pub fn submodule_update_init(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+        self.ensure_available()?;
+        self.git(
+            worktree_path,
+            ["submodule", "update", "--init", "--recursive"],
+        )?;
+        Ok(())
+    }
+
+    /// Download Git LFS objects for the checked out revision. Requires git-lfs.
+    pub fn lfs_pull(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+        self.ensure_available()?;
+        self.git(worktree_path, ["lfs", "pull"])?;
+        Ok(())
+    }
+
     /// Run `git -C <repo> worktree remove <path>`
     pub fn worktree_remove(
         &self,
@@ -342,6 +418,61 @@ impl GitCli {
         self.git(worktree_path, ["commit", "-m", message])?;
         Ok(())
     }
+
+    /// Restore `path` in both the index and the worktree to its state at `commit`.
+    pub fn checkout_path_from_commit(
+        &self,
+        worktree_path: &Path,
+        commit: &str,
+        path: &str,
+    ) -> Result<(), GitCliError> {
+        self.git(worktree_path, ["checkout", commit, "--", path])?;
+        Ok(())
+    }
+
+    /// Stage `path`, including deletions.
+    pub fn add_path(&self, worktree_path: &Path, path: &str) -> Result<(), GitCliError> {
+        self.git(worktree_path, ["add", "-A", "--", path])?;
+        Ok(())
+    }
+
+    /// Drop `path` from the index if it is tracked (no-op otherwise).
+    pub fn remove_cached_path(&self, worktree_path: &Path, path: &str) -> Result<(), GitCliError> {
+        self.git(
+            worktree_path,
+            ["rm", "-q", "--cached", "--ignore-unmatch", "--", path],
+        )?;
+        Ok(())
+    }
+
+    /// Return true if `path` differs from HEAD in the index or the worktree.
+    pub fn path_has_changes(&self, worktree_path: &Path, path: &str) -> Result<bool, GitCliError> {
+        let out = self.git(
+            worktree_path,
+            ["--no-optional-locks", "status", "--porcelain", "--", path],
+        )?;
+        Ok(!out.trim().is_empty())
+    }
+
+    /// Commit only the given paths, leaving any other staged changes in the index.
+    pub fn commit_paths(
+        &self,
+        worktree_path: &Path,
+        message: &str,
+        paths: &[&str],
+    ) -> Result<(), GitCliError> {
+        let mut args: Vec<OsString> = vec![
+            "commit".into(),
+            "-m".into(),
+            message.into(),
+            "--only".into(),
+            "--".into(),
+        ];
+        args.extend(paths.iter().map(OsString::from));
+        self.git(worktree_path, args)?;
+        Ok(())
+    }
+
     /// Fetch a branch to the given remote using native git authentication.
     pub fn fetch_with_refspec(
         &self,
@@ -608,6 +739,14 @@ impl GitCli {
         Ok(sha)
     }
 
+    /// Cherry-pick `commits` onto HEAD in order, recording the source SHA (`-x`).
+    /// On conflict git stops and leaves the cherry-pick in progress.
+    pub fn cherry_pick(&self, worktree_path: &Path, commits: &[String]) -> Result<(), GitCliError> {
+        let mut args: Vec<OsString> = vec!["cherry-pick".into(), "-x".into()];
+        args.extend(commits.iter().map(OsString::from));
+        self.git(worktree_path, args).map(|_| ())
+    }
+
     /// Update a ref to a specific sha in the repo.
     pub fn update_ref(
         &self,
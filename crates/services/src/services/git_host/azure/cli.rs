@@ -56,6 +56,7 @@ struct AzThreadsResponse {
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct AzThread {
+    id: Option<i64>,
     comments: Option<Vec<AzThreadComment>>,
     thread_context: Option<AzThreadContext>,
 }
@@ -393,6 +394,55 @@ impl AzCli {
         Self::parse_pr_threads(&raw)
     }
 
+    /// Post a comment in an existing PR thread, as a reply to `parent_comment_id`.
+    pub fn reply_to_thread(
+        &self,
+        repo_info: &AzureRepoInfo,
+        pr_id: i64,
+        thread_id: i64,
+        parent_comment_id: Option<i64>,
+        content: &str,
+    ) -> Result<(), AzCliError> {
+        let body = serde_json::json!({
+            "content": content,
+            "parentCommentId": parent_comment_id.unwrap_or(0),
+            "commentType": 1,
+        });
+        let mut body_file = tempfile::NamedTempFile::new()
+            .map_err(|err| AzCliError::CommandFailed(err.to_string()))?;
+        std::io::Write::write_all(&mut body_file, body.to_string().as_bytes())
+            .map_err(|err| AzCliError::CommandFailed(err.to_string()))?;
+
+        let mut args: Vec<OsString> = Vec::with_capacity(20);
+        args.push(OsString::from("devops"));
+        args.push(OsString::from("invoke"));
+        args.push(OsString::from("--area"));
+        args.push(OsString::from("git"));
+        args.push(OsString::from("--resource"));
+        args.push(OsString::from("pullRequestThreadComments"));
+        args.push(OsString::from("--route-parameters"));
+        args.push(OsString::from(format!("project={}", repo_info.project_id)));
+        args.push(OsString::from(format!(
+            "repositoryId={}",
+            repo_info.repo_id
+        )));
+        args.push(OsString::from(format!("pullRequestId={}", pr_id)));
+        args.push(OsString::from(format!("threadId={}", thread_id)));
+        args.push(OsString::from("--organization"));
+        args.push(OsString::from(&repo_info.organization_url));
+        args.push(OsString::from("--http-method"));
+        args.push(OsString::from("POST"));
+        args.push(OsString::from("--in-file"));
+        args.push(body_file.path().as_os_str().to_os_string());
+        args.push(OsString::from("--api-version"));
+        args.push(OsString::from("7.0"));
+        args.push(OsString::from("--output"));
+        args.push(OsString::from("json"));
+
+        self.run(args, None)?;
+        Ok(())
+    }
+
     /// Parse PR URL to extract organization and PR ID.
     ///
     /// Only extracts the minimal info needed for `az repos pr show`.
@@ -525,6 +575,7 @@ impl AzCli {
                             line,
                             side: None,
                             diff_hunk: None,
+                            thread_id: thread.id,
                         });
                     } else {
                         comments.push(UnifiedPrComment::General {
@@ -534,6 +585,7 @@ impl AzCli {
                             body,
                             created_at,
                             url: None,
+                            thread_id: thread.id,
                         });
                     }
                 }
@@ -14,7 +14,7 @@ use tracing::info;
 
 use super::{
     GitHostProvider,
-    types::{CreatePrRequest, GitHostError, ProviderKind, UnifiedPrComment},
+    types::{CreatePrRequest, GitHostError, PrCommentThread, ProviderKind, UnifiedPrComment},
 };
 
 #[derive(Debug, Clone)]
@@ -246,6 +246,35 @@ impl GitHostProvider for AzureDevOpsProvider {
         .await
     }
 
+    async fn reply_to_pr_comment(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+        thread: &PrCommentThread,
+        body: &str,
+    ) -> Result<(), GitHostError> {
+        let thread_id = thread.thread_id.ok_or_else(|| {
+            GitHostError::PullRequest("Azure DevOps replies need the comment's thread".to_string())
+        })?;
+        let parent_comment_id = thread.comment_id.parse::<i64>().ok();
+        let repo_info = self.get_repo_info(repo_path, remote_url).await?;
+
+        // Not retried: a reply that timed out may still have been posted
+        let cli = self.az_cli.clone();
+        let body = body.to_string();
+        task::spawn_blocking(move || {
+            cli.reply_to_thread(&repo_info, pr_number, thread_id, parent_comment_id, &body)
+        })
+        .await
+        .map_err(|err| {
+            GitHostError::PullRequest(format!(
+                "Failed to execute Azure CLI for replying to a PR comment: {err}"
+            ))
+        })?
+        .map_err(GitHostError::from)
+    }
+
     fn provider_kind(&self) -> ProviderKind {
         ProviderKind::AzureDevOps
     }
@@ -309,6 +309,52 @@ impl GhCli {
         )?;
         Self::parse_pr_review_comments(&raw)
     }
+
+    /// Reply in the thread of an inline review comment.
+    pub fn reply_to_review_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: i64,
+        comment_id: i64,
+        body: &str,
+    ) -> Result<(), GhCliError> {
+        self.run(
+            [
+                "api",
+                "--method",
+                "POST",
+                &format!("repos/{owner}/{repo}/pulls/{pr_number}/comments/{comment_id}/replies"),
+                "-f",
+                &format!("body={body}"),
+            ],
+            None,
+        )?;
+        Ok(())
+    }
+
+    /// Add a general comment to a pull request.
+    pub fn comment_on_pr(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: i64,
+        body: &str,
+    ) -> Result<(), GhCliError> {
+        self.run(
+            [
+                "pr",
+                "comment",
+                &pr_number.to_string(),
+                "--repo",
+                &format!("{owner}/{repo}"),
+                "--body",
+                body,
+            ],
+            None,
+        )?;
+        Ok(())
+    }
 }
 
 impl GhCli {
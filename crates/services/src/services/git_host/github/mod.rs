@@ -14,7 +14,7 @@ use tracing::info;
 
 use super::{
     GitHostProvider,
-    types::{CreatePrRequest, GitHostError, ProviderKind, UnifiedPrComment},
+    types::{CreatePrRequest, GitHostError, PrCommentThread, ProviderKind, UnifiedPrComment},
 };
 
 #[derive(Debug, Clone)]
@@ -335,6 +335,7 @@ impl GitHostProvider for GitHubProvider {
                 body: c.body,
                 created_at: c.created_at,
                 url: Some(c.url),
+                thread_id: None,
             });
         }
 
@@ -350,6 +351,7 @@ impl GitHostProvider for GitHubProvider {
                 line: c.line,
                 side: c.side,
                 diff_hunk: Some(c.diff_hunk),
+                thread_id: None,
             });
         }
 
@@ -359,6 +361,48 @@ impl GitHostProvider for GitHubProvider {
         Ok(unified)
     }
 
+    async fn reply_to_pr_comment(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+        thread: &PrCommentThread,
+        body: &str,
+    ) -> Result<(), GitHostError> {
+        let repo_info = self.get_repo_info(remote_url, repo_path).await?;
+        let review_comment_id = if thread.is_review {
+            Some(thread.comment_id.parse::<i64>().map_err(|_| {
+                GitHostError::PullRequest(format!(
+                    "Invalid review comment id: {}",
+                    thread.comment_id
+                ))
+            })?)
+        } else {
+            None
+        };
+
+        // Not retried: a reply that timed out may still have been posted
+        let cli = self.gh_cli.clone();
+        let body = body.to_string();
+        task::spawn_blocking(move || match review_comment_id {
+            Some(comment_id) => cli.reply_to_review_comment(
+                &repo_info.owner,
+                &repo_info.repo_name,
+                pr_number,
+                comment_id,
+                &body,
+            ),
+            None => cli.comment_on_pr(&repo_info.owner, &repo_info.repo_name, pr_number, &body),
+        })
+        .await
+        .map_err(|err| {
+            GitHostError::PullRequest(format!(
+                "Failed to execute GitHub CLI for replying to a PR comment: {err}"
+            ))
+        })?
+        .map_err(GitHostError::from)
+    }
+
     fn provider_kind(&self) -> ProviderKind {
         ProviderKind::GitHub
     }
@@ -11,8 +11,8 @@ use db::models::merge::PullRequestInfo;
 use detection::detect_provider_from_url;
 use enum_dispatch::enum_dispatch;
 pub use types::{
-    CreatePrRequest, GitHostError, PrComment, PrCommentAuthor, PrReviewComment, ProviderKind,
-    ReviewCommentUser, UnifiedPrComment,
+    CreatePrRequest, GitHostError, PrComment, PrCommentAuthor, PrCommentThread, PrReviewComment,
+    ProviderKind, ReviewCommentUser, UnifiedPrComment,
 };
 
 use self::{azure::AzureDevOpsProvider, github::GitHubProvider};
@@ -43,6 +43,15 @@ pub trait GitHostProvider: Send + Sync {
         pr_number: i64,
     ) -> Result<Vec<UnifiedPrComment>, GitHostError>;
 
+    async fn reply_to_pr_comment(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+        thread: &PrCommentThread,
+        body: &str,
+    ) -> Result<(), GitHostError>;
+
     fn provider_kind(&self) -> ProviderKind;
 }
 
@@ -111,6 +111,8 @@ pub enum UnifiedPrComment {
         body: String,
         created_at: DateTime<Utc>,
         url: Option<String>,
+        /// Thread holding the comment, needed to reply on Azure DevOps
+        thread_id: Option<i64>,
     },
     Review {
         id: i64,
@@ -123,9 +125,21 @@ pub enum UnifiedPrComment {
         line: Option<i64>,
         side: Option<String>,
         diff_hunk: Option<String>,
+        /// Thread holding the comment, needed to reply on Azure DevOps
+        thread_id: Option<i64>,
     },
 }
 
+/// Where a reply to a PR comment is posted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrCommentThread {
+    pub comment_id: String,
+    pub thread_id: Option<i64>,
+    /// Inline review comments are answered in their thread, general comments
+    /// with a new PR comment
+    pub is_review: bool,
+}
+
 impl UnifiedPrComment {
     pub fn created_at(&self) -> DateTime<Utc> {
         match self {
@@ -133,4 +147,26 @@ impl UnifiedPrComment {
             UnifiedPrComment::Review { created_at, .. } => *created_at,
         }
     }
+
+    pub fn body(&self) -> &str {
+        match self {
+            UnifiedPrComment::General { body, .. } => body,
+            UnifiedPrComment::Review { body, .. } => body,
+        }
+    }
+
+    pub fn thread(&self) -> PrCommentThread {
+        match self {
+            UnifiedPrComment::General { id, thread_id, .. } => PrCommentThread {
+                comment_id: id.clone(),
+                thread_id: *thread_id,
+                is_review: false,
+            },
+            UnifiedPrComment::Review { id, thread_id, .. } => PrCommentThread {
+                comment_id: id.to_string(),
+                thread_id: *thread_id,
+                is_review: true,
+            },
+        }
+    }
 }
@@ -171,6 +171,11 @@ impl ImageService {
         Ok(())
     }
 
+    /// Directory image files are stored in, relative to which `file_path` resolves
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+
     pub fn get_absolute_path(&self, image: &Image) -> PathBuf {
         self.cache_dir.join(&image.file_path)
     }
@@ -1,9 +1,13 @@
 pub mod analytics;
 pub mod approvals;
 pub mod auth;
+pub mod backup;
 pub mod config;
 // pub mod config_watcher;  // Temporarily disabled due to compiler ICE
 pub mod container;
+pub mod content_search;
+pub mod conversation_export;
+pub mod conversation_search;
 pub mod diff_stream;
 pub mod events;
 pub mod file_ranker;
@@ -15,14 +19,19 @@ pub mod git_host;
 pub mod image;
 pub mod notification;
 pub mod oauth_credentials;
+pub mod pr_comment_follow_ups;
 pub mod pr_monitor;
 pub mod project;
 #[cfg(feature = "qa-mode")]
 pub mod qa_repos;
 pub mod queued_message;
+pub mod remote_access;
 pub mod remote_client;
 pub mod repo;
 pub mod repo_validator;
+pub mod review_comments;
 pub mod session_exporter;
+pub mod session_import;
+pub mod symbol_index;
 pub mod workspace_manager;
 pub mod worktree_manager;
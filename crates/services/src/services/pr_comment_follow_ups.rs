@@ -0,0 +1,265 @@
+//! Hands comments left on a workspace's pull request to its coding agent.
+//!
+//! Comments are queued as a follow-up on the workspace's latest session, either on
+//! request or automatically by the PR monitor. Each comment is tracked per PR so it
+//! is only sent once; after the agent's turn the branch is pushed and the comments
+//! can be answered with the commit that addressed them.
+
+use std::collections::HashSet;
+
+use db::models::{
+    execution_process::ExecutionProcess,
+    merge::{PrMerge, PullRequestInfo},
+    pr_comment_follow_up::{PrCommentFollowUp, PrCommentFollowUpStatus, PrCommentRef},
+    repo::Repo,
+    scratch::DraftFollowUpData,
+    session::Session,
+};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::{
+    git::{GitService, GitServiceError},
+    git_host::{self, GitHostError, GitHostProvider, UnifiedPrComment},
+    queued_message::QueuedMessageService,
+};
+
+/// Opens every follow-up prompt built from PR comments
+pub const PROMPT_HEADER: &str = "Please address the following comments left on pull request";
+
+/// Replies posted once a comment is addressed start with this, so they are never
+/// picked up as new comments themselves
+pub const REPLY_PREFIX: &str = "Addressed in ";
+
+#[derive(Debug, Error)]
+pub enum PrCommentFollowUpError {
+    #[error(transparent)]
+    GitHost(#[from] GitHostError),
+    #[error(transparent)]
+    GitService(#[from] GitServiceError),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("Workspace has no session to send the comments to")]
+    NoSession,
+    #[error("No executor to send the comments to")]
+    NoExecutor,
+}
+
+/// Unique key of a comment within its PR
+pub fn comment_key(comment: &UnifiedPrComment) -> String {
+    match comment {
+        UnifiedPrComment::General { id, .. } => format!("general:{id}"),
+        UnifiedPrComment::Review { id, .. } => format!("review:{id}"),
+    }
+}
+
+fn comment_ref(comment: &UnifiedPrComment) -> PrCommentRef {
+    let thread = comment.thread();
+    PrCommentRef {
+        comment_key: comment_key(comment),
+        comment_id: thread.comment_id,
+        thread_id: thread.thread_id,
+        is_review: thread.is_review,
+    }
+}
+
+pub fn is_follow_up_reply(comment: &UnifiedPrComment) -> bool {
+    comment.body().trim_start().starts_with(REPLY_PREFIX)
+}
+
+pub fn reply_body(commit_sha: &str) -> String {
+    format!("{REPLY_PREFIX}{commit_sha}")
+}
+
+/// Comments not yet handed to the agent, oldest first. Comments whose queued
+/// message was cancelled count as pending again.
+pub fn pending_comments(
+    comments: Vec<UnifiedPrComment>,
+    tracked: &[PrCommentFollowUp],
+    queued_messages: &QueuedMessageService,
+) -> Vec<UnifiedPrComment> {
+    let handled: HashSet<&str> = tracked
+        .iter()
+        .filter(|t| match t.status {
+            PrCommentFollowUpStatus::Queued => t
+                .session_id
+                .is_some_and(|session_id| queued_messages.has_queued(session_id)),
+            PrCommentFollowUpStatus::Sent | PrCommentFollowUpStatus::Addressed => true,
+            PrCommentFollowUpStatus::Failed => false,
+        })
+        .map(|t| t.comment_key.as_str())
+        .collect();
+    let mut pending: Vec<_> = comments
+        .into_iter()
+        .filter(|c| !is_follow_up_reply(c) && !handled.contains(comment_key(c).as_str()))
+        .collect();
+    pending.sort_by_key(|c| c.created_at());
+    pending
+}
+
+/// One follow-up prompt covering every comment, with the file and line of inline
+/// review comments
+pub fn build_follow_up_prompt(pr_info: &PullRequestInfo, comments: &[UnifiedPrComment]) -> String {
+    let mut prompt = format!(
+        "{PROMPT_HEADER} #{} ({}). Make the requested changes and commit them.\n",
+        pr_info.number, pr_info.url
+    );
+    for comment in comments {
+        match comment {
+            UnifiedPrComment::General { author, body, .. } => {
+                prompt.push_str(&format!("\n## Comment from {author}\n"));
+                prompt.push_str(&format!("\n{}\n", body.trim()));
+            }
+            UnifiedPrComment::Review {
+                author,
+                body,
+                path,
+                line,
+                diff_hunk,
+                ..
+            } => {
+                let location = match line {
+                    Some(line) => format!("{path}, line {line}"),
+                    None => path.clone(),
+                };
+                prompt.push_str(&format!(
+                    "\n## Review comment from {author} on {location}\n"
+                ));
+                if let Some(hunk) = diff_hunk.as_deref().filter(|h| !h.trim().is_empty()) {
+                    prompt.push_str(&format!("\n```diff\n{}\n```\n", hunk.trim_end()));
+                }
+                prompt.push_str(&format!("\n{}\n", body.trim()));
+            }
+        }
+    }
+    prompt
+}
+
+/// Fetch the comments of a PR through the provider of the target branch's remote
+pub async fn fetch_pr_comments(
+    git: &GitService,
+    repo: &Repo,
+    pr_merge: &PrMerge,
+) -> Result<Vec<UnifiedPrComment>, PrCommentFollowUpError> {
+    let remote_url = git.get_remote_url(
+        &repo.path,
+        &git.resolve_remote_name_for_branch(&repo.path, &pr_merge.target_branch_name)?,
+    )?;
+    let git_host = git_host::GitHostService::from_url(&remote_url)?;
+    Ok(git_host
+        .get_pr_comments(&repo.path, &remote_url, pr_merge.pr_info.number)
+        .await?)
+}
+
+/// Queue `comments` as a follow-up on the workspace's latest session, appended to
+/// any message already waiting there. Returns the session.
+pub async fn queue_follow_up(
+    pool: &SqlitePool,
+    queued_messages: &QueuedMessageService,
+    pr_merge: &PrMerge,
+    comments: &[UnifiedPrComment],
+) -> Result<Uuid, PrCommentFollowUpError> {
+    let session = Session::find_latest_by_workspace_id(pool, pr_merge.workspace_id)
+        .await?
+        .ok_or(PrCommentFollowUpError::NoSession)?;
+    let prompt = build_follow_up_prompt(&pr_merge.pr_info, comments);
+
+    let data = match queued_messages.get_queued(session.id) {
+        Some(existing) => DraftFollowUpData {
+            message: format!("{}\n\n{prompt}", existing.data.message),
+            executor_profile_id: existing.data.executor_profile_id,
+        },
+        None => DraftFollowUpData {
+            message: prompt,
+            executor_profile_id: ExecutionProcess::latest_executor_profile_for_session(
+                pool, session.id,
+            )
+            .await?
+            .ok_or(PrCommentFollowUpError::NoExecutor)?,
+        },
+    };
+
+    let refs: Vec<PrCommentRef> = comments.iter().map(comment_ref).collect();
+    PrCommentFollowUp::record_many(
+        pool,
+        pr_merge.workspace_id,
+        pr_merge.id,
+        &refs,
+        PrCommentFollowUpStatus::Queued,
+        Some(session.id),
+    )
+    .await?;
+    queued_messages.queue_message(session.id, data);
+
+    Ok(session.id)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use db::models::merge::MergeStatus;
+
+    use super::*;
+
+    fn review(id: i64, body: &str, minutes_ago: i64) -> UnifiedPrComment {
+        UnifiedPrComment::Review {
+            id,
+            author: "octocat".to_string(),
+            author_association: None,
+            body: body.to_string(),
+            created_at: Utc::now() - Duration::minutes(minutes_ago),
+            url: None,
+            path: "src/lib.rs".to_string(),
+            line: Some(12),
+            side: Some("RIGHT".to_string()),
+            diff_hunk: Some("@@ -10,3 +10,3 @@\n-let x = 1;\n+let x = 2;".to_string()),
+            thread_id: None,
+        }
+    }
+
+    #[test]
+    fn pending_skips_replies_and_handled_comments() {
+        let now = Utc::now();
+        let tracked = PrCommentFollowUp {
+            id: Uuid::new_v4(),
+            workspace_id: Uuid::new_v4(),
+            merge_id: Uuid::new_v4(),
+            comment_key: "review:1".to_string(),
+            comment_id: "1".to_string(),
+            thread_id: None,
+            is_review: true,
+            status: PrCommentFollowUpStatus::Addressed,
+            session_id: None,
+            execution_process_id: None,
+            addressed_commit: Some("abc123".to_string()),
+            created_at: now,
+            updated_at: now,
+        };
+        let comments = vec![
+            review(3, "Rename this", 1),
+            review(1, "Already done", 10),
+            review(2, &reply_body("abc123"), 5),
+            review(4, "Add a test", 2),
+        ];
+        let pending = pending_comments(comments, &[tracked], &QueuedMessageService::new());
+        let keys: Vec<_> = pending.iter().map(comment_key).collect();
+        assert_eq!(keys, vec!["review:4", "review:3"]);
+    }
+
+    #[test]
+    fn prompt_includes_location_and_hunk() {
+        let pr_info = PullRequestInfo {
+            number: 7,
+            url: "https://github.com/acme/app/pull/7".to_string(),
+            status: MergeStatus::Open,
+            merged_at: None,
+            merge_commit_sha: None,
+        };
+        let prompt = build_follow_up_prompt(&pr_info, &[review(1, "Use a constant", 0)]);
+        assert!(prompt.starts_with(PROMPT_HEADER));
+        assert!(prompt.contains("## Review comment from octocat on src/lib.rs, line 12"));
+        assert!(prompt.contains("```diff\n@@ -10,3 +10,3 @@"));
+        assert!(prompt.ends_with("\nUse a constant\n"));
+    }
+}
@@ -1,9 +1,15 @@
-use std::time::Duration;
+use std::{
+    collections::HashSet,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use db::{
     DBService,
     models::{
         merge::{Merge, MergeStatus, PrMerge},
+        pr_comment_follow_up::{PrCommentFollowUp, PrCommentFollowUpStatus},
+        repo::Repo,
         task::{Task, TaskStatus},
         workspace::{Workspace, WorkspaceError},
     },
@@ -11,12 +17,21 @@ use db::{
 use serde_json::json;
 use sqlx::error::Error as SqlxError;
 use thiserror::Error;
-use tokio::time::interval;
+use tokio::{
+    sync::{RwLock, mpsc},
+    time::interval,
+};
 use tracing::{debug, error, info};
+use utils::metrics::metrics;
+use uuid::Uuid;
 
 use crate::services::{
     analytics::AnalyticsContext,
+    config::Config,
+    git::GitService,
     git_host::{self, GitHostError, GitHostProvider},
+    pr_comment_follow_ups::{self, PrCommentFollowUpError},
+    queued_message::QueuedMessageService,
 };
 
 #[derive(Debug, Error)]
@@ -27,6 +42,8 @@ enum PrMonitorError {
     WorkspaceError(#[from] WorkspaceError),
     #[error(transparent)]
     Sqlx(#[from] SqlxError),
+    #[error(transparent)]
+    PrCommentFollowUp(#[from] PrCommentFollowUpError),
 }
 
 /// Service to monitor PRs and update task status when they are merged
@@ -34,17 +51,29 @@ pub struct PrMonitorService {
     db: DBService,
     poll_interval: Duration,
     analytics: Option<AnalyticsContext>,
+    config: Arc<RwLock<Config>>,
+    git: GitService,
+    queued_messages: QueuedMessageService,
+    /// Receives sessions with newly queued PR comments so idle ones can be started
+    wake_tx: mpsc::UnboundedSender<Uuid>,
 }
 
 impl PrMonitorService {
     pub async fn spawn(
         db: DBService,
         analytics: Option<AnalyticsContext>,
+        config: Arc<RwLock<Config>>,
+        queued_messages: QueuedMessageService,
+        wake_tx: mpsc::UnboundedSender<Uuid>,
     ) -> tokio::task::JoinHandle<()> {
         let service = Self {
             db,
             poll_interval: Duration::from_secs(60), // Check every minute
             analytics,
+            config,
+            git: GitService::new(),
+            queued_messages,
+            wake_tx,
         };
         tokio::spawn(async move {
             service.start().await;
@@ -61,9 +90,11 @@ impl PrMonitorService {
 
         loop {
             interval.tick().await;
+            let started = Instant::now();
             if let Err(e) = self.check_all_open_prs().await {
                 error!("Error checking open PRs: {}", e);
             }
+            metrics().pr_monitor_poll.observe(&[], started.elapsed());
         }
     }
 
@@ -141,7 +172,60 @@ impl PrMonitorService {
                     );
                 }
             }
+        } else if let Err(e) = self.check_pr_comments(pr_merge).await {
+            error!(
+                "Error checking comments on PR #{} for workspace {}: {}",
+                pr_merge.pr_info.number, pr_merge.workspace_id, e
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Queue comments left since the PR was opened or attached as a follow-up for
+    /// the workspace's agent. Comments that failed to be addressed are left for a
+    /// manual import.
+    async fn check_pr_comments(&self, pr_merge: &PrMerge) -> Result<(), PrMonitorError> {
+        if !self.config.read().await.pr_comment_follow_ups_enabled {
+            return Ok(());
         }
+        let Some(repo) = Repo::find_by_id(&self.db.pool, pr_merge.repo_id).await? else {
+            return Ok(());
+        };
+
+        let comments = pr_comment_follow_ups::fetch_pr_comments(&self.git, &repo, pr_merge).await?;
+        let tracked = PrCommentFollowUp::find_by_merge_id(&self.db.pool, pr_merge.id).await?;
+        let failed: HashSet<&str> = tracked
+            .iter()
+            .filter(|t| t.status == PrCommentFollowUpStatus::Failed)
+            .map(|t| t.comment_key.as_str())
+            .collect();
+        let pending: Vec<_> =
+            pr_comment_follow_ups::pending_comments(comments, &tracked, &self.queued_messages)
+                .into_iter()
+                .filter(|c| {
+                    c.created_at() > pr_merge.created_at
+                        && !failed.contains(pr_comment_follow_ups::comment_key(c).as_str())
+                })
+                .collect();
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        let session_id = pr_comment_follow_ups::queue_follow_up(
+            &self.db.pool,
+            &self.queued_messages,
+            pr_merge,
+            &pending,
+        )
+        .await?;
+        info!(
+            "Queued {} new comments on PR #{} for workspace {}",
+            pending.len(),
+            pr_merge.pr_info.number,
+            pr_merge.workspace_id
+        );
+        let _ = self.wake_tx.send(session_id);
 
         Ok(())
     }
@@ -14,7 +14,8 @@ use utils::api::projects::RemoteProject;
 use uuid::Uuid;
 
 use super::{
-    file_search::{FileSearchCache, SearchQuery},
+    file_ranker::FileRanker,
+    file_search::{FileSearchCache, SearchKind, SearchQuery},
     repo::{RepoError, RepoService},
 };
 
@@ -264,13 +265,12 @@ impl ProjectService {
         let search_futures: Vec<_> = repositories
             .iter()
             .map(|repo| {
+                FileRanker::apply_repo_settings(repo);
                 let repo_name = repo.name.clone();
                 let repo_path = repo.path.clone();
-                let mode = query.mode.clone();
-                let query_str = query_str.to_string();
                 async move {
                     let results = cache
-                        .search_repo(&repo_path, &query_str, mode)
+                        .search_repo(&repo_path, query)
                         .await
                         .unwrap_or_else(|e| {
                             tracing::warn!("Search failed for repo {}: {}", repo_name, e);
@@ -288,25 +288,29 @@ impl ProjectService {
             .flat_map(|(repo_name, results)| {
                 results.into_iter().map(move |r| SearchResult {
                     path: format!("{}/{}", repo_name, r.path),
-                    is_file: r.is_file,
-                    match_type: r.match_type.clone(),
-                    score: r.score,
+                    ..r
                 })
             })
             .collect();
 
-        all_results.sort_by(|a, b| {
-            let priority = |m: &SearchMatchType| match m {
-                SearchMatchType::FileName => 0,
-                SearchMatchType::DirectoryName => 1,
-                SearchMatchType::FullPath => 2,
-            };
-            priority(&a.match_type)
-                .cmp(&priority(&b.match_type))
-                .then_with(|| b.score.cmp(&a.score)) // Higher scores first
-        });
-
-        all_results.truncate(10);
+        match query.kind {
+            SearchKind::Files => all_results.sort_by(|a, b| {
+                let priority = |m: &SearchMatchType| match m {
+                    SearchMatchType::FileName => 0,
+                    SearchMatchType::DirectoryName => 1,
+                    SearchMatchType::FullPath => 2,
+                    SearchMatchType::Content | SearchMatchType::Symbol => 3,
+                };
+                priority(&a.match_type)
+                    .cmp(&priority(&b.match_type))
+                    .then_with(|| b.score.cmp(&a.score)) // Higher scores first
+            }),
+            // Stable, so repo order is kept among equally ranked symbols
+            SearchKind::Symbols => all_results.sort_by(|a, b| b.score.cmp(&a.score)),
+            SearchKind::Content => {}
+        }
+
+        all_results.truncate(query.kind.result_limit());
         Ok(all_results)
     }
 }
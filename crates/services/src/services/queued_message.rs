@@ -76,6 +76,11 @@ impl QueuedMessageService {
         self.queue.contains_key(&session_id)
     }
 
+    /// Sessions that currently have a queued message
+    pub fn queued_session_ids(&self) -> Vec<Uuid> {
+        self.queue.iter().map(|entry| *entry.key()).collect()
+    }
+
     /// Get queue status for frontend display
     pub fn get_status(&self, session_id: Uuid) -> QueueStatus {
         match self.get_queued(session_id) {
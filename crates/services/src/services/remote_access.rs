@@ -0,0 +1,216 @@
+//! Credentials and TLS material for remote access to the local server.
+//!
+//! Passwords are stored as salted PBKDF2-SHA256 hashes in the config. Generated
+//! tokens are random, so a plain SHA-256 hash is enough for them and keeps
+//! rejecting unknown bearer tokens cheap. Without a
+//! user-supplied certificate, a self-signed one is generated with `openssl` into
+//! the asset directory and reused across restarts.
+
+use std::path::PathBuf;
+
+use base64::{Engine, engine::general_purpose::STANDARD_NO_PAD};
+use pbkdf2::pbkdf2_hmac;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+use thiserror::Error;
+use tokio::process::Command;
+use utils::{assets::asset_dir, shell::resolve_executable_path};
+use uuid::Uuid;
+
+use super::config::RemoteAccessConfig;
+
+const HASH_SCHEME: &str = "pbkdf2-sha256";
+const HASH_ROUNDS: u32 = 600_000;
+const HASH_LEN: usize = 32;
+const TOKEN_HASH_SCHEME: &str = "sha256";
+const TOKEN_PREFIX: &str = "vkr_";
+const SELF_SIGNED_DAYS: &str = "825";
+
+#[derive(Debug, Error)]
+pub enum RemoteAccessError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Both a TLS certificate and key path must be set")]
+    IncompleteTlsConfig,
+    #[error("openssl is required to generate a self-signed certificate")]
+    OpensslNotFound,
+    #[error("Failed to generate a self-signed certificate: {0}")]
+    CertificateGeneration(String),
+}
+
+/// Hash a password for storage in the config
+pub fn hash_secret(secret: &str) -> String {
+    hash_secret_with_rounds(secret, HASH_ROUNDS)
+}
+
+fn hash_secret_with_rounds(secret: &str, rounds: u32) -> String {
+    let salt = Uuid::new_v4();
+    let hash = derive(secret, salt.as_bytes(), rounds);
+    format!(
+        "{HASH_SCHEME}${rounds}${}${}",
+        STANDARD_NO_PAD.encode(salt.as_bytes()),
+        STANDARD_NO_PAD.encode(hash)
+    )
+}
+
+pub fn verify_secret(secret: &str, stored: &str) -> bool {
+    let mut parts = stored.split('$');
+    let (Some(HASH_SCHEME), Some(rounds), Some(salt), Some(expected), None) = (
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+    ) else {
+        return false;
+    };
+    let (Ok(rounds), Ok(salt), Ok(expected)) = (
+        rounds.parse::<u32>(),
+        STANDARD_NO_PAD.decode(salt),
+        STANDARD_NO_PAD.decode(expected),
+    ) else {
+        return false;
+    };
+    derive(secret, &salt, rounds)[..].ct_eq(&expected).into()
+}
+
+fn derive(secret: &str, salt: &[u8], rounds: u32) -> [u8; HASH_LEN] {
+    let mut hash = [0u8; HASH_LEN];
+    pbkdf2_hmac::<Sha256>(secret.as_bytes(), salt, rounds, &mut hash);
+    hash
+}
+
+/// Hash a generated token for storage in the config
+pub fn hash_token(token: &str) -> String {
+    format!("{TOKEN_HASH_SCHEME}${:x}", Sha256::digest(token))
+}
+
+pub fn verify_token(token: &str, stored: &str) -> bool {
+    hash_token(token).as_bytes().ct_eq(stored.as_bytes()).into()
+}
+
+/// A new random bearer token, shown to the user once
+pub fn generate_token() -> String {
+    format!(
+        "{TOKEN_PREFIX}{}{}",
+        Uuid::new_v4().simple(),
+        Uuid::new_v4().simple()
+    )
+}
+
+/// A new random session identifier for login cookies
+pub fn generate_session_id() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+#[derive(Debug, Clone)]
+pub struct TlsFiles {
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+    pub self_signed: bool,
+}
+
+/// The configured certificate and key, or a self-signed pair generated on first use
+pub async fn resolve_tls_files(
+    config: &RemoteAccessConfig,
+    hostname: &str,
+) -> Result<TlsFiles, RemoteAccessError> {
+    match (&config.tls_cert_path, &config.tls_key_path) {
+        (Some(cert), Some(key)) => {
+            return Ok(TlsFiles {
+                cert_path: PathBuf::from(cert),
+                key_path: PathBuf::from(key),
+                self_signed: false,
+            });
+        }
+        (None, None) => {}
+        _ => return Err(RemoteAccessError::IncompleteTlsConfig),
+    }
+
+    let dir = asset_dir().join("tls");
+    let files = TlsFiles {
+        cert_path: dir.join("self-signed-cert.pem"),
+        key_path: dir.join("self-signed-key.pem"),
+        self_signed: true,
+    };
+    if files.cert_path.exists() && files.key_path.exists() {
+        return Ok(files);
+    }
+
+    tokio::fs::create_dir_all(&dir).await?;
+    let openssl = resolve_executable_path("openssl")
+        .await
+        .ok_or(RemoteAccessError::OpensslNotFound)?;
+    let output = Command::new(openssl)
+        .args(["req", "-x509", "-newkey", "rsa:2048", "-nodes", "-sha256"])
+        .args(["-days", SELF_SIGNED_DAYS, "-subj", "/CN=Vibe Kanban"])
+        .arg("-addext")
+        .arg(format!(
+            "subjectAltName=DNS:localhost,IP:127.0.0.1,DNS:{hostname}"
+        ))
+        .arg("-keyout")
+        .arg(&files.key_path)
+        .arg("-out")
+        .arg(&files.cert_path)
+        .output()
+        .await?;
+    if !output.status.success() {
+        return Err(RemoteAccessError::CertificateGeneration(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        tokio::fs::set_permissions(&files.key_path, std::fs::Permissions::from_mode(0o600)).await?;
+    }
+
+    tracing::info!(
+        "Generated a self-signed certificate at {}",
+        files.cert_path.display()
+    );
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashes_verify_only_their_secret() {
+        let stored = hash_secret_with_rounds("correct horse", 1_000);
+        assert!(stored.starts_with("pbkdf2-sha256$1000$"));
+        assert!(verify_secret("correct horse", &stored));
+        assert!(!verify_secret("correct horse ", &stored));
+        assert!(!verify_secret("correct horse", "plain-text"));
+        // Each hash gets its own salt
+        assert_ne!(stored, hash_secret_with_rounds("correct horse", 1_000));
+    }
+
+    #[test]
+    fn token_hashes_verify_only_their_token() {
+        let token = generate_token();
+        let stored = hash_token(&token);
+        assert!(stored.starts_with("sha256$"));
+        assert!(verify_token(&token, &stored));
+        assert!(!verify_token(&generate_token(), &stored));
+        assert!(!verify_token(
+            &token,
+            &hash_secret_with_rounds(&token, 1_000)
+        ));
+    }
+
+    #[test]
+    fn clients_never_see_a_credential_hash() {
+        let config = RemoteAccessConfig {
+            enabled: true,
+            password_hash: Some(hash_secret_with_rounds("correct horse", 1_000)),
+            token_hash: Some(hash_token(&generate_token())),
+            metrics_token_hash: Some(hash_token(&generate_token())),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&config.without_secrets()).unwrap();
+        assert!(!json.contains("sha256$"));
+        assert!(json.contains(r#""enabled":true"#));
+    }
+}
@@ -28,10 +28,21 @@ pub enum RepoError {
     Git(#[from] GitServiceError),
     #[error("Invalid folder name: {0}")]
     InvalidFolderName(String),
+    #[error("Invalid partial clone filter: {0}")]
+    InvalidCloneFilter(String),
 }
 
 pub type Result<T> = std::result::Result<T, RepoError>;
 
+/// Options for cloning large repositories
+#[derive(Debug, Clone, Default)]
+pub struct CloneOptions {
+    /// Partial clone filter, e.g. `blob:none`
+    pub filter: Option<String>,
+    /// Shallow clone depth
+    pub depth: Option<u32>,
+}
+
 #[derive(Clone, Default)]
 pub struct RepoService;
 
@@ -98,6 +109,58 @@ impl RepoService {
         parent_path: &str,
         folder_name: &str,
     ) -> Result<RepoModel> {
+        let repo_path = self.new_repo_path(parent_path, folder_name)?;
+
+        git.initialize_repo_with_main_branch(&repo_path)?;
+
+        let repo = RepoModel::find_or_create(pool, &repo_path, folder_name).await?;
+        Ok(repo)
+    }
+
+    /// Clone a remote repository and register it. A partial (`filter`) or shallow
+    /// (`depth`) clone lets very large repositories be registered without
+    /// downloading their full history and contents.
+    pub async fn clone_repo(
+        &self,
+        pool: &SqlitePool,
+        git: &GitService,
+        url: &str,
+        parent_path: &str,
+        folder_name: &str,
+        options: &CloneOptions,
+    ) -> Result<RepoModel> {
+        if let Some(filter) = &options.filter
+            && !is_valid_clone_filter(filter)
+        {
+            return Err(RepoError::InvalidCloneFilter(filter.clone()));
+        }
+
+        let repo_path = self.new_repo_path(parent_path, folder_name)?;
+
+        let git = git.clone();
+        let clone_url = url.to_string();
+        let target = repo_path.clone();
+        let clone_options = options.clone();
+        tokio::task::spawn_blocking(move || {
+            git.clone_with_filter(
+                &clone_url,
+                &target,
+                clone_options.filter.as_deref(),
+                clone_options.depth,
+            )
+        })
+        .await
+        .map_err(std::io::Error::other)??;
+
+        let repo = RepoModel::find_or_create(pool, &repo_path, folder_name).await?;
+        if options.filter.is_some() {
+            RepoModel::set_partial_clone_filter(pool, repo.id, options.filter.as_deref()).await?;
+        }
+        self.get_by_id(pool, repo.id).await
+    }
+
+    /// Validate `folder_name` and return the not-yet-existing path under `parent_path`.
+    fn new_repo_path(&self, parent_path: &str, folder_name: &str) -> Result<PathBuf> {
         if folder_name.is_empty()
             || folder_name.contains('/')
             || folder_name.contains('\\')
@@ -119,10 +182,22 @@ impl RepoService {
         if repo_path.exists() {
             return Err(RepoError::DirectoryAlreadyExists(repo_path));
         }
+        Ok(repo_path)
+    }
+}
 
-        git.initialize_repo_with_main_branch(&repo_path)?;
-
-        let repo = RepoModel::find_or_create(pool, &repo_path, folder_name).await?;
-        Ok(repo)
+/// Accept the filter specs git supports for partial clones: `blob:none`,
+/// `blob:limit=<n>[kmg]` and `tree:<depth>`.
+fn is_valid_clone_filter(filter: &str) -> bool {
+    if filter == "blob:none" {
+        return true;
+    }
+    if let Some(limit) = filter.strip_prefix("blob:limit=") {
+        let digits = limit.trim_end_matches(['k', 'm', 'g']);
+        return !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit());
+    }
+    if let Some(depth) = filter.strip_prefix("tree:") {
+        return !depth.is_empty() && depth.chars().all(|c| c.is_ascii_digit());
     }
+    false
 }
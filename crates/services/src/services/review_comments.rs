@@ -0,0 +1,253 @@
+//! Anchoring of inline review comments to workspace diffs, and the follow-up
+//! prompt that hands them to the coding agent.
+//!
+//! Each comment keeps a blob snapshot of the file side it was written against.
+//! When the diff changes (new commits, rebases onto a moved target branch) the
+//! snapshot is diffed against the current side to move the comment with its line.
+
+use std::path::{Component, Path};
+
+use db::models::review_comment::{ReviewAnchor, ReviewComment, ReviewSide};
+use utils::diff::map_line;
+
+use super::git::{GitService, GitServiceError};
+
+pub fn validate_file_path(file_path: &str) -> Result<(), GitServiceError> {
+    let path = Path::new(file_path);
+    let is_safe =
+        !file_path.is_empty() && path.components().all(|c| matches!(c, Component::Normal(_)));
+    if is_safe {
+        Ok(())
+    } else {
+        Err(GitServiceError::InvalidRepository(format!(
+            "Invalid file path: {file_path}"
+        )))
+    }
+}
+
+/// Current text of one side of the workspace diff: the file at `base_commit` for
+/// `old`, the worktree copy for `new`. None when the file does not exist there.
+pub fn side_content(
+    git: &GitService,
+    worktree_path: &Path,
+    base_commit: &str,
+    side: ReviewSide,
+    file_path: &str,
+) -> Result<Option<String>, GitServiceError> {
+    validate_file_path(file_path)?;
+    match side {
+        ReviewSide::Old => git.read_file_at_commit(worktree_path, base_commit, file_path),
+        ReviewSide::New => match std::fs::read_to_string(worktree_path.join(file_path)) {
+            Ok(content) => Ok(Some(content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        },
+    }
+}
+
+/// Anchor `line` of `content`, snapshotting the content into the object database
+pub fn anchor_line(
+    git: &GitService,
+    worktree_path: &Path,
+    content: &str,
+    line: u32,
+) -> Result<ReviewAnchor, GitServiceError> {
+    let line_content = (line as usize)
+        .checked_sub(1)
+        .and_then(|index| content.lines().nth(index))
+        .map(str::to_string);
+    Ok(ReviewAnchor {
+        line,
+        anchor_blob: Some(git.write_blob(worktree_path, content)?),
+        outdated: line_content.is_none(),
+        line_content,
+    })
+}
+
+/// Move a comment to where its line is in `current`, the present text of its side.
+/// Falls back to the nearest line with identical text, then marks it outdated.
+pub fn reanchor(
+    git: &GitService,
+    worktree_path: &Path,
+    comment: &ReviewComment,
+    current: Option<&str>,
+) -> Result<ReviewAnchor, GitServiceError> {
+    let existing = ReviewAnchor {
+        line: comment.line,
+        anchor_blob: comment.anchor_blob.clone(),
+        line_content: comment.line_content.clone(),
+        outdated: comment.outdated,
+    };
+    let Some(current) = current else {
+        return Ok(ReviewAnchor {
+            outdated: true,
+            ..existing
+        });
+    };
+
+    let previous = match &comment.anchor_blob {
+        Some(blob) => git.read_blob(worktree_path, blob)?,
+        None => None,
+    };
+    if previous.as_deref() == Some(current) {
+        return Ok(existing);
+    }
+    if let Some(line) = previous
+        .as_deref()
+        .and_then(|previous| map_line(previous, current, comment.line as usize))
+    {
+        return anchor_line(git, worktree_path, current, line as u32);
+    }
+    if let Some(line) = comment
+        .line_content
+        .as_deref()
+        .and_then(|text| find_nearest_line(current, text, comment.line))
+    {
+        return anchor_line(git, worktree_path, current, line);
+    }
+
+    Ok(ReviewAnchor {
+        outdated: true,
+        ..existing
+    })
+}
+
+fn find_nearest_line(content: &str, text: &str, near: u32) -> Option<u32> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.trim() == text)
+        .map(|(index, _)| index as u32 + 1)
+        .min_by_key(|line| line.abs_diff(near))
+}
+
+/// Whether an agent turn touched the commented line. `before` and `after` are the
+/// workspace copy of the file around the turn. Comments on the base version have
+/// no line in the workspace copy, so any change to the file counts for them.
+pub fn line_changed(comment: &ReviewComment, before: &str, after: Option<&str>) -> bool {
+    let Some(after) = after else {
+        return true;
+    };
+    match comment.side {
+        ReviewSide::New => map_line(before, after, comment.line as usize).is_none(),
+        ReviewSide::Old => before != after,
+    }
+}
+
+/// One follow-up prompt covering every comment, grouped by file
+pub fn build_review_prompt(comments: &[(String, &ReviewComment)]) -> String {
+    let mut prompt = String::from(
+        "Please address the following review comments on your changes. \
+         Line numbers refer to the current version of each file unless marked as the original version.\n",
+    );
+    let mut current_file: Option<String> = None;
+    for (repo_name, comment) in comments {
+        let file = format!("{repo_name}/{}", comment.file_path);
+        if current_file.as_ref() != Some(&file) {
+            prompt.push_str(&format!("\n## {file}\n"));
+            current_file = Some(file);
+        }
+        let location = match comment.side {
+            ReviewSide::New => format!("Line {}", comment.line),
+            ReviewSide::Old => format!("Original line {}", comment.line),
+        };
+        prompt.push_str(&format!("\n- {location}"));
+        if let Some(text) = comment
+            .line_content
+            .as_deref()
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+        {
+            prompt.push_str(&format!(" (`{text}`)"));
+        }
+        prompt.push_str(":\n");
+        for body_line in comment.body.trim().lines() {
+            prompt.push_str(&format!("  {body_line}\n"));
+        }
+    }
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use db::models::review_comment::ReviewCommentStatus;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn comment(side: ReviewSide, line: u32, anchor: ReviewAnchor) -> ReviewComment {
+        ReviewComment {
+            id: Uuid::new_v4(),
+            workspace_id: Uuid::new_v4(),
+            repo_id: Uuid::new_v4(),
+            file_path: "src/lib.rs".to_string(),
+            side,
+            line,
+            body: "Handle the error\ninstead of unwrapping".to_string(),
+            anchor_blob: anchor.anchor_blob,
+            line_content: anchor.line_content,
+            outdated: anchor.outdated,
+            status: ReviewCommentStatus::Open,
+            sent_execution_process_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn reanchors_through_edits_and_falls_back_to_text() {
+        let dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(dir.path()).unwrap();
+        let git = GitService::new();
+
+        let original = "fn a() {}\nlet x = f().unwrap();\nfn b() {}\n";
+        let anchor = anchor_line(&git, dir.path(), original, 2).unwrap();
+        assert_eq!(
+            anchor.line_content.as_deref(),
+            Some("let x = f().unwrap();")
+        );
+        let c = comment(ReviewSide::New, 2, anchor);
+
+        // Lines inserted above move the comment down
+        let shifted = "use std::io;\n\nfn a() {}\nlet x = f().unwrap();\nfn b() {}\n";
+        let moved = reanchor(&git, dir.path(), &c, Some(shifted)).unwrap();
+        assert_eq!((moved.line, moved.outdated), (4, false));
+
+        // Without the snapshot the line text is matched instead
+        let c_without_blob = ReviewComment {
+            anchor_blob: None,
+            ..c.clone()
+        };
+        let moved = reanchor(&git, dir.path(), &c_without_blob, Some(shifted)).unwrap();
+        assert_eq!(moved.line, 4);
+
+        // Rewriting the line leaves the comment outdated at its old position
+        let rewritten = "fn a() {}\nlet x = f()?;\nfn b() {}\n";
+        let stale = reanchor(&git, dir.path(), &c, Some(rewritten)).unwrap();
+        assert_eq!((stale.line, stale.outdated), (2, true));
+        assert!(line_changed(&c, original, Some(rewritten)));
+        assert!(!line_changed(&c, original, Some(shifted)));
+    }
+
+    #[test]
+    fn prompt_groups_comments_by_file() {
+        let anchor = ReviewAnchor {
+            line: 2,
+            anchor_blob: None,
+            line_content: Some("let x = f().unwrap();".to_string()),
+            outdated: false,
+        };
+        let first = comment(ReviewSide::New, 2, anchor.clone());
+        let second = comment(ReviewSide::Old, 7, anchor);
+        let prompt =
+            build_review_prompt(&[("app".to_string(), &first), ("app".to_string(), &second)]);
+        assert_eq!(prompt.matches("## app/src/lib.rs").count(), 1);
+        assert!(prompt.contains("- Line 2 (`let x = f().unwrap();`):\n  Handle the error\n"));
+        assert!(prompt.contains("- Original line 7"));
+    }
+}
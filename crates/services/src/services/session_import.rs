@@ -0,0 +1,558 @@
+//! Discovery and import of coding agent sessions that were started outside Vibe
+//! Kanban. Session files are converted into the stdout format each executor emits,
+//! so the executor's own `normalize_logs` can render them like any other run.
+
+use std::{
+    fs::{self, File},
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use chrono::{DateTime, Utc};
+use executors::executors::BaseCodingAgent;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use thiserror::Error;
+use ts_rs::TS;
+
+/// Sessions listed per agent, most recently modified first
+const MAX_DISCOVERED_SESSIONS: usize = 200;
+/// Lines read from the head of a session file when listing it
+const METADATA_SCAN_LINES: usize = 50;
+const PROMPT_PREVIEW_CHARS: usize = 120;
+
+#[derive(Debug, Error)]
+pub enum SessionImportError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("Could not find home directory")]
+    HomeDirNotFound,
+    #[error("{0} is not a session file of {1}")]
+    NotASessionFile(String, ExternalAgent),
+    #[error("Session has no messages to import")]
+    Empty,
+}
+
+/// Coding agents whose local session files can be imported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ExternalAgent {
+    ClaudeCode,
+    Codex,
+    Opencode,
+}
+
+impl ExternalAgent {
+    pub fn base_coding_agent(self) -> BaseCodingAgent {
+        match self {
+            ExternalAgent::ClaudeCode => BaseCodingAgent::ClaudeCode,
+            ExternalAgent::Codex => BaseCodingAgent::Codex,
+            ExternalAgent::Opencode => BaseCodingAgent::Opencode,
+        }
+    }
+
+    /// Directory the agent stores its sessions in
+    pub fn sessions_dir(self) -> Result<PathBuf, SessionImportError> {
+        let home = dirs::home_dir().ok_or(SessionImportError::HomeDirNotFound)?;
+        Ok(match self {
+            ExternalAgent::ClaudeCode => home.join(".claude").join("projects"),
+            ExternalAgent::Codex => std::env::var_os("CODEX_HOME")
+                .map(PathBuf::from)
+                .unwrap_or_else(|| home.join(".codex"))
+                .join("sessions"),
+            ExternalAgent::Opencode => std::env::var_os("XDG_DATA_HOME")
+                .map(PathBuf::from)
+                .unwrap_or_else(|| home.join(".local").join("share"))
+                .join("opencode")
+                .join("storage"),
+        })
+    }
+}
+
+impl std::fmt::Display for ExternalAgent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ExternalAgent::ClaudeCode => "Claude Code",
+            ExternalAgent::Codex => "Codex",
+            ExternalAgent::Opencode => "OpenCode",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ExternalSessionSummary {
+    pub agent: ExternalAgent,
+    pub agent_session_id: String,
+    /// Session file to pass back when importing
+    pub path: String,
+    /// Directory the session was started in
+    pub cwd: Option<String>,
+    pub git_branch: Option<String>,
+    pub first_prompt: Option<String>,
+    pub modified_at: DateTime<Utc>,
+}
+
+/// A session read from disk, converted to the executor's stdout log lines
+#[derive(Debug, Clone)]
+pub struct ExternalSession {
+    pub agent: ExternalAgent,
+    pub agent_session_id: String,
+    pub path: PathBuf,
+    pub cwd: Option<PathBuf>,
+    pub first_prompt: Option<String>,
+    pub stdout_lines: Vec<String>,
+}
+
+/// List the agent's local sessions, most recently modified first
+pub fn discover_sessions(
+    agent: ExternalAgent,
+) -> Result<Vec<ExternalSessionSummary>, SessionImportError> {
+    let root = agent.sessions_dir()?;
+    let files_root = match agent {
+        ExternalAgent::Opencode => root.join("session"),
+        _ => root.clone(),
+    };
+    if !files_root.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut files: Vec<(PathBuf, SystemTime)> = walkdir::WalkDir::new(&files_root)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && is_session_file(agent, e.path()))
+        .map(|e| {
+            let modified = e
+                .metadata()
+                .ok()
+                .and_then(|m| m.modified().ok())
+                .unwrap_or(SystemTime::UNIX_EPOCH);
+            (e.into_path(), modified)
+        })
+        .collect();
+    files.sort_by(|a, b| b.1.cmp(&a.1));
+
+    Ok(files
+        .into_iter()
+        .take(MAX_DISCOVERED_SESSIONS)
+        .filter_map(|(path, modified)| {
+            let summary = match agent {
+                ExternalAgent::ClaudeCode => summarize_claude_session(&path),
+                ExternalAgent::Codex => summarize_codex_session(&path),
+                ExternalAgent::Opencode => summarize_opencode_session(&path),
+            };
+            summary.map(|mut summary| {
+                summary.modified_at = modified.into();
+                summary
+            })
+        })
+        .collect())
+}
+
+fn is_session_file(agent: ExternalAgent, path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    match agent {
+        // agent-* files hold sub-agent sidechains rather than sessions
+        ExternalAgent::ClaudeCode => name.ends_with(".jsonl") && !name.starts_with("agent-"),
+        ExternalAgent::Codex => name.starts_with("rollout-") && name.ends_with(".jsonl"),
+        ExternalAgent::Opencode => name.starts_with("ses_") && name.ends_with(".json"),
+    }
+}
+
+/// Read a session file and convert it for the matching executor's normalizer
+pub fn read_session(
+    agent: ExternalAgent,
+    path: &Path,
+) -> Result<ExternalSession, SessionImportError> {
+    let root = agent.sessions_dir()?;
+    let path = path.canonicalize()?;
+    if !path.starts_with(root.canonicalize().unwrap_or(root)) || !is_session_file(agent, &path) {
+        return Err(SessionImportError::NotASessionFile(
+            path.to_string_lossy().to_string(),
+            agent,
+        ));
+    }
+
+    let session = match agent {
+        ExternalAgent::ClaudeCode => read_claude_session(&path)?,
+        ExternalAgent::Codex => read_codex_session(&path)?,
+        ExternalAgent::Opencode => read_opencode_session(&path)?,
+    };
+    if session.stdout_lines.is_empty() {
+        return Err(SessionImportError::Empty);
+    }
+    Ok(session)
+}
+
+fn jsonl_values(path: &Path) -> Result<impl Iterator<Item = Value>, SessionImportError> {
+    let reader = BufReader::new(File::open(path)?);
+    Ok(reader
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<Value>(&line).ok()))
+}
+
+fn preview(text: &str) -> String {
+    let text = text.trim().replace('\n', " ");
+    match text.char_indices().nth(PROMPT_PREVIEW_CHARS) {
+        Some((idx, _)) => format!("{}...", &text[..idx]),
+        None => text,
+    }
+}
+
+fn str_field(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+// ===== Claude Code =====
+// ~/.claude/projects/<encoded cwd>/<session id>.jsonl, one record per message
+
+/// Text typed by the user in a Claude record, skipping tool results and the
+/// command caveats Claude Code injects as user messages
+fn claude_user_text(record: &Value) -> Option<String> {
+    if record.get("type").and_then(Value::as_str) != Some("user")
+        || record.get("isMeta").and_then(Value::as_bool) == Some(true)
+    {
+        return None;
+    }
+    let content = record.get("message")?.get("content")?;
+    let text = match content {
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items
+            .iter()
+            .filter(|item| item.get("type").and_then(Value::as_str) == Some("text"))
+            .filter_map(|item| str_field(item, "text"))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => return None,
+    };
+    (!text.trim().is_empty() && !text.trim_start().starts_with('<')).then_some(text)
+}
+
+fn summarize_claude_session(path: &Path) -> Option<ExternalSessionSummary> {
+    let mut summary = ExternalSessionSummary {
+        agent: ExternalAgent::ClaudeCode,
+        agent_session_id: path.file_stem()?.to_str()?.to_string(),
+        path: path.to_string_lossy().to_string(),
+        cwd: None,
+        git_branch: None,
+        first_prompt: None,
+        modified_at: Utc::now(),
+    };
+    for record in jsonl_values(path).ok()?.take(METADATA_SCAN_LINES) {
+        summary.cwd = summary.cwd.or_else(|| str_field(&record, "cwd"));
+        summary.git_branch = summary
+            .git_branch
+            .or_else(|| str_field(&record, "gitBranch"));
+        if summary.first_prompt.is_none() {
+            summary.first_prompt = claude_user_text(&record).map(|text| preview(&text));
+        }
+    }
+    Some(summary)
+}
+
+fn read_claude_session(path: &Path) -> Result<ExternalSession, SessionImportError> {
+    let mut session = ExternalSession {
+        agent: ExternalAgent::ClaudeCode,
+        agent_session_id: path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default()
+            .to_string(),
+        path: path.to_path_buf(),
+        cwd: None,
+        first_prompt: None,
+        stdout_lines: Vec::new(),
+    };
+
+    for record in jsonl_values(path)? {
+        if session.cwd.is_none() {
+            session.cwd = str_field(&record, "cwd").map(PathBuf::from);
+        }
+        if session.first_prompt.is_none() {
+            session.first_prompt = claude_user_text(&record);
+        }
+        let record_type = record.get("type").and_then(Value::as_str);
+        if !matches!(record_type, Some("user" | "assistant"))
+            || record.get("isSidechain").and_then(Value::as_bool) == Some(true)
+        {
+            continue;
+        }
+        let Some(message) = record.get("message") else {
+            continue;
+        };
+        // Same shape as `claude -p --output-format=stream-json`
+        session.stdout_lines.push(
+            json!({
+                "type": record_type,
+                "message": message,
+                "session_id": record.get("sessionId"),
+            })
+            .to_string(),
+        );
+    }
+    Ok(session)
+}
+
+/// Name of the directory Claude Code keeps the sessions of `cwd` in
+pub fn claude_project_dir_name(cwd: &Path) -> String {
+    cwd.to_string_lossy()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+/// Copy a Claude Code session next to the sessions of `cwd`, where
+/// `claude --resume` looks it up when a follow-up runs in that directory
+pub fn install_claude_session(session_path: &Path, cwd: &Path) -> std::io::Result<()> {
+    let Some(projects_dir) = session_path.parent().and_then(Path::parent) else {
+        return Ok(());
+    };
+    let target_dir = projects_dir.join(claude_project_dir_name(cwd));
+    let Some(file_name) = session_path.file_name() else {
+        return Ok(());
+    };
+    let target = target_dir.join(file_name);
+    if target == session_path || target.exists() {
+        return Ok(());
+    }
+    fs::create_dir_all(&target_dir)?;
+    fs::copy(session_path, target)?;
+    Ok(())
+}
+
+// ===== Codex =====
+// ~/.codex/sessions/YYYY/MM/DD/rollout-<timestamp>-<session id>.jsonl with a
+// `session_meta` header followed by `event_msg` protocol events
+
+fn summarize_codex_session(path: &Path) -> Option<ExternalSessionSummary> {
+    let mut summary: Option<ExternalSessionSummary> = None;
+    for record in jsonl_values(path).ok()?.take(METADATA_SCAN_LINES) {
+        let payload = record.get("payload").unwrap_or(&Value::Null);
+        match record.get("type").and_then(Value::as_str) {
+            Some("session_meta") if summary.is_none() => {
+                summary = Some(ExternalSessionSummary {
+                    agent: ExternalAgent::Codex,
+                    agent_session_id: str_field(payload, "id")?,
+                    path: path.to_string_lossy().to_string(),
+                    cwd: str_field(payload, "cwd"),
+                    git_branch: payload.get("git").and_then(|git| str_field(git, "branch")),
+                    first_prompt: None,
+                    modified_at: Utc::now(),
+                });
+            }
+            Some("event_msg")
+                if payload.get("type").and_then(Value::as_str) == Some("user_message") =>
+            {
+                if let Some(summary) = summary.as_mut().filter(|s| s.first_prompt.is_none()) {
+                    summary.first_prompt = str_field(payload, "message").map(|m| preview(&m));
+                }
+            }
+            _ => {}
+        }
+    }
+    summary
+}
+
+fn read_codex_session(path: &Path) -> Result<ExternalSession, SessionImportError> {
+    let mut session = ExternalSession {
+        agent: ExternalAgent::Codex,
+        agent_session_id: String::new(),
+        path: path.to_path_buf(),
+        cwd: None,
+        first_prompt: None,
+        stdout_lines: Vec::new(),
+    };
+
+    for record in jsonl_values(path)? {
+        let Some(payload) = record.get("payload") else {
+            continue;
+        };
+        match record.get("type").and_then(Value::as_str) {
+            Some("session_meta") if session.agent_session_id.is_empty() => {
+                session.agent_session_id = str_field(payload, "id").unwrap_or_default();
+                session.cwd = str_field(payload, "cwd").map(PathBuf::from);
+            }
+            Some("event_msg") => {
+                let Some(event_type) = payload.get("type").and_then(Value::as_str) else {
+                    continue;
+                };
+                if event_type == "user_message" && session.first_prompt.is_none() {
+                    session.first_prompt = str_field(payload, "message");
+                }
+                // Same shape as the notifications of `codex app-server`
+                session.stdout_lines.push(
+                    json!({
+                        "method": format!("codex/event/{event_type}"),
+                        "params": { "msg": payload },
+                    })
+                    .to_string(),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    if session.agent_session_id.is_empty() {
+        return Err(SessionImportError::NotASessionFile(
+            path.to_string_lossy().to_string(),
+            ExternalAgent::Codex,
+        ));
+    }
+    Ok(session)
+}
+
+// ===== OpenCode =====
+// storage/session/<project>/<session id>.json, with messages in
+// storage/message/<session id>/ and their parts in storage/part/<message id>/
+
+fn read_json(path: &Path) -> Option<Value> {
+    serde_json::from_slice(&fs::read(path).ok()?).ok()
+}
+
+/// JSON files of a storage directory ordered by their time-sortable ids
+fn read_json_dir(dir: &Path) -> Vec<Value> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("json"))
+        .collect();
+    paths.sort();
+    paths.iter().filter_map(|p| read_json(p)).collect()
+}
+
+fn summarize_opencode_session(path: &Path) -> Option<ExternalSessionSummary> {
+    let info = read_json(path)?;
+    Some(ExternalSessionSummary {
+        agent: ExternalAgent::Opencode,
+        agent_session_id: str_field(&info, "id")?,
+        path: path.to_string_lossy().to_string(),
+        cwd: str_field(&info, "directory"),
+        git_branch: None,
+        first_prompt: str_field(&info, "title").map(|title| preview(&title)),
+        modified_at: Utc::now(),
+    })
+}
+
+fn read_opencode_session(path: &Path) -> Result<ExternalSession, SessionImportError> {
+    let info: Value = serde_json::from_slice(&fs::read(path)?)?;
+    let session_id = str_field(&info, "id").ok_or_else(|| {
+        SessionImportError::NotASessionFile(
+            path.to_string_lossy().to_string(),
+            ExternalAgent::Opencode,
+        )
+    })?;
+    // storage/session/<project>/<id>.json -> storage/
+    let storage = path
+        .parent()
+        .and_then(Path::parent)
+        .and_then(Path::parent)
+        .unwrap_or(path);
+
+    let mut session = ExternalSession {
+        agent: ExternalAgent::Opencode,
+        agent_session_id: session_id.clone(),
+        path: path.to_path_buf(),
+        cwd: str_field(&info, "directory").map(PathBuf::from),
+        first_prompt: None,
+        stdout_lines: vec![
+            json!({ "type": "session_start", "session_id": session_id }).to_string(),
+        ],
+    };
+
+    let sdk_event = |event_type: &str, properties: Value| {
+        json!({
+            "type": "sdk_event",
+            "event": { "type": event_type, "properties": properties },
+        })
+        .to_string()
+    };
+
+    for message in read_json_dir(&storage.join("message").join(&session_id)) {
+        let Some(message_id) = str_field(&message, "id") else {
+            continue;
+        };
+        let is_user = message.get("role").and_then(Value::as_str) == Some("user");
+        let parts = read_json_dir(&storage.join("part").join(&message_id));
+        if is_user && session.first_prompt.is_none() {
+            session.first_prompt = parts
+                .iter()
+                .filter(|part| part.get("type").and_then(Value::as_str) == Some("text"))
+                .find_map(|part| str_field(part, "text"));
+        }
+
+        session
+            .stdout_lines
+            .push(sdk_event("message.updated", json!({ "info": message })));
+        for part in parts {
+            session
+                .stdout_lines
+                .push(sdk_event("message.part.updated", json!({ "part": part })));
+        }
+    }
+
+    // Only the session start marker: nothing was said in this session
+    if session.stdout_lines.len() == 1 {
+        session.stdout_lines.clear();
+    }
+    Ok(session)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn claude_records_become_stream_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("4f1d.jsonl");
+        let records = [
+            json!({"type": "summary", "summary": "Fix the build"}),
+            json!({
+                "type": "user",
+                "sessionId": "4f1d",
+                "cwd": "/work/app",
+                "message": {"role": "user", "content": "Fix the build"},
+            }),
+            json!({
+                "type": "assistant",
+                "sessionId": "4f1d",
+                "isSidechain": true,
+                "message": {"role": "assistant", "content": [{"type": "text", "text": "sub"}]},
+            }),
+            json!({
+                "type": "assistant",
+                "sessionId": "4f1d",
+                "message": {"role": "assistant", "content": [{"type": "text", "text": "On it"}]},
+            }),
+        ];
+        let contents: String = records.iter().map(|r| format!("{r}\n")).collect();
+        fs::write(&path, contents).unwrap();
+
+        let session = read_claude_session(&path).unwrap();
+        assert_eq!(session.agent_session_id, "4f1d");
+        assert_eq!(session.cwd, Some(PathBuf::from("/work/app")));
+        assert_eq!(session.first_prompt.as_deref(), Some("Fix the build"));
+        assert_eq!(session.stdout_lines.len(), 2);
+
+        let first: Value = serde_json::from_str(&session.stdout_lines[0]).unwrap();
+        assert_eq!(first["type"], "user");
+        assert_eq!(first["session_id"], "4f1d");
+    }
+
+    #[test]
+    fn claude_project_dir_name_replaces_separators() {
+        assert_eq!(
+            claude_project_dir_name(Path::new("/tmp/vibe-kanban/worktrees/a04a.fix")),
+            "-tmp-vibe-kanban-worktrees-a04a-fix"
+        );
+    }
+}
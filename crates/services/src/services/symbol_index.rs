@@ -0,0 +1,349 @@
+//! Definition index built with tree-sitter for @-mentioning symbols in prompts.
+//! Covers functions, methods and type definitions in Rust, TypeScript/JavaScript
+//! and Python sources.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use db::models::project::{SearchMatchType, SearchResult, SearchSymbol, SymbolKind};
+use tree_sitter::{Language, Node, Parser};
+
+/// Larger files are usually generated or vendored
+const MAX_FILE_BYTES: u64 = 512 * 1024;
+const MAX_INDEXED_FILES: usize = 10_000;
+const MAX_SIGNATURE_CHARS: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum SourceLanguage {
+    Rust,
+    TypeScript,
+    Tsx,
+    Python,
+}
+
+impl SourceLanguage {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "rs" => Some(Self::Rust),
+            "ts" | "mts" | "cts" => Some(Self::TypeScript),
+            // The TSX grammar is a superset of JavaScript
+            "tsx" | "js" | "jsx" | "mjs" | "cjs" => Some(Self::Tsx),
+            "py" | "pyi" => Some(Self::Python),
+            _ => None,
+        }
+    }
+
+    fn grammar(self) -> Language {
+        match self {
+            Self::Rust => tree_sitter_rust::LANGUAGE.into(),
+            Self::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            Self::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            Self::Python => tree_sitter_python::LANGUAGE.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedSymbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// Path relative to the repository root
+    pub path: String,
+    /// 1-based line of the definition
+    pub line: u32,
+    /// First line of the definition
+    pub signature: String,
+}
+
+#[derive(Debug, Default)]
+pub struct SymbolIndex {
+    symbols: Vec<IndexedSymbol>,
+}
+
+impl SymbolIndex {
+    /// Parse the given repository-relative files; unsupported ones are skipped
+    pub fn build<'a>(repo_path: &Path, files: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut parsers: HashMap<SourceLanguage, Parser> = HashMap::new();
+        let mut symbols = Vec::new();
+
+        for relative in files
+            .into_iter()
+            .filter(|relative| SourceLanguage::from_path(Path::new(relative)).is_some())
+            .take(MAX_INDEXED_FILES)
+        {
+            let path: PathBuf = repo_path.join(relative);
+            let Some(language) = SourceLanguage::from_path(&path) else {
+                continue;
+            };
+            if !std::fs::metadata(&path).is_ok_and(|m| m.len() <= MAX_FILE_BYTES) {
+                continue;
+            }
+            let Ok(source) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+
+            let parser = match parsers.entry(language) {
+                std::collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+                std::collections::hash_map::Entry::Vacant(entry) => {
+                    let mut parser = Parser::new();
+                    if let Err(e) = parser.set_language(&language.grammar()) {
+                        tracing::warn!("Failed to load {:?} grammar: {}", language, e);
+                        continue;
+                    }
+                    entry.insert(parser)
+                }
+            };
+            symbols.extend(extract_symbols(parser, language, relative, &source));
+        }
+
+        Self { symbols }
+    }
+
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /// Case-insensitive name search ranking exact, then prefix, then substring matches
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchResult> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+
+        let mut matches: Vec<(i64, &IndexedSymbol)> = self
+            .symbols
+            .iter()
+            .filter_map(|symbol| {
+                let name = symbol.name.to_lowercase();
+                let score = if name == query {
+                    300
+                } else if name.starts_with(&query) {
+                    200
+                } else if name.contains(&query) {
+                    100
+                } else {
+                    return None;
+                };
+                Some((score, symbol))
+            })
+            .collect();
+        matches.sort_by(|(score_a, a), (score_b, b)| {
+            score_b
+                .cmp(score_a)
+                .then_with(|| a.name.len().cmp(&b.name.len()))
+                .then_with(|| a.path.cmp(&b.path))
+                .then_with(|| a.line.cmp(&b.line))
+        });
+
+        matches
+            .into_iter()
+            .take(limit)
+            .map(|(score, symbol)| SearchResult {
+                path: symbol.path.clone(),
+                is_file: true,
+                match_type: SearchMatchType::Symbol,
+                score,
+                line: Some(symbol.line),
+                preview: Some(symbol.signature.clone()),
+                symbol: Some(SearchSymbol {
+                    name: symbol.name.clone(),
+                    kind: symbol.kind.clone(),
+                }),
+            })
+            .collect()
+    }
+}
+
+fn extract_symbols(
+    parser: &mut Parser,
+    language: SourceLanguage,
+    path: &str,
+    source: &str,
+) -> Vec<IndexedSymbol> {
+    let Some(tree) = parser.parse(source, None) else {
+        return Vec::new();
+    };
+
+    let mut symbols = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if let Some(kind) = definition_kind(language, node)
+            && let Some(name) = definition_name(node, source)
+        {
+            symbols.push(IndexedSymbol {
+                name,
+                kind,
+                path: path.to_string(),
+                line: node.start_position().row as u32 + 1,
+                signature: signature(node, source),
+            });
+        }
+
+        let mut cursor = node.walk();
+        // Reversed so the stack yields definitions in source order
+        let children: Vec<_> = node.named_children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+    symbols
+}
+
+fn definition_kind(language: SourceLanguage, node: Node) -> Option<SymbolKind> {
+    match language {
+        SourceLanguage::Rust => match node.kind() {
+            "function_item" | "function_signature_item" => Some(
+                if ancestor_kind(node, 2).is_some_and(|k| k == "impl_item" || k == "trait_item") {
+                    SymbolKind::Method
+                } else {
+                    SymbolKind::Function
+                },
+            ),
+            "struct_item" | "union_item" => Some(SymbolKind::Struct),
+            "enum_item" => Some(SymbolKind::Enum),
+            "trait_item" => Some(SymbolKind::Trait),
+            "type_item" => Some(SymbolKind::TypeAlias),
+            "mod_item" => Some(SymbolKind::Module),
+            _ => None,
+        },
+        SourceLanguage::TypeScript | SourceLanguage::Tsx => match node.kind() {
+            "function_declaration" | "generator_function_declaration" => Some(SymbolKind::Function),
+            "class_declaration" | "abstract_class_declaration" => Some(SymbolKind::Class),
+            "interface_declaration" => Some(SymbolKind::Interface),
+            "type_alias_declaration" => Some(SymbolKind::TypeAlias),
+            "enum_declaration" => Some(SymbolKind::Enum),
+            "method_definition" => Some(SymbolKind::Method),
+            // `const Foo = () => ...` at module level, the usual shape of components and hooks
+            "variable_declarator"
+                if node.child_by_field_name("value").is_some_and(|value| {
+                    matches!(value.kind(), "arrow_function" | "function_expression")
+                }) && is_module_level_declarator(node) =>
+            {
+                Some(SymbolKind::Function)
+            }
+            _ => None,
+        },
+        SourceLanguage::Python => match node.kind() {
+            "function_definition" => Some(
+                if ancestor_kind(node, 2).is_some_and(|k| k == "class_definition")
+                    || ancestor_kind(node, 3).is_some_and(|k| k == "class_definition")
+                {
+                    SymbolKind::Method
+                } else {
+                    SymbolKind::Function
+                },
+            ),
+            "class_definition" => Some(SymbolKind::Class),
+            _ => None,
+        },
+    }
+}
+
+fn ancestor_kind(node: Node, depth: usize) -> Option<&'static str> {
+    let mut current = node;
+    for _ in 0..depth {
+        current = current.parent()?;
+    }
+    Some(current.kind())
+}
+
+fn is_module_level_declarator(node: Node) -> bool {
+    let Some(declaration) = node.parent() else {
+        return false;
+    };
+    let Some(mut parent) = declaration.parent() else {
+        return false;
+    };
+    if parent.kind() == "export_statement" {
+        match parent.parent() {
+            Some(grandparent) => parent = grandparent,
+            None => return false,
+        }
+    }
+    parent.kind() == "program"
+}
+
+fn definition_name(node: Node, source: &str) -> Option<String> {
+    let name = node.child_by_field_name("name")?;
+    let text = name.utf8_text(source.as_bytes()).ok()?.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn signature(node: Node, source: &str) -> String {
+    let text = node.utf8_text(source.as_bytes()).unwrap_or_default();
+    let first_line = text.lines().next().unwrap_or_default().trim();
+    first_line.chars().take(MAX_SIGNATURE_CHARS).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbols(language: SourceLanguage, source: &str) -> Vec<(String, SymbolKind, u32)> {
+        let mut parser = Parser::new();
+        parser.set_language(&language.grammar()).unwrap();
+        extract_symbols(&mut parser, language, "file", source)
+            .into_iter()
+            .map(|s| (s.name, s.kind, s.line))
+            .collect()
+    }
+
+    #[test]
+    fn extracts_rust_definitions() {
+        let source = "pub struct Repo;\n\nimpl Repo {\n    fn open() {}\n}\n\nfn main() {}\n";
+        assert_eq!(
+            symbols(SourceLanguage::Rust, source),
+            vec![
+                ("Repo".to_string(), SymbolKind::Struct, 1),
+                ("open".to_string(), SymbolKind::Method, 4),
+                ("main".to_string(), SymbolKind::Function, 7),
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_typescript_definitions() {
+        let source = "export interface Props { a: string }\n\
+                      export const Button = () => null;\n\
+                      class Store {\n  load() {}\n}\n\
+                      function inner() { const helper = () => 1; }\n";
+        assert_eq!(
+            symbols(SourceLanguage::Tsx, source),
+            vec![
+                ("Props".to_string(), SymbolKind::Interface, 1),
+                ("Button".to_string(), SymbolKind::Function, 2),
+                ("Store".to_string(), SymbolKind::Class, 3),
+                ("load".to_string(), SymbolKind::Method, 4),
+                ("inner".to_string(), SymbolKind::Function, 6),
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_python_definitions_and_ranks_matches() {
+        let source =
+            "class Indexer:\n    def build(self):\n        pass\n\ndef build_index():\n    pass\n";
+        assert_eq!(
+            symbols(SourceLanguage::Python, source),
+            vec![
+                ("Indexer".to_string(), SymbolKind::Class, 1),
+                ("build".to_string(), SymbolKind::Method, 2),
+                ("build_index".to_string(), SymbolKind::Function, 5),
+            ]
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("index.py"), source).unwrap();
+        let index = SymbolIndex::build(dir.path(), ["index.py", "README.md"]);
+        let names: Vec<_> = index
+            .search("BUILD", 10)
+            .into_iter()
+            .filter_map(|r| r.symbol.map(|s| s.name))
+            .collect();
+        assert_eq!(names, vec!["build", "build_index"]);
+    }
+}
@@ -6,7 +6,9 @@ use thiserror::Error;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
-use super::worktree_manager::{WorktreeCleanup, WorktreeError, WorktreeManager};
+use super::worktree_manager::{
+    WorktreeCheckoutOptions, WorktreeCleanup, WorktreeError, WorktreeManager,
+};
 
 #[derive(Debug, Clone)]
 pub struct RepoWorkspaceInput {
@@ -51,6 +53,24 @@ pub struct WorktreeContainer {
     pub worktrees: Vec<RepoWorktree>,
 }
 
+/// Worktree options from the repo's settings
+fn checkout_options(repo: &Repo) -> WorktreeCheckoutOptions {
+    let sparse_paths = repo
+        .sparse_checkout_paths
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(str::to_string)
+        .collect();
+    WorktreeCheckoutOptions {
+        sparse_paths,
+        init_submodules: repo.init_submodules,
+        fetch_lfs: repo.fetch_lfs,
+    }
+}
+
 pub struct WorkspaceManager;
 
 impl WorkspaceManager {
@@ -90,6 +110,7 @@ impl WorkspaceManager {
                 &worktree_path,
                 &input.target_branch,
                 true,
+                &checkout_options(&input.repo),
             )
             .await
             {
@@ -166,8 +187,13 @@ impl WorkspaceManager {
                 worktree_path.display()
             );
 
-            WorktreeManager::ensure_worktree_exists(&repo.path, branch_name, &worktree_path)
-                .await?;
+            WorktreeManager::ensure_worktree_exists(
+                &repo.path,
+                branch_name,
+                &worktree_path,
+                &checkout_options(repo),
+            )
+            .await?;
         }
 
         Ok(())
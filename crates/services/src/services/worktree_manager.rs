@@ -53,6 +53,15 @@ pub enum WorktreeError {
     Repository(String),
 }
 
+/// Per-repository options applied when a worktree is (re)created
+#[derive(Debug, Clone, Default)]
+pub struct WorktreeCheckoutOptions {
+    /// Directories for a cone-mode sparse checkout; empty checks out everything
+    pub sparse_paths: Vec<String>,
+    pub init_submodules: bool,
+    pub fetch_lfs: bool,
+}
+
 pub struct WorktreeManager;
 
 impl WorktreeManager {
@@ -60,13 +69,15 @@ impl WorktreeManager {
         let _ = WORKSPACE_DIR_OVERRIDE.set(path);
     }
 
-    /// Create a worktree with a new branch
+    /// Create a worktree with a new branch.
+    /// `options` controls sparse checkout, submodules and LFS for the new worktree.
     pub async fn create_worktree(
         repo_path: &Path,
         branch_name: &str,
         worktree_path: &Path,
         base_branch: &str,
         create_branch: bool,
+        options: &WorktreeCheckoutOptions,
     ) -> Result<(), WorktreeError> {
         if create_branch {
             let repo_path_owned = repo_path.to_path_buf();
@@ -88,7 +99,7 @@ impl WorktreeManager {
             .map_err(|e| WorktreeError::TaskJoin(format!("Task join error: {e}")))??;
         }
 
-        Self::ensure_worktree_exists(repo_path, branch_name, worktree_path).await
+        Self::ensure_worktree_exists(repo_path, branch_name, worktree_path, options).await
     }
 
     /// Ensure worktree exists, recreating if necessary with proper synchronization
@@ -97,6 +108,7 @@ impl WorktreeManager {
         repo_path: &Path,
         branch_name: &str,
         worktree_path: &Path,
+        options: &WorktreeCheckoutOptions,
     ) -> Result<(), WorktreeError> {
         let path_str = worktree_path.to_string_lossy().to_string();
 
@@ -120,7 +132,7 @@ impl WorktreeManager {
 
         // If worktree doesn't exist or isn't properly set up, recreate it
         info!("Worktree needs recreation at path: {}", path_str);
-        Self::recreate_worktree_internal(repo_path, branch_name, worktree_path).await
+        Self::recreate_worktree_internal(repo_path, branch_name, worktree_path, options).await
     }
 
     /// Internal worktree recreation function (always recreates)
@@ -128,6 +140,7 @@ impl WorktreeManager {
         repo_path: &Path,
         branch_name: &str,
         worktree_path: &Path,
+        options: &WorktreeCheckoutOptions,
     ) -> Result<(), WorktreeError> {
         let path_str = worktree_path.to_string_lossy().to_string();
         let branch_name_owned = branch_name.to_string();
@@ -156,6 +169,7 @@ impl WorktreeManager {
             &branch_name_owned,
             &worktree_path_owned,
             &path_str,
+            options,
         )
         .await
     }
@@ -322,16 +336,23 @@ impl WorktreeManager {
         branch_name: &str,
         worktree_path: &Path,
         path_str: &str,
+        options: &WorktreeCheckoutOptions,
     ) -> Result<(), WorktreeError> {
         let git_repo_path = git_repo_path.to_path_buf();
         let branch_name = branch_name.to_string();
         let worktree_path = worktree_path.to_path_buf();
         let path_str = path_str.to_string();
+        let options = options.clone();
 
         tokio::task::spawn_blocking(move || -> Result<(), WorktreeError> {
             // Prefer git CLI for worktree add to inherit sparse-checkout semantics
             let git_service = GitService::new();
-            match git_service.add_worktree(&git_repo_path, &worktree_path, &branch_name, false) {
+            match git_service.add_sparse_worktree(
+                &git_repo_path,
+                &worktree_path,
+                &branch_name,
+                &options.sparse_paths,
+            ) {
                 Ok(()) => {
                     if !worktree_path.exists() {
                         return Err(WorktreeError::Repository(format!(
@@ -342,6 +363,7 @@ impl WorktreeManager {
                         "Successfully created worktree {} at {} (git CLI)",
                         branch_name, path_str
                     );
+                    Self::populate_worktree(&git_service, &worktree_path, &options);
                     Ok(())
                 }
                 Err(e) => {
@@ -356,11 +378,11 @@ impl WorktreeManager {
                     if worktree_path.exists() {
                         std::fs::remove_dir_all(&worktree_path).map_err(WorktreeError::Io)?;
                     }
-                    if let Err(e2) = git_service.add_worktree(
+                    if let Err(e2) = git_service.add_sparse_worktree(
                         &git_repo_path,
                         &worktree_path,
                         &branch_name,
-                        false,
+                        &options.sparse_paths,
                     ) {
                         return Err(WorktreeError::GitService(e2));
                     }
@@ -373,6 +395,7 @@ impl WorktreeManager {
                         "Successfully created worktree {} at {} after metadata cleanup (git CLI)",
                         branch_name, path_str
                     );
+                    Self::populate_worktree(&git_service, &worktree_path, &options);
                     Ok(())
                 }
             }
@@ -381,6 +404,33 @@ impl WorktreeManager {
         .map_err(|e| WorktreeError::TaskJoin(format!("{e}")))?
     }
 
+    /// Initialize submodules and fetch LFS objects in a fresh worktree when enabled.
+    /// Failures are logged rather than failing worktree creation (blocking).
+    fn populate_worktree(
+        git_service: &GitService,
+        worktree_path: &Path,
+        options: &WorktreeCheckoutOptions,
+    ) {
+        if options.init_submodules
+            && let Err(e) = git_service.init_submodules(worktree_path)
+        {
+            tracing::warn!(
+                "Failed to initialize submodules in {}: {}",
+                worktree_path.display(),
+                e
+            );
+        }
+        if options.fetch_lfs
+            && let Err(e) = git_service.lfs_pull(worktree_path)
+        {
+            tracing::warn!(
+                "Failed to fetch LFS objects in {}: {}",
+                worktree_path.display(),
+                e
+            );
+        }
+    }
+
     /// Get the git repository path
     fn get_git_repo_path(repo: &Repository) -> Result<PathBuf, WorktreeError> {
         repo.workdir()
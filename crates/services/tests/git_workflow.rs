@@ -7,7 +7,7 @@ use std::{
 use git2::{Repository, build::CheckoutBuilder};
 use services::services::git::{DiffTarget, GitCli, GitService};
 use tempfile::TempDir;
-use utils::diff::DiffChangeKind;
+use utils::diff::{DiffChangeKind, DiffPointer};
 
 fn add_path(repo_path: &Path, path: &str) {
     let git = GitCli::new();
@@ -520,3 +520,333 @@ fn squash_merge_libgit2_sets_author_without_user() {
         assert_eq!(email.as_deref(), Some("noreply@vibekanban.com"));
     }
 }
+
+#[test]
+fn revert_file_and_hunk_commit_only_that_path() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+
+    let base = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\n";
+    write_file(&repo_path, "a.txt", base);
+    write_file(&repo_path, "b.txt", "keep\n");
+    s.commit(&repo_path, "base").unwrap();
+    let base_sha = s.get_head_info(&repo_path).unwrap().oid;
+
+    write_file(
+        &repo_path,
+        "a.txt",
+        "1\nTWO\n3\n4\n5\n6\n7\n8\n9\n10\nELEVEN\n12\n",
+    );
+    write_file(&repo_path, "b.txt", "changed\n");
+    write_file(&repo_path, "new.txt", "agent file\n");
+    s.commit(&repo_path, "agent changes").unwrap();
+
+    // Hunk listing and hunk revert agree on indexes
+    let hunks = s.file_hunks(&repo_path, "a.txt", &base_sha).unwrap();
+    assert_eq!(hunks.len(), 2);
+    assert!(
+        s.file_hunks(&repo_path, "../../etc/passwd", &base_sha)
+            .is_err()
+    );
+    assert!(s.file_hunks(&repo_path, "/etc/passwd", &base_sha).is_err());
+
+    // Revert only the second hunk of a.txt
+    let sha = s
+        .revert_hunk(&repo_path, "a.txt", &base_sha, 1, "revert hunk")
+        .unwrap();
+    assert!(sha.is_some());
+    let a = fs::read_to_string(repo_path.join("a.txt")).unwrap();
+    assert_eq!(a, "1\nTWO\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\n");
+
+    // Revert a file added after the base commit: it is deleted
+    s.revert_file(&repo_path, "new.txt", &base_sha, "revert new")
+        .unwrap()
+        .unwrap();
+    assert!(!repo_path.join("new.txt").exists());
+
+    // Untouched files keep their changes and the tree stays clean
+    assert_eq!(
+        fs::read_to_string(repo_path.join("b.txt")).unwrap(),
+        "changed\n"
+    );
+    assert!(s.is_worktree_clean(&repo_path).unwrap());
+
+    // Reverting again is a no-op
+    assert!(
+        s.revert_file(&repo_path, "new.txt", &base_sha, "noop")
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        s.revert_file(&repo_path, "../escape.txt", &base_sha, "bad")
+            .is_err()
+    );
+}
+
+#[test]
+fn reverting_the_only_hunk_of_an_added_file_removes_it() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+
+    write_file(&repo_path, "keep.txt", "keep\n");
+    s.commit(&repo_path, "base").unwrap();
+    let base_sha = s.get_head_info(&repo_path).unwrap().oid;
+
+    write_file(&repo_path, "src/new.txt", "agent file\nsecond line\n");
+    s.commit(&repo_path, "agent file").unwrap();
+
+    let hunks = s.file_hunks(&repo_path, "src/new.txt", &base_sha).unwrap();
+    assert_eq!(hunks.len(), 1);
+    s.revert_hunk(&repo_path, "src/new.txt", &base_sha, 0, "revert new")
+        .unwrap()
+        .unwrap();
+
+    assert!(!repo_path.join("src/new.txt").exists());
+    assert!(s.is_worktree_clean(&repo_path).unwrap());
+    assert!(
+        s.file_hunks(&repo_path, "src/new.txt", &base_sha)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn cherry_pick_branch_commits_onto_release_branch() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+
+    write_file(&repo_path, "shared.txt", "base\n");
+    s.commit(&repo_path, "base").unwrap();
+    create_branch(&repo_path, "release");
+
+    create_branch(&repo_path, "feature");
+    checkout_branch(&repo_path, "feature");
+    write_file(&repo_path, "a.txt", "feature a\n");
+    s.commit(&repo_path, "feature a").unwrap();
+    write_file(&repo_path, "b.txt", "feature b\n");
+    s.commit(&repo_path, "feature b").unwrap();
+
+    let commits = s.get_branch_commits(&repo_path, "feature", "main").unwrap();
+    assert_eq!(commits.len(), 2);
+
+    checkout_branch(&repo_path, "release");
+    s.cherry_pick_commits(&repo_path, &commits).unwrap();
+    assert!(repo_path.join("a.txt").exists());
+    assert!(repo_path.join("b.txt").exists());
+    assert!(s.is_worktree_clean(&repo_path).unwrap());
+
+    // A conflicting pick is left in progress and reported
+    write_file(&repo_path, "shared.txt", "release change\n");
+    s.commit(&repo_path, "release change").unwrap();
+    checkout_branch(&repo_path, "feature");
+    write_file(&repo_path, "shared.txt", "feature change\n");
+    s.commit(&repo_path, "feature change").unwrap();
+    let conflicting = s.get_head_info(&repo_path).unwrap().oid;
+
+    checkout_branch(&repo_path, "release");
+    match s.cherry_pick_commits(&repo_path, &[conflicting]) {
+        Err(services::services::git::GitServiceError::MergeConflicts {
+            conflicted_files, ..
+        }) => assert_eq!(conflicted_files, vec!["shared.txt".to_string()]),
+        other => panic!("expected conflicts, got {other:?}"),
+    }
+    s.abort_conflicts(&repo_path).unwrap();
+    assert!(s.is_worktree_clean(&repo_path).unwrap());
+}
+
+#[test]
+fn branch_commit_log_lists_branch_commits_newest_first_with_merges() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+
+    write_file(&repo_path, "base.txt", "base\n");
+    s.commit(&repo_path, "base").unwrap();
+
+    create_branch(&repo_path, "side");
+    create_branch(&repo_path, "feature");
+    checkout_branch(&repo_path, "side");
+    write_file(&repo_path, "side.txt", "side\n");
+    s.commit(&repo_path, "side change").unwrap();
+
+    checkout_branch(&repo_path, "feature");
+    write_file(&repo_path, "a.txt", "a\n");
+    s.commit(&repo_path, "feature a").unwrap();
+    GitCli::new()
+        .git(&repo_path, ["merge", "--no-ff", "-m", "merge side", "side"])
+        .unwrap();
+
+    let log = s
+        .get_branch_commit_log(&repo_path, "feature", "main")
+        .unwrap();
+    let subjects: Vec<_> = log.iter().map(|c| c.subject.as_str()).collect();
+    assert_eq!(subjects.len(), 3);
+    assert_eq!(subjects[0], "merge side");
+    assert!(subjects.contains(&"feature a"));
+    assert!(subjects.contains(&"side change"));
+    assert!(!subjects.contains(&"base"));
+
+    assert!(log[0].is_merge);
+    assert!(log[1..].iter().all(|c| !c.is_merge));
+    assert_eq!(log[0].sha, s.get_head_info(&repo_path).unwrap().oid);
+    assert_eq!(log[0].author_name, "Test User");
+}
+
+#[test]
+fn commit_range_covers_only_commits_made_during_an_execution() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+
+    write_file(&repo_path, "before.txt", "before\n");
+    s.commit(&repo_path, "before").unwrap();
+    let before = s.get_head_info(&repo_path).unwrap().oid;
+
+    write_file(&repo_path, "one.txt", "one\n");
+    s.commit(&repo_path, "one").unwrap();
+    let one = s.get_head_info(&repo_path).unwrap().oid;
+    write_file(&repo_path, "two.txt", "two\n");
+    write_file(&repo_path, "before.txt", "changed\n");
+    s.commit(&repo_path, "two").unwrap();
+    let after = s.get_head_info(&repo_path).unwrap().oid;
+
+    let mut range = s.get_commit_range(&repo_path, &before, &after).unwrap();
+    range.sort();
+    let mut expected = vec![one, after.clone()];
+    expected.sort();
+    assert_eq!(range, expected);
+    assert!(
+        s.get_commit_range(&repo_path, &after, &after)
+            .unwrap()
+            .is_empty()
+    );
+    assert!(s.get_commit_range(&repo_path, "not-a-sha", &after).is_err());
+
+    let diffs = s
+        .get_diffs(
+            DiffTarget::CommitRange {
+                repo_path: &repo_path,
+                from_commit: &before,
+                to_commit: &after,
+            },
+            None,
+        )
+        .unwrap();
+    let mut paths: Vec<_> = diffs.iter().filter_map(|d| d.new_path.as_deref()).collect();
+    paths.sort();
+    assert_eq!(paths, vec!["before.txt", "one.txt", "two.txt"]);
+}
+
+#[test]
+fn commit_diff_shows_only_that_commits_changes() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+
+    write_file(&repo_path, "a.txt", "a\n");
+    s.commit(&repo_path, "add a").unwrap();
+    write_file(&repo_path, "a.txt", "a changed\n");
+    write_file(&repo_path, "b.txt", "b\n");
+    s.commit(&repo_path, "change a, add b").unwrap();
+    let target = s.get_head_info(&repo_path).unwrap().oid;
+    write_file(&repo_path, "c.txt", "c\n");
+    s.commit(&repo_path, "add c").unwrap();
+
+    let diffs = s
+        .get_diffs(
+            DiffTarget::Commit {
+                repo_path: &repo_path,
+                commit_sha: &target,
+            },
+            None,
+        )
+        .unwrap();
+    let mut paths: Vec<_> = diffs.iter().filter_map(|d| d.new_path.as_deref()).collect();
+    paths.sort();
+    assert_eq!(paths, vec!["a.txt", "b.txt"]);
+
+    let a = diffs
+        .iter()
+        .find(|d| d.new_path.as_deref() == Some("a.txt"))
+        .unwrap();
+    assert!(matches!(a.change, DiffChangeKind::Modified));
+    assert_eq!(a.old_content.as_deref(), Some("a\n"));
+    assert_eq!(a.new_content.as_deref(), Some("a changed\n"));
+}
+
+#[test]
+fn sparse_worktree_only_materializes_cone_paths() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+
+    write_file(&repo_path, "apps/web/index.ts", "web\n");
+    write_file(&repo_path, "apps/api/main.rs", "api\n");
+    write_file(&repo_path, "README.md", "readme\n");
+    s.commit(&repo_path, "monorepo").unwrap();
+    create_branch(&repo_path, "feature");
+
+    let worktree_path = td.path().join("wt-sparse");
+    s.add_sparse_worktree(
+        &repo_path,
+        &worktree_path,
+        "feature",
+        &["apps/web".to_string()],
+    )
+    .unwrap();
+
+    // Cone mode keeps top-level files and the selected directory only
+    assert!(worktree_path.join("apps/web/index.ts").exists());
+    assert!(worktree_path.join("README.md").exists());
+    assert!(!worktree_path.join("apps/api/main.rs").exists());
+    assert!(s.is_worktree_clean(&worktree_path).unwrap());
+
+    // The main checkout is unaffected
+    assert!(repo_path.join("apps/api/main.rs").exists());
+}
+
+#[test]
+fn branch_diff_summarizes_lfs_pointer_files() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    let _ = s.commit(&repo_path, "base").unwrap();
+
+    create_branch(&repo_path, "feature");
+    checkout_branch(&repo_path, "feature");
+    let oid = "4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393";
+    write_file(
+        &repo_path,
+        "model.bin",
+        &format!("version https://git-lfs.github.com/spec/v1\noid sha256:{oid}\nsize 12345\n"),
+    );
+    let _ = s.commit(&repo_path, "add lfs file").unwrap();
+
+    let diffs = s
+        .get_diffs(
+            DiffTarget::Branch {
+                repo_path: Path::new(&repo_path),
+                branch_name: "feature",
+                base_branch: "main",
+            },
+            None,
+        )
+        .unwrap();
+    let lfs = diffs
+        .iter()
+        .find(|d| d.new_path.as_deref() == Some("model.bin"))
+        .expect("lfs diff present");
+    assert!(lfs.new_content.is_none());
+    assert_eq!(
+        lfs.pointer,
+        Some(DiffPointer::Lfs {
+            old_oid: None,
+            new_oid: Some(oid.to_string()),
+            old_size: None,
+            new_size: Some(12345),
+        })
+    );
+}
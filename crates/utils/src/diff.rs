@@ -2,7 +2,7 @@ use std::borrow::Cow;
 
 use git2::{DiffOptions, Patch};
 use serde::{Deserialize, Serialize};
-use similar::TextDiff;
+use similar::{DiffTag, TextDiff};
 use ts_rs::TS;
 use uuid::Uuid;
 
@@ -30,6 +30,65 @@ pub struct Diff {
     pub additions: Option<usize>,
     pub deletions: Option<usize>,
     pub repo_id: Option<Uuid>,
+    /// Set for submodule and Git LFS entries, which are shown as a summary
+    /// instead of file contents
+    #[serde(default)]
+    pub pointer: Option<DiffPointer>,
+}
+
+/// Diff entries whose contents live outside the repository's regular blobs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum DiffPointer {
+    /// The commit recorded for a submodule changed
+    #[serde(rename_all = "camelCase")]
+    Submodule {
+        old_commit: Option<String>,
+        new_commit: Option<String>,
+    },
+    /// A file stored in Git LFS; oids are the LFS object ids (sha256)
+    #[serde(rename_all = "camelCase")]
+    Lfs {
+        old_oid: Option<String>,
+        new_oid: Option<String>,
+        old_size: Option<usize>,
+        new_size: Option<usize>,
+    },
+}
+
+/// Parsed Git LFS pointer file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LfsPointer {
+    pub oid: String,
+    pub size: usize,
+}
+
+const LFS_POINTER_VERSION: &str = "version https://git-lfs.github.com/spec/v1";
+/// Pointer files are small; anything larger is real content
+const LFS_POINTER_MAX_BYTES: usize = 1024;
+
+/// Parse a Git LFS pointer file, returning None for regular content.
+pub fn parse_lfs_pointer(content: &str) -> Option<LfsPointer> {
+    if content.len() > LFS_POINTER_MAX_BYTES {
+        return None;
+    }
+    let mut lines = content.lines();
+    if lines.next()? != LFS_POINTER_VERSION {
+        return None;
+    }
+    let mut oid = None;
+    let mut size = None;
+    for line in lines {
+        if let Some(value) = line.strip_prefix("oid sha256:") {
+            oid = Some(value.to_string());
+        } else if let Some(value) = line.strip_prefix("size ") {
+            size = value.parse().ok();
+        }
+    }
+    Some(LfsPointer {
+        oid: oid?,
+        size: size?,
+    })
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -44,6 +103,19 @@ pub enum DiffChangeKind {
     PermissionChange,
 }
 
+/// A single hunk of a line diff between two text snapshots, addressed by index.
+/// Hunks use the same 3-line context radius as `create_unified_diff`.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffHunk {
+    pub index: usize,
+    pub header: String,
+    pub old_start: usize,
+    pub old_lines: usize,
+    pub new_start: usize,
+    pub new_lines: usize,
+}
+
 // ==============================
 // Unified diff utility functions
 // ==============================
@@ -246,3 +318,129 @@ pub fn normalize_unified_diff(file_path: &str, unified_diff: &str) -> String {
     let hunks = extract_unified_diff_hunks(unified_diff);
     concatenate_diff_hunks(file_path, &hunks)
 }
+
+const HUNK_CONTEXT_LINES: usize = 3;
+
+/// List the hunks between two text snapshots.
+pub fn compute_diff_hunks(old: &str, new: &str) -> Vec<DiffHunk> {
+    let diff = TextDiff::from_lines(old, new);
+    diff.grouped_ops(HUNK_CONTEXT_LINES)
+        .iter()
+        .enumerate()
+        .filter_map(|(index, group)| {
+            let first = group.first()?;
+            let last = group.last()?;
+            let old_start = first.old_range().start;
+            let old_lines = last.old_range().end - old_start;
+            let new_start = first.new_range().start;
+            let new_lines = last.new_range().end - new_start;
+            Some(DiffHunk {
+                index,
+                header: format!(
+                    "@@ -{},{old_lines} +{},{new_lines} @@",
+                    old_start + 1,
+                    new_start + 1
+                ),
+                old_start: old_start + 1,
+                old_lines,
+                new_start: new_start + 1,
+                new_lines,
+            })
+        })
+        .collect()
+}
+
+/// Rebuild `new` with the hunk at `hunk_index` replaced by its `old` lines,
+/// leaving every other change in place. Returns None if the index is out of range.
+pub fn revert_diff_hunk(old: &str, new: &str, hunk_index: usize) -> Option<String> {
+    let diff = TextDiff::from_lines(old, new);
+    let groups = diff.grouped_ops(HUNK_CONTEXT_LINES);
+    let target = groups.get(hunk_index)?;
+    let reverted: Vec<_> = target
+        .iter()
+        .filter(|op| op.tag() != DiffTag::Equal)
+        .map(|op| (op.old_range(), op.new_range()))
+        .collect();
+
+    let old_lines = diff.old_slices();
+    let new_lines = diff.new_slices();
+    let mut out = String::with_capacity(new.len());
+    for op in diff.ops() {
+        let ranges = (op.old_range(), op.new_range());
+        if op.tag() != DiffTag::Equal && reverted.contains(&ranges) {
+            old_lines[ranges.0]
+                .iter()
+                .for_each(|line| out.push_str(line));
+        } else {
+            new_lines[ranges.1]
+                .iter()
+                .for_each(|line| out.push_str(line));
+        }
+    }
+    Some(out)
+}
+
+/// Follow a 1-based line of `old` to its position in `new`. Returns None when
+/// the line was changed or removed.
+pub fn map_line(old: &str, new: &str, line: usize) -> Option<usize> {
+    let index = line.checked_sub(1)?;
+    let diff = TextDiff::from_lines(old, new);
+    diff.ops()
+        .iter()
+        .find(|op| op.old_range().contains(&index))
+        .filter(|op| op.tag() == DiffTag::Equal)
+        .map(|op| op.new_range().start + (index - op.old_range().start) + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: &str = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\n";
+    const NEW: &str = "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\nK\nl\nm\n";
+
+    #[test]
+    fn hunks_are_split_by_context() {
+        let hunks = compute_diff_hunks(OLD, NEW);
+        assert_eq!(hunks.len(), 2);
+        assert_eq!(hunks[0].header, "@@ -1,5 +1,5 @@");
+        assert_eq!(hunks[1].old_start, 8);
+    }
+
+    #[test]
+    fn revert_single_hunk_keeps_other_changes() {
+        let reverted = revert_diff_hunk(OLD, NEW, 1).unwrap();
+        assert_eq!(reverted, "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\n");
+
+        let reverted = revert_diff_hunk(OLD, NEW, 0).unwrap();
+        assert_eq!(reverted, "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nK\nl\nm\n");
+    }
+
+    #[test]
+    fn maps_lines_across_edits() {
+        assert_eq!(map_line(OLD, NEW, 1), Some(1));
+        assert_eq!(map_line(OLD, NEW, 2), None);
+        assert_eq!(map_line(OLD, NEW, 10), Some(10));
+        assert_eq!(map_line("a\nb\n", "x\ny\na\nb\n", 2), Some(4));
+        assert_eq!(map_line(OLD, NEW, 0), None);
+    }
+
+    #[test]
+    fn revert_out_of_range_hunk() {
+        assert!(revert_diff_hunk(OLD, NEW, 2).is_none());
+        assert!(compute_diff_hunks(OLD, OLD).is_empty());
+    }
+
+    #[test]
+    fn parses_lfs_pointer() {
+        let pointer = "version https://git-lfs.github.com/spec/v1\noid sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393\nsize 12345\n";
+        assert_eq!(
+            parse_lfs_pointer(pointer),
+            Some(LfsPointer {
+                oid: "4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393".to_string(),
+                size: 12345,
+            })
+        );
+        assert!(parse_lfs_pointer("plain text\n").is_none());
+    }
+}
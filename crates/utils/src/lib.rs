@@ -11,6 +11,7 @@ pub mod diff;
 pub mod git;
 pub mod jwt;
 pub mod log_msg;
+pub mod metrics;
 pub mod msg_store;
 pub mod path;
 pub mod port_file;
@@ -18,6 +19,7 @@ pub mod response;
 pub mod sentry;
 pub mod shell;
 pub mod stream_lines;
+pub mod telemetry;
 pub mod text;
 pub mod tokio;
 pub mod version;
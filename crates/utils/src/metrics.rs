@@ -0,0 +1,273 @@
+//! Process-wide metrics in the Prometheus text exposition format.
+//!
+//! Events are recorded where they happen through the counters and histograms on
+//! [`metrics()`]; values that are cheap to read at scrape time, such as pool
+//! sizes, are written as gauges by the metrics route instead.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{LazyLock, Mutex},
+    time::Duration,
+};
+
+const PREFIX: &str = "vibe_kanban_";
+
+/// Seconds, for agent runs and approvals that take minutes to hours
+const LONG_BUCKETS: &[f64] = &[
+    1.0, 5.0, 15.0, 30.0, 60.0, 120.0, 300.0, 600.0, 1800.0, 3600.0, 7200.0,
+];
+/// Seconds, for background work that should finish quickly
+const SHORT_BUCKETS: &[f64] = &[0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0];
+
+pub struct Counter {
+    name: &'static str,
+    help: &'static str,
+    labels: &'static [&'static str],
+    values: Mutex<BTreeMap<Vec<String>, u64>>,
+}
+
+impl Counter {
+    const fn new(name: &'static str, help: &'static str, labels: &'static [&'static str]) -> Self {
+        Self {
+            name,
+            help,
+            labels,
+            values: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Add one for the given label values, in the order the counter declares them
+    pub fn inc(&self, label_values: &[&str]) {
+        let mut values = self.values.lock().unwrap_or_else(|e| e.into_inner());
+        *values.entry(owned(label_values)).or_default() += 1;
+    }
+
+    fn render(&self, out: &mut String) {
+        header(out, self.name, self.help, "counter");
+        let values = self.values.lock().unwrap_or_else(|e| e.into_inner());
+        for (label_values, value) in values.iter() {
+            let labels = format_labels(self.labels, label_values, None);
+            let _ = writeln!(out, "{PREFIX}{}{labels} {value}", self.name);
+        }
+    }
+}
+
+#[derive(Default)]
+struct HistogramData {
+    /// Non-cumulative count per bucket, plus one for +Inf
+    buckets: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+pub struct Histogram {
+    name: &'static str,
+    help: &'static str,
+    labels: &'static [&'static str],
+    bounds: &'static [f64],
+    values: Mutex<BTreeMap<Vec<String>, HistogramData>>,
+}
+
+impl Histogram {
+    const fn new(
+        name: &'static str,
+        help: &'static str,
+        labels: &'static [&'static str],
+        bounds: &'static [f64],
+    ) -> Self {
+        Self {
+            name,
+            help,
+            labels,
+            bounds,
+            values: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn observe(&self, label_values: &[&str], duration: Duration) {
+        let seconds = duration.as_secs_f64();
+        let mut values = self.values.lock().unwrap_or_else(|e| e.into_inner());
+        let data = values.entry(owned(label_values)).or_default();
+        if data.buckets.is_empty() {
+            data.buckets = vec![0; self.bounds.len() + 1];
+        }
+        let bucket = self
+            .bounds
+            .iter()
+            .position(|bound| seconds <= *bound)
+            .unwrap_or(self.bounds.len());
+        data.buckets[bucket] += 1;
+        data.sum += seconds;
+        data.count += 1;
+    }
+
+    fn render(&self, out: &mut String) {
+        header(out, self.name, self.help, "histogram");
+        let name = self.name;
+        let values = self.values.lock().unwrap_or_else(|e| e.into_inner());
+        for (label_values, data) in values.iter() {
+            let mut cumulative = 0;
+            for (i, count) in data.buckets.iter().enumerate() {
+                cumulative += count;
+                let le = self
+                    .bounds
+                    .get(i)
+                    .map_or_else(|| "+Inf".to_string(), |bound| bound.to_string());
+                let labels = format_labels(self.labels, label_values, Some(&le));
+                let _ = writeln!(out, "{PREFIX}{name}_bucket{labels} {cumulative}");
+            }
+            let labels = format_labels(self.labels, label_values, None);
+            let _ = writeln!(out, "{PREFIX}{name}_sum{labels} {}", data.sum);
+            let _ = writeln!(out, "{PREFIX}{name}_count{labels} {}", data.count);
+        }
+    }
+}
+
+pub struct Metrics {
+    /// Labels: executor, run_reason, status
+    pub executions_finished: Counter,
+    /// Labels: executor, run_reason, status
+    pub execution_duration: Histogram,
+    /// Labels: outcome
+    pub approval_wait: Histogram,
+    /// Labels: result (hit or miss)
+    pub file_search_cache_lookups: Counter,
+    /// Labels: outcome
+    pub file_search_cache_build: Histogram,
+    pub pr_monitor_poll: Histogram,
+}
+
+pub fn metrics() -> &'static Metrics {
+    static METRICS: LazyLock<Metrics> = LazyLock::new(|| Metrics {
+        executions_finished: Counter::new(
+            "executions_finished_total",
+            "Execution processes that ended",
+            &["executor", "run_reason", "status"],
+        ),
+        execution_duration: Histogram::new(
+            "execution_duration_seconds",
+            "Wall-clock duration of execution processes",
+            &["executor", "run_reason", "status"],
+            LONG_BUCKETS,
+        ),
+        approval_wait: Histogram::new(
+            "approval_wait_seconds",
+            "Time tool approvals waited for a response",
+            &["outcome"],
+            LONG_BUCKETS,
+        ),
+        file_search_cache_lookups: Counter::new(
+            "file_search_cache_lookups_total",
+            "File search cache lookups",
+            &["result"],
+        ),
+        file_search_cache_build: Histogram::new(
+            "file_search_cache_build_seconds",
+            "Time to build a repository's file search index",
+            &["outcome"],
+            SHORT_BUCKETS,
+        ),
+        pr_monitor_poll: Histogram::new(
+            "pr_monitor_poll_seconds",
+            "Time to check all open pull requests",
+            &[],
+            SHORT_BUCKETS,
+        ),
+    });
+    &METRICS
+}
+
+impl Metrics {
+    pub fn render(&self, out: &mut String) {
+        self.executions_finished.render(out);
+        self.execution_duration.render(out);
+        self.approval_wait.render(out);
+        self.file_search_cache_lookups.render(out);
+        self.file_search_cache_build.render(out);
+        self.pr_monitor_poll.render(out);
+    }
+}
+
+/// Write a gauge read at scrape time, one sample per set of label values
+pub fn write_gauge(
+    out: &mut String,
+    name: &str,
+    help: &str,
+    labels: &[&str],
+    samples: &[(Vec<String>, f64)],
+) {
+    header(out, name, help, "gauge");
+    for (label_values, value) in samples {
+        let labels = format_labels(labels, label_values, None);
+        let _ = writeln!(out, "{PREFIX}{name}{labels} {value}");
+    }
+}
+
+fn header(out: &mut String, name: &str, help: &str, kind: &str) {
+    let _ = writeln!(out, "# HELP {PREFIX}{name} {help}");
+    let _ = writeln!(out, "# TYPE {PREFIX}{name} {kind}");
+}
+
+fn owned(label_values: &[&str]) -> Vec<String> {
+    label_values.iter().map(|value| value.to_string()).collect()
+}
+
+fn format_labels(names: &[&str], values: &[String], le: Option<&str>) -> String {
+    let mut pairs: Vec<String> = names
+        .iter()
+        .zip(values)
+        .map(|(name, value)| format!("{name}=\"{}\"", escape(value)))
+        .collect();
+    if let Some(le) = le {
+        pairs.push(format!("le=\"{le}\""));
+    }
+    if pairs.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", pairs.join(","))
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace('"', r#"\""#)
+        .replace('\n', r"\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_cumulative_histogram_buckets() {
+        let histogram = Histogram::new("wait_seconds", "Wait", &["outcome"], &[1.0, 5.0]);
+        histogram.observe(&["approved"], Duration::from_millis(500));
+        histogram.observe(&["approved"], Duration::from_secs(3));
+        histogram.observe(&["approved"], Duration::from_secs(60));
+
+        let mut out = String::new();
+        histogram.render(&mut out);
+        assert_eq!(
+            out,
+            "# HELP vibe_kanban_wait_seconds Wait\n\
+             # TYPE vibe_kanban_wait_seconds histogram\n\
+             vibe_kanban_wait_seconds_bucket{outcome=\"approved\",le=\"1\"} 1\n\
+             vibe_kanban_wait_seconds_bucket{outcome=\"approved\",le=\"5\"} 2\n\
+             vibe_kanban_wait_seconds_bucket{outcome=\"approved\",le=\"+Inf\"} 3\n\
+             vibe_kanban_wait_seconds_sum{outcome=\"approved\"} 63.5\n\
+             vibe_kanban_wait_seconds_count{outcome=\"approved\"} 3\n"
+        );
+
+        let mut out = String::new();
+        write_gauge(
+            &mut out,
+            "sessions",
+            "Sessions",
+            &["executor"],
+            &[(vec!["say \"hi\"".to_string()], 2.0)],
+        );
+        assert!(out.ends_with("vibe_kanban_sessions{executor=\"say \\\"hi\\\"\"} 2\n"));
+    }
+}
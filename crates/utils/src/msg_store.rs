@@ -97,6 +97,16 @@ impl MsgStore {
             .collect()
     }
 
+    /// Approximate size of the retained history
+    pub fn history_bytes(&self) -> usize {
+        self.inner.read().unwrap().total_bytes
+    }
+
+    /// Live listeners, such as open WebSocket and SSE streams
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+
     /// History then live, as `LogMsg`.
     pub fn history_plus_stream(
         &self,
@@ -74,6 +74,11 @@ pub fn resolve_executable_path_blocking(executable: &str) -> Option<PathBuf> {
     block_on(resolve_executable_path(executable))
 }
 
+/// Single-quote a word for a POSIX shell, e.g. one running a command over SSH
+pub fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
 /// Merge two PATH strings into a single, de-duplicated PATH.
 ///
 /// - Keeps the order of entries from `primary`.
@@ -0,0 +1,175 @@
+//! Optional OpenTelemetry trace export over OTLP/HTTP.
+//!
+//! Export is enabled by setting `OTEL_EXPORTER_OTLP_ENDPOINT` (or
+//! `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT`), e.g. `http://localhost:4318` for a local
+//! Jaeger or collector. The other standard `OTEL_*` variables, such as
+//! `OTEL_EXPORTER_OTLP_HEADERS` and `OTEL_SERVICE_NAME`, are honoured as well.
+
+use std::{future::Future, sync::OnceLock};
+
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::SpanExporter;
+use opentelemetry_sdk::{
+    Resource,
+    trace::{SdkTracer, SdkTracerProvider},
+};
+use tokio::task::JoinHandle;
+use tracing::Instrument;
+use tracing_opentelemetry::OpenTelemetryLayer;
+
+static PROVIDER: OnceLock<SdkTracerProvider> = OnceLock::new();
+
+fn endpoint_configured(env: impl Fn(&str) -> Option<String>) -> bool {
+    [
+        "OTEL_EXPORTER_OTLP_ENDPOINT",
+        "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+    ]
+    .iter()
+    .any(|var| env(var).is_some_and(|value| !value.is_empty()))
+}
+
+/// Layer exporting spans over OTLP, or `None` when no endpoint is configured.
+/// Must be called at most once, before the subscriber is installed.
+pub fn otel_layer<S>() -> Option<OpenTelemetryLayer<S, SdkTracer>>
+where
+    S: tracing::Subscriber,
+    S: for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+{
+    layer_from_env(|var| std::env::var(var).ok())
+}
+
+fn layer_from_env<S>(
+    env: impl Fn(&str) -> Option<String>,
+) -> Option<OpenTelemetryLayer<S, SdkTracer>>
+where
+    S: tracing::Subscriber,
+    S: for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+{
+    if !endpoint_configured(&env) {
+        return None;
+    }
+
+    let exporter = match SpanExporter::builder().with_http().build() {
+        Ok(exporter) => exporter,
+        Err(e) => {
+            // The subscriber isn't installed yet, so this can't go through tracing
+            eprintln!("Failed to create OTLP span exporter: {e}");
+            return None;
+        }
+    };
+    let service_name = env("OTEL_SERVICE_NAME").unwrap_or_else(|| "vibe-kanban".to_string());
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(
+            Resource::builder()
+                .with_service_name(service_name)
+                .with_attribute(opentelemetry::KeyValue::new(
+                    "service.version",
+                    env!("CARGO_PKG_VERSION"),
+                ))
+                .build(),
+        )
+        .build();
+    let tracer = provider.tracer("vibe-kanban");
+    let _ = PROVIDER.set(provider);
+
+    Some(tracing_opentelemetry::layer().with_tracer(tracer))
+}
+
+/// Flush spans still buffered for export; call before the process exits
+pub fn shutdown() {
+    if let Some(provider) = PROVIDER.get()
+        && let Err(e) = provider.shutdown()
+    {
+        tracing::warn!("Failed to flush OpenTelemetry spans: {}", e);
+    }
+}
+
+/// Spawn a task inside the caller's span, so a trace covers the work the task
+/// does rather than ending when the call that started it returns
+pub fn spawn_in_current_span<F>(future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    tokio::spawn(future.in_current_span())
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing::{Span, dispatcher, info_span};
+    use tracing_subscriber::{Registry, registry::LookupSpan};
+
+    use super::*;
+
+    fn env_with(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: Vec<(String, String)> = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        move |var| {
+            vars.iter()
+                .find(|(name, _)| name == var)
+                .map(|(_, value)| value.clone())
+        }
+    }
+
+    #[test]
+    fn endpoint_is_configured_by_either_variable() {
+        assert!(!endpoint_configured(env_with(&[])));
+        assert!(!endpoint_configured(env_with(&[(
+            "OTEL_EXPORTER_OTLP_ENDPOINT",
+            ""
+        )])));
+        assert!(endpoint_configured(env_with(&[(
+            "OTEL_EXPORTER_OTLP_ENDPOINT",
+            "http://localhost:4318"
+        )])));
+        assert!(endpoint_configured(env_with(&[(
+            "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+            "http://localhost:4318/v1/traces"
+        )])));
+    }
+
+    #[test]
+    fn no_layer_without_an_endpoint() {
+        assert!(layer_from_env::<Registry>(env_with(&[])).is_none());
+        assert!(
+            layer_from_env::<Registry>(env_with(&[("OTEL_SERVICE_NAME", "vibe-kanban-test")]))
+                .is_none()
+        );
+    }
+
+    /// Parent of the span with `id` in the current registry
+    fn parent_of(id: &tracing::Id) -> Option<tracing::Id> {
+        dispatcher::get_default(|dispatch| {
+            let registry = dispatch.downcast_ref::<Registry>()?;
+            registry.span(id)?.parent().map(|parent| parent.id())
+        })
+    }
+
+    #[tokio::test]
+    async fn spawned_task_runs_inside_the_callers_span() {
+        let _guard = tracing::subscriber::set_default(Registry::default());
+        let parent = info_span!("request");
+        let parent_id = parent.id().unwrap();
+
+        let (current, child_parent) = parent
+            .in_scope(|| {
+                spawn_in_current_span(async {
+                    let child = info_span!("work");
+                    (Span::current().id(), parent_of(&child.id().unwrap()))
+                })
+            })
+            .await
+            .unwrap();
+        assert_eq!(current, Some(parent_id.clone()));
+        assert_eq!(child_parent, Some(parent_id));
+
+        let detached = parent
+            .in_scope(|| tokio::spawn(async { Span::current().id() }))
+            .await
+            .unwrap();
+        assert_eq!(detached, None);
+    }
+}
@@ -57,6 +57,29 @@ function labelAndIcon(diff: Diff, t: (key: string) => string) {
   return { label: undefined as string | undefined, Icon: PencilLine };
 }
 
+function shortSha(sha: string | null) {
+  return sha ? sha.slice(0, 7) : '-';
+}
+
+function pointerSummary(
+  diff: Diff,
+  t: (key: string, options?: Record<string, unknown>) => string
+) {
+  const pointer = diff.pointer;
+  if (!pointer) return null;
+  if (pointer.kind === 'submodule') {
+    return t('messages.submoduleChanged', {
+      from: shortSha(pointer.oldCommit),
+      to: shortSha(pointer.newCommit),
+    });
+  }
+  return t('messages.lfsObjectChanged', {
+    from: shortSha(pointer.oldOid),
+    to: shortSha(pointer.newOid),
+    size: pointer.newSize ?? pointer.oldSize ?? '-',
+  });
+}
+
 function readPlainLine(
   diffFile: DiffFile | null,
   lineNumber: number,
@@ -99,6 +122,7 @@ export default function DiffCard({
     getHighLightLanguageFromPath(newName || oldName || '') || 'plaintext';
   const { label, Icon } = labelAndIcon(diff, t);
   const isOmitted = !!diff.contentOmitted;
+  const pointerText = pointerSummary(diff, t);
 
   // Build a diff from raw contents so the viewer can expand beyond hunks
   const oldContentSafe = diff.oldContent || '';
@@ -326,15 +350,17 @@ export default function DiffCard({
           className="px-4 pb-4 text-xs font-mono"
           style={{ color: 'hsl(var(--muted-foreground) / 0.9)' }}
         >
-          {isOmitted
-            ? t('messages.contentOmitted')
-            : isContentEqual
-              ? diff.change === 'renamed'
-                ? t('messages.renamedNoChanges')
-                : diff.change === 'permissionChange'
-                  ? t('messages.permissionChanged')
-                  : t('messages.noChanges')
-              : t('errors.renderFailed')}
+          {pointerText
+            ? pointerText
+            : isOmitted
+              ? t('messages.contentOmitted')
+              : isContentEqual
+                ? diff.change === 'renamed'
+                  ? t('messages.renamedNoChanges')
+                  : diff.change === 'permissionChange'
+                    ? t('messages.permissionChanged')
+                    : t('messages.noChanges')
+                : t('errors.renderFailed')}
         </div>
       )}
     </div>
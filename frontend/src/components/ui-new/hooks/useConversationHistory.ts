@@ -61,7 +61,8 @@ export const useConversationHistory = ({
       (ep) =>
         ep.run_reason === 'setupscript' ||
         ep.run_reason === 'cleanupscript' ||
-        ep.run_reason === 'codingagent'
+        ep.run_reason === 'codingagent' ||
+        ep.run_reason === 'gatescript'
     );
   }, [executionProcessesRaw]);
 
@@ -286,6 +287,9 @@ export const useConversationHistory = ({
               case 'ToolInstallScript':
                 toolName = 'Tool Install Script';
                 break;
+              case 'GateScript':
+                toolName = 'Gate Script';
+                break;
               default:
                 return [];
             }
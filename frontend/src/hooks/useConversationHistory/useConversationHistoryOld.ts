@@ -52,7 +52,8 @@ export const useConversationHistoryOld = ({
       (ep) =>
         ep.run_reason === 'setupscript' ||
         ep.run_reason === 'cleanupscript' ||
-        ep.run_reason === 'codingagent'
+        ep.run_reason === 'codingagent' ||
+        ep.run_reason === 'gatescript'
     );
   }, [executionProcessesRaw]);
 
@@ -263,6 +264,9 @@ export const useConversationHistoryOld = ({
               case 'ToolInstallScript':
                 toolName = 'Tool Install Script';
                 break;
+              case 'GateScript':
+                toolName = 'Gate Script';
+                break;
               default:
                 return [];
             }
@@ -12,6 +12,10 @@ type DiffStreamEvent = {
 
 export interface UseDiffStreamOptions {
   statsOnly?: boolean;
+  /** Only the changes made by this execution process */
+  executionProcessId?: string;
+  /** Only the changes made by this commit */
+  commit?: { repoId: string; sha: string };
 }
 
 interface UseDiffStreamResult {
@@ -28,13 +32,19 @@ export const useDiffStream = (
   const endpoint = (() => {
     if (!attemptId) return undefined;
     const query = `/api/task-attempts/${attemptId}/diff/ws`;
+    const params = new URLSearchParams();
     if (typeof options?.statsOnly === 'boolean') {
-      const params = new URLSearchParams();
       params.set('stats_only', String(options.statsOnly));
-      return `${query}?${params.toString()}`;
-    } else {
-      return query;
     }
+    if (options?.executionProcessId) {
+      params.set('execution_process_id', options.executionProcessId);
+    }
+    if (options?.commit) {
+      params.set('repo_id', options.commit.repoId);
+      params.set('commit', options.commit.sha);
+    }
+    const search = params.toString();
+    return search ? `${query}?${search}` : query;
   })();
 
   const initialData = useCallback(
@@ -23,6 +23,7 @@ import {
   CreateProjectRepo,
   UpdateRepo,
   SearchMode,
+  SearchKind,
   SearchResult,
   Task,
   TaskRelationships,
@@ -81,16 +82,46 @@ import {
   CurrentUserResponse,
   QueueStatus,
   PrCommentsResponse,
+  ImportPrCommentsRequest,
+  ImportPrCommentsResponse,
   MergeTaskAttemptRequest,
   PushTaskAttemptRequest,
   RepoBranchStatus,
   AbortConflictsRequest,
   Session,
   Workspace,
+  WorkspaceCommit,
+  ReviewComment,
+  CreateReviewComment,
+  UpdateReviewComment,
+  SendReviewRequest,
   StartReviewRequest,
   ReviewError,
   PathConfigInfo,
   SetCustomPathResponse,
+  ConversationExportQuery,
+  ConversationSearchHit,
+  ConversationSearchParams,
+  ExternalAgent,
+  ExternalSessionSummary,
+  ImportAgentSessionRequest,
+  ImportAgentSessionResponse,
+  CreateTerminalSessionRequest,
+  TerminalSession,
+  RemoteAccessStatus,
+  RemoteAccessLoginRequest,
+  RemoteAccessRole,
+  UpdateRemoteAccessRequest,
+  UpdateRemoteAccessResponse,
+  RemoteAccessTokenResponse,
+  WorkerHost,
+  CreateWorkerHost,
+  WorkerHostCheckResponse,
+  User,
+  CreateUserRequest,
+  UpdateUserRequest,
+  UserConfigOverrides,
+  Activity,
 } from 'shared/types';
 import type { WorkspaceWithSession } from '@/types/attempt';
 import { createWorkspaceWithSession } from '@/types/attempt';
@@ -172,12 +203,15 @@ export const handleApiResponse = async <T, E = T>(
 ): Promise<T> => {
   if (!response.ok) {
     let errorMessage = `Request failed with status ${response.status}`;
+    // Some errors (e.g. failed merge/push gates) carry structured data
+    let errorDataPayload: E | undefined;
 
     try {
       const errorData = await response.json();
       if (errorData.message) {
         errorMessage = errorData.message;
       }
+      errorDataPayload = errorData.error_data ?? undefined;
     } catch {
       // Fallback to status text if JSON parsing fails
       errorMessage = response.statusText || errorMessage;
@@ -190,7 +224,12 @@ export const handleApiResponse = async <T, E = T>(
       endpoint: response.url,
       timestamp: new Date().toISOString(),
     });
-    throw new ApiError<E>(errorMessage, response.status, response);
+    throw new ApiError<E>(
+      errorMessage,
+      response.status,
+      response,
+      errorDataPayload
+    );
   }
 
   if (response.status === 204) {
@@ -254,6 +293,17 @@ export const projectsApi = {
     return handleApiResponse<Project>(response);
   },
 
+  /**
+   * What people did across the project's tasks, newest first
+   */
+  getActivity: async (projectId: string, limit?: number): Promise<Activity[]> => {
+    const query = limit ? `?limit=${limit}` : '';
+    const response = await makeRequest(
+      `/api/projects/${projectId}/activity${query}`
+    );
+    return handleApiResponse<Activity[]>(response);
+  },
+
   getRemoteMembers: async (
     projectId: string
   ): Promise<RemoteProjectMembersResponse> => {
@@ -369,6 +419,15 @@ export const tasksApi = {
     return handleApiResponse<Task>(response);
   },
 
+  /**
+   * What people did on the task, newest first
+   */
+  getActivity: async (taskId: string, limit?: number): Promise<Activity[]> => {
+    const query = limit ? `?limit=${limit}` : '';
+    const response = await makeRequest(`/api/tasks/${taskId}/activity${query}`);
+    return handleApiResponse<Activity[]>(response);
+  },
+
   create: async (data: CreateTask): Promise<Task> => {
     const response = await makeRequest(`/api/tasks`, {
       method: 'POST',
@@ -527,11 +586,13 @@ export const attemptsApi = {
   searchFiles: async (
     workspaceId: string,
     query: string,
-    mode?: string
+    mode?: string,
+    kind?: SearchKind
   ): Promise<SearchResult[]> => {
     const modeParam = mode ? `&mode=${encodeURIComponent(mode)}` : '';
+    const kindParam = kind ? `&kind=${encodeURIComponent(kind)}` : '';
     const response = await makeRequest(
-      `/api/task-attempts/${workspaceId}/search?q=${encodeURIComponent(query)}${modeParam}`
+      `/api/task-attempts/${workspaceId}/search?q=${encodeURIComponent(query)}${modeParam}${kindParam}`
     );
     return handleApiResponse<SearchResult[]>(response);
   },
@@ -571,6 +632,76 @@ export const attemptsApi = {
     return handleApiResponse<RepoBranchStatus[]>(response);
   },
 
+  getCommits: async (attemptId: string): Promise<WorkspaceCommit[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/commits`
+    );
+    return handleApiResponse<WorkspaceCommit[]>(response);
+  },
+
+  getReviewComments: async (attemptId: string): Promise<ReviewComment[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/review-comments`
+    );
+    return handleApiResponse<ReviewComment[]>(response);
+  },
+
+  createReviewComment: async (
+    attemptId: string,
+    data: CreateReviewComment
+  ): Promise<ReviewComment> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/review-comments`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ReviewComment>(response);
+  },
+
+  updateReviewComment: async (
+    attemptId: string,
+    commentId: string,
+    data: UpdateReviewComment
+  ): Promise<ReviewComment> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/review-comments/${commentId}`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ReviewComment>(response);
+  },
+
+  deleteReviewComment: async (
+    attemptId: string,
+    commentId: string
+  ): Promise<void> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/review-comments/${commentId}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  sendReview: async (
+    attemptId: string,
+    data: SendReviewRequest
+  ): Promise<ExecutionProcess> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/review-comments/send`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ExecutionProcess>(response);
+  },
+
   getRepos: async (attemptId: string): Promise<RepoWithTargetBranch[]> => {
     const response = await makeRequest(`/api/task-attempts/${attemptId}/repos`);
     return handleApiResponse<RepoWithTargetBranch[]>(response);
@@ -750,6 +881,21 @@ export const attemptsApi = {
     return handleApiResponse<PrCommentsResponse>(response);
   },
 
+  /** Queue new PR comments as a follow-up for the workspace's agent */
+  importPrComments: async (
+    attemptId: string,
+    data: ImportPrCommentsRequest
+  ): Promise<ImportPrCommentsResponse> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/pr/comments/import`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ImportPrCommentsResponse>(response);
+  },
+
   /** Mark all coding agent turns for a workspace as seen */
   markSeen: async (attemptId: string): Promise<void> => {
     const response = await makeRequest(
@@ -987,6 +1133,109 @@ export const tagsApi = {
   },
 };
 
+// Conversation search APIs
+export const searchApi = {
+  conversations: async (
+    params: ConversationSearchParams
+  ): Promise<ConversationSearchHit[]> => {
+    const query = new URLSearchParams();
+    query.set('query', params.query);
+    if (params.project_id) query.set('project_id', params.project_id);
+    if (params.executor) query.set('executor', params.executor);
+    if (params.from) query.set('from', params.from.toISOString());
+    if (params.to) query.set('to', params.to.toISOString());
+    if (params.entry_types) query.set('entry_types', params.entry_types);
+    if (params.limit != null) query.set('limit', params.limit.toString());
+    const response = await makeRequest(
+      `/api/search/conversations?${query.toString()}`
+    );
+    return handleApiResponse<ConversationSearchHit[]>(response);
+  },
+};
+
+export const exportApi = {
+  conversations: async (
+    params: ConversationExportQuery
+  ): Promise<{ blob: Blob; filename: string }> => {
+    const query = new URLSearchParams({
+      scope: params.scope,
+      id: params.id,
+      format: params.format,
+      archive: String(params.archive),
+      redact_env: String(params.redact_env),
+      redact_paths: String(params.redact_paths),
+    });
+    const response = await makeRequest(
+      `/api/export/conversations?${query.toString()}`
+    );
+    if (!response.ok) {
+      const error = await response.json().catch(() => null);
+      throw new ApiError(
+        error?.message || 'Failed to export conversations',
+        response.status,
+        response
+      );
+    }
+    const disposition = response.headers.get('Content-Disposition');
+    const match = disposition?.match(/filename="(.+)"/);
+    const extension = params.archive
+      ? 'zip'
+      : { json: 'json', markdown: 'md', html: 'html' }[params.format];
+    return {
+      blob: await response.blob(),
+      filename: match?.[1] ?? `conversations.${extension}`,
+    };
+  },
+};
+
+// External agent session import APIs
+export const agentSessionsApi = {
+  list: async (agent: ExternalAgent): Promise<ExternalSessionSummary[]> => {
+    const response = await makeRequest(
+      `/api/agent-sessions?agent=${encodeURIComponent(agent)}`
+    );
+    return handleApiResponse<ExternalSessionSummary[]>(response);
+  },
+
+  import: async (
+    data: ImportAgentSessionRequest
+  ): Promise<ImportAgentSessionResponse> => {
+    const response = await makeRequest('/api/agent-sessions/import', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<ImportAgentSessionResponse>(response);
+  },
+};
+
+// Persistent terminal session APIs
+export const terminalApi = {
+  listSessions: async (workspaceId: string): Promise<TerminalSession[]> => {
+    const response = await makeRequest(
+      `/api/terminal/sessions?workspace_id=${encodeURIComponent(workspaceId)}`
+    );
+    return handleApiResponse<TerminalSession[]>(response);
+  },
+
+  createSession: async (
+    data: CreateTerminalSessionRequest
+  ): Promise<TerminalSession> => {
+    const response = await makeRequest('/api/terminal/sessions', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<TerminalSession>(response);
+  },
+
+  killSession: async (sessionId: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/terminal/sessions/${sessionId}`,
+      { method: 'DELETE' }
+    );
+    return handleApiResponse<void>(response);
+  },
+};
+
 // MCP Servers APIs
 export const mcpServersApi = {
   load: async (query: McpServerQuery): Promise<GetMcpServerResponse> => {
@@ -1447,3 +1696,153 @@ export const appManagementApi = {
     }>(response);
   },
 };
+
+// Remote Access API
+export const remoteAccessApi = {
+  getStatus: async (): Promise<RemoteAccessStatus> => {
+    const response = await makeRequest('/api/remote-access/status');
+    return handleApiResponse<RemoteAccessStatus>(response);
+  },
+
+  login: async (data: RemoteAccessLoginRequest): Promise<RemoteAccessRole> => {
+    const response = await makeRequest('/api/remote-access/login', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<RemoteAccessRole>(response);
+  },
+
+  logout: async (): Promise<void> => {
+    const response = await makeRequest('/api/remote-access/logout', {
+      method: 'POST',
+    });
+    return handleApiResponse<void>(response);
+  },
+
+  update: async (
+    data: UpdateRemoteAccessRequest
+  ): Promise<UpdateRemoteAccessResponse> => {
+    const response = await makeRequest('/api/remote-access', {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<UpdateRemoteAccessResponse>(response);
+  },
+
+  /**
+   * Issue a new bearer token, replacing any existing one
+   */
+  regenerateToken: async (): Promise<RemoteAccessTokenResponse> => {
+    const response = await makeRequest('/api/remote-access/token', {
+      method: 'POST',
+    });
+    return handleApiResponse<RemoteAccessTokenResponse>(response);
+  },
+
+  revokeToken: async (): Promise<void> => {
+    const response = await makeRequest('/api/remote-access/token', {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
+  },
+
+  /**
+   * Issue a new token for scraping /api/metrics, replacing any existing one
+   */
+  regenerateMetricsToken: async (): Promise<RemoteAccessTokenResponse> => {
+    const response = await makeRequest('/api/remote-access/metrics-token', {
+      method: 'POST',
+    });
+    return handleApiResponse<RemoteAccessTokenResponse>(response);
+  },
+
+  revokeMetricsToken: async (): Promise<void> => {
+    const response = await makeRequest('/api/remote-access/metrics-token', {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
+  },
+};
+
+export const workerHostsApi = {
+  list: async (): Promise<WorkerHost[]> => {
+    const response = await makeRequest('/api/worker-hosts');
+    return handleApiResponse<WorkerHost[]>(response);
+  },
+
+  create: async (data: CreateWorkerHost): Promise<WorkerHost> => {
+    const response = await makeRequest('/api/worker-hosts', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<WorkerHost>(response);
+  },
+
+  update: async (id: string, data: CreateWorkerHost): Promise<WorkerHost> => {
+    const response = await makeRequest(`/api/worker-hosts/${id}`, {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<WorkerHost>(response);
+  },
+
+  delete: async (id: string): Promise<void> => {
+    const response = await makeRequest(`/api/worker-hosts/${id}`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
+  },
+
+  /**
+   * Connect to the host over SSH and report its git version
+   */
+  check: async (id: string): Promise<WorkerHostCheckResponse> => {
+    const response = await makeRequest(`/api/worker-hosts/${id}/check`, {
+      method: 'POST',
+    });
+    return handleApiResponse<WorkerHostCheckResponse>(response);
+  },
+};
+
+export const usersApi = {
+  list: async (): Promise<User[]> => {
+    const response = await makeRequest('/api/users');
+    return handleApiResponse<User[]>(response);
+  },
+
+  create: async (data: CreateUserRequest): Promise<User> => {
+    const response = await makeRequest('/api/users', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<User>(response);
+  },
+
+  update: async (id: string, data: UpdateUserRequest): Promise<User> => {
+    const response = await makeRequest(`/api/users/${id}`, {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<User>(response);
+  },
+
+  delete: async (id: string): Promise<void> => {
+    const response = await makeRequest(`/api/users/${id}`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
+  },
+
+  /**
+   * Replace the signed-in user's own editor, language and notification settings
+   */
+  updateMyConfig: async (
+    data: UserConfigOverrides
+  ): Promise<UserConfigOverrides> => {
+    const response = await makeRequest('/api/users/me/config', {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<UserConfigOverrides>(response);
+  },
+};
@@ -7,6 +7,7 @@ import { ConfigSourceManagement } from './data-management/ConfigSourceManagement
 import { SessionManagement } from './data-management/SessionManagement';
 import { RepoPathManagement } from './data-management/RepoPathManagement';
 import { ImportExportManagement } from './data-management/ImportExportManagement';
+import { BackupManagement } from './data-management/BackupManagement';
 
 type TabValue = 'config' | 'sessions' | 'repos' | 'import-export';
 
@@ -73,6 +74,7 @@ export function DataManagement() {
         </TabsContent>
 
         <TabsContent value="import-export" className="space-y-4">
+          <BackupManagement />
           <ImportExportManagement />
         </TabsContent>
       </Tabs>
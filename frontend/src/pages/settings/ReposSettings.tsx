@@ -36,6 +36,9 @@ interface RepoScriptsFormState {
   cleanup_script: string;
   copy_files: string;
   dev_server_script: string;
+  pre_merge_script: string;
+  pre_push_script: string;
+  gate_timeout_secs: string;
 }
 
 function repoToFormState(repo: Repo): RepoScriptsFormState {
@@ -46,6 +49,9 @@ function repoToFormState(repo: Repo): RepoScriptsFormState {
     cleanup_script: repo.cleanup_script ?? '',
     copy_files: repo.copy_files ?? '',
     dev_server_script: repo.dev_server_script ?? '',
+    pre_merge_script: repo.pre_merge_script ?? '',
+    pre_push_script: repo.pre_push_script ?? '',
+    gate_timeout_secs: repo.gate_timeout_secs?.toString() ?? '',
   };
 }
 
@@ -182,6 +188,11 @@ export function ReposSettings() {
         copy_files: draft.copy_files.trim() || null,
         parallel_setup_script: draft.parallel_setup_script,
         dev_server_script: draft.dev_server_script.trim() || null,
+        pre_merge_script: draft.pre_merge_script.trim() || null,
+        pre_push_script: draft.pre_push_script.trim() || null,
+        gate_timeout_secs: draft.gate_timeout_secs.trim()
+          ? Number(draft.gate_timeout_secs.trim())
+          : null,
       };
 
       const updatedRepo = await repoApi.update(selectedRepo.id, updateData);
@@ -419,6 +430,65 @@ export function ReposSettings() {
                 </p>
               </div>
 
+              <div className="space-y-2">
+                <Label htmlFor="pre-merge-script">
+                  {t('settings.repos.scripts.preMerge.label')}
+                </Label>
+                <AutoExpandingTextarea
+                  id="pre-merge-script"
+                  value={draft.pre_merge_script}
+                  onChange={(e) =>
+                    updateDraft({
+                      pre_merge_script: e.target.value,
+                    })
+                  }
+                  maxRows={12}
+                  className="w-full px-3 py-2 border border-input bg-background text-foreground rounded-md focus:outline-none focus:ring-2 focus:ring-ring font-mono"
+                />
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.repos.scripts.preMerge.helper')}
+                </p>
+              </div>
+
+              <div className="space-y-2">
+                <Label htmlFor="pre-push-script">
+                  {t('settings.repos.scripts.prePush.label')}
+                </Label>
+                <AutoExpandingTextarea
+                  id="pre-push-script"
+                  value={draft.pre_push_script}
+                  onChange={(e) =>
+                    updateDraft({
+                      pre_push_script: e.target.value,
+                    })
+                  }
+                  maxRows={12}
+                  className="w-full px-3 py-2 border border-input bg-background text-foreground rounded-md focus:outline-none focus:ring-2 focus:ring-ring font-mono"
+                />
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.repos.scripts.prePush.helper')}
+                </p>
+              </div>
+
+              <div className="space-y-2">
+                <Label htmlFor="gate-timeout">
+                  {t('settings.repos.scripts.gateTimeout.label')}
+                </Label>
+                <Input
+                  id="gate-timeout"
+                  type="number"
+                  min={1}
+                  value={draft.gate_timeout_secs}
+                  onChange={(e) =>
+                    updateDraft({ gate_timeout_secs: e.target.value })
+                  }
+                  placeholder="1800"
+                />
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.repos.scripts.gateTimeout.helper')}
+                </p>
+              </div>
+
               <div className="space-y-2">
                 <Label htmlFor="copy-files">
                   {t('settings.repos.scripts.copyFiles.label')}
@@ -0,0 +1,344 @@
+import { useCallback, useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { Button } from '@/components/ui/button';
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from '@/components/ui/card';
+import { Checkbox } from '@/components/ui/checkbox';
+import { Input } from '@/components/ui/input';
+import { Label } from '@/components/ui/label';
+import { toast } from 'sonner';
+import { Archive, Download, RotateCcw } from 'lucide-react';
+import { useUserSystem } from '@/components/ConfigProvider';
+import type {
+  AutoBackupConfig,
+  BackupFile,
+  RestoreBackupResponse,
+} from 'shared/types';
+
+function formatSize(bytes: number): string {
+  if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`;
+  return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+}
+
+export function BackupManagement() {
+  const { t } = useTranslation();
+  const { config, updateAndSaveConfig } = useUserSystem();
+  const [backups, setBackups] = useState<BackupFile[]>([]);
+  const [isBackingUp, setIsBackingUp] = useState(false);
+  const [isRestoring, setIsRestoring] = useState(false);
+  const [relinkFrom, setRelinkFrom] = useState('');
+  const [relinkTo, setRelinkTo] = useState('');
+  const [restoreResult, setRestoreResult] =
+    useState<RestoreBackupResponse | null>(null);
+
+  const loadBackups = useCallback(async () => {
+    try {
+      const response = await fetch('/api/data-management/backups');
+      const data = await response.json();
+      if (data.success) {
+        setBackups(data.data);
+      }
+    } catch (error) {
+      console.error(error);
+    }
+  }, []);
+
+  useEffect(() => {
+    loadBackups();
+  }, [loadBackups]);
+
+  const autoBackup = config?.auto_backup;
+  const updateAutoBackup = (updates: Partial<AutoBackupConfig>) => {
+    if (!autoBackup) return;
+    void updateAndSaveConfig({ auto_backup: { ...autoBackup, ...updates } });
+  };
+
+  const handleBackup = async () => {
+    setIsBackingUp(true);
+    try {
+      const response = await fetch('/api/data-management/backup', {
+        method: 'POST',
+      });
+
+      if (!response.ok) {
+        const error = await response.json();
+        throw new Error(error.message || 'Backup failed');
+      }
+
+      const blob = await response.blob();
+      const url = URL.createObjectURL(blob);
+      const a = document.createElement('a');
+      a.href = url;
+
+      const disposition = response.headers.get('Content-Disposition');
+      const match = disposition?.match(/filename="(.+)"/);
+      a.download = match?.[1] ?? 'vibe-kanban-backup.zip';
+      document.body.appendChild(a);
+      a.click();
+      document.body.removeChild(a);
+      URL.revokeObjectURL(url);
+
+      toast.success(t('settings.general.dataManagement.toasts.backupCreated'));
+      loadBackups();
+    } catch (error) {
+      toast.error(
+        error instanceof Error
+          ? error.message
+          : t('settings.general.dataManagement.toasts.backupFailed')
+      );
+      console.error(error);
+    } finally {
+      setIsBackingUp(false);
+    }
+  };
+
+  const handleRestore = () => {
+    const input = document.createElement('input');
+    input.type = 'file';
+    input.accept = '.zip';
+    input.onchange = async (e) => {
+      const file = (e.target as HTMLInputElement).files?.[0];
+      if (!file) return;
+      if (
+        !window.confirm(
+          t(
+            'settings.general.dataManagement.importExport.backup.restoreConfirm'
+          )
+        )
+      ) {
+        return;
+      }
+
+      setIsRestoring(true);
+      setRestoreResult(null);
+
+      try {
+        const formData = new FormData();
+        formData.append('backup_file', file);
+
+        const params = new URLSearchParams();
+        if (relinkFrom.trim() && relinkTo.trim()) {
+          params.append('relink_from', relinkFrom.trim());
+          params.append('relink_to', relinkTo.trim());
+        }
+
+        const response = await fetch(`/api/data-management/restore?${params}`, {
+          method: 'POST',
+          body: formData,
+        });
+        const data = await response.json();
+
+        if (data.success) {
+          setRestoreResult(data.data);
+          toast.success(
+            t('settings.general.dataManagement.toasts.backupRestored')
+          );
+          loadBackups();
+        } else {
+          toast.error(
+            data.message ||
+              t('settings.general.dataManagement.toasts.restoreFailed')
+          );
+        }
+      } catch (error) {
+        toast.error(t('settings.general.dataManagement.toasts.restoreFailed'));
+        console.error(error);
+      } finally {
+        setIsRestoring(false);
+      }
+    };
+    input.click();
+  };
+
+  const invalidRepos = restoreResult?.repos.filter((repo) => !repo.valid) ?? [];
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>
+          {t('settings.general.dataManagement.importExport.backup.title')}
+        </CardTitle>
+        <CardDescription>
+          {t('settings.general.dataManagement.importExport.backup.description')}
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <div className="flex flex-col gap-2 sm:flex-row">
+          <Button onClick={handleBackup} disabled={isBackingUp || isRestoring}>
+            <Archive className="h-4 w-4 mr-2" />
+            {isBackingUp
+              ? t(
+                  'settings.general.dataManagement.importExport.backup.backingUp'
+                )
+              : t(
+                  'settings.general.dataManagement.importExport.backup.backupButton'
+                )}
+          </Button>
+          <Button
+            onClick={handleRestore}
+            disabled={isBackingUp || isRestoring}
+            variant="outline"
+          >
+            <RotateCcw className="h-4 w-4 mr-2" />
+            {isRestoring
+              ? t(
+                  'settings.general.dataManagement.importExport.backup.restoring'
+                )
+              : t(
+                  'settings.general.dataManagement.importExport.backup.restoreButton'
+                )}
+          </Button>
+        </div>
+
+        <div className="space-y-2">
+          <Label className="text-sm font-medium">
+            {t('settings.general.dataManagement.importExport.backup.relink')}
+          </Label>
+          <p className="text-sm text-muted-foreground">
+            {t(
+              'settings.general.dataManagement.importExport.backup.relinkHelper'
+            )}
+          </p>
+          <div className="flex flex-col gap-2 sm:flex-row">
+            <Input
+              value={relinkFrom}
+              onChange={(e) => setRelinkFrom(e.target.value)}
+              placeholder={t(
+                'settings.general.dataManagement.importExport.backup.relinkFrom'
+              )}
+            />
+            <Input
+              value={relinkTo}
+              onChange={(e) => setRelinkTo(e.target.value)}
+              placeholder={t(
+                'settings.general.dataManagement.importExport.backup.relinkTo'
+              )}
+            />
+          </div>
+        </div>
+
+        {restoreResult && (
+          <div className="rounded-lg border p-4 space-y-2 text-sm">
+            <p className="font-medium">
+              {t(
+                'settings.general.dataManagement.importExport.backup.restoredFrom',
+                { version: restoreResult.manifest.app_version }
+              )}
+            </p>
+            {invalidRepos.length > 0 && (
+              <div>
+                <p className="text-yellow-600 font-medium">
+                  {t(
+                    'settings.general.dataManagement.importExport.backup.invalidRepos',
+                    { count: invalidRepos.length }
+                  )}
+                </p>
+                <ul className="list-disc list-inside text-muted-foreground ml-2">
+                  {invalidRepos.map((repo) => (
+                    <li key={repo.repo_id}>{repo.path}</li>
+                  ))}
+                </ul>
+              </div>
+            )}
+          </div>
+        )}
+
+        {autoBackup && (
+          <div className="space-y-2">
+            <div className="flex items-center space-x-2">
+              <Checkbox
+                id="auto-backup"
+                checked={autoBackup.enabled}
+                onCheckedChange={(checked) =>
+                  updateAutoBackup({ enabled: checked as boolean })
+                }
+              />
+              <label htmlFor="auto-backup" className="text-sm cursor-pointer">
+                {t(
+                  'settings.general.dataManagement.importExport.backup.autoBackup'
+                )}
+              </label>
+            </div>
+            <div className="grid grid-cols-2 gap-2 sm:w-96">
+              <Label htmlFor="auto-backup-interval" className="self-center">
+                {t(
+                  'settings.general.dataManagement.importExport.backup.intervalHours'
+                )}
+              </Label>
+              <Input
+                id="auto-backup-interval"
+                type="number"
+                min={1}
+                value={autoBackup.interval_hours}
+                disabled={!autoBackup.enabled}
+                onChange={(e) =>
+                  updateAutoBackup({
+                    interval_hours: Math.max(1, Number(e.target.value) || 1),
+                  })
+                }
+              />
+              <Label htmlFor="auto-backup-keep" className="self-center">
+                {t('settings.general.dataManagement.importExport.backup.keep')}
+              </Label>
+              <Input
+                id="auto-backup-keep"
+                type="number"
+                min={1}
+                value={autoBackup.keep}
+                disabled={!autoBackup.enabled}
+                onChange={(e) =>
+                  updateAutoBackup({
+                    keep: Math.max(1, Number(e.target.value) || 1),
+                  })
+                }
+              />
+            </div>
+          </div>
+        )}
+
+        <div className="space-y-2">
+          <Label className="text-sm font-medium">
+            {t(
+              'settings.general.dataManagement.importExport.backup.storedBackups'
+            )}
+          </Label>
+          {backups.length === 0 ? (
+            <p className="text-sm text-muted-foreground">
+              {t(
+                'settings.general.dataManagement.importExport.backup.noBackups'
+              )}
+            </p>
+          ) : (
+            <ul className="space-y-1 text-sm">
+              {backups.map((backup) => (
+                <li
+                  key={backup.name}
+                  className="flex items-center justify-between gap-2"
+                >
+                  <span className="truncate font-mono text-xs">
+                    {backup.name}
+                  </span>
+                  <span className="flex items-center gap-2 text-muted-foreground">
+                    {formatSize(backup.size)}
+                    <a
+                      href={`/api/data-management/backups/${encodeURIComponent(backup.name)}`}
+                      download={backup.name}
+                      aria-label={backup.name}
+                    >
+                      <Download className="h-4 w-4" />
+                    </a>
+                  </span>
+                </li>
+              ))}
+            </ul>
+          )}
+        </div>
+      </CardContent>
+    </Card>
+  );
+}
@@ -4,23 +4,88 @@
 
 // If you are an AI, and you absolutely have to edit this file, please confirm with the user first.
 
-export type Project = { id: string, name: string, default_agent_working_dir: string | null, remote_project_id: string | null, created_at: Date, updated_at: Date, };
+export type Project = { id: string, name: string, default_agent_working_dir: string | null, remote_project_id: string | null, 
+/**
+ * Image for workspace containers; workspaces run on the host when unset
+ */
+container_image: string | null, 
+/**
+ * Start workspace containers without network access
+ */
+container_network_isolated: boolean, 
+/**
+ * Port the dev server listens on in the container, published on the
+ * host's loopback interface
+ */
+container_dev_server_port: number | null, 
+/**
+ * SSH worker host that runs the project's agents and scripts
+ */
+worker_host_id: string | null, 
+/**
+ * Pick the least loaded worker host for each new workspace
+ */
+worker_host_auto: boolean, created_at: Date, updated_at: Date, };
 
 export type CreateProject = { name: string, repositories: Array<CreateProjectRepo>, };
 
-export type UpdateProject = { name: string | null, };
+export type UpdateProject = { name: string | null, container_image?: string | null, container_network_isolated?: boolean | null, container_dev_server_port?: number | null, worker_host_id?: string | null, worker_host_auto?: boolean | null, };
 
 export type SearchResult = { path: string, is_file: boolean, match_type: SearchMatchType, 
 /**
  * Ranking score based on git history (higher = more recently/frequently edited)
  */
-score: bigint, };
+score: bigint, 
+/**
+ * 1-based line of a content or symbol match
+ */
+line: number | null, 
+/**
+ * Matching line for content matches, first line of the definition for symbols
+ */
+preview: string | null, symbol: SearchSymbol | null, };
+
+export type SearchMatchType = "FileName" | "DirectoryName" | "FullPath" | "Content" | "Symbol";
 
-export type SearchMatchType = "FileName" | "DirectoryName" | "FullPath";
+export type SearchSymbol = { name: string, kind: SymbolKind, };
 
-export type Repo = { id: string, path: string, name: string, display_name: string, setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean, dev_server_script: string | null, default_target_branch: string | null, created_at: Date, updated_at: Date, };
+export type SymbolKind = "function" | "method" | "class" | "struct" | "enum" | "interface" | "trait" | "type_alias" | "module";
+
+export type Repo = { id: string, path: string, name: string, display_name: string, setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean, dev_server_script: string | null, default_target_branch: string | null, 
+/**
+ * Comma-separated directories for a cone-mode sparse checkout in workspace worktrees
+ */
+sparse_checkout_paths: string | null, 
+/**
+ * Partial clone filter used when the repository was cloned, e.g. `blob:none`
+ */
+partial_clone_filter: string | null, 
+/**
+ * Number of recent commits scanned when ranking files for search
+ */
+file_ranker_history_depth: bigint | null, 
+/**
+ * Run `git submodule update --init --recursive` when a worktree is created
+ */
+init_submodules: boolean, 
+/**
+ * Run `git lfs pull` when a worktree is created
+ */
+fetch_lfs: boolean, 
+/**
+ * Gate script that must pass in the worktree before merging
+ */
+pre_merge_script: string | null, 
+/**
+ * Gate script that must pass in the worktree before pushing
+ */
+pre_push_script: string | null, 
+/**
+ * Seconds a gate script may run before it is killed; None uses the default
+ */
+gate_timeout_secs: bigint | null, created_at: Date, updated_at: Date, };
 
-export type UpdateRepo = { display_name?: string | null, setup_script?: string | null, cleanup_script?: string | null, copy_files?: string | null, parallel_setup_script?: boolean | null, dev_server_script?: string | null, default_target_branch?: string | null, };
+export type UpdateRepo = { display_name?: string | null, setup_script?: string | null, cleanup_script?: string | null, copy_files?: string | null, parallel_setup_script?: boolean | null, dev_server_script?: string | null, default_target_branch?: string | null, sparse_checkout_paths?: string | null, partial_clone_filter?: string | null, file_ranker_history_depth?: number | null, init_submodules?: boolean | null, fetch_lfs?: boolean | null, pre_merge_script?: string | null, pre_push_script?: string | null, gate_timeout_secs?: number | null, };
 
 export type ProjectRepo = { id: string, project_id: string, repo_id: string, };
 
@@ -30,7 +95,39 @@ export type WorkspaceRepo = { id: string, workspace_id: string, repo_id: string,
 
 export type CreateWorkspaceRepo = { repo_id: string, target_branch: string, };
 
-export type RepoWithTargetBranch = { target_branch: string, id: string, path: string, name: string, display_name: string, setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean, dev_server_script: string | null, default_target_branch: string | null, created_at: Date, updated_at: Date, };
+export type RepoWithTargetBranch = { target_branch: string, id: string, path: string, name: string, display_name: string, setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean, dev_server_script: string | null, default_target_branch: string | null, 
+/**
+ * Comma-separated directories for a cone-mode sparse checkout in workspace worktrees
+ */
+sparse_checkout_paths: string | null, 
+/**
+ * Partial clone filter used when the repository was cloned, e.g. `blob:none`
+ */
+partial_clone_filter: string | null, 
+/**
+ * Number of recent commits scanned when ranking files for search
+ */
+file_ranker_history_depth: bigint | null, 
+/**
+ * Run `git submodule update --init --recursive` when a worktree is created
+ */
+init_submodules: boolean, 
+/**
+ * Run `git lfs pull` when a worktree is created
+ */
+fetch_lfs: boolean, 
+/**
+ * Gate script that must pass in the worktree before merging
+ */
+pre_merge_script: string | null, 
+/**
+ * Gate script that must pass in the worktree before pushing
+ */
+pre_push_script: string | null, 
+/**
+ * Seconds a gate script may run before it is killed; None uses the default
+ */
+gate_timeout_secs: bigint | null, created_at: Date, updated_at: Date, };
 
 export type Tag = { id: string, tag_name: string, content: string, created_at: string, updated_at: string, };
 
@@ -90,10 +187,121 @@ dropped: boolean, started_at: string, completed_at: string | null, created_at: s
 
 export enum ExecutionProcessStatus { running = "running", completed = "completed", failed = "failed", killed = "killed" }
 
-export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "codingagent" | "devserver";
+export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "codingagent" | "devserver" | "gatescript";
 
 export type ExecutionProcessRepoState = { id: string, execution_process_id: string, repo_id: string, before_head_commit: string | null, after_head_commit: string | null, merge_commit: string | null, created_at: Date, updated_at: Date, };
 
+export type LogStorageStats = { 
+/**
+ * Bytes held in uncompressed log rows
+ */
+uncompressed_bytes: bigint, 
+/**
+ * Uncompressed bytes belonging to finished processes, awaiting compaction
+ */
+pending_compaction_bytes: bigint, 
+/**
+ * Bytes held in compressed chunks
+ */
+compressed_bytes: bigint, 
+/**
+ * Original size of the logs held in compressed chunks
+ */
+compressed_original_bytes: bigint, 
+/**
+ * Estimated bytes freed by compacting finished processes
+ */
+reclaimable_by_compaction_bytes: bigint, 
+/**
+ * Compressed bytes of raw output past the retention period; normalized
+ * entries are kept, so slightly less than this is actually freed
+ */
+reclaimable_by_retention_bytes: bigint, raw_log_retention_days: number | null, };
+
+export type ConversationSearchParams = { query: string, project_id: string | null, 
+/**
+ * Executor name as stored on the session, e.g. `CLAUDE_CODE`
+ */
+executor: string | null, 
+/**
+ * Only runs started at or after this time
+ */
+from: Date | null, 
+/**
+ * Only runs started before this time
+ */
+to: Date | null, 
+/**
+ * Comma-separated entry types to match
+ */
+entry_types: string | null, limit: bigint | null, };
+
+export type ConversationSearchHit = { execution_process_id: string, session_id: string, workspace_id: string, task_id: string, project_id: string, task_title: string, executor: string | null, 
+/**
+ * Index into the normalized entries of the execution process
+ */
+entry_index: bigint, entry_type: string, 
+/**
+ * Matching text with hits wrapped in `<mark>` tags
+ */
+snippet: string, created_at: Date, };
+
+export type ReviewSide = "old" | "new";
+
+export type ReviewCommentStatus = "open" | "sent" | "resolved";
+
+export type ReviewComment = { id: string, workspace_id: string, repo_id: string, file_path: string, side: ReviewSide, 
+/**
+ * 1-based line on `side`
+ */
+line: number, body: string, 
+/**
+ * Text of the commented line when it was last anchored
+ */
+line_content: string | null, 
+/**
+ * The commented line no longer exists; `line` is its last known position
+ */
+outdated: boolean, status: ReviewCommentStatus, sent_execution_process_id: string | null, created_at: Date, updated_at: Date, };
+
+export type CreateReviewComment = { repo_id: string, file_path: string, side: ReviewSide, line: number, body: string, };
+
+export type UpdateReviewComment = { body: string | null, status: ReviewCommentStatus | null, };
+
+export type WorkerHost = { id: string, name: string, 
+/**
+ * Hostname or `~/.ssh/config` alias
+ */
+host: string, port: number | null, username: string | null, identity_file: string | null, 
+/**
+ * Absolute directory on the host holding repository mirrors and worktrees
+ */
+base_dir: string, 
+/**
+ * Skipped by load-based selection once this many executions run on it
+ */
+max_concurrent_executions: number | null, created_at: Date, updated_at: Date, };
+
+export type CreateWorkerHost = { name: string, host: string, port: number | null, username: string | null, identity_file: string | null, base_dir: string, max_concurrent_executions: number | null, };
+
+export type User = { id: string, username: string, display_name: string | null, role: UserRole, created_at: Date, updated_at: Date, };
+
+export type UserRole = "admin" | "member";
+
+export type CreateUser = { username: string, display_name: string | null, role: UserRole, };
+
+export type Activity = { id: string, 
+/**
+ * None for the local user, and once the user is deleted
+ */
+user_id: string | null, actor: string, project_id: string, task_id: string | null, workspace_id: string | null, action: ActivityAction, 
+/**
+ * Short action-specific summary, such as the PR URL or approval outcome
+ */
+details: string | null, created_at: Date, };
+
+export type ActivityAction = "task_created" | "follow_up_sent" | "approval_responded" | "merged" | "pr_created";
+
 export type Merge = { "type": "direct" } & DirectMerge | { "type": "pr" } & PrMerge;
 
 export type DirectMerge = { id: string, workspace_id: string, repo_id: string, merge_commit: string, target_branch_name: string, created_at: string, };
@@ -118,10 +326,19 @@ contentOmitted: boolean,
 /**
  * Optional precomputed stats for omitted content
  */
-additions: number | null, deletions: number | null, repoId: string | null, };
+additions: number | null, deletions: number | null, repoId: string | null, 
+/**
+ * Set for submodule and Git LFS entries, which are shown as a summary
+ * instead of file contents
+ */
+pointer: DiffPointer | null, };
 
 export type DiffChangeKind = "added" | "deleted" | "modified" | "renamed" | "copied" | "permissionChange";
 
+export type DiffPointer = { "kind": "submodule", oldCommit: string | null, newCommit: string | null, } | { "kind": "lfs", oldOid: string | null, newOid: string | null, oldSize: number | null, newSize: number | null, };
+
+export type DiffHunk = { index: number, header: string, oldStart: number, oldLines: number, newStart: number, newLines: number, };
+
 export type ApiResponse<T, E = T> = { success: boolean, data: T | null, error_data: E | null, message: string | null, };
 
 export type LoginStatus = { "status": "loggedout" } | { "status": "loggedin", profile: ProfileResponse, };
@@ -188,11 +405,119 @@ export type RegisterRepoRequest = { path: string, display_name: string | null, }
 
 export type InitRepoRequest = { parent_path: string, folder_name: string, };
 
+export type CloneRepoRequest = { url: string, parent_path: string, folder_name: string, 
+/**
+ * Partial clone filter, e.g. `blob:none`
+ */
+partial_clone_filter: string | null, 
+/**
+ * Shallow clone depth
+ */
+depth: number | null, };
+
 export type TagSearchParams = { search: string | null, };
 
+export type ReplayQuery = { 
+/**
+ * Playback speed multiplier; 1.0 replays at the original pace
+ */
+speed: number | null, 
+/**
+ * Fast-forward to this entry index before playing at normal speed
+ */
+entry_index: number | null, };
+
+export type ReplayCommand = { "type": "seek", entry_index: number, } | { "type": "speed", speed: number, } | { "type": "pause" } | { "type": "resume" };
+
+export type ConversationExportFormat = "json" | "markdown" | "html";
+
+export type ConversationExportScope = "execution" | "session" | "task" | "project";
+
+export type ConversationExportQuery = { scope: ConversationExportScope, 
+/**
+ * ID of the execution process, session, task or project to export
+ */
+id: string, format: ConversationExportFormat, 
+/**
+ * Bundle one file per session into a ZIP archive
+ */
+archive: boolean, 
+/**
+ * Replace environment variable values and secrets with a placeholder
+ */
+redact_env: boolean, 
+/**
+ * Replace workspace, repository and home directory paths with placeholders
+ */
+redact_paths: boolean, };
+
+export type ExternalAgent = "claude_code" | "codex" | "opencode";
+
+export type ExternalSessionSummary = { agent: ExternalAgent, agent_session_id: string, 
+/**
+ * Session file to pass back when importing
+ */
+path: string, 
+/**
+ * Directory the session was started in
+ */
+cwd: string | null, git_branch: string | null, first_prompt: string | null, modified_at: string, };
+
+export type ImportAgentSessionRequest = { agent: ExternalAgent, 
+/**
+ * Session file path as returned by the session listing
+ */
+path: string, project_id: string, repos: Array<WorkspaceRepoInput>, 
+/**
+ * Task title, defaults to the session's first prompt
+ */
+title: string | null, };
+
+export type ImportAgentSessionResponse = { task_id: string, workspace_id: string, session_id: string, execution_process_id: string, };
+
+export type CreateTerminalSessionRequest = { workspace_id: string, name: string | null, cols: number, rows: number, };
+
+export type TerminalSession = { id: string, workspace_id: string, name: string, 
+/**
+ * Persistent sessions survive disconnects until they are killed
+ */
+persistent: boolean, created_at: string, 
+/**
+ * Number of WebSockets currently attached
+ */
+viewers: number, 
+/**
+ * The shell has exited; only its scrollback remains
+ */
+exited: boolean, };
+
+export type WorkspaceCommit = { repo_id: string, repo_name: string, sha: string, subject: string, author_name: string, committed_at: Date, is_merge: boolean, 
+/**
+ * Coding agent turn that produced this commit, if any
+ */
+execution_process_id: string | null, };
+
+export type SendReviewRequest = { 
+/**
+ * Session to continue, defaults to the workspace's latest session
+ */
+session_id: string | null, 
+/**
+ * Defaults to the executor of the session's latest coding agent turn
+ */
+executor_profile_id: ExecutorProfileId | null, };
+
 export type TokenResponse = { access_token: string, expires_at: string | null, };
 
-export type UserSystemInfo = { config: Config, analytics_user_id: string, login_status: LoginStatus, environment: Environment, 
+export type UserSystemInfo = { 
+/**
+ * The global config with the signed-in user's own settings applied
+ */
+config: Config, 
+/**
+ * Account the client is signed in to, if any
+ */
+user: User | null, analytics_user_id: string, login_status: LoginStatus, environment: Environment, 
 /**
  * Capabilities supported per executor (e.g., { "CLAUDE_CODE": ["SESSION_FORK"] })
  */
@@ -262,6 +587,14 @@ export type GitOperationError = { "type": "merge_conflicts", message: string, op
 
 export type PushError = { "type": "force_push_required" };
 
+export type GateKind = "pre_merge" | "pre_push";
+
+export type GateFailure = { gate: GateKind, repo_id: string, repo_name: string, execution_process_id: string, status: ExecutionProcessStatus, exit_code: bigint | null, 
+/**
+ * The script was killed after running past the repo's gate timeout
+ */
+timed_out: boolean, };
+
 export type PrError = { "type": "cli_not_installed", provider: ProviderKind, } | { "type": "cli_not_logged_in", provider: ProviderKind, } | { "type": "git_cli_not_logged_in" } | { "type": "git_cli_not_installed" } | { "type": "target_branch_not_found", branch: string, } | { "type": "unsupported_provider" };
 
 export type RunScriptError = { "type": "no_script_configured" } | { "type": "process_already_running" };
@@ -276,7 +609,100 @@ export type GetPrCommentsError = { "type": "no_pr_attached" } | { "type": "cli_n
 
 export type GetPrCommentsQuery = { repo_id: string, };
 
-export type UnifiedPrComment = { "comment_type": "general", id: string, author: string, author_association: string | null, body: string, created_at: string, url: string | null, } | { "comment_type": "review", id: bigint, author: string, author_association: string | null, body: string, created_at: string, url: string | null, path: string, line: bigint | null, side: string | null, diff_hunk: string | null, };
+export type ImportPrCommentsRequest = { repo_id: string, };
+
+export type ImportPrCommentsResponse = { comment_count: number, 
+/**
+ * The follow-up turn, when the workspace was idle. Otherwise the comments wait
+ * in the session's queue for the running execution to finish.
+ */
+execution_process_id: string | null, };
+
+export type RevertFileRequest = { repo_id: string, 
+/**
+ * Path relative to the repository root
+ */
+path: string, 
+/**
+ * Revert to this execution's `before_head_commit` instead of the workspace base commit
+ */
+execution_process_id: string | null, };
+
+export type RevertHunkRequest = { repo_id: string, 
+/**
+ * Path relative to the repository root
+ */
+path: string, 
+/**
+ * Index into the hunks returned by `GET /revert/hunks`
+ */
+hunk_index: number, 
+/**
+ * Revert to this execution's `before_head_commit` instead of the workspace base commit
+ */
+execution_process_id: string | null, };
+
+export type FileHunksQuery = { repo_id: string, path: string, execution_process_id: string | null, };
+
+export type RevertResponse = { 
+/**
+ * Commit the file or hunk was reverted to
+ */
+source_commit: string, 
+/**
+ * New commit recording the revert; None if the file already matched
+ */
+commit_sha: string | null, };
+
+export type FileHunksResponse = { source_commit: string, hunks: Array<DiffHunk>, };
+
+export type BackportConflictResolution = "agent" | "manual";
+
+export type BackportRequest = { repo_id: string, 
+/**
+ * Branch to backport onto, e.g. `release/1.2`
+ */
+target_branch: string, 
+/**
+ * Executor used for conflict resolution; defaults to the source workspace's latest one
+ */
+executor_profile_id: ExecutorProfileId | null, conflict_resolution: BackportConflictResolution, 
+/**
+ * Push the new branch and open a PR against `target_branch` when the cherry-pick is clean
+ */
+create_pr: boolean, pr_title: string | null, pr_body: string | null, draft: boolean | null, };
+
+export type BackportResponse = { 
+/**
+ * Workspace created on the backport branch
+ */
+workspace: Workspace, 
+/**
+ * Commits from the source workspace that were cherry-picked, oldest first
+ */
+cherry_picked: Array<string>, 
+/**
+ * Files left in conflict; empty when the cherry-pick applied cleanly
+ */
+conflicted_files: Array<string>, 
+/**
+ * Whether a coding agent was started to resolve the conflicts
+ */
+conflict_resolution_started: boolean, pr_url: string | null, 
+/**
+ * Why the PR could not be opened, if `create_pr` was requested and failed
+ */
+pr_error: string | null, };
+
+export type UnifiedPrComment = { "comment_type": "general", id: string, author: string, author_association: string | null, body: string, created_at: string, url: string | null, 
+/**
+ * Thread holding the comment, needed to reply on Azure DevOps
+ */
+thread_id: bigint | null, } | { "comment_type": "review", id: bigint, author: string, author_association: string | null, body: string, created_at: string, url: string | null, path: string, line: bigint | null, side: string | null, diff_hunk: string | null, 
+/**
+ * Thread holding the comment, needed to reply on Azure DevOps
+ */
+thread_id: bigint | null, };
 
 export type ProviderKind = "git_hub" | "azure_dev_ops" | "unknown";
 
@@ -354,7 +780,21 @@ export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_pa
 
 export type SearchMode = "taskform" | "settings";
 
-export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, beta_workspaces: boolean, beta_workspaces_invitation_sent: boolean, commit_reminder: boolean, send_message_shortcut: SendMessageShortcut, };
+export type SearchKind = "files" | "content" | "symbols";
+
+export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, beta_workspaces: boolean, beta_workspaces_invitation_sent: boolean, commit_reminder: boolean, send_message_shortcut: SendMessageShortcut, 
+/**
+ * Days to keep raw agent output before only normalized entries are kept; None keeps it forever
+ */
+raw_log_retention_days: number | null, 
+/**
+ * Queue new PR comments as follow-ups for the workspace's agent automatically
+ */
+pr_comment_follow_ups_enabled: boolean, 
+/**
+ * Reply to PR comments with the commit that addressed them
+ */
+pr_comment_replies_enabled: boolean, remote_access: RemoteAccessConfig, auto_backup: AutoBackupConfig, };
 
 export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, };
 
@@ -376,6 +816,100 @@ export type ShowcaseState = { seen_features: Array<string>, };
 
 export type SendMessageShortcut = "ModifierEnter" | "Enter";
 
+export type RemoteAccessConfig = { enabled: boolean, 
+/**
+ * Port to listen on; the PORT environment variable takes precedence
+ */
+port: number | null, 
+/**
+ * PEM certificate chain; a self-signed certificate is generated when unset
+ */
+tls_cert_path: string | null, tls_key_path: string | null, 
+/**
+ * Header carrying the username set by an authenticating reverse proxy.
+ * Only read on connections from this machine, where the proxy runs.
+ */
+trusted_user_header: string | null, };
+
+export type AutoBackupConfig = { enabled: boolean, interval_hours: number, 
+/**
+ * Number of automatic backups kept; older ones are deleted after each new
+ * automatic backup
+ */
+keep: number, };
+
+export type UserConfigOverrides = { editor: EditorConfig | null, language: UiLanguage | null, notifications: NotificationConfig | null, };
+
+export type BackupManifest = { app_version: string, 
+/**
+ * Newest database migration applied when the snapshot was taken
+ */
+schema_version: number, created_at: Date, image_count: number, };
+
+export type BackupKind = "manual" | "auto" | "safety";
+
+export type BackupFile = { name: string, kind: BackupKind, size: number, created_at: Date, };
+
+export type RestoreBackupResponse = { manifest: BackupManifest, 
+/**
+ * Status of every repository after re-linking, so broken paths can be fixed
+ */
+repos: Array<RepoValidationInfo>, };
+
+export type RemoteAccessRole = "admin" | "member";
+
+export type RemoteAccessStatus = { enabled: boolean, 
+/**
+ * None when the client has to log in
+ */
+role: RemoteAccessRole | null, 
+/**
+ * Account the client is signed in to, if any
+ */
+user: User | null, has_password: boolean, has_token: boolean, has_metrics_token: boolean, };
+
+export type RemoteAccessLoginRequest = { 
+/**
+ * Signs in to this user account with `password` instead of as the admin
+ */
+username: string | null, password: string | null, token: string | null, };
+
+export type UpdateRemoteAccessRequest = { enabled: boolean, port: number | null, tls_cert_path: string | null, tls_key_path: string | null, trusted_user_header: string | null, 
+/**
+ * Replaces the admin password when set
+ */
+password: string | null, };
+
+export type UpdateRemoteAccessResponse = { config: RemoteAccessConfig, 
+/**
+ * Listening address and TLS changes apply after a restart
+ */
+restart_required: boolean, };
+
+export type RemoteAccessTokenResponse = { 
+/**
+ * Shown once; only its hash is stored
+ */
+token: string, };
+
+export type CreateUserRequest = { 
+/**
+ * Accounts without a password can only be used through the proxy header
+ */
+password: string | null, username: string, display_name: string | null, role: UserRole, };
+
+export type UpdateUserRequest = { display_name: string | null, role: UserRole, 
+/**
+ * Replaces the password when set
+ */
+password: string | null, };
+
+export type WorkerHostCheckResponse = { 
+/**
+ * `git --version` output from the host
+ */
+git_version: string, };
+
 export type GitBranch = { name: string, is_current: boolean, is_remote: boolean, last_commit_date: Date, };
 
 export type QueuedMessage = { 
@@ -402,7 +936,7 @@ export type McpConfig = { servers: { [key in string]?: JsonValue }, servers_path
 
 export type ExecutorActionType = { "type": "CodingAgentInitialRequest" } & CodingAgentInitialRequest | { "type": "CodingAgentFollowUpRequest" } & CodingAgentFollowUpRequest | { "type": "ScriptRequest" } & ScriptRequest | { "type": "ReviewRequest" } & ReviewRequest;
 
-export type ScriptContext = "SetupScript" | "CleanupScript" | "DevServer" | "ToolInstallScript";
+export type ScriptContext = "SetupScript" | "CleanupScript" | "DevServer" | "ToolInstallScript" | "GateScript";
 
 export type ScriptRequest = { script: string, language: ScriptRequestLanguage, context: ScriptContext, 
 /**